@@ -1,6 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
-use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+use std::path::PathBuf;
+use tracing_subscriber::{fmt, fmt::format::FmtSpan, prelude::*, EnvFilter};
 
 #[derive(Parser, Debug)]
 #[command(name = "polar")]
@@ -10,6 +11,15 @@ struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
 
+    /// Directory to store networks in (overrides `POLAR_DATA_DIR` and config.json)
+    #[arg(long, global = true, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Serve read-only network status as JSON on this localhost port while the TUI runs
+    #[cfg(feature = "status-server")]
+    #[arg(long, value_name = "PORT")]
+    serve: Option<u16>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -18,8 +28,13 @@ struct Cli {
 enum Commands {
     /// Launch the interactive TUI
     Tui,
-    /// List all networks
+    /// List all networks with the disk space their files take up
     List,
+    /// Remove the extracted credentials of a network, and its export once deleted
+    Clean {
+        /// Name of the network
+        name: String,
+    },
     /// Create a new network
     Create {
         /// Name of the network
@@ -29,6 +44,10 @@ enum Commands {
     Start {
         /// Name of the network
         name: String,
+
+        /// Print the images, containers and ports a start would use, then exit
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Stop a network
     Stop {
@@ -50,12 +69,37 @@ fn setup_logging(verbosity: u8) {
         _ => "trace",
     };
 
+    // From -vv on, closing spans report how long each operation took
+    let span_events = if verbosity >= 2 {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+
     tracing_subscriber::registry()
-        .with(fmt::layer())
+        .with(fmt::layer().with_span_events(span_events))
         .with(EnvFilter::new(filter))
         .init();
 }
 
+/// Format a byte count for the `list` output, e.g. `12.3 KiB`.
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -64,17 +108,55 @@ async fn main() -> Result<()> {
     match cli.command {
         Some(Commands::Tui) | None => {
             tracing::info!("Launching TUI...");
-            polar_tui::run().await?;
+            #[cfg(feature = "status-server")]
+            if let Some(port) = cli.serve {
+                polar_tui::run_with_status_server(cli.data_dir, port).await?;
+                return Ok(());
+            }
+            Box::pin(polar_tui::run(cli.data_dir)).await?;
         }
         Some(Commands::List) => {
-            // TODO: Implement network listing
-            println!("No networks found. Use 'polar create <name>' to create one.");
+            let manager = polar_tui::NetworkManager::with_data_dir(cli.data_dir)?;
+            let usage = manager.data_usage()?;
+            if usage.is_empty() {
+                println!("No networks found. Use 'polar create <name>' to create one.");
+            }
+            for entry in usage {
+                let state = manager.get_network(&entry.network).map_or_else(
+                    || "deleted".to_string(),
+                    |network| format!("{:?}, {} nodes", network.status, network.nodes.len()),
+                );
+                let reclaimable = if entry.reclaimable() > 0 {
+                    format!(" ({} reclaimable)", format_size(entry.reclaimable()))
+                } else {
+                    String::new()
+                };
+                println!(
+                    "{:<24} {:<20} {:>10}{reclaimable}",
+                    entry.network,
+                    state,
+                    format_size(entry.total())
+                );
+            }
+        }
+        Some(Commands::Clean { name }) => {
+            let manager = polar_tui::NetworkManager::with_data_dir(cli.data_dir)?;
+            let freed = manager.clean_network_data(&name)?;
+            println!("Removed {} of stale data of '{name}'", format_size(freed));
         }
         Some(Commands::Create { name }) => {
             // TODO: Implement network creation
             println!("Created network: {name}");
         }
-        Some(Commands::Start { name }) => {
+        Some(Commands::Start { name, dry_run: true }) => {
+            let manager = polar_tui::NetworkManager::with_data_dir(cli.data_dir)?;
+            let plan = manager.plan_start(&name).await?;
+            print!("{plan}");
+            if plan.has_problems() {
+                anyhow::bail!("network '{name}' would fail to start");
+            }
+        }
+        Some(Commands::Start { name, .. }) => {
             // TODO: Implement network start
             println!("Started network: {name}");
         }
@@ -0,0 +1,97 @@
+//! Channel point parsing.
+//!
+//! lnd identifies a channel by its funding outpoint, printed as
+//! `funding_txid:output_index`. Parsing it up front turns a typo into a
+//! clear error instead of an opaque one from inside the container.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// A channel's funding outpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChannelPoint {
+    /// Funding transaction ID, 64 hex characters.
+    pub funding_txid: String,
+    /// Index of the funding output.
+    pub output_index: u32,
+}
+
+impl FromStr for ChannelPoint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            Error::InvalidChannelPoint(format!("'{s}' {reason}, expected 'txid:index'"))
+        };
+
+        let (txid, index) = s.split_once(':').ok_or_else(|| invalid("has no ':'"))?;
+        if txid.len() != 64 {
+            return Err(invalid(&format!(
+                "has a {} character txid instead of 64",
+                txid.len()
+            )));
+        }
+        if !txid.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid("has a txid that is not hex"));
+        }
+        // u32 parsing alone would accept a leading '+'
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("has an output index that is not a number"));
+        }
+        let output_index = index
+            .parse()
+            .map_err(|_| invalid("has an output index that is too large"))?;
+
+        Ok(Self {
+            funding_txid: txid.to_string(),
+            output_index,
+        })
+    }
+}
+
+impl fmt::Display for ChannelPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.funding_txid, self.output_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TXID: &str = "a3f1c2d4e5b6978800112233445566778899aabbccddeeff0011223344556677";
+
+    #[test]
+    fn test_parse_valid_channel_point() {
+        let point: ChannelPoint = format!("{TXID}:1").parse().unwrap();
+        assert_eq!(point.funding_txid, TXID);
+        assert_eq!(point.output_index, 1);
+        // The wire format round-trips unchanged
+        assert_eq!(point.to_string(), format!("{TXID}:1"));
+    }
+
+    #[test]
+    fn test_parse_invalid_channel_points() {
+        for input in [
+            String::new(),
+            ":0".to_string(),
+            TXID.to_string(),
+            format!("{TXID}:"),
+            format!("{TXID}:-1"),
+            format!("{TXID}:+1"),
+            format!("{TXID}:1:2"),
+            format!("{TXID}:4294967296"),
+            format!("{}:0", &TXID[..63]),
+            format!("{TXID}0:0"),
+            format!("{}g:0", &TXID[..63]),
+        ] {
+            let err = input.parse::<ChannelPoint>().unwrap_err();
+            assert!(
+                err.to_string().contains("expected 'txid:index'"),
+                "{input:?}: {err}"
+            );
+        }
+    }
+}
@@ -1,12 +1,19 @@
 //! Configuration management.
 
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 
 use crate::{Error, Result};
 
+/// Default Bitcoin Core RPC username.
+pub const DEFAULT_RPC_USER: &str = "polaruser";
+
+/// Default Bitcoin Core RPC password.
+pub const DEFAULT_RPC_PASS: &str = "polarpass";
+
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -14,6 +21,238 @@ pub struct Config {
     pub data_dir: PathBuf,
     /// Docker socket path.
     pub docker_socket: Option<String>,
+    /// Docker host to connect to, e.g. `unix:///var/run/docker.sock` or
+    /// `tcp://192.168.64.2:2375`. Falls back to `DOCKER_HOST` when unset.
+    #[serde(default)]
+    pub docker_host: Option<String>,
+    /// Bitcoin Core RPC username.
+    #[serde(default = "default_rpc_user")]
+    pub rpc_user: String,
+    /// Bitcoin Core RPC password.
+    #[serde(default = "default_rpc_pass")]
+    pub rpc_pass: String,
+    /// Seconds to wait for lnd to shut down gracefully before forcing it.
+    #[serde(default = "default_lnd_stop_timeout_secs")]
+    pub lnd_stop_timeout_secs: u64,
+    /// Run the watchtower server and client on LND nodes.
+    #[serde(default)]
+    pub lnd_watchtowers: bool,
+    /// Allow networks to use images outside the supported version lists.
+    #[serde(default)]
+    pub allow_custom_images: bool,
+    /// Allow LND channels above the 16,777,215 sat protocol limit.
+    #[serde(default)]
+    pub wumbo: bool,
+    /// Confirmations to mine and wait for after auto-mined wallet funding.
+    #[serde(default = "default_funding_confirmations")]
+    pub funding_confirmations: u32,
+    /// Read LND node info over its REST API rather than through `lncli`.
+    #[serde(default)]
+    pub lnd_rest_client: bool,
+    /// Reuse one coinbase address when mining to the Bitcoin wallet instead
+    /// of generating a new one for every batch of blocks.
+    #[serde(default = "default_reuse_mining_address")]
+    pub reuse_mining_address: bool,
+    /// Milliseconds node info command output is reused for; 0 disables the
+    /// cache.
+    #[serde(default = "default_info_cache_ttl_ms")]
+    pub info_cache_ttl_ms: u64,
+    /// Stop running networks when quitting rather than leaving their
+    /// containers up; the default answer of the quit dialog.
+    #[serde(default)]
+    pub stop_on_exit: bool,
+    /// Capacity in sats the open channel dialog starts with.
+    #[serde(default = "default_channel_capacity")]
+    pub default_channel_capacity: u64,
+    /// Amount in sats the open channel dialog pushes to the remote side.
+    #[serde(default = "default_push_amount")]
+    pub default_push_amount: u64,
+    /// Amount in sats the send payment dialog starts with.
+    #[serde(default = "default_payment_amount")]
+    pub default_payment_amount: u64,
+    /// Amount in BTC the fund wallet dialogs start with.
+    #[serde(default = "default_fund_amount")]
+    pub default_fund_amount: f64,
+    /// Blocks the mine blocks dialog starts with.
+    #[serde(default = "default_mine_blocks")]
+    pub default_mine_blocks: u32,
+    /// Show amounts in BTC rather than sats when the app starts.
+    #[serde(default)]
+    pub display_btc: bool,
+    /// Times an image pull is retried after a transient network error.
+    #[serde(default = "default_image_pull_retries")]
+    pub image_pull_retries: u32,
+    /// Milliseconds before the first image pull retry; doubles after each.
+    #[serde(default = "default_image_pull_retry_delay_ms")]
+    pub image_pull_retry_delay_ms: u64,
+    /// CPU and memory caps for node containers.
+    #[serde(default)]
+    pub resource_limits: NodeResourceLimits,
+}
+
+fn default_rpc_user() -> String {
+    DEFAULT_RPC_USER.to_string()
+}
+
+fn default_rpc_pass() -> String {
+    DEFAULT_RPC_PASS.to_string()
+}
+
+const fn default_lnd_stop_timeout_secs() -> u64 {
+    10
+}
+
+const fn default_funding_confirmations() -> u32 {
+    6
+}
+
+const fn default_reuse_mining_address() -> bool {
+    true
+}
+
+const fn default_info_cache_ttl_ms() -> u64 {
+    2_000
+}
+
+const fn default_channel_capacity() -> u64 {
+    1_000_000
+}
+
+const fn default_push_amount() -> u64 {
+    500_000
+}
+
+const fn default_payment_amount() -> u64 {
+    10_000
+}
+
+const fn default_fund_amount() -> f64 {
+    1.0
+}
+
+/// Enough to mature a coinbase output.
+const fn default_mine_blocks() -> u32 {
+    100
+}
+
+const fn default_image_pull_retries() -> u32 {
+    3
+}
+
+const fn default_image_pull_retry_delay_ms() -> u64 {
+    2_000
+}
+
+/// CPU and memory caps applied to a node's container.
+///
+/// Unset fields leave the container unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// CPUs the container may use, e.g. `0.5` for half a core.
+    #[serde(default)]
+    pub cpus: Option<f64>,
+    /// Memory limit in MiB.
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Create limits of `cpus` CPUs and `memory_mb` MiB of memory.
+    #[must_use]
+    pub const fn new(cpus: f64, memory_mb: u64) -> Self {
+        Self {
+            cpus: Some(cpus),
+            memory_mb: Some(memory_mb),
+        }
+    }
+
+    /// CPU limit in units of 10^-9 CPUs, as Docker's `NanoCpus` expects.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn nano_cpus(&self) -> Option<i64> {
+        self.cpus
+            .filter(|cpus| *cpus > 0.0)
+            .map(|cpus| (cpus * 1e9).round() as i64)
+    }
+
+    /// Memory limit in bytes, as Docker's `Memory` expects.
+    #[must_use]
+    pub fn memory_bytes(&self) -> Option<i64> {
+        self.memory_mb
+            .filter(|mb| *mb > 0)
+            .and_then(|mb| i64::try_from(mb.saturating_mul(1024 * 1024)).ok())
+    }
+}
+
+/// Container resource limits for each node kind.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NodeResourceLimits {
+    /// Limits for Bitcoin Core containers.
+    #[serde(default = "default_bitcoin_limits")]
+    pub bitcoin: ResourceLimits,
+    /// Limits for LND containers.
+    #[serde(default = "default_lnd_limits")]
+    pub lnd: ResourceLimits,
+}
+
+impl Default for NodeResourceLimits {
+    fn default() -> Self {
+        Self {
+            bitcoin: default_bitcoin_limits(),
+            lnd: default_lnd_limits(),
+        }
+    }
+}
+
+/// Regtest bitcoind stays well under this even with many peers.
+const fn default_bitcoin_limits() -> ResourceLimits {
+    ResourceLimits::new(2.0, 2048)
+}
+
+/// Leaves room for a dozen LND nodes on a laptop.
+const fn default_lnd_limits() -> ResourceLimits {
+    ResourceLimits::new(1.0, 1024)
+}
+
+/// Bitcoin Core RPC credentials shared by bitcoind, bitcoin-cli and LND.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcCredentials {
+    /// RPC username.
+    pub user: String,
+    /// RPC password.
+    pub pass: String,
+}
+
+impl Default for RpcCredentials {
+    fn default() -> Self {
+        Self {
+            user: default_rpc_user(),
+            pass: default_rpc_pass(),
+        }
+    }
+}
+
+impl RpcCredentials {
+    /// Create credentials from a username and password.
+    pub fn new(user: impl Into<String>, pass: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            pass: pass.into(),
+        }
+    }
+
+    /// Build a regtest `bitcoin-cli` command line authenticated with these credentials.
+    #[must_use]
+    pub fn bitcoin_cli_command(&self, args: &[&str]) -> Vec<String> {
+        let mut cmd = vec![
+            "bitcoin-cli".to_string(),
+            "-regtest".to_string(),
+            format!("-rpcuser={}", self.user),
+            format!("-rpcpassword={}", self.pass),
+        ];
+        cmd.extend(args.iter().map(ToString::to_string));
+        cmd
+    }
 }
 
 impl Default for Config {
@@ -25,11 +264,53 @@ impl Default for Config {
         Self {
             data_dir,
             docker_socket: None,
+            docker_host: None,
+            rpc_user: default_rpc_user(),
+            rpc_pass: default_rpc_pass(),
+            lnd_stop_timeout_secs: default_lnd_stop_timeout_secs(),
+            lnd_watchtowers: false,
+            allow_custom_images: false,
+            wumbo: false,
+            funding_confirmations: default_funding_confirmations(),
+            lnd_rest_client: false,
+            reuse_mining_address: default_reuse_mining_address(),
+            info_cache_ttl_ms: default_info_cache_ttl_ms(),
+            stop_on_exit: false,
+            default_channel_capacity: default_channel_capacity(),
+            default_push_amount: default_push_amount(),
+            default_payment_amount: default_payment_amount(),
+            default_fund_amount: default_fund_amount(),
+            default_mine_blocks: default_mine_blocks(),
+            display_btc: false,
+            image_pull_retries: default_image_pull_retries(),
+            image_pull_retry_delay_ms: default_image_pull_retry_delay_ms(),
+            resource_limits: NodeResourceLimits::default(),
         }
     }
 }
 
 impl Config {
+    /// Environment variable overriding the configured data directory.
+    pub const DATA_DIR_ENV: &'static str = "POLAR_DATA_DIR";
+
+    /// Get the configured Bitcoin Core RPC credentials.
+    #[must_use]
+    pub fn rpc_credentials(&self) -> RpcCredentials {
+        RpcCredentials::new(&self.rpc_user, &self.rpc_pass)
+    }
+
+    /// Get the Docker host to connect to, or `None` for local defaults.
+    ///
+    /// The configured `docker_host` wins, then the legacy `docker_socket`,
+    /// then the `DOCKER_HOST` environment variable.
+    #[must_use]
+    pub fn docker_host(&self) -> Option<String> {
+        resolve_docker_host(
+            [self.docker_host.as_deref(), self.docker_socket.as_deref()],
+            std::env::var("DOCKER_HOST").ok(),
+        )
+    }
+
     /// Load configuration from disk or create default.
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
@@ -45,6 +326,62 @@ impl Config {
         }
     }
 
+    /// Load configuration, overriding where networks are stored.
+    ///
+    /// An explicit `data_dir` (the `--data-dir` flag) wins, then the
+    /// `POLAR_DATA_DIR` environment variable, then `data_dir` from
+    /// `config.json`. The override is never written back to the config file,
+    /// so separate data directories can share one config.
+    pub fn load_with_data_dir(data_dir: Option<PathBuf>) -> Result<Self> {
+        let mut config = Self::load()?;
+        config.data_dir = resolve_data_dir(
+            data_dir,
+            std::env::var_os(Self::DATA_DIR_ENV),
+            config.data_dir,
+        );
+        Ok(config)
+    }
+
+    /// Check the configuration is usable before saving it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] naming the first invalid setting, including
+    /// a data directory that cannot be created or written to.
+    pub fn validate(&self) -> Result<()> {
+        if self.rpc_user.is_empty() || self.rpc_pass.is_empty() {
+            return Err(Error::Config(
+                "RPC user and password must not be empty".into(),
+            ));
+        }
+        // bitcoind splits `user:password` on the first colon
+        if self.rpc_user.contains(':') {
+            return Err(Error::Config("RPC user must not contain ':'".into()));
+        }
+        if self.default_channel_capacity == 0 {
+            return Err(Error::Config(
+                "default channel capacity must be above 0".into(),
+            ));
+        }
+        if self.default_push_amount >= self.default_channel_capacity {
+            return Err(Error::Config(
+                "default push amount must be below the channel capacity".into(),
+            ));
+        }
+        if self.default_payment_amount == 0 {
+            return Err(Error::Config(
+                "default payment amount must be above 0".into(),
+            ));
+        }
+        if !self.default_fund_amount.is_finite() || self.default_fund_amount <= 0.0 {
+            return Err(Error::Config("default fund amount must be above 0".into()));
+        }
+        if self.default_mine_blocks == 0 {
+            return Err(Error::Config("default mine blocks must be above 0".into()));
+        }
+        check_writable(&self.data_dir)
+    }
+
     /// Save configuration to disk.
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
@@ -65,3 +402,166 @@ impl Config {
             .ok_or_else(|| Error::Config("could not determine config directory".into()))
     }
 }
+
+/// Make sure `dir` exists and files can be created in it.
+fn check_writable(dir: &Path) -> Result<()> {
+    if dir.as_os_str().is_empty() {
+        return Err(Error::Config("data dir must not be empty".into()));
+    }
+    let probe = dir.join(".polar-write-test");
+    std::fs::create_dir_all(dir)
+        .and_then(|()| std::fs::write(&probe, b""))
+        .and_then(|()| std::fs::remove_file(&probe))
+        .map_err(|e| Error::Config(format!("data dir {} is not writable: {e}", dir.display())))
+}
+
+/// Pick the explicit data directory, then the environment, then the config.
+fn resolve_data_dir(
+    explicit: Option<PathBuf>,
+    env: Option<OsString>,
+    configured: PathBuf,
+) -> PathBuf {
+    explicit
+        .or_else(|| env.filter(|dir| !dir.is_empty()).map(PathBuf::from))
+        .unwrap_or(configured)
+}
+
+/// Pick the first non-empty configured host, falling back to the environment.
+fn resolve_docker_host(configured: [Option<&str>; 2], env: Option<String>) -> Option<String> {
+    configured
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|host| !host.is_empty())
+        .map(ToString::to_string)
+        .or_else(|| env.filter(|host| !host.trim().is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_fields_default_when_missing() {
+        let config: Config =
+            serde_json::from_str(r#"{"data_dir": "/tmp/polar", "docker_socket": null}"#).unwrap();
+        assert_eq!(config.rpc_user, DEFAULT_RPC_USER);
+        assert_eq!(config.rpc_pass, DEFAULT_RPC_PASS);
+        assert_eq!(config.lnd_stop_timeout_secs, 10);
+        assert_eq!(config.info_cache_ttl_ms, 2_000);
+        assert_eq!(config.default_channel_capacity, 1_000_000);
+        assert_eq!(config.default_push_amount, 500_000);
+        assert_eq!(config.default_payment_amount, 10_000);
+        assert!((config.default_fund_amount - 1.0).abs() < f64::EPSILON);
+        assert_eq!(config.default_mine_blocks, 100);
+        assert_eq!(config.image_pull_retries, 3);
+        assert_eq!(config.image_pull_retry_delay_ms, 2_000);
+        assert_eq!(config.resource_limits, NodeResourceLimits::default());
+    }
+
+    #[test]
+    fn test_validate() {
+        let dir = std::env::temp_dir().join(format!("polar-config-test-{}", std::process::id()));
+        let valid = Config {
+            data_dir: dir.clone(),
+            ..Config::default()
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid = [
+            Config {
+                rpc_user: "alice:bob".into(),
+                ..valid.clone()
+            },
+            Config {
+                default_push_amount: valid.default_channel_capacity,
+                ..valid.clone()
+            },
+            Config {
+                default_fund_amount: f64::NAN,
+                ..valid.clone()
+            },
+            Config {
+                default_mine_blocks: 0,
+                ..valid.clone()
+            },
+            // A regular file cannot hold networks
+            Config {
+                data_dir: dir.join("file").join("nested"),
+                ..valid
+            },
+        ];
+        std::fs::write(dir.join("file"), b"").unwrap();
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?} should be rejected");
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_resource_limits() {
+        let limits: NodeResourceLimits =
+            serde_json::from_str(r#"{"lnd": {"cpus": 0.5, "memory_mb": 256}}"#).unwrap();
+        assert_eq!(limits.bitcoin, default_bitcoin_limits());
+        assert_eq!(limits.lnd.nano_cpus(), Some(500_000_000));
+        assert_eq!(limits.lnd.memory_bytes(), Some(256 * 1024 * 1024));
+
+        // Missing or zero limits leave the container unlimited
+        let unlimited: ResourceLimits = serde_json::from_str(r#"{"cpus": 0}"#).unwrap();
+        assert_eq!(unlimited.nano_cpus(), None);
+        assert_eq!(unlimited.memory_bytes(), None);
+    }
+
+    #[test]
+    fn test_resolve_data_dir() {
+        let configured = PathBuf::from("/config");
+        let env = Some(OsString::from("/env"));
+        let explicit = Some(PathBuf::from("/flag"));
+
+        assert_eq!(
+            resolve_data_dir(explicit, env.clone(), configured.clone()),
+            PathBuf::from("/flag")
+        );
+        assert_eq!(
+            resolve_data_dir(None, env, configured.clone()),
+            PathBuf::from("/env")
+        );
+        assert_eq!(
+            resolve_data_dir(None, Some(OsString::new()), configured.clone()),
+            configured
+        );
+    }
+
+    #[test]
+    fn test_resolve_docker_host() {
+        let env = Some("tcp://10.0.0.2:2375".to_string());
+        assert_eq!(
+            resolve_docker_host([Some("unix:///tmp/docker.sock"), None], env.clone()).as_deref(),
+            Some("unix:///tmp/docker.sock")
+        );
+        assert_eq!(
+            resolve_docker_host([Some(""), Some("/var/run/docker.sock")], env.clone()).as_deref(),
+            Some("/var/run/docker.sock")
+        );
+        assert_eq!(
+            resolve_docker_host([None, None], env).as_deref(),
+            Some("tcp://10.0.0.2:2375")
+        );
+        assert_eq!(resolve_docker_host([None, None], Some(String::new())), None);
+    }
+
+    #[test]
+    fn test_bitcoin_cli_command_uses_credentials() {
+        let cmd = RpcCredentials::new("alice", "secret").bitcoin_cli_command(&["getblockcount"]);
+        assert_eq!(
+            cmd,
+            [
+                "bitcoin-cli",
+                "-regtest",
+                "-rpcuser=alice",
+                "-rpcpassword=secret",
+                "getblockcount"
+            ]
+        );
+    }
+}
@@ -16,6 +16,78 @@ pub enum Error {
     #[error("node not found: {0}")]
     NodeNotFound(String),
 
+    /// Node exists but its container is not running.
+    #[error("node not running: {0}")]
+    NodeNotRunning(String),
+
+    /// Not enough funds for the requested operation.
+    #[error("insufficient funds on {node}: have {have} sats, need {need} sats")]
+    InsufficientFunds {
+        /// Node whose wallet or channel is short of funds.
+        node: String,
+        /// Spendable balance in satoshis.
+        have: u64,
+        /// Amount required in satoshis.
+        need: u64,
+    },
+
+    /// A payment failed, with LND's failure reason.
+    #[error("payment failed: {0}")]
+    PaymentFailed(String),
+
+    /// A payment neither succeeded nor failed in time.
+    #[error("payment timed out after {0}s")]
+    PaymentTimeout(u64),
+
+    /// A channel point that is not `txid:index`.
+    #[error("invalid channel point {0}")]
+    InvalidChannelPoint(String),
+
+    /// No payment route between two nodes.
+    #[error("no route from {from} to {to}")]
+    NoRoute {
+        /// Paying node.
+        from: String,
+        /// Destination node.
+        to: String,
+    },
+
+    /// An LND node's chain height did not follow newly mined blocks.
+    #[error("LND not receiving block notifications on {0} — check ZMQ")]
+    NoBlockNotifications(String),
+
+    /// Some nodes of a network could not be stopped; the others were.
+    #[error(
+        "failed to stop {} in network {network}",
+        failures.iter().map(|(node, e)| format!("{node} ({e})")).collect::<Vec<_>>().join(", ")
+    )]
+    StopFailed {
+        /// Network being stopped.
+        network: String,
+        /// Nodes that are still running, with the error each one hit.
+        failures: Vec<(String, String)>,
+    },
+
+    /// Output of a node RPC or CLI command could not be parsed.
+    #[error("rpc parse error: {0}")]
+    RpcParse(String),
+
+    /// A node rejected an argument of a request, e.g. a malformed invoice.
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// A command executed inside a container reported a failure.
+    #[error("container exec error: {0}")]
+    ContainerExec(String),
+
+    /// A request to a node's HTTP API failed.
+    #[error("http error: {0}")]
+    Http(String),
+
+    /// An operation did not complete before its deadline.
+    #[error("timed out: {0}")]
+    Timeout(String),
+
     /// Docker error.
     #[error("docker error: {0}")]
     Docker(String),
@@ -3,12 +3,26 @@
 //! This crate provides shared data structures, configuration management,
 //! and error types used across the polar workspace.
 
+mod channel_point;
 mod config;
 mod error;
 mod network;
 mod node_info;
+mod preimage;
 
-pub use config::Config;
+pub use channel_point::ChannelPoint;
+pub use config::{
+    Config, DEFAULT_RPC_PASS, DEFAULT_RPC_USER, NodeResourceLimits, ResourceLimits, RpcCredentials,
+};
 pub use error::{Error, Result};
-pub use network::{LightningImpl, Network, NetworkStatus, Node, NodeKind, NodePorts, PortConfig};
-pub use node_info::{BitcoinNodeInfo, ChannelInfo, LndNodeInfo, NodeInfo};
+pub use network::{
+    ExtraArgs, LightningImpl, NETWORK_SCHEMA_VERSION, Network, NetworkStatus, Node, NodeKind,
+    NodePorts, NodeStatus, PortConfig, Topology,
+};
+pub use node_info::{
+    BitcoinNodeInfo, BitcoinPeerInfo, ChainTip, ChannelInfo, DecodedInvoice, GraphEdge, GraphInfo,
+    GraphNode, HtlcAttempt, InvoiceInfo, LndNodeInfo, MempoolEntry, MempoolInfo,
+    MessageVerification, NodeInfo, PaymentHistory, PaymentInfo, PaymentUpdate, PeerInfo,
+    PendingChannelInfo, PendingChannelState, RouteEstimate, RoutingPolicy, TowerInfo, UtxoInfo,
+};
+pub use preimage::Preimage;
@@ -1,12 +1,24 @@
 //! Network and node types.
 
+use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// On-disk schema version written with every saved [`Network`].
+///
+/// Version 1 covers every file saved before versioning, version 2 adds the
+/// `schema_version` field itself. Bump this and add a step to
+/// [`Network::migrate`] whenever a change to the saved shape needs more than
+/// a `#[serde(default)]` to load older files.
+pub const NETWORK_SCHEMA_VERSION: u32 = 2;
+
 /// A Lightning Network development environment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Network {
+    /// On-disk schema version, see [`NETWORK_SCHEMA_VERSION`].
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     /// Unique identifier.
     pub id: Uuid,
     /// Human-readable name.
@@ -27,6 +39,40 @@ pub struct Network {
     /// Port mappings for nodes (node_id -> PortConfig)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub port_mappings: HashMap<Uuid, PortConfig>,
+    /// Extra daemon flags passed to every node of the network.
+    #[serde(default, skip_serializing_if = "ExtraArgs::is_empty")]
+    pub extra_args: ExtraArgs,
+    /// Channel topology to wire up on the next start, cleared once applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topology: Option<Topology>,
+    /// Existing user-defined Docker network to attach containers to instead
+    /// of creating a `polar-<id>` one. Polar never creates or removes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_network: Option<String>,
+}
+
+/// Schema version of network files saved before versioning was added.
+const fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// Extra daemon flags appended to the generated node command lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraArgs {
+    /// Flags for every lnd node, e.g. `--protocol.wumbo-channels`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lnd: Vec<String>,
+    /// Flags for every bitcoind node, e.g. `-txindex=1`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bitcoin: Vec<String>,
+}
+
+impl ExtraArgs {
+    /// Whether no extra flags are set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lnd.is_empty() && self.bitcoin.is_empty()
+    }
 }
 
 /// Port configuration for a node.
@@ -66,6 +112,7 @@ impl Network {
     /// Create a new network with the given name.
     pub fn new(name: impl Into<String>) -> Self {
         Self {
+            schema_version: NETWORK_SCHEMA_VERSION,
             id: Uuid::new_v4(),
             name: name.into(),
             status: NetworkStatus::Stopped,
@@ -74,7 +121,47 @@ impl Network {
             btc_version: None,
             alias_prefix: None,
             port_mappings: HashMap::new(),
+            extra_args: ExtraArgs::default(),
+            topology: None,
+            external_network: None,
+        }
+    }
+
+    /// Name of the Docker network the network's containers are attached to.
+    #[must_use]
+    pub fn docker_network_name(&self) -> String {
+        self.external_network
+            .clone()
+            .unwrap_or_else(|| format!("polar-{}", self.id))
+    }
+
+    /// Upgrade a saved network to [`NETWORK_SCHEMA_VERSION`] in place.
+    ///
+    /// Works on the raw JSON so fields can be renamed or filled before the
+    /// typed deserialization runs. Files without a `schema_version` are
+    /// treated as version 1. Returns the version the file was migrated from,
+    /// or `None` if it was already current.
+    pub fn migrate(value: &mut serde_json::Value) -> Result<Option<u32>> {
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| Error::Config("network file is not a JSON object".to_string()))?;
+        let version = object
+            .get("schema_version")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(Ok(legacy_schema_version()), u32::try_from)
+            .map_err(|_| Error::Config("invalid network schema version".to_string()))?;
+
+        if version > NETWORK_SCHEMA_VERSION {
+            return Err(Error::Config(format!(
+                "network schema version {version} is newer than supported version {NETWORK_SCHEMA_VERSION}"
+            )));
         }
+        if version == NETWORK_SCHEMA_VERSION {
+            return Ok(None);
+        }
+
+        object.insert("schema_version".to_string(), NETWORK_SCHEMA_VERSION.into());
+        Ok(Some(version))
     }
 
     /// Add a node to this network.
@@ -82,6 +169,99 @@ impl Network {
         self.nodes.push(node);
     }
 
+    /// Rename a node.
+    ///
+    /// Containers are named after the node ID (`polar-lnd-<id>`), not its
+    /// display name, so a rename never requires recreating a container.
+    /// Names must be unique within the network and may not contain
+    /// whitespace or parentheses, which the TUI uses to show the node kind.
+    pub fn rename_node(&mut self, old_name: &str, new_name: &str) -> Result<()> {
+        check_node_name(new_name)?;
+        if new_name != old_name && self.nodes.iter().any(|n| n.name == new_name) {
+            return Err(Error::Config(format!(
+                "Node '{new_name}' already exists in network '{}'",
+                self.name
+            )));
+        }
+
+        let node = self
+            .nodes
+            .iter_mut()
+            .find(|n| n.name == old_name)
+            .ok_or_else(|| Error::NodeNotFound(old_name.to_string()))?;
+        node.name = new_name.to_string();
+        Ok(())
+    }
+
+    /// Adopt a container started outside polar as a node.
+    ///
+    /// An existing node of the same kind takes over the container, otherwise
+    /// a new node is added. Either way the node is marked as external.
+    pub fn attach_container(
+        &mut self,
+        node_name: &str,
+        kind: NodeKind,
+        container_id: String,
+    ) -> Result<()> {
+        if let Some(node) = self.nodes.iter_mut().find(|n| n.name == node_name) {
+            if node.kind != kind {
+                return Err(Error::Config(format!(
+                    "Node '{node_name}' is a {} node, not {kind}",
+                    node.kind
+                )));
+            }
+            node.container_id = Some(container_id);
+            node.external = true;
+            return Ok(());
+        }
+
+        check_node_name(node_name)?;
+        let mut node = Node::new(node_name, kind);
+        node.container_id = Some(container_id);
+        node.external = true;
+        self.add_node(node);
+        Ok(())
+    }
+
+    /// Get the primary Bitcoin Core node that LND nodes are wired to.
+    ///
+    /// This is the first Bitcoin Core node added to the network.
+    #[must_use]
+    pub fn primary_bitcoin_node(&self) -> Option<&Node> {
+        self.nodes.iter().find(|n| n.kind == NodeKind::BitcoinCore)
+    }
+
+    /// Get a copy of this network suitable for sharing as a portable file.
+    ///
+    /// Container IDs are stripped and the status is reset to stopped, since
+    /// neither is meaningful on another machine.
+    #[must_use]
+    pub fn to_portable(&self) -> Self {
+        let mut network = self.clone();
+        network.status = NetworkStatus::Stopped;
+        for node in &mut network.nodes {
+            node.container_id = None;
+        }
+        network
+    }
+
+    /// Prepare an imported network for use on this machine.
+    ///
+    /// Assigns fresh network and node IDs, so container names and data
+    /// volumes never collide with the network the file was exported from,
+    /// and clears port mappings so they are re-allocated on next start
+    /// instead of conflicting with existing networks. Channels and peers
+    /// refer to nodes by name and stay valid.
+    pub fn prepare_import(&mut self) {
+        self.id = Uuid::new_v4();
+        self.status = NetworkStatus::Stopped;
+        self.port_mappings.clear();
+        for node in &mut self.nodes {
+            node.id = Uuid::new_v4();
+            node.container_id = None;
+        }
+    }
+
     /// Allocate ports for a new node, avoiding conflicts with existing nodes.
     pub fn allocate_ports(&mut self, node_id: Uuid, kind: NodeKind) -> PortConfig {
         let base_port = self.find_next_available_base_port();
@@ -156,6 +336,21 @@ pub enum NetworkStatus {
     Error,
 }
 
+/// Runtime state of a node's container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    /// No container has been started for the node.
+    NotStarted,
+    /// Container is running.
+    Running,
+    /// Container exited with the given code.
+    Exited(i64),
+    /// Container is restarting.
+    Restarting,
+    /// Container state could not be determined.
+    Unknown,
+}
+
 /// A node in a Lightning Network.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Node {
@@ -167,6 +362,17 @@ pub struct Node {
     pub kind: NodeKind,
     /// Docker container ID (if running).
     pub container_id: Option<String>,
+    /// Whether the container was started outside polar and adopted, so
+    /// polar never creates, stops or removes it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
+    /// Environment variables set on the node's container when it is created.
+    ///
+    /// They override variables of the same name baked into the image, but
+    /// flags on the generated command line, extra args included, still win
+    /// for any setting the daemon reads from both.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<(String, String)>,
 }
 
 impl Node {
@@ -177,6 +383,8 @@ impl Node {
             name: name.into(),
             kind,
             container_id: None,
+            external: false,
+            env: Vec::new(),
         }
     }
 }
@@ -211,6 +419,54 @@ impl std::fmt::Display for LightningImpl {
     }
 }
 
+/// Shape of the channel graph between a network's LND nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Topology {
+    /// Each node opens a channel to the next one.
+    Line,
+    /// A line whose last node opens a channel back to the first.
+    Ring,
+    /// The first node opens a channel to every other node.
+    Star,
+    /// Every pair of nodes shares a channel.
+    Mesh,
+}
+
+impl Topology {
+    /// Get all available topologies.
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[Self::Line, Self::Ring, Self::Star, Self::Mesh]
+    }
+
+    /// Channels connecting `count` nodes, as (opener, peer) indices.
+    ///
+    /// A ring of two nodes is a single channel, like a line.
+    #[must_use]
+    pub fn channels(self, count: usize) -> Vec<(usize, usize)> {
+        match self {
+            Self::Line => (1..count).map(|i| (i - 1, i)).collect(),
+            Self::Ring if count > 2 => (0..count).map(|i| (i, (i + 1) % count)).collect(),
+            Self::Ring => Self::Line.channels(count),
+            Self::Star => (1..count).map(|i| (0, i)).collect(),
+            Self::Mesh => (0..count)
+                .flat_map(|i| (i + 1..count).map(move |j| (i, j)))
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for Topology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Line => write!(f, "Line"),
+            Self::Ring => write!(f, "Ring"),
+            Self::Star => write!(f, "Star"),
+            Self::Mesh => write!(f, "Mesh"),
+        }
+    }
+}
+
 /// Type of node.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NodeKind {
@@ -235,3 +491,185 @@ impl std::fmt::Display for NodeKind {
         }
     }
 }
+
+/// Reject node names the TUI cannot display unambiguously.
+fn check_node_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name
+            .chars()
+            .any(|c| c.is_whitespace() || c == '(' || c == ')')
+    {
+        return Err(Error::Config(format!("Invalid node name '{name}'")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A network file as saved before schema versioning was added.
+    const V1_NETWORK: &str = r#"{
+        "id": "6f1c2a8e-3b4d-4e5f-8a9b-0c1d2e3f4a5b",
+        "name": "legacy",
+        "status": "Stopped",
+        "nodes": [
+            {
+                "id": "0a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d",
+                "name": "bitcoin-1",
+                "kind": "BitcoinCore",
+                "container_id": null
+            },
+            {
+                "id": "1b2c3d4e-5f6a-4b7c-9d8e-0f1a2b3c4d5e",
+                "name": "lnd-1",
+                "kind": "Lnd",
+                "container_id": "abc123"
+            }
+        ],
+        "lnd_version": "polarlightning/lnd:0.18.3-beta",
+        "alias_prefix": "legacy"
+    }"#;
+
+    #[test]
+    fn test_migrate_v1_network() {
+        // Unversioned files load as version 1
+        let unmigrated: Network = serde_json::from_str(V1_NETWORK).unwrap();
+        assert_eq!(unmigrated.schema_version, 1);
+
+        // Migrating only stamps the current version, the rest loads as saved
+        let mut value: serde_json::Value = serde_json::from_str(V1_NETWORK).unwrap();
+        assert_eq!(Network::migrate(&mut value).unwrap(), Some(1));
+        assert_eq!(value["schema_version"], NETWORK_SCHEMA_VERSION);
+
+        let network: Network = serde_json::from_value(value).unwrap();
+        assert_eq!(network.schema_version, NETWORK_SCHEMA_VERSION);
+        assert_eq!(network.name, "legacy");
+        assert_eq!(network.nodes.len(), 2);
+        assert_eq!(network.nodes[1].kind, NodeKind::Lnd);
+        assert_eq!(network.nodes[1].container_id.as_deref(), Some("abc123"));
+        assert_eq!(network.alias_prefix.as_deref(), Some("legacy"));
+
+        // A current file is left alone, a newer one is rejected
+        let mut current = serde_json::to_value(&network).unwrap();
+        assert_eq!(Network::migrate(&mut current).unwrap(), None);
+        current["schema_version"] = (NETWORK_SCHEMA_VERSION + 1).into();
+        assert!(Network::migrate(&mut current).is_err());
+    }
+
+    #[test]
+    fn test_port_mappings_survive_reload() {
+        let mut network = Network::new("ports");
+        let btc = Node::new("bitcoin-1", NodeKind::BitcoinCore);
+        let lnd = Node::new("lnd-1", NodeKind::Lnd);
+        let btc_ports = network.allocate_ports(btc.id, btc.kind).get_all_ports();
+        let lnd_ports = network.allocate_ports(lnd.id, lnd.kind).get_all_ports();
+        network.add_node(btc.clone());
+        network.add_node(lnd.clone());
+
+        let json = serde_json::to_string(&network).unwrap();
+        let reloaded: Network = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(reloaded.port_mappings[&btc.id].get_all_ports(), btc_ports);
+        assert_eq!(reloaded.port_mappings[&lnd.id].get_all_ports(), lnd_ports);
+    }
+
+    #[test]
+    fn test_docker_network_name() {
+        let mut network = Network::new("external");
+        assert_eq!(
+            network.docker_network_name(),
+            format!("polar-{}", network.id)
+        );
+
+        network.external_network = Some("ci-net".to_string());
+        let json = serde_json::to_string(&network).unwrap();
+        let reloaded: Network = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.docker_network_name(), "ci-net");
+    }
+
+    #[test]
+    fn test_rename_node() {
+        let mut network = Network::new("rename");
+        network.add_node(Node::new("bitcoin-1", NodeKind::BitcoinCore));
+        network.add_node(Node::new("lnd-1", NodeKind::Lnd));
+        let id = network.nodes[1].id;
+
+        network.rename_node("lnd-1", "alice").unwrap();
+        assert_eq!(network.nodes[1].name, "alice");
+        assert_eq!(network.nodes[1].id, id);
+
+        assert!(network.rename_node("alice", "bitcoin-1").is_err());
+        assert!(network.rename_node("alice", "bob (LND)").is_err());
+        assert!(network.rename_node("alice", "").is_err());
+        assert!(matches!(
+            network.rename_node("carol", "dave"),
+            Err(Error::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_attach_container() {
+        let mut network = Network::new("test");
+        network.add_node(Node::new("bitcoin-1", NodeKind::BitcoinCore));
+
+        network
+            .attach_container("bitcoin-1", NodeKind::BitcoinCore, "abc".to_string())
+            .unwrap();
+        let node = &network.nodes[0];
+        assert_eq!(node.container_id.as_deref(), Some("abc"));
+        assert!(node.external);
+
+        network
+            .attach_container("manual", NodeKind::Lnd, "def".to_string())
+            .unwrap();
+        assert_eq!(network.nodes.len(), 2);
+        assert!(network.nodes[1].external);
+
+        assert!(
+            network
+                .attach_container("bitcoin-1", NodeKind::Lnd, "ghi".to_string())
+                .is_err()
+        );
+        assert!(
+            network
+                .attach_container("bad name", NodeKind::Lnd, "ghi".to_string())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_topology_channels() {
+        assert_eq!(Topology::Line.channels(3), vec![(0, 1), (1, 2)]);
+        assert_eq!(Topology::Ring.channels(3), vec![(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(Topology::Ring.channels(2), vec![(0, 1)]);
+        assert_eq!(Topology::Star.channels(4), vec![(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(Topology::Mesh.channels(3), vec![(0, 1), (0, 2), (1, 2)]);
+        assert!(Topology::Ring.channels(1).is_empty());
+    }
+
+    #[test]
+    fn test_portable_round_trip() {
+        let mut network = Network::new("shared");
+        let mut lnd = Node::new("lnd-1", NodeKind::Lnd);
+        lnd.container_id = Some("abc123".to_string());
+        network.allocate_ports(lnd.id, lnd.kind);
+        network.add_node(lnd);
+        network.status = NetworkStatus::Running;
+
+        let portable = network.to_portable();
+        assert_eq!(portable.status, NetworkStatus::Stopped);
+        assert!(portable.nodes.iter().all(|n| n.container_id.is_none()));
+
+        let json = serde_json::to_string(&portable).unwrap();
+        let mut imported: Network = serde_json::from_str(&json).unwrap();
+        imported.prepare_import();
+
+        assert_ne!(imported.id, network.id);
+        assert_eq!(imported.name, "shared");
+        assert_eq!(imported.nodes.len(), 1);
+        assert_ne!(imported.nodes[0].id, network.nodes[0].id);
+        assert_eq!(imported.nodes[0].name, "lnd-1");
+        assert!(imported.port_mappings.is_empty());
+    }
+}
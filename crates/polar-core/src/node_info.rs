@@ -23,15 +23,107 @@ pub struct BitcoinNodeInfo {
     pub rpc_host: String,
     /// P2P host:port.
     pub p2p_host: String,
+    /// Known chain tips; more than one means the node has seen a fork.
+    #[serde(default)]
+    pub chain_tips: Vec<ChainTip>,
+    /// Connected P2P peers.
+    #[serde(default)]
+    pub peers: Vec<BitcoinPeerInfo>,
+    /// Number of transactions in the mempool.
+    #[serde(default)]
+    pub mempool_size: u64,
+    /// Transactions in the mempool, oldest first.
+    #[serde(default)]
+    pub mempool: Vec<MempoolEntry>,
+}
+
+/// An unconfirmed transaction from `bitcoin-cli getrawmempool true`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolEntry {
+    /// Transaction ID.
+    pub txid: String,
+    /// Fee paid in satoshis.
+    pub fee_sat: u64,
+    /// Virtual size in vbytes.
+    pub vsize: u64,
+    /// Unix time the transaction entered the mempool.
+    pub time: u64,
+}
+
+impl MempoolEntry {
+    /// Fee rate in sat/vB.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fee_rate(&self) -> f64 {
+        if self.vsize == 0 {
+            0.0
+        } else {
+            self.fee_sat as f64 / self.vsize as f64
+        }
+    }
+}
+
+/// Mempool summary from `bitcoin-cli getmempoolinfo`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MempoolInfo {
+    /// Number of transactions.
+    pub size: u64,
+    /// Sum of the virtual sizes of all transactions.
+    pub bytes: u64,
+    /// Sum of all fees in BTC.
+    #[serde(default)]
+    pub total_fee: f64,
+}
+
+/// A P2P peer as reported by `bitcoin-cli getpeerinfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitcoinPeerInfo {
+    /// Peer address as host:port.
+    pub addr: String,
+    /// User agent the peer announced.
+    #[serde(default)]
+    pub subver: String,
+    /// Whether the peer connected to this node.
+    pub inbound: bool,
+    /// Last header height in common with the peer, or -1 if unknown.
+    #[serde(default = "unknown_height")]
+    pub synced_headers: i64,
+    /// Last block height in common with the peer, or -1 if unknown.
+    #[serde(default = "unknown_height")]
+    pub synced_blocks: i64,
+}
+
+const fn unknown_height() -> i64 {
+    -1
+}
+
+/// A chain tip as reported by `bitcoin-cli getchaintips`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainTip {
+    /// Height of the tip.
+    pub height: u64,
+    /// Block hash of the tip.
+    pub hash: String,
+    /// Length of the branch connecting the tip to the active chain.
+    #[serde(rename = "branchlen")]
+    pub branch_len: u64,
+    /// Tip status, e.g. "active", "valid-fork" or "headers-only".
+    pub status: String,
 }
 
 /// Information about a Lightning channel.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelInfo {
+    /// Short channel ID.
+    #[serde(default)]
+    pub chan_id: String,
     /// Channel point (funding_txid:output_index).
     pub channel_point: String,
     /// Remote node public key.
     pub remote_pubkey: String,
+    /// Name of the remote node when it belongs to the same network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_name: Option<String>,
     /// Channel capacity in satoshis.
     pub capacity: i64,
     /// Local balance in satoshis.
@@ -40,6 +132,101 @@ pub struct ChannelInfo {
     pub remote_balance: i64,
     /// Whether the channel is active.
     pub active: bool,
+    /// Whether the remote peer is connected, so a cooperative close can proceed.
+    #[serde(default)]
+    pub peer_online: bool,
+    /// Commitment type, e.g. `ANCHORS` or `SIMPLE_TAPROOT`.
+    #[serde(default)]
+    pub commitment_type: String,
+    /// Whether the channel is private, i.e. not announced to the graph.
+    #[serde(default)]
+    pub private: bool,
+}
+
+/// Information about a connected Lightning peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerInfo {
+    /// Peer public key.
+    pub pubkey: String,
+    /// Name of the peer node when it belongs to the same network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Peer network address (host:port).
+    pub address: String,
+    /// Last ping round-trip time in microseconds.
+    pub ping_time: i64,
+    /// Satoshis sent to this peer.
+    pub sat_sent: i64,
+    /// Satoshis received from this peer.
+    pub sat_recv: i64,
+}
+
+/// A watchtower registered with an LND node's watchtower client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TowerInfo {
+    /// Watchtower public key.
+    pub pubkey: String,
+    /// Known addresses of the watchtower (host:port).
+    pub addresses: Vec<String>,
+    /// Number of sessions negotiated with the watchtower.
+    pub num_sessions: u32,
+}
+
+/// An unspent output in an LND node's on-chain wallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UtxoInfo {
+    /// Outpoint as `txid:output_index`.
+    pub outpoint: String,
+    /// Address the output pays to.
+    pub address: String,
+    /// Output value in satoshis.
+    pub amount_sat: u64,
+    /// Number of confirmations.
+    pub confirmations: u64,
+}
+
+/// State of a channel that is not yet open or not yet fully closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PendingChannelState {
+    /// Funding transaction is waiting for confirmations.
+    Opening,
+    /// Cooperative close is in progress.
+    Closing,
+    /// Channel was force-closed and funds are time-locked.
+    ForceClosing,
+    /// Closing transaction is waiting for confirmation.
+    WaitingClose,
+}
+
+impl std::fmt::Display for PendingChannelState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Opening => write!(f, "Opening"),
+            Self::Closing => write!(f, "Closing"),
+            Self::ForceClosing => write!(f, "Force Closing"),
+            Self::WaitingClose => write!(f, "Waiting Close"),
+        }
+    }
+}
+
+/// Information about a pending Lightning channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingChannelInfo {
+    /// Channel point (`funding_txid:output_index`).
+    pub channel_point: String,
+    /// Remote node public key.
+    pub remote_pubkey: String,
+    /// Name of the remote node when it belongs to the same network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_name: Option<String>,
+    /// Channel capacity in satoshis.
+    pub capacity: i64,
+    /// Local balance in satoshis.
+    pub local_balance: i64,
+    /// Pending state of the channel.
+    pub state: PendingChannelState,
+    /// Blocks until time-locked funds mature (force-closing channels only).
+    pub blocks_til_maturity: Option<i32>,
 }
 
 /// Information about an LND node.
@@ -63,18 +250,271 @@ pub struct LndNodeInfo {
     pub synced_to_graph: bool,
     /// Block height.
     pub block_height: u32,
+    /// Blocks this node trails the network's Bitcoin node by, when known.
+    #[serde(default)]
+    pub chain_height_drift: Option<u64>,
     /// Block hash.
     pub block_hash: String,
-    /// Wallet balance (satoshis).
-    pub wallet_balance: i64,
-    /// Channel balance (satoshis).
-    pub channel_balance: i64,
+    /// Confirmed on-chain wallet balance (satoshis).
+    pub wallet_confirmed: i64,
+    /// Unconfirmed on-chain wallet balance (satoshis).
+    pub wallet_unconfirmed: i64,
+    /// On-chain balance locked by pending transactions (satoshis).
+    pub wallet_locked: i64,
+    /// Local balance of open channels (satoshis).
+    pub channel_local: i64,
+    /// Remote balance of open channels (satoshis).
+    pub channel_remote: i64,
+    /// Local balance of channels pending open (satoshis).
+    pub channel_pending: i64,
     /// REST API host:port.
     pub rest_host: String,
     /// gRPC host:port.
     pub grpc_host: String,
     /// List of active channels.
     pub channels: Vec<ChannelInfo>,
+    /// List of pending (opening or closing) channels.
+    pub pending_channels: Vec<PendingChannelInfo>,
+    /// List of connected peers.
+    pub peers: Vec<PeerInfo>,
+    /// Watchtowers registered with the watchtower client.
+    #[serde(default)]
+    pub towers: Vec<TowerInfo>,
+}
+
+/// A node in the Lightning Network channel graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Node public key.
+    pub pubkey: String,
+    /// Node alias as announced to the graph.
+    pub alias: String,
+}
+
+/// Fees and time lock one side of a channel charges to forward over it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingPolicy {
+    /// Fixed fee per forwarded HTLC in millisatoshis.
+    pub base_fee_msat: u64,
+    /// Proportional fee in parts per million.
+    pub fee_rate_ppm: u64,
+    /// CLTV delta required by the forwarding node.
+    pub time_lock_delta: u32,
+}
+
+/// A channel edge in the Lightning Network channel graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// Short channel ID.
+    pub channel_id: String,
+    /// Channel point (`funding_txid:output_index`).
+    #[serde(default)]
+    pub channel_point: String,
+    /// Public key of the first node.
+    pub node1_pubkey: String,
+    /// Public key of the second node.
+    pub node2_pubkey: String,
+    /// Channel capacity in satoshis.
+    pub capacity: i64,
+    /// Policy of the first node, once it has announced one.
+    #[serde(default)]
+    pub node1_policy: Option<RoutingPolicy>,
+    /// Policy of the second node, once it has announced one.
+    #[serde(default)]
+    pub node2_policy: Option<RoutingPolicy>,
+}
+
+/// Snapshot of the channel graph as seen by one LND node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphInfo {
+    /// Nodes known to the graph.
+    pub nodes: Vec<GraphNode>,
+    /// Channels known to the graph.
+    pub edges: Vec<GraphEdge>,
+}
+
+impl GraphInfo {
+    /// Look up a node alias by public key.
+    #[must_use]
+    pub fn alias_for(&self, pubkey: &str) -> Option<&str> {
+        self.nodes
+            .iter()
+            .find(|n| n.pubkey == pubkey)
+            .map(|n| n.alias.as_str())
+            .filter(|alias| !alias.is_empty())
+    }
+}
+
+/// An invoice created by an LND node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceInfo {
+    /// Payment hash (hex).
+    pub payment_hash: String,
+    /// Invoice memo.
+    pub memo: String,
+    /// Requested amount in satoshis.
+    pub amount_sat: i64,
+    /// Amount received in satoshis.
+    pub amount_paid_sat: i64,
+    /// Invoice state, e.g. `OPEN`, `SETTLED` or `CANCELED`.
+    pub state: String,
+    /// Creation time as a Unix timestamp.
+    pub creation_date: i64,
+}
+
+/// An outgoing payment made by an LND node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentInfo {
+    /// Payment hash (hex).
+    pub payment_hash: String,
+    /// Amount sent in satoshis, excluding fees.
+    pub amount_sat: i64,
+    /// Routing fee paid in satoshis.
+    pub fee_sat: i64,
+    /// Payment status, e.g. `SUCCEEDED`, `IN_FLIGHT` or `FAILED`.
+    pub status: String,
+    /// Number of settled HTLCs the payment was split into.
+    #[serde(default)]
+    pub parts: usize,
+    /// Creation time as a Unix timestamp.
+    pub creation_date: i64,
+}
+
+/// Recent invoices and outgoing payments of one LND node, newest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaymentHistory {
+    /// Name of the node the history belongs to.
+    pub node: String,
+    /// Invoices created by the node.
+    pub invoices: Vec<InvoiceInfo>,
+    /// Payments sent by the node.
+    pub payments: Vec<PaymentInfo>,
+}
+
+/// A candidate payment route found by `lncli queryroutes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteEstimate {
+    /// Routing fees along the route in millisatoshis.
+    pub total_fees_msat: u64,
+    /// Amount sent including fees in millisatoshis.
+    pub total_amt_msat: u64,
+    /// Number of channels the payment crosses.
+    pub hops: usize,
+    /// Absolute block height the first HTLC times out at.
+    pub total_time_lock: u32,
+}
+
+impl RouteEstimate {
+    /// Routing fees rounded up to whole satoshis.
+    #[must_use]
+    pub const fn total_fees_sat(&self) -> u64 {
+        self.total_fees_msat.div_ceil(1000)
+    }
+}
+
+/// One HTLC attempt made while sending a payment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HtlcAttempt {
+    /// Attempt status, e.g. `IN_FLIGHT`, `SUCCEEDED` or `FAILED`.
+    pub status: String,
+    /// Number of channels the attempt's route crosses.
+    pub hops: usize,
+    /// Amount sent including fees in millisatoshis.
+    pub total_amt_msat: u64,
+    /// Failure code of a failed attempt, e.g. `TEMPORARY_CHANNEL_FAILURE`.
+    pub failure: Option<String>,
+}
+
+/// A status update of a payment while it is being sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentUpdate {
+    /// Payment hash (hex).
+    pub payment_hash: String,
+    /// Payment status, e.g. `IN_FLIGHT`, `SUCCEEDED` or `FAILED`.
+    pub status: String,
+    /// Why the payment failed, if it did.
+    pub failure_reason: Option<String>,
+    /// HTLC attempts made so far, oldest first.
+    pub attempts: Vec<HtlcAttempt>,
+}
+
+impl PaymentUpdate {
+    /// Number of attempts that failed.
+    #[must_use]
+    pub fn failed_attempts(&self) -> usize {
+        self.attempts
+            .iter()
+            .filter(|attempt| attempt.status == "FAILED")
+            .count()
+    }
+}
+
+impl std::fmt::Display for PaymentUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.status)?;
+        if let Some(attempt) = self.attempts.last() {
+            write!(
+                f,
+                ", attempt {} over {} hops",
+                self.attempts.len(),
+                attempt.hops
+            )?;
+        }
+        let failed = self.failed_attempts();
+        if failed > 0 {
+            write!(f, ", {failed} failed")?;
+            if let Some(failure) = self.attempts.iter().rev().find_map(|a| a.failure.as_ref()) {
+                write!(f, " (last: {failure})")?;
+            }
+        }
+        if let Some(reason) = &self.failure_reason {
+            write!(f, ": {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A decoded bolt11 payment request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedInvoice {
+    /// Destination node public key.
+    pub destination: String,
+    /// Payment hash.
+    pub payment_hash: String,
+    /// Payment secret the final hop must carry, hex encoded.
+    #[serde(default)]
+    pub payment_addr: String,
+    /// Amount in satoshis (0 for any-amount invoices).
+    pub num_satoshis: u64,
+    /// Invoice memo.
+    pub description: String,
+    /// Creation time as a unix timestamp.
+    pub timestamp: u64,
+    /// Seconds after `timestamp` until the invoice expires.
+    pub expiry: u64,
+}
+
+impl DecodedInvoice {
+    /// Unix timestamp at which the invoice expires.
+    #[must_use]
+    pub const fn expires_at(&self) -> u64 {
+        self.timestamp.saturating_add(self.expiry)
+    }
+
+    /// Whether the invoice has expired at the given unix time.
+    #[must_use]
+    pub const fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at()
+    }
+}
+
+/// Result of checking a signed message against the node's graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageVerification {
+    /// Whether the signature is valid and the signer is a known node.
+    pub valid: bool,
+    /// Public key recovered from the signature.
+    pub pubkey: String,
 }
 
 /// Unified node information.
@@ -85,3 +525,14 @@ pub enum NodeInfo {
     /// LND node information.
     Lnd(LndNodeInfo),
 }
+
+impl NodeInfo {
+    /// Host endpoints an external client can connect to, as (label, `host:port`).
+    #[must_use]
+    pub fn endpoints(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            Self::Bitcoin(info) => vec![("RPC", &info.rpc_host), ("P2P", &info.p2p_host)],
+            Self::Lnd(info) => vec![("REST", &info.rest_host), ("gRPC", &info.grpc_host)],
+        }
+    }
+}
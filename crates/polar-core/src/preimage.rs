@@ -0,0 +1,66 @@
+//! Payment preimage generation for hold invoices.
+//!
+//! Hold invoices are created from a payment hash only, so the caller owns the
+//! preimage and must keep it around to settle the invoice later.
+
+use sha2::{Digest, Sha256};
+
+/// A payment preimage and its SHA-256 payment hash, both hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preimage {
+    /// 32-byte preimage revealed when settling.
+    pub preimage: String,
+    /// SHA-256 of the preimage, used to create the hold invoice.
+    pub payment_hash: String,
+}
+
+impl Preimage {
+    /// Generate a random preimage and its payment hash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator fails
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        getrandom::fill(&mut bytes).expect("OS random number generator should be available");
+        Self::from_bytes(&bytes)
+    }
+
+    /// Build a preimage/hash pair from known preimage bytes.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self {
+            preimage: to_hex(bytes),
+            payment_hash: to_hex(&Sha256::digest(bytes)),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    bytes.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preimage_hash_pair() {
+        let pair = Preimage::from_bytes(&[0u8; 32]);
+        assert_eq!(pair.preimage, "0".repeat(64));
+        assert_eq!(
+            pair.payment_hash,
+            "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925"
+        );
+
+        let random = Preimage::generate();
+        assert_eq!(random.preimage.len(), 64);
+        assert_ne!(random.preimage, Preimage::generate().preimage);
+    }
+}
@@ -6,26 +6,252 @@ use bollard::container::{
     Config, CreateContainerOptions, RemoveContainerOptions, StartContainerOptions,
     StopContainerOptions,
 };
-use polar_core::{Error, Result};
+use bollard::models::{ContainerState, ContainerStateStatusEnum, CreateImageInfo};
+use polar_core::{Error, NodeStatus, ResourceLimits, Result};
+use std::time::Duration;
+
+/// Messages lnd, bitcoind and their CLIs emit while a node is still starting.
+const STARTUP_ERRORS: &[&str] = &[
+    "connection refused",
+    "could not connect to the server",
+    "error code: -28",
+    "loading block index",
+    "loading wallet",
+    "verifying blocks",
+    "rescanning",
+    "in the process of starting",
+    "rpc services not available",
+    "unable to read macaroon path",
+    "tls.cert: no such file",
+    "is restarting",
+];
+
+/// Check whether an exec failure or command output indicates a node that is
+/// still starting up, so the command is worth retrying.
+///
+/// JSON output is a successful response and never retryable. lncli errors
+/// start with `[lncli]`, which must not be mistaken for a JSON array.
+#[must_use]
+pub fn is_retryable_exec_error(message: &str) -> bool {
+    let trimmed = message.trim_start();
+    if trimmed.starts_with('{') || (trimmed.starts_with('[') && !trimmed.starts_with("[lncli]")) {
+        return false;
+    }
+    let message = message.to_lowercase();
+    STARTUP_ERRORS.iter().any(|error| message.contains(error))
+}
+
+/// Registry answers for images that can never be pulled as written.
+const PERMANENT_PULL_ERRORS: &[&str] = &[
+    "manifest unknown",
+    "not found",
+    "unauthorized",
+    "denied",
+    "invalid reference format",
+];
+
+/// Network failures that may clear up on another attempt.
+const TRANSIENT_PULL_ERRORS: &[&str] = &[
+    "connection reset",
+    "connection refused",
+    "timeout",
+    "timed out",
+    "unexpected eof",
+    "broken pipe",
+    "tls handshake",
+    "temporary failure",
+    "no such host",
+    "too many requests",
+    "toomanyrequests",
+    "service unavailable",
+    "bad gateway",
+];
+
+/// Longest pause between two attempts of [`ContainerManager::pull_image_with_progress`].
+const MAX_PULL_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Check whether an image pull failure is a network hiccup worth retrying.
+///
+/// Unknown manifests, denied access and bad references fail the same way on
+/// every attempt, so they are never retried even when the message also
+/// mentions a transient cause.
+#[must_use]
+pub fn is_retryable_pull_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    !PERMANENT_PULL_ERRORS
+        .iter()
+        .any(|error| message.contains(error))
+        && TRANSIENT_PULL_ERRORS
+            .iter()
+            .any(|error| message.contains(error))
+}
+
+/// Name of the data volume kept for the container called `container_name`.
+///
+/// Deriving it from the container name lets the volume be found again from
+/// just a container listing, e.g. when pruning orphaned containers.
+#[must_use]
+pub fn data_volume_name(container_name: &str) -> String {
+    format!("{container_name}-data")
+}
+
+/// Pass each complete line at the start of `pending` to `on_line`, leaving
+/// an unfinished last line for the next chunk of output.
+fn drain_lines(pending: &mut Vec<u8>, on_line: &mut impl FnMut(&str)) {
+    while let Some(end) = pending.iter().position(|&byte| byte == b'\n') {
+        let line: Vec<u8> = pending.drain(..=end).collect();
+        on_line(String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']));
+    }
+}
+
+/// Longest command output included in exec debug events, in characters.
+const MAX_LOGGED_OUTPUT: usize = 200;
+
+/// Join a command for logging, hiding the values of RPC password flags.
+fn display_command(cmd: &[&str]) -> String {
+    cmd.iter()
+        .map(|arg| match arg.split_once('=') {
+            Some((flag, _)) if flag.ends_with("rpcpassword") || flag.ends_with("rpcpass") => {
+                format!("{flag}=***")
+            }
+            _ => (*arg).to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cut command output down to [`MAX_LOGGED_OUTPUT`] characters for logging.
+fn truncate_output(output: &str) -> &str {
+    let output = output.trim();
+    output
+        .char_indices()
+        .nth(MAX_LOGGED_OUTPUT)
+        .map_or(output, |(end, _)| &output[..end])
+}
+
+/// Check whether a Docker host address should be reached over HTTP.
+fn is_http_host(host: &str) -> bool {
+    host.starts_with("tcp://") || host.starts_with("http://")
+}
+
+/// Get the host name of a remote Docker host address, where its containers
+/// publish their ports, or `None` for a local socket.
+#[must_use]
+pub fn docker_host_name(host: &str) -> Option<&str> {
+    let rest = host
+        .strip_prefix("tcp://")
+        .or_else(|| host.strip_prefix("http://"))?;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let name = authority
+        .rsplit_once(':')
+        .map_or(authority, |(name, _)| name);
+    (!name.is_empty()).then_some(name)
+}
+
+/// What a command run with [`ContainerManager::exec_command_output`] printed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecOutput {
+    /// stdout and stderr, interleaved as the command wrote them.
+    pub output: String,
+    /// stderr on its own.
+    pub stderr: String,
+    /// Exit code, `None` if Docker did not report one.
+    pub exit_code: Option<i64>,
+}
+
+/// Error for a command that exited with a non-zero `code`.
+///
+/// Carries stderr, or the whole output for commands that report errors on
+/// stdout.
+fn exit_code_error(program: &str, code: i64, output: &ExecOutput) -> Error {
+    let message = if output.stderr.trim().is_empty() {
+        output.output.trim()
+    } else {
+        output.stderr.trim()
+    };
+    Error::ContainerExec(format!("{program} exited with code {code}: {message}"))
+}
 
 /// Manages Docker containers for nodes.
+///
+/// Cloning is cheap and shares the underlying Docker connection.
+#[derive(Clone)]
 pub struct ContainerManager {
     docker: Docker,
+    /// Attempts after the first one for image pulls.
+    pull_retries: u32,
+    /// Pause before the first image pull retry.
+    pull_retry_delay: Duration,
 }
 
 impl ContainerManager {
+    /// Default retries for [`Self::exec_command_with_retry`].
+    pub const EXEC_RETRIES: u32 = 5;
+
+    /// Default first delay for [`Self::exec_command_with_retry`].
+    pub const EXEC_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+    /// Longest pause between two attempts of [`Self::exec_command_with_retry`].
+    pub const MAX_EXEC_RETRY_DELAY: Duration = Duration::from_secs(4);
+
+    /// Default retries for image pulls.
+    pub const PULL_RETRIES: u32 = 3;
+
+    /// Default first delay between image pull attempts.
+    pub const PULL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+    /// Wrap a Docker client with the default pull retries.
+    const fn from_docker(docker: Docker) -> Self {
+        Self {
+            docker,
+            pull_retries: Self::PULL_RETRIES,
+            pull_retry_delay: Self::PULL_RETRY_DELAY,
+        }
+    }
+
     /// Create a new container manager.
     pub fn new() -> Result<Self> {
         let docker =
             Docker::connect_with_local_defaults().map_err(|e| Error::Docker(e.to_string()))?;
-        Ok(Self { docker })
+        Ok(Self::from_docker(docker))
     }
 
     /// Create a new container manager with a custom socket path.
     pub fn with_socket(socket_path: &str) -> Result<Self> {
         let docker = Docker::connect_with_socket(socket_path, 120, bollard::API_DEFAULT_VERSION)
             .map_err(|e| Error::Docker(e.to_string()))?;
-        Ok(Self { docker })
+        Ok(Self::from_docker(docker))
+    }
+
+    /// Create a new container manager connected to a Docker daemon over HTTP.
+    ///
+    /// Accepts `tcp://host:port` or `http://host:port` addresses.
+    pub fn with_http(url: &str) -> Result<Self> {
+        let docker = Docker::connect_with_http(url, 120, bollard::API_DEFAULT_VERSION)
+            .map_err(|e| Error::Docker(e.to_string()))?;
+        Ok(Self::from_docker(docker))
+    }
+
+    /// Create a new container manager for a Docker host address.
+    ///
+    /// `tcp://` and `http://` hosts connect over HTTP, anything else is
+    /// treated as a socket path. `None` uses the local defaults.
+    pub fn from_host(host: Option<&str>) -> Result<Self> {
+        match host {
+            None => Self::new(),
+            Some(host) if is_http_host(host) => Self::with_http(host),
+            Some(socket_path) => Self::with_socket(socket_path),
+        }
+    }
+
+    /// Set how often image pulls are retried after transient failures.
+    ///
+    /// The delay doubles after every attempt, up to 30 seconds.
+    #[must_use]
+    pub const fn with_pull_retries(mut self, retries: u32, delay: Duration) -> Self {
+        self.pull_retries = retries;
+        self.pull_retry_delay = delay;
+        self
     }
 
     /// Get a reference to the Docker client.
@@ -40,11 +266,28 @@ impl ContainerManager {
         image: &str,
         cmd: Option<Vec<String>>,
     ) -> Result<String> {
-        self.create_container_with_config(name, image, cmd, None, None)
-            .await
+        self.create_container_with_config(
+            name,
+            image,
+            cmd,
+            None,
+            None,
+            ResourceLimits::default(),
+            &[],
+            &[],
+        )
+        .await
     }
 
     /// Create a container with advanced configuration.
+    ///
+    /// `limits` caps the container's CPU and memory; the default leaves it
+    /// unlimited. `env` holds `(name, value)` pairs set in the container's
+    /// environment on top of the image's own. `volumes` holds
+    /// `(volume, path)` pairs mounting named volumes into the container;
+    /// Docker creates missing volumes and keeps them after the container is
+    /// removed.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_container_with_config(
         &self,
         name: &str,
@@ -52,6 +295,9 @@ impl ContainerManager {
         cmd: Option<Vec<String>>,
         port_map: Option<PortMap>,
         network: Option<&str>,
+        limits: ResourceLimits,
+        env: &[(String, String)],
+        volumes: &[(String, String)],
     ) -> Result<String> {
         use bollard::service::{EndpointSettings, HostConfig};
         use std::collections::HashMap;
@@ -85,9 +331,24 @@ impl ContainerManager {
         let config = Config {
             image: Some(image.to_string()),
             cmd: cmd.map(|c| c.into_iter().collect()),
+            env: (!env.is_empty()).then(|| {
+                env.iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect()
+            }),
             exposed_ports,
             host_config: Some(HostConfig {
                 port_bindings,
+                nano_cpus: limits.nano_cpus(),
+                memory: limits.memory_bytes(),
+                // Without a matching swap limit the container could swap past its cap
+                memory_swap: limits.memory_bytes(),
+                binds: (!volumes.is_empty()).then(|| {
+                    volumes
+                        .iter()
+                        .map(|(volume, path)| format!("{volume}:{path}"))
+                        .collect()
+                }),
                 ..Default::default()
             }),
             ..Default::default()
@@ -136,6 +397,75 @@ impl ContainerManager {
         Ok(response.id)
     }
 
+    /// Check whether a Docker network with this name or ID exists.
+    pub async fn network_exists(&self, name: &str) -> Result<bool> {
+        use bollard::network::InspectNetworkOptions;
+
+        match self
+            .docker
+            .inspect_network(name, None::<InspectNetworkOptions<String>>)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(false),
+            Err(e) => Err(Error::Docker(format!(
+                "Failed to inspect network {name}: {e}"
+            ))),
+        }
+    }
+
+    /// Connect a running container to a Docker network, doing nothing if it
+    /// is already connected.
+    pub async fn connect_network(&self, name: &str, container_id: &str) -> Result<()> {
+        use bollard::network::ConnectNetworkOptions;
+
+        let info = self.inspect_container(container_id).await?;
+        let connected = info
+            .network_settings
+            .and_then(|s| s.networks)
+            .is_some_and(|networks| networks.contains_key(name));
+        if connected {
+            return Ok(());
+        }
+
+        self.docker
+            .connect_network(
+                name,
+                ConnectNetworkOptions {
+                    container: container_id,
+                    endpoint_config: bollard::models::EndpointSettings::default(),
+                },
+            )
+            .await
+            .map_err(|e| {
+                Error::Docker(format!(
+                    "Failed to connect {container_id} to network {name}: {e}"
+                ))
+            })
+    }
+
+    /// Disconnect a container from a Docker network.
+    pub async fn disconnect_network(&self, name: &str, container_id: &str) -> Result<()> {
+        use bollard::network::DisconnectNetworkOptions;
+
+        self.docker
+            .disconnect_network(
+                name,
+                DisconnectNetworkOptions {
+                    container: container_id,
+                    force: true,
+                },
+            )
+            .await
+            .map_err(|e| {
+                Error::Docker(format!(
+                    "Failed to disconnect {container_id} from network {name}: {e}"
+                ))
+            })
+    }
+
     /// Remove a Docker network.
     pub async fn remove_network(&self, name: &str) -> Result<()> {
         self.docker
@@ -154,22 +484,48 @@ impl ContainerManager {
         Ok(())
     }
 
-    /// Stop a container.
+    /// Stop a container, treating a container that no longer exists as stopped.
     pub async fn stop_container(&self, container_id: &str) -> Result<()> {
         let options = StopContainerOptions { t: 10 };
-        self.docker
+        match self
+            .docker
             .stop_container(container_id, Some(options))
             .await
-            .map_err(|e| Error::Docker(e.to_string()))?;
-        Ok(())
+        {
+            Ok(())
+            | Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(()),
+            Err(e) => Err(Error::Docker(e.to_string())),
+        }
     }
 
-    /// Remove a container.
+    /// Remove a container, ignoring containers that do not exist.
     pub async fn remove_container(&self, container_id: &str) -> Result<()> {
         let options = RemoveContainerOptions {
             force: true,
             ..Default::default()
         };
+        match self
+            .docker
+            .remove_container(container_id, Some(options))
+            .await
+        {
+            Ok(())
+            | Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(()),
+            Err(e) => Err(Error::Docker(e.to_string())),
+        }
+    }
+
+    /// Remove a container along with its anonymous volumes.
+    pub async fn remove_container_with_volumes(&self, container_id: &str) -> Result<()> {
+        let options = RemoveContainerOptions {
+            force: true,
+            v: true,
+            ..Default::default()
+        };
         self.docker
             .remove_container(container_id, Some(options))
             .await
@@ -177,6 +533,25 @@ impl ContainerManager {
         Ok(())
     }
 
+    /// Remove a named volume, ignoring volumes that do not exist.
+    pub async fn remove_volume(&self, name: &str) -> Result<()> {
+        use bollard::volume::RemoveVolumeOptions;
+
+        match self
+            .docker
+            .remove_volume(name, Some(RemoveVolumeOptions { force: true }))
+            .await
+        {
+            Ok(())
+            | Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(()),
+            Err(e) => Err(Error::Docker(format!(
+                "Failed to remove volume {name}: {e}"
+            ))),
+        }
+    }
+
     /// Check if Docker is available.
     pub async fn ping(&self) -> Result<()> {
         self.docker
@@ -188,6 +563,50 @@ impl ContainerManager {
 
     /// Pull a Docker image.
     pub async fn pull_image(&self, image: &str) -> Result<()> {
+        self.pull_image_with_progress(image, |_| {}).await
+    }
+
+    /// Pull a Docker image, reporting overall download progress.
+    ///
+    /// `progress` receives messages like `Pulling polarlightning/lnd... 45%`
+    /// as the layers download. Failures that [`is_retryable_pull_error`]
+    /// accepts are retried with exponential backoff; layers that finished
+    /// downloading are kept by Docker between attempts.
+    pub async fn pull_image_with_progress(
+        &self,
+        image: &str,
+        mut progress: impl FnMut(String),
+    ) -> Result<()> {
+        let mut delay = self.pull_retry_delay;
+        let mut attempt = 0;
+        loop {
+            let result = self.pull_image_once(image, &mut progress).await;
+            let error = match result {
+                Err(e)
+                    if attempt < self.pull_retries && is_retryable_pull_error(&e.to_string()) =>
+                {
+                    e
+                }
+                result => return result,
+            };
+
+            attempt += 1;
+            tracing::warn!(
+                "Pull of {image} failed, retry {attempt}/{} in {delay:?}: {error}",
+                self.pull_retries
+            );
+            progress(format!(
+                "Pull of {image} failed, retrying in {}s ({attempt}/{}): {error}",
+                delay.as_secs_f32(),
+                self.pull_retries
+            ));
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_PULL_RETRY_DELAY);
+        }
+    }
+
+    /// Make a single attempt at pulling `image`.
+    async fn pull_image_once(&self, image: &str, progress: &mut impl FnMut(String)) -> Result<()> {
         use bollard::image::CreateImageOptions;
         use futures_util::StreamExt;
 
@@ -197,11 +616,15 @@ impl ContainerManager {
         });
 
         let mut stream = self.docker.create_image(options, None, None);
+        let mut tracker = PullProgress::default();
 
+        progress(format!("Pulling {image}..."));
         while let Some(result) = stream.next().await {
             match result {
-                Ok(_info) => {
-                    // Progress update - could log this
+                Ok(info) => {
+                    if let Some(percent) = tracker.update(&info) {
+                        progress(format!("Pulling {image}... {percent}%"));
+                    }
                 }
                 Err(e) => {
                     return Err(Error::Docker(format!(
@@ -211,6 +634,7 @@ impl ContainerManager {
                 }
             }
         }
+        progress(format!("Pulled {image}"));
 
         Ok(())
     }
@@ -237,16 +661,98 @@ impl ContainerManager {
         Ok(!images.is_empty())
     }
 
+    /// Check if an image exists locally or can be found in its registry.
+    pub async fn image_available_remote(&self, image: &str) -> Result<bool> {
+        Ok(self.image_exists(image).await? || self.image_in_registry(image).await?)
+    }
+
+    /// Check if an image can be found in its registry, without looking at
+    /// local images.
+    ///
+    /// Registries answer unknown repositories or tags with 401/404, which is
+    /// reported as `false` rather than an error.
+    pub async fn image_in_registry(&self, image: &str) -> Result<bool> {
+        match self.docker.inspect_registry_image(image, None).await {
+            Ok(_) => Ok(true),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 401 | 403 | 404,
+                ..
+            }) => Ok(false),
+            Err(e) => Err(Error::Docker(format!(
+                "Failed to look up image {image}: {e}"
+            ))),
+        }
+    }
+
     /// Pull image if it doesn't exist locally.
     pub async fn ensure_image(&self, image: &str) -> Result<()> {
+        self.ensure_image_with_progress(image, |_| {}).await
+    }
+
+    /// Pull image if it doesn't exist locally, reporting download progress.
+    pub async fn ensure_image_with_progress(
+        &self,
+        image: &str,
+        progress: impl FnMut(String),
+    ) -> Result<()> {
         if !self.image_exists(image).await? {
-            self.pull_image(image).await?;
+            self.pull_image_with_progress(image, progress).await?;
         }
         Ok(())
     }
 
     /// Execute a command in a running container and return the output.
+    ///
+    /// A non-zero exit code fails with [`Error::ContainerExec`] naming the
+    /// code and what the command wrote to stderr, rather than returning the
+    /// error text as output.
     pub async fn exec_command(&self, container_id: &str, cmd: Vec<&str>) -> Result<String> {
+        let program = cmd.first().copied().unwrap_or_default().to_string();
+        let output = self.exec_command_output(container_id, cmd).await?;
+        match output.exit_code {
+            Some(code) if code != 0 => Err(exit_code_error(&program, code, &output)),
+            _ => Ok(output.output),
+        }
+    }
+
+    /// Execute a command and return its output whatever its exit code.
+    ///
+    /// For callers that read the error text a failed command prints, e.g. to
+    /// turn an lncli rpc error into a specific error.
+    pub async fn exec_command_unchecked(
+        &self,
+        container_id: &str,
+        cmd: Vec<&str>,
+    ) -> Result<String> {
+        Ok(self.exec_command_output(container_id, cmd).await?.output)
+    }
+
+    /// Execute a command and collect its output and exit code.
+    pub async fn exec_command_output(
+        &self,
+        container_id: &str,
+        cmd: Vec<&str>,
+    ) -> Result<ExecOutput> {
+        self.exec_command_streaming(container_id, cmd, |_| {}).await
+    }
+
+    /// Execute a command, passing each line it writes to stdout to `on_line`
+    /// as soon as it arrives, and collect its output and exit code.
+    ///
+    /// Runs in a debug span recording the container and command, with RPC
+    /// passwords hidden, and emits the exit code and the start of the output
+    /// as a debug event.
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, cmd, on_line),
+        fields(cmd = %display_command(&cmd))
+    )]
+    pub async fn exec_command_streaming(
+        &self,
+        container_id: &str,
+        cmd: Vec<&str>,
+        mut on_line: impl FnMut(&str) + Send,
+    ) -> Result<ExecOutput> {
         use bollard::exec::{CreateExecOptions, StartExecResults};
         use futures_util::StreamExt;
 
@@ -267,6 +773,8 @@ impl ContainerManager {
 
         // Start and collect output
         let mut output = Vec::new();
+        let mut stderr = Vec::new();
+        let mut pending_line = Vec::new();
         if let StartExecResults::Attached {
             output: mut stream, ..
         } = self
@@ -278,16 +786,155 @@ impl ContainerManager {
             while let Some(Ok(msg)) = stream.next().await {
                 use bollard::container::LogOutput;
                 match msg {
-                    LogOutput::StdOut { message } | LogOutput::StdErr { message } => {
+                    LogOutput::StdOut { message } => {
+                        output.extend_from_slice(&message);
+                        pending_line.extend_from_slice(&message);
+                        drain_lines(&mut pending_line, &mut on_line);
+                    }
+                    LogOutput::StdErr { message } => {
                         output.extend_from_slice(&message);
+                        stderr.extend_from_slice(&message);
                     }
                     _ => {}
                 }
             }
         }
 
-        String::from_utf8(output)
-            .map_err(|e| Error::Docker(format!("Failed to parse command output: {}", e)))
+        if !pending_line.is_empty() {
+            on_line(String::from_utf8_lossy(&pending_line).trim_end_matches('\r'));
+        }
+
+        // The exit code is only known once the output stream has drained
+        let exit_code = self
+            .docker
+            .inspect_exec(&exec.id)
+            .await
+            .map_err(|e| Error::Docker(format!("Failed to inspect exec: {e}")))?
+            .exit_code;
+
+        let parse = |bytes: Vec<u8>| {
+            String::from_utf8(bytes)
+                .map_err(|e| Error::Docker(format!("Failed to parse command output: {e}")))
+        };
+        let output = ExecOutput {
+            output: parse(output)?,
+            stderr: parse(stderr)?,
+            exit_code,
+        };
+        tracing::debug!(
+            exit_code,
+            output = truncate_output(&output.output),
+            "exec finished"
+        );
+        Ok(output)
+    }
+
+    /// Copy a single file out of a container.
+    ///
+    /// Docker serves the file as a tar archive, which is unpacked in memory.
+    ///
+    /// # Arguments
+    /// * `container_id` - Container to read from
+    /// * `path` - Absolute path of the file inside the container
+    pub async fn download_file(&self, container_id: &str, path: &str) -> Result<Vec<u8>> {
+        use bollard::container::DownloadFromContainerOptions;
+        use futures_util::TryStreamExt;
+
+        let archive: Vec<u8> = self
+            .docker
+            .download_from_container(container_id, Some(DownloadFromContainerOptions { path }))
+            .map_ok(|chunk| chunk.to_vec())
+            .try_concat()
+            .await
+            .map_err(|e| Error::Docker(format!("Failed to download {path}: {e}")))?;
+
+        extract_single_file(&archive)
+            .map_err(|e| Error::Docker(format!("Failed to unpack {path}: {e}")))
+    }
+
+    /// Execute a command, retrying while the node is still starting up.
+    ///
+    /// Docker errors and command output are both checked with
+    /// [`is_retryable_exec_error`], since the CLIs report an unready node in
+    /// their output rather than through the exec result. The delay doubles
+    /// after every attempt, up to 4 seconds. Other failures return at once.
+    ///
+    /// # Arguments
+    /// * `container_id` - Container to run the command in
+    /// * `cmd` - Command and arguments
+    /// * `retries` - Attempts after the first one
+    /// * `delay` - Pause before the first retry
+    pub async fn exec_command_with_retry(
+        &self,
+        container_id: &str,
+        cmd: Vec<&str>,
+        retries: u32,
+        delay: Duration,
+    ) -> Result<String> {
+        let mut delay = delay;
+        let mut attempt = 0;
+        loop {
+            let result = self.exec_command(container_id, cmd.clone()).await;
+            let retryable = match &result {
+                Ok(output) => is_retryable_exec_error(output),
+                Err(e) => is_retryable_exec_error(&e.to_string()),
+            };
+            if !retryable || attempt >= retries {
+                return result;
+            }
+
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(Self::MAX_EXEC_RETRY_DELAY);
+            attempt += 1;
+        }
+    }
+
+    /// Check whether a container is currently running.
+    pub async fn is_running(&self, container_id: &str) -> Result<bool> {
+        let info = self.inspect_container(container_id).await?;
+        Ok(info.state.and_then(|s| s.running).unwrap_or(false))
+    }
+
+    /// Get the runtime status of a container.
+    pub async fn container_status(&self, container_id: &str) -> Result<NodeStatus> {
+        let info = self.inspect_container(container_id).await?;
+        Ok(status_from_state(info.state.as_ref()))
+    }
+
+    /// List all containers, running or not, whose name starts with `prefix`.
+    ///
+    /// # Returns
+    /// `(container_id, name)` pairs, with Docker's leading `/` stripped from names
+    pub async fn list_containers_with_prefix(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        use bollard::container::ListContainersOptions;
+        use std::collections::HashMap;
+
+        // Docker's name filter is a substring match, so prefixes are checked below
+        let mut filters = HashMap::new();
+        filters.insert("name".to_string(), vec![prefix.to_string()]);
+
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters,
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| Error::Docker(e.to_string()))?;
+
+        Ok(containers
+            .into_iter()
+            .filter_map(|container| {
+                let id = container.id?;
+                let name = container
+                    .names?
+                    .into_iter()
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .find(|name| name.starts_with(prefix))?;
+                Some((id, name))
+            })
+            .collect())
     }
 
     /// Get container inspection details.
@@ -301,3 +948,286 @@ impl ContainerManager {
             .map_err(|e| Error::Docker(format!("Failed to inspect container: {}", e)))
     }
 }
+
+/// Map Docker's container state onto a node status.
+fn status_from_state(state: Option<&ContainerState>) -> NodeStatus {
+    let Some(state) = state else {
+        return NodeStatus::Unknown;
+    };
+
+    match state.status {
+        Some(ContainerStateStatusEnum::CREATED) => NodeStatus::NotStarted,
+        Some(ContainerStateStatusEnum::RUNNING) => NodeStatus::Running,
+        Some(ContainerStateStatusEnum::RESTARTING) => NodeStatus::Restarting,
+        Some(ContainerStateStatusEnum::EXITED | ContainerStateStatusEnum::DEAD) => {
+            NodeStatus::Exited(state.exit_code.unwrap_or_default())
+        }
+        _ => NodeStatus::Unknown,
+    }
+}
+
+/// Read the first regular file out of a tar archive.
+fn extract_single_file(archive: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    for entry in tar::Archive::new(archive).entries()? {
+        let mut entry = entry?;
+        if entry.header().entry_type().is_file() {
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            return Ok(content);
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "archive contains no file",
+    ))
+}
+
+/// Overall download progress across the layers of an image pull.
+#[derive(Debug, Default)]
+struct PullProgress {
+    /// Downloaded and total bytes per layer.
+    layers: std::collections::HashMap<String, (u64, u64)>,
+    /// Last percentage handed to the caller.
+    reported: Option<u64>,
+}
+
+impl PullProgress {
+    /// Smallest increase worth reporting, to keep the log readable.
+    const STEP: u64 = 5;
+
+    /// Record a pull update, returning the overall percentage when it has
+    /// advanced by at least [`Self::STEP`] since the last report.
+    fn update(&mut self, info: &CreateImageInfo) -> Option<u64> {
+        let id = info.id.as_ref()?;
+        match info.status.as_deref()? {
+            "Downloading" => {
+                let detail = info.progress_detail.as_ref()?;
+                let current = u64::try_from(detail.current?).ok()?;
+                let total = u64::try_from(detail.total?).ok()?;
+                self.layers.insert(id.clone(), (current, total));
+            }
+            "Download complete" => {
+                let layer = self.layers.get_mut(id)?;
+                layer.0 = layer.1;
+            }
+            _ => return None,
+        }
+
+        let (current, total) = self
+            .layers
+            .values()
+            .fold((0, 0), |(current, total), (c, t)| (current + c, total + t));
+        if total == 0 {
+            return None;
+        }
+
+        // Layers start downloading at different times, so only report gains
+        let percent = (current * 100 / total).min(100);
+        if self
+            .reported
+            .is_some_and(|reported| percent < reported + Self::STEP)
+        {
+            return None;
+        }
+        self.reported = Some(percent);
+        Some(percent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_single_file() {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o600);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "admin.macaroon", &[0x02, 0x01, 0x03, 0x6c][..])
+            .unwrap();
+        let archive = builder.into_inner().unwrap();
+
+        assert_eq!(
+            extract_single_file(&archive).unwrap(),
+            [0x02, 0x01, 0x03, 0x6c]
+        );
+        assert!(extract_single_file(&[0; 1024]).is_err());
+    }
+
+    #[test]
+    fn test_drain_lines() {
+        let mut lines = Vec::new();
+        let mut pending = b"{\r\n  \"status\": ".to_vec();
+        drain_lines(&mut pending, &mut |line| lines.push(line.to_string()));
+        assert_eq!(lines, ["{"]);
+        assert_eq!(pending, b"  \"status\": ");
+
+        pending.extend_from_slice(b"\"IN_FLIGHT\"\n}\n");
+        drain_lines(&mut pending, &mut |line| lines.push(line.to_string()));
+        assert_eq!(lines, ["{", "  \"status\": \"IN_FLIGHT\"", "}"]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_data_volume_name() {
+        assert_eq!(data_volume_name("polar-btc-1234"), "polar-btc-1234-data");
+    }
+
+    #[test]
+    fn test_is_retryable_pull_error() {
+        assert!(is_retryable_pull_error(
+            "Failed to pull image lnd: error trying to connect: Connection reset by peer"
+        ));
+        assert!(is_retryable_pull_error(
+            "Get https://registry-1.docker.io/v2/: net/http: TLS handshake timeout"
+        ));
+        assert!(is_retryable_pull_error(
+            "Docker responded with status code 500: toomanyrequests: rate limit exceeded"
+        ));
+        assert!(!is_retryable_pull_error(
+            "Docker responded with status code 404: manifest unknown: manifest unknown"
+        ));
+        assert!(!is_retryable_pull_error(
+            "pull access denied for polarlightning/nope, repository does not exist"
+        ));
+        // A permanent cause wins over a transient-sounding one
+        assert!(!is_retryable_pull_error(
+            "manifest for lnd:v0.0.0 not found after timeout"
+        ));
+        assert!(!is_retryable_pull_error("no space left on device"));
+    }
+
+    #[test]
+    fn test_exit_code_error() {
+        let output = ExecOutput {
+            output: "{}\n[lncli] rpc error: boom\n".to_string(),
+            stderr: "[lncli] rpc error: boom\n".to_string(),
+            exit_code: Some(1),
+        };
+        assert_eq!(
+            exit_code_error("lncli", 1, &output).to_string(),
+            "container exec error: lncli exited with code 1: [lncli] rpc error: boom"
+        );
+
+        let stdout_only = ExecOutput {
+            output: "error code: -8\n".to_string(),
+            ..ExecOutput::default()
+        };
+        assert!(
+            exit_code_error("bitcoin-cli", 8, &stdout_only)
+                .to_string()
+                .ends_with("code 8: error code: -8")
+        );
+    }
+
+    #[test]
+    fn test_exec_logging_helpers() {
+        assert_eq!(
+            display_command(&[
+                "bitcoin-cli",
+                "-rpcuser=polaruser",
+                "-rpcpassword=secret",
+                "getblockcount"
+            ]),
+            "bitcoin-cli -rpcuser=polaruser -rpcpassword=*** getblockcount"
+        );
+        assert_eq!(truncate_output(" 101\n"), "101");
+        let long = "é".repeat(MAX_LOGGED_OUTPUT + 10);
+        assert_eq!(truncate_output(&long).chars().count(), MAX_LOGGED_OUTPUT);
+    }
+
+    #[test]
+    fn test_is_http_host() {
+        assert!(is_http_host("tcp://192.168.64.2:2375"));
+        assert!(is_http_host("http://localhost:2375"));
+        assert!(!is_http_host("unix:///var/run/docker.sock"));
+        assert!(!is_http_host("/var/run/docker.sock"));
+    }
+
+    #[test]
+    fn test_docker_host_name() {
+        assert_eq!(
+            docker_host_name("tcp://192.168.64.2:2375"),
+            Some("192.168.64.2")
+        );
+        assert_eq!(docker_host_name("http://docker.lan/"), Some("docker.lan"));
+        assert_eq!(docker_host_name("unix:///var/run/docker.sock"), None);
+        assert_eq!(docker_host_name("/var/run/docker.sock"), None);
+    }
+
+    #[test]
+    fn test_is_retryable_exec_error() {
+        assert!(is_retryable_exec_error(
+            "error code: -28\nerror message:\nLoading block index…"
+        ));
+        assert!(is_retryable_exec_error(
+            "[lncli] rpc error: code = Unknown desc = the RPC server is in the process of starting up, but not yet ready to accept calls"
+        ));
+        assert!(is_retryable_exec_error(
+            "error: Could not connect to the server 127.0.0.1:18443"
+        ));
+        assert!(!is_retryable_exec_error(
+            "[lncli] rpc error: code = Unknown desc = insufficient funds available"
+        ));
+        assert!(!is_retryable_exec_error(
+            r#"{"alias": "connection refused"}"#
+        ));
+    }
+
+    #[test]
+    fn test_status_from_state() {
+        let state = |status, exit_code| ContainerState {
+            status: Some(status),
+            exit_code: Some(exit_code),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            status_from_state(Some(&state(ContainerStateStatusEnum::RUNNING, 0))),
+            NodeStatus::Running
+        );
+        assert_eq!(
+            status_from_state(Some(&state(ContainerStateStatusEnum::EXITED, 137))),
+            NodeStatus::Exited(137)
+        );
+        assert_eq!(
+            status_from_state(Some(&state(ContainerStateStatusEnum::RESTARTING, 0))),
+            NodeStatus::Restarting
+        );
+        assert_eq!(status_from_state(None), NodeStatus::Unknown);
+    }
+
+    #[test]
+    fn test_pull_progress() {
+        use bollard::models::ProgressDetail;
+
+        let downloading = |id: &str, current, total| CreateImageInfo {
+            id: Some(id.to_string()),
+            status: Some("Downloading".to_string()),
+            progress_detail: Some(ProgressDetail {
+                current: Some(current),
+                total: Some(total),
+            }),
+            ..Default::default()
+        };
+        let complete = |id: &str| CreateImageInfo {
+            id: Some(id.to_string()),
+            status: Some("Download complete".to_string()),
+            ..Default::default()
+        };
+
+        let mut progress = PullProgress::default();
+        assert_eq!(progress.update(&downloading("a", 10, 100)), Some(10));
+        // Gains below the step are not reported
+        assert_eq!(progress.update(&downloading("a", 12, 100)), None);
+        assert_eq!(progress.update(&downloading("b", 50, 100)), Some(31));
+        assert_eq!(progress.update(&complete("a")), Some(75));
+        assert_eq!(progress.update(&complete("b")), Some(100));
+        assert_eq!(progress.update(&complete("unknown")), None);
+    }
+}
@@ -7,6 +7,9 @@ mod container;
 mod logs;
 mod ports;
 
-pub use container::ContainerManager;
+pub use container::{
+    ContainerManager, ExecOutput, data_volume_name, docker_host_name, is_retryable_exec_error,
+    is_retryable_pull_error,
+};
 pub use logs::LogStream;
 pub use ports::PortMap;
@@ -1,7 +1,7 @@
 //! Docker log streaming.
 
-use bollard::container::LogsOptions;
 use bollard::Docker;
+use bollard::container::LogsOptions;
 use futures_util::StreamExt;
 use tokio::sync::mpsc;
 
@@ -0,0 +1,48 @@
+//! On-chain address types shared by the node implementations.
+
+/// Script type of a new receive address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressType {
+    /// Native segwit v0 (`bcrt1q...`).
+    #[default]
+    P2wkh,
+    /// Segwit v0 nested in P2SH (`2...`).
+    Np2wkh,
+    /// Taproot (`bcrt1p...`).
+    P2tr,
+}
+
+impl AddressType {
+    /// Type argument for `lncli newaddress`.
+    #[must_use]
+    pub const fn lncli_name(self) -> &'static str {
+        match self {
+            Self::P2wkh => "p2wkh",
+            Self::Np2wkh => "np2wkh",
+            Self::P2tr => "p2tr",
+        }
+    }
+
+    /// `address_type` argument for `bitcoin-cli getnewaddress`, matching
+    /// the values of bitcoind's `-addresstype` option.
+    #[must_use]
+    pub const fn bitcoind_name(self) -> &'static str {
+        match self {
+            Self::P2wkh => "bech32",
+            Self::Np2wkh => "p2sh-segwit",
+            Self::P2tr => "bech32m",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_type_names() {
+        assert_eq!(AddressType::default().lncli_name(), "p2wkh");
+        assert_eq!(AddressType::P2tr.lncli_name(), "p2tr");
+        assert_eq!(AddressType::P2tr.bitcoind_name(), "bech32m");
+    }
+}
@@ -0,0 +1,77 @@
+//! Validation of user-supplied daemon arguments.
+
+use polar_core::{Error, Result};
+
+/// Name of a command-line flag, without leading dashes or a value.
+fn flag_name(arg: &str) -> &str {
+    let arg = arg.trim_start_matches('-');
+    arg.split_once('=').map_or(arg, |(name, _)| name)
+}
+
+/// Value of the last `flag` set on a command line, as later flags win.
+pub fn flag_value<'a>(cmd: &'a [String], flag: &str) -> Option<&'a str> {
+    cmd.iter()
+        .rev()
+        .find(|arg| flag_name(arg) == flag)
+        .and_then(|arg| arg.split_once('='))
+        .map(|(_, value)| value)
+}
+
+/// Append extra arguments to a daemon command line.
+///
+/// Arguments that set a flag already on the command line, or one listed in
+/// `reserved`, are rejected so they cannot take the node off regtest or
+/// break how it is wired to the rest of the network. Negated forms such as
+/// `-noserver` count as setting the flag they negate.
+pub fn append_extra_args(cmd: &mut Vec<String>, extra: &[String], reserved: &[&str]) -> Result<()> {
+    for arg in extra {
+        if !arg.starts_with('-') {
+            return Err(Error::Config(format!(
+                "Extra argument '{arg}' must be a flag starting with '-'"
+            )));
+        }
+
+        let name = flag_name(arg);
+        let collides = |flag: &str| name == flag || name.strip_prefix("no") == Some(flag);
+        let required = cmd
+            .iter()
+            .skip(1)
+            .any(|existing| collides(flag_name(existing)));
+        if required || reserved.iter().any(|flag| collides(flag)) {
+            return Err(Error::Config(format!(
+                "Extra argument '{arg}' overrides a flag managed by polar"
+            )));
+        }
+    }
+
+    cmd.extend(extra.iter().cloned());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_append_extra_args() {
+        let mut cmd = args(&["bitcoind", "-regtest", "-rpcuser=polaruser"]);
+        append_extra_args(&mut cmd, &args(&["-txindex=1"]), &["chain"]).unwrap();
+        assert_eq!(cmd.last().map(String::as_str), Some("-txindex=1"));
+
+        for bad in ["-rpcuser=bob", "-noregtest", "-chain=main", "txindex"] {
+            assert!(append_extra_args(&mut cmd, &args(&[bad]), &["chain"]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_flag_value() {
+        let cmd = args(&["lnd", "--alias=a", "--bitcoin.active", "--alias=b"]);
+        assert_eq!(flag_value(&cmd, "alias"), Some("b"));
+        assert_eq!(flag_value(&cmd, "bitcoin.active"), None);
+        assert_eq!(flag_value(&cmd, "debuglevel"), None);
+    }
+}
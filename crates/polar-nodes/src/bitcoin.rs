@@ -1,7 +1,23 @@
 //! Bitcoin Core node implementation.
 
-use polar_core::{Node, NodeKind, Result};
-use polar_docker::{ContainerManager, PortMap};
+use polar_core::{
+    BitcoinPeerInfo, ChainTip, MempoolEntry, MempoolInfo, Node, NodeKind, ResourceLimits, Result,
+    RpcCredentials,
+};
+use polar_docker::{ContainerManager, PortMap, data_volume_name};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::address::AddressType;
+use crate::args::{append_extra_args, flag_value};
+
+/// Coinbase addresses reused by [`BitcoinNode::mine_blocks_fast`], keyed by
+/// container ID so every `BitcoinNode` for the same container shares one.
+///
+/// A restarted node gets a new container ID, so stale entries are never hit.
+static MINING_ADDRESSES: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 /// Available Bitcoin Core versions.
 pub const BITCOIN_VERSIONS: &[&str] = &[
@@ -16,20 +32,153 @@ pub struct BitcoinNode {
     pub node: Node,
     /// Docker image to use.
     pub image: String,
+    /// RPC credentials for bitcoind and bitcoin-cli.
+    pub rpc: RpcCredentials,
+    /// Additional bitcoind flags appended to the generated command line.
+    pub extra_args: Vec<String>,
 }
 
 impl BitcoinNode {
     /// Default Bitcoin Core image.
     pub const DEFAULT_IMAGE: &'static str = "polarlightning/bitcoind:28.0";
 
+    /// Directory bitcoind keeps its chain and wallets in inside the image.
+    pub const DATA_DIR: &'static str = "/home/bitcoin/.bitcoin";
+
+    /// How long [`Self::wait_for_confirmations`] polls before giving up.
+    pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Delay between confirmation polls.
+    const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Confirmations a coinbase output needs before the wallet can spend it.
+    pub const COINBASE_MATURITY: u32 = 100;
+
+    /// Most blocks [`Self::blocks_to_fund`] asks to mine.
+    pub const MAX_FUNDING_BLOCKS: u32 = 1_000;
+
+    /// Wallet created for mining and funding; Bitcoin Core 28 has none by
+    /// default.
+    pub const WALLET_NAME: &'static str = "default";
+
+    /// Container port bitcoind publishes raw blocks on over ZMQ.
+    pub const ZMQ_BLOCK_PORT: u16 = 28334;
+
+    /// Container port bitcoind publishes raw transactions on over ZMQ.
+    pub const ZMQ_TX_PORT: u16 = 28335;
+
+    /// Blocks between subsidy halvings on regtest.
+    const HALVING_INTERVAL: u64 = 150;
+
+    /// Flags extra args may not set, on top of those polar already passes.
+    const RESERVED_FLAGS: &'static [&'static str] = &[
+        "chain", "testnet", "testnet4", "signet", "datadir", "port", "rpcport",
+    ];
+
     /// Create a new Bitcoin Core node.
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             node: Node::new(name, NodeKind::BitcoinCore),
             image: Self::DEFAULT_IMAGE.to_string(),
+            rpc: RpcCredentials::default(),
+            extra_args: Vec::new(),
         }
     }
 
+    /// Set custom RPC credentials.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: RpcCredentials) -> Self {
+        self.rpc = rpc;
+        self
+    }
+
+    /// Append extra bitcoind flags, e.g. `-txindex=1`.
+    #[must_use]
+    pub fn with_extra_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra_args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set a variable in the container's environment.
+    ///
+    /// Flags on the generated command line take precedence over anything
+    /// bitcoind also reads from the environment.
+    #[must_use]
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.node.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Check that the extra args don't override flags polar relies on.
+    pub fn check_extra_args(&self) -> Result<()> {
+        self.daemon_command().map(|_| ())
+    }
+
+    /// Container ports bitcoind publishes raw blocks and transactions on,
+    /// read back from its command line.
+    pub fn zmq_ports(&self) -> Result<(u16, u16)> {
+        let cmd = self.daemon_command()?;
+        let port = |flag: &str| {
+            flag_value(&cmd, flag)
+                .and_then(|endpoint| endpoint.rsplit_once(':'))
+                .and_then(|(_, port)| port.parse().ok())
+                .ok_or_else(|| {
+                    polar_core::Error::Config(format!("bitcoind command has no valid -{flag}"))
+                })
+        };
+        Ok((port("zmqpubrawblock")?, port("zmqpubrawtx")?))
+    }
+
+    /// Build the bitcoind command line for this node.
+    fn daemon_command(&self) -> Result<Vec<String>> {
+        let mut cmd = vec![
+            "bitcoind".to_string(),
+            "-regtest".to_string(),
+            "-server".to_string(),
+            format!("-rpcuser={}", self.rpc.user),
+            format!("-rpcpassword={}", self.rpc.pass),
+            "-rpcallowip=0.0.0.0/0".to_string(),
+            "-rpcbind=0.0.0.0".to_string(),
+            format!("-zmqpubrawblock=tcp://0.0.0.0:{}", Self::ZMQ_BLOCK_PORT),
+            format!("-zmqpubrawtx=tcp://0.0.0.0:{}", Self::ZMQ_TX_PORT),
+            "-fallbackfee=0.00001".to_string(), // Enable fallback fee for regtest
+        ];
+        append_extra_args(&mut cmd, &self.extra_args, Self::RESERVED_FLAGS)?;
+        Ok(cmd)
+    }
+
+    /// Run a bitcoin-cli command in the node's container.
+    async fn exec_cli(
+        &self,
+        manager: &ContainerManager,
+        container_id: &str,
+        args: &[&str],
+    ) -> Result<String> {
+        let cmd = self.rpc.bitcoin_cli_command(args);
+        manager
+            .exec_command(container_id, cmd.iter().map(String::as_str).collect())
+            .await
+    }
+
+    /// Name of the Docker container this node runs in.
+    #[must_use]
+    pub fn container_name(&self) -> String {
+        format!("polar-btc-{}", self.node.id)
+    }
+
+    /// Name of the Docker volume holding [`Self::DATA_DIR`].
+    ///
+    /// The volume outlives the container, so chain state survives a stop and
+    /// start until the volume is removed.
+    #[must_use]
+    pub fn volume_name(&self) -> String {
+        data_volume_name(&self.container_name())
+    }
+
     /// Start the Bitcoin Core container.
     pub async fn start(&mut self, manager: &ContainerManager) -> Result<()> {
         self.start_with_network(manager, None).await
@@ -41,7 +190,8 @@ impl BitcoinNode {
         manager: &ContainerManager,
         network: Option<&str>,
     ) -> Result<()> {
-        self.start_with_ports(manager, network, None).await
+        self.start_with_ports(manager, network, None, ResourceLimits::default())
+            .await
     }
 
     /// Start the Bitcoin Core container with custom port mappings.
@@ -50,37 +200,29 @@ impl BitcoinNode {
     /// * `manager` - Docker container manager
     /// * `network` - Optional Docker network name
     /// * `ports` - Optional port configuration (rpc, p2p, zmq_block, zmq_tx)
+    /// * `limits` - CPU and memory caps for the container
+    #[tracing::instrument(skip_all, fields(node = %self.node.name, image = %self.image))]
     pub async fn start_with_ports(
         &mut self,
         manager: &ContainerManager,
         network: Option<&str>,
         ports: Option<(u16, u16, u16, u16)>,
+        limits: ResourceLimits,
     ) -> Result<()> {
         // Ensure the image exists locally
         manager.ensure_image(&self.image).await?;
 
-        let container_name = format!("polar-btc-{}", self.node.id);
+        let container_name = self.container_name();
 
-        let cmd = vec![
-            "bitcoind".to_string(),
-            "-regtest".to_string(),
-            "-server".to_string(),
-            "-rpcuser=polaruser".to_string(),
-            "-rpcpassword=polarpass".to_string(),
-            "-rpcallowip=0.0.0.0/0".to_string(),
-            "-rpcbind=0.0.0.0".to_string(),
-            "-zmqpubrawblock=tcp://0.0.0.0:28334".to_string(),
-            "-zmqpubrawtx=tcp://0.0.0.0:28335".to_string(),
-            "-fallbackfee=0.00001".to_string(), // Enable fallback fee for regtest
-        ];
+        let cmd = self.daemon_command()?;
 
         // Configure port mappings if ports are provided
         let port_map = ports.map(|(rpc_port, p2p_port, zmq_block_port, zmq_tx_port)| {
             PortMap::from(vec![
-                (18443, rpc_port),       // RPC port
-                (18444, p2p_port),       // P2P port
-                (28334, zmq_block_port), // ZMQ block port
-                (28335, zmq_tx_port),    // ZMQ tx port
+                (18443, rpc_port), // RPC port
+                (18444, p2p_port), // P2P port
+                (Self::ZMQ_BLOCK_PORT, zmq_block_port),
+                (Self::ZMQ_TX_PORT, zmq_tx_port),
             ])
         });
 
@@ -91,6 +233,9 @@ impl BitcoinNode {
                 Some(cmd),
                 port_map,
                 network,
+                limits,
+                &self.node.env,
+                &[(self.volume_name(), Self::DATA_DIR.to_string())],
             )
             .await?;
 
@@ -100,23 +245,71 @@ impl BitcoinNode {
         // Wait a bit for bitcoind to fully start
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 
-        // Create a default wallet (required for Bitcoin Core 28.0+)
-        // This will fail if wallet already exists, which is fine - we'll ignore that error
-        let _ = manager
-            .exec_command(
-                &container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "createwallet",
-                    "default",
-                ],
-            )
-            .await;
+        self.ensure_wallet(manager).await
+    }
 
-        Ok(())
+    /// Make sure a wallet is loaded, loading [`Self::WALLET_NAME`] from disk
+    /// or creating it if none is.
+    ///
+    /// A wallet left on disk by an earlier run is not loaded automatically
+    /// when bitcoind restarts, so wallet RPCs call this first.
+    pub async fn ensure_wallet(&self, manager: &ContainerManager) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+        Self::ensure_wallet_in(manager, &self.rpc, container_id).await
+    }
+
+    /// Make sure the bitcoind in `container_id` has a wallet loaded, see
+    /// [`Self::ensure_wallet`].
+    ///
+    /// The wallet is only created when none is on disk; failing to load an
+    /// existing one is an error rather than a reason to create a new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the wallet RPCs fail or the existing wallet
+    /// cannot be loaded
+    pub async fn ensure_wallet_in(
+        manager: &ContainerManager,
+        rpc: &RpcCredentials,
+        container_id: &str,
+    ) -> Result<()> {
+        let exec_cli = |args: &[&str]| {
+            let cmd = rpc.bitcoin_cli_command(args);
+            async move {
+                manager
+                    .exec_command(container_id, cmd.iter().map(String::as_str).collect())
+                    .await
+            }
+        };
+
+        if has_loaded_wallet(&exec_cli(&["listwallets"]).await?)? {
+            return Ok(());
+        }
+
+        if has_wallet_on_disk(&exec_cli(&["listwalletdir"]).await?, Self::WALLET_NAME)? {
+            return match exec_cli(&["loadwallet", Self::WALLET_NAME]).await {
+                Ok(_) => Ok(()),
+                // Another call loaded it in the meantime
+                Err(e) if e.to_string().contains("already loaded") => Ok(()),
+                Err(e) => Err(polar_core::Error::ContainerExec(format!(
+                    "Failed to load wallet '{}': {e}",
+                    Self::WALLET_NAME
+                ))),
+            };
+        }
+        match exec_cli(&["createwallet", Self::WALLET_NAME]).await {
+            Ok(_) => Ok(()),
+            // Another call loaded it in the meantime
+            Err(e) if e.to_string().contains("already loaded") => Ok(()),
+            Err(e) => Err(polar_core::Error::ContainerExec(format!(
+                "Failed to create wallet '{}': {e}",
+                Self::WALLET_NAME
+            ))),
+        }
     }
 
     /// Stop the Bitcoin Core container.
@@ -145,57 +338,95 @@ impl BitcoinNode {
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("Bitcoin node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
         // Get or create an address to mine to
         let mining_address = if let Some(addr) = address {
             addr.to_string()
         } else {
-            // Generate a new address
-            let output = manager
-                .exec_command(
-                    container_id,
-                    vec![
-                        "bitcoin-cli",
-                        "-regtest",
-                        "-rpcuser=polaruser",
-                        "-rpcpassword=polarpass",
-                        "getnewaddress",
-                    ],
-                )
-                .await
-                .map_err(|e| {
-                    if e.to_string().contains("No wallet is loaded") {
-                        polar_core::Error::Config(
-                            "No wallet loaded. Try restarting the network.".to_string(),
-                        )
-                    } else {
-                        e
-                    }
-                })?;
-            let addr = output.trim().to_string();
-            addr
+            self.mining_address(manager, true).await?
         };
 
-        // Mine the blocks
-        let output = manager
-            .exec_command(
+        self.generate_to_address(manager, container_id, blocks, &mining_address)
+            .await
+    }
+
+    /// Mine blocks to this node's wallet, reusing the coinbase address of
+    /// earlier calls instead of asking the wallet for a new one each time.
+    ///
+    /// The first call for a container, such as after a network reload,
+    /// generates the address.
+    pub async fn mine_blocks_fast(
+        &self,
+        manager: &ContainerManager,
+        blocks: u32,
+    ) -> Result<Vec<String>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let mining_address = self.mining_address(manager, false).await?;
+        let result = self
+            .generate_to_address(manager, container_id, blocks, &mining_address)
+            .await;
+        if result.is_err() {
+            // Don't keep reusing an address that may no longer be valid
+            mining_addresses().remove(container_id);
+        }
+        result
+    }
+
+    /// Get the coinbase address for this node's wallet.
+    ///
+    /// Reuses the cached address unless `force_new` is set or there is none
+    /// yet; a newly generated address replaces the cached one.
+    pub async fn mining_address(
+        &self,
+        manager: &ContainerManager,
+        force_new: bool,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        if !force_new {
+            if let Some(address) = mining_addresses().get(container_id) {
+                return Ok(address.clone());
+            }
+        }
+
+        self.ensure_wallet(manager).await?;
+        let output = self
+            .exec_cli(manager, container_id, &["getnewaddress"])
+            .await?;
+        let address = output.trim().to_string();
+        mining_addresses().insert(container_id.clone(), address.clone());
+        Ok(address)
+    }
+
+    /// Mine `blocks` blocks paying the coinbase to `address`.
+    async fn generate_to_address(
+        &self,
+        manager: &ContainerManager,
+        container_id: &str,
+        blocks: u32,
+        address: &str,
+    ) -> Result<Vec<String>> {
+        let output = self
+            .exec_cli(
+                manager,
                 container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "generatetoaddress",
-                    &blocks.to_string(),
-                    &mining_address,
-                ],
+                &["generatetoaddress", &blocks.to_string(), address],
             )
             .await?;
 
         // Parse the block hashes from the output
         let block_hashes: Vec<String> = serde_json::from_str(&output).map_err(|e| {
-            polar_core::Error::Config(format!(
+            polar_core::Error::RpcParse(format!(
                 "Failed to parse block hashes: {}. Output was: {}",
                 e, output
             ))
@@ -205,25 +436,30 @@ impl BitcoinNode {
     }
 
     /// Get a new Bitcoin address from the node's wallet.
-    pub async fn get_new_address(&self, manager: &ContainerManager) -> Result<String> {
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `address_type` - Script type (None uses the wallet's default)
+    pub async fn get_new_address(
+        &self,
+        manager: &ContainerManager,
+        address_type: Option<AddressType>,
+    ) -> Result<String> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("Bitcoin node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
-        let output = manager
-            .exec_command(
-                container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "getnewaddress",
-                ],
-            )
-            .await?;
+        let type_arg;
+        let mut args = vec!["-named", "getnewaddress"];
+        if let Some(address_type) = address_type {
+            type_arg = format!("address_type={}", address_type.bitcoind_name());
+            args.push(&type_arg);
+        }
+
+        self.ensure_wallet(manager).await?;
+        let output = self.exec_cli(manager, container_id, &args).await?;
 
         Ok(output.trim().to_string())
     }
@@ -239,57 +475,632 @@ impl BitcoinNode {
         manager: &ContainerManager,
         address: &str,
         amount: f64,
+    ) -> Result<String> {
+        self.send_to_address_with_fee(manager, address, amount, None)
+            .await
+    }
+
+    /// Send Bitcoin to an address with an explicit fee rate.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `address` - Destination address
+    /// * `amount` - Amount in BTC
+    /// * `sat_per_vb` - Fee rate in sat/vB (None uses the wallet's default)
+    pub async fn send_to_address_with_fee(
+        &self,
+        manager: &ContainerManager,
+        address: &str,
+        amount: f64,
+        sat_per_vb: Option<u64>,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let address_arg = format!("address={address}");
+        let amount_arg = format!("amount={amount}");
+        let mut args = vec!["-named", "sendtoaddress", &address_arg, &amount_arg];
+
+        let fee_rate_arg;
+        if let Some(fee_rate) = sat_per_vb {
+            fee_rate_arg = format!("fee_rate={fee_rate}");
+            args.push(&fee_rate_arg);
+        }
+
+        self.ensure_wallet(manager).await?;
+        let output = self.exec_cli(manager, container_id, &args).await?;
+
+        Ok(output.trim().to_string())
+    }
+
+    /// Send to several addresses in one transaction with `sendmany`.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `outputs` - Destination addresses and amounts in BTC
+    pub async fn send_many(
+        &self,
+        manager: &ContainerManager,
+        outputs: &[(String, f64)],
     ) -> Result<String> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("Bitcoin node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let amounts: serde_json::Map<String, serde_json::Value> = outputs
+            .iter()
+            .map(|(address, amount)| (address.clone(), (*amount).into()))
+            .collect();
+        let amounts = serde_json::Value::Object(amounts).to_string();
+        self.ensure_wallet(manager).await?;
+        let output = self
+            .exec_cli(manager, container_id, &["sendmany", "", &amounts])
+            .await?;
+
+        Ok(output.trim().to_string())
+    }
+
+    /// Replace an unconfirmed wallet transaction with a higher-fee one via
+    /// `bumpfee`.
+    ///
+    /// The transaction must signal replaceability (BIP 125); otherwise the
+    /// error says so and suggests bumping a child output instead.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `txid` - Transaction to replace
+    /// * `sat_per_vb` - New fee rate in sat/vB (None lets the wallet pick)
+    ///
+    /// # Returns
+    /// The transaction ID of the replacement
+    pub async fn bump_fee(
+        &self,
+        manager: &ContainerManager,
+        txid: &str,
+        sat_per_vb: Option<u64>,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let txid_arg = format!("txid={txid}");
+        let mut args = vec!["-named", "bumpfee", &txid_arg];
+
+        let fee_rate_arg;
+        if let Some(fee_rate) = sat_per_vb {
+            fee_rate_arg = format!("fee_rate={fee_rate}");
+            args.push(&fee_rate_arg);
+        }
 
+        self.ensure_wallet(manager).await?;
+        // A refused bump is explained from the error text it prints
+        let cmd = self.rpc.bitcoin_cli_command(&args);
         let output = manager
-            .exec_command(
+            .exec_command_unchecked(container_id, cmd.iter().map(String::as_str).collect())
+            .await?;
+
+        parse_bump_fee(txid, &output)
+    }
+
+    /// Get the height of the current chain tip.
+    pub async fn get_block_count(&self, manager: &ContainerManager) -> Result<u64> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(manager, container_id, &["getblockcount"])
+            .await?;
+
+        output
+            .trim()
+            .parse()
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse block count: {e}")))
+    }
+
+    /// Blocks to mine on top of `height` so that at least `sats` of new
+    /// coinbase rewards become spendable.
+    ///
+    /// Returns `None` when that takes more than [`Self::MAX_FUNDING_BLOCKS`],
+    /// as the regtest subsidy halves every 150 blocks.
+    #[must_use]
+    pub fn blocks_to_fund(height: u64, sats: u64) -> Option<u32> {
+        if sats == 0 {
+            return Some(0);
+        }
+        let mut rewarded = 0;
+        let mut blocks = 0;
+        while rewarded < sats {
+            blocks += 1;
+            if blocks + Self::COINBASE_MATURITY > Self::MAX_FUNDING_BLOCKS {
+                return None;
+            }
+            let halvings = (height + u64::from(blocks)) / Self::HALVING_INTERVAL;
+            if halvings >= 64 {
+                return None;
+            }
+            rewarded += 5_000_000_000 >> halvings;
+        }
+        Some(blocks + Self::COINBASE_MATURITY)
+    }
+
+    /// Estimate the fee rate needed to confirm within `conf_target` blocks.
+    ///
+    /// Returns the rate in sat/vB, or None when the node does not have
+    /// enough data to produce an estimate (common on regtest).
+    pub async fn estimate_fee(
+        &self,
+        manager: &ContainerManager,
+        conf_target: u32,
+    ) -> Result<Option<f64>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(
+                manager,
                 container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "sendtoaddress",
-                    address,
-                    &amount.to_string(),
-                ],
+                &["estimatesmartfee", &conf_target.to_string()],
             )
             .await?;
 
+        let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
+            polar_core::Error::RpcParse(format!(
+                "Failed to parse fee estimate: {e}. Output: {output}"
+            ))
+        })?;
+
+        Ok(parse_fee_estimate(&json))
+    }
+
+    /// Add or remove a P2P peer via `addnode`.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `peer` - Peer address in format "host:port"
+    /// * `command` - One of "add", "remove" or "onetry"
+    pub async fn add_node(
+        &self,
+        manager: &ContainerManager,
+        peer: &str,
+        command: &str,
+    ) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        self.exec_cli(manager, container_id, &["addnode", peer, command])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Immediately disconnect a connected P2P peer.
+    pub async fn disconnect_node(&self, manager: &ContainerManager, peer: &str) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        self.exec_cli(manager, container_id, &["disconnectnode", peer])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the hash of the current chain tip.
+    pub async fn get_best_block_hash(&self, manager: &ContainerManager) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(manager, container_id, &["getbestblockhash"])
+            .await?;
+
         Ok(output.trim().to_string())
     }
 
+    /// Get the connected P2P peers.
+    pub async fn get_peer_info(&self, manager: &ContainerManager) -> Result<Vec<BitcoinPeerInfo>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(manager, container_id, &["getpeerinfo"])
+            .await?;
+
+        serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse peer info: {e}")))
+    }
+
+    /// Get the unconfirmed transactions in the mempool, oldest first.
+    pub async fn get_mempool(&self, manager: &ContainerManager) -> Result<Vec<MempoolEntry>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(manager, container_id, &["getrawmempool", "true"])
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse mempool: {e}")))?;
+        Ok(Self::parse_mempool(&json))
+    }
+
+    /// Get the mempool summary.
+    pub async fn get_mempool_info(&self, manager: &ContainerManager) -> Result<MempoolInfo> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(manager, container_id, &["getmempoolinfo"])
+            .await?;
+
+        serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse mempool info: {e}")))
+    }
+
+    /// Read the entries of a `getrawmempool true` response, oldest first.
+    ///
+    /// An empty mempool is an empty object, giving an empty list.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn parse_mempool(json: &serde_json::Value) -> Vec<MempoolEntry> {
+        let mut entries: Vec<MempoolEntry> = json
+            .as_object()
+            .map(|txs| {
+                txs.iter()
+                    .map(|(txid, tx)| MempoolEntry {
+                        txid: txid.clone(),
+                        fee_sat: tx["fees"]["base"]
+                            .as_f64()
+                            .map_or(0, |btc| (btc * 100_000_000.0).round() as u64),
+                        vsize: tx["vsize"].as_u64().unwrap_or(0),
+                        time: tx["time"].as_u64().unwrap_or(0),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.txid.cmp(&b.txid)));
+        entries
+    }
+
+    /// Get all known chain tips, including forks.
+    pub async fn get_chain_tips(&self, manager: &ContainerManager) -> Result<Vec<ChainTip>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = self
+            .exec_cli(manager, container_id, &["getchaintips"])
+            .await?;
+
+        serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse chain tips: {e}")))
+    }
+
+    /// Wait until a transaction has at least `confs` confirmations.
+    ///
+    /// Polls `gettransaction`, falling back to `getrawtransaction` for
+    /// transactions outside the node's wallet, until the target is reached
+    /// or [`Self::CONFIRMATION_TIMEOUT`] elapses.
+    ///
+    /// # Returns
+    /// The number of confirmations observed
+    pub async fn wait_for_confirmations(
+        &self,
+        manager: &ContainerManager,
+        txid: &str,
+        confs: u32,
+    ) -> Result<u32> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let deadline = tokio::time::Instant::now() + Self::CONFIRMATION_TIMEOUT;
+        loop {
+            let output = match self
+                .exec_cli(manager, container_id, &["gettransaction", txid])
+                .await
+            {
+                Ok(output) => output,
+                Err(_) => {
+                    self.exec_cli(manager, container_id, &["getrawtransaction", txid, "true"])
+                        .await?
+                }
+            };
+            let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
+                polar_core::Error::RpcParse(format!(
+                    "Failed to parse transaction {txid}: {e}. Output: {output}"
+                ))
+            })?;
+
+            let seen = parse_confirmations(&json);
+            if seen >= confs {
+                return Ok(seen);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(polar_core::Error::Timeout(format!(
+                    "transaction {txid} has {seen} of {confs} confirmations after {}s",
+                    Self::CONFIRMATION_TIMEOUT.as_secs()
+                )));
+            }
+            tokio::time::sleep(Self::CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
     /// Get the wallet balance.
     pub async fn get_balance(&self, manager: &ContainerManager) -> Result<f64> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("Bitcoin node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
-        let output = manager
-            .exec_command(
-                container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "getbalance",
-                ],
-            )
+        self.ensure_wallet(manager).await?;
+        let output = self
+            .exec_cli(manager, container_id, &["getbalance"])
             .await?;
 
         let balance: f64 = output
             .trim()
             .parse()
-            .map_err(|e| polar_core::Error::Config(format!("Failed to parse balance: {}", e)))?;
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse balance: {}", e)))?;
 
         Ok(balance)
     }
 }
+
+fn mining_addresses() -> std::sync::MutexGuard<'static, HashMap<String, String>> {
+    MINING_ADDRESSES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Whether `listwallets` output names at least one loaded wallet.
+fn has_loaded_wallet(output: &str) -> Result<bool> {
+    let wallets: Vec<String> = serde_json::from_str(output).map_err(|e| {
+        polar_core::Error::RpcParse(format!(
+            "Failed to parse wallet list: {e}. Output was: {output}"
+        ))
+    })?;
+    Ok(!wallets.is_empty())
+}
+
+/// Whether `listwalletdir` output lists a wallet called `name`.
+fn has_wallet_on_disk(output: &str, name: &str) -> Result<bool> {
+    let json: serde_json::Value = serde_json::from_str(output).map_err(|e| {
+        polar_core::Error::RpcParse(format!(
+            "Failed to parse wallet directory: {e}. Output was: {output}"
+        ))
+    })?;
+    Ok(json
+        .get("wallets")
+        .and_then(serde_json::Value::as_array)
+        .is_some_and(|wallets| {
+            wallets
+                .iter()
+                .any(|wallet| wallet.get("name").and_then(serde_json::Value::as_str) == Some(name))
+        }))
+}
+
+/// Convert an `estimatesmartfee` response to sat/vB.
+///
+/// Bitcoin Core reports `feerate` in BTC/kvB and omits it entirely when
+/// there is insufficient data, so a missing field maps to None.
+fn parse_fee_estimate(json: &serde_json::Value) -> Option<f64> {
+    json.get("feerate")
+        .and_then(serde_json::Value::as_f64)
+        .map(|btc_per_kvb| btc_per_kvb * 100_000.0)
+}
+
+/// Read the replacement txid from `bumpfee` output.
+///
+/// bitcoin-cli prints `error code: ..` / `error message: ..` instead of JSON
+/// when the bump is refused; transactions that do not signal RBF get an
+/// explicit hint.
+fn parse_bump_fee(txid: &str, output: &str) -> Result<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output) else {
+        let output = output.trim();
+        let message = output
+            .split_once("error message:")
+            .map_or(output, |(_, message)| message.trim());
+        if message.contains("BIP 125") || message.contains("not replaceable") {
+            return Err(polar_core::Error::ContainerExec(format!(
+                "Transaction {txid} does not signal RBF (BIP 125) and cannot be replaced; \
+                 bump one of its outputs with CPFP from an LND node instead"
+            )));
+        }
+        return Err(polar_core::Error::ContainerExec(format!(
+            "Failed to bump fee of {txid}: {message}"
+        )));
+    };
+
+    if let Some(error) = json["errors"]
+        .as_array()
+        .and_then(|errors| errors.first())
+        .and_then(serde_json::Value::as_str)
+    {
+        return Err(polar_core::Error::ContainerExec(format!(
+            "Failed to bump fee of {txid}: {error}"
+        )));
+    }
+
+    json["txid"].as_str().map(str::to_string).ok_or_else(|| {
+        polar_core::Error::RpcParse(format!("Missing txid in bumpfee output: {json}"))
+    })
+}
+
+/// Read the confirmation count of a `gettransaction` or `getrawtransaction`
+/// response.
+///
+/// Mempool transactions omit the field in raw responses, and conflicted
+/// wallet transactions report a negative count; both count as zero.
+fn parse_confirmations(json: &serde_json::Value) -> u32 {
+    json.get("confirmations")
+        .and_then(serde_json::Value::as_i64)
+        .and_then(|confs| u32::try_from(confs).ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_to_fund() {
+        assert_eq!(BitcoinNode::blocks_to_fund(0, 0), Some(0));
+        // One 50 BTC reward covers 1 BTC, once it has matured
+        assert_eq!(BitcoinNode::blocks_to_fund(0, 100_000_000), Some(101));
+        assert_eq!(BitcoinNode::blocks_to_fund(0, 10_000_000_000), Some(102));
+        // Past the first halving each block only pays 25 BTC
+        assert_eq!(BitcoinNode::blocks_to_fund(200, 10_000_000_000), Some(104));
+        // Late in the chain rewards are too small to fund large amounts
+        assert_eq!(BitcoinNode::blocks_to_fund(3_000, 100_000_000), None);
+    }
+
+    #[test]
+    fn test_parse_chain_tips() {
+        let output = r#"[
+            {"height": 210, "hash": "7a0c", "branchlen": 0, "status": "active"},
+            {"height": 209, "hash": "3f1e", "branchlen": 2, "status": "valid-fork"}
+        ]"#;
+
+        let tips: Vec<ChainTip> = serde_json::from_str(output).unwrap();
+        assert_eq!(tips.len(), 2);
+        assert_eq!(tips[0].status, "active");
+        assert_eq!(tips[1].branch_len, 2);
+        assert_eq!(tips[1].height, 209);
+    }
+
+    #[test]
+    fn test_parse_peer_info() {
+        let output = r#"[
+            {"id": 0, "addr": "172.18.0.3:18444", "subver": "/Satoshi:28.0.0/",
+             "inbound": false, "synced_headers": 210, "synced_blocks": 210},
+            {"id": 1, "addr": "172.18.0.4:50012", "inbound": true}
+        ]"#;
+
+        let peers: Vec<BitcoinPeerInfo> = serde_json::from_str(output).unwrap();
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].subver, "/Satoshi:28.0.0/");
+        assert_eq!(peers[0].synced_blocks, 210);
+        assert!(peers[1].inbound);
+        assert_eq!(peers[1].synced_headers, -1);
+    }
+
+    #[test]
+    fn test_parse_mempool() {
+        let json = serde_json::json!({
+            "bb": {"vsize": 141, "time": 1_700_000_100, "fees": {"base": 0.000_014_1}},
+            "aa": {"vsize": 110, "time": 1_700_000_000, "fees": {"base": 0.000_022}}
+        });
+
+        let entries = BitcoinNode::parse_mempool(&json);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].txid, "aa");
+        assert_eq!(entries[0].fee_sat, 2_200);
+        assert!((entries[0].fee_rate() - 20.0).abs() < f64::EPSILON);
+        assert_eq!(entries[1].fee_sat, 1_410);
+        assert_eq!(entries[1].vsize, 141);
+
+        assert!(BitcoinNode::parse_mempool(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_has_loaded_wallet() {
+        assert!(has_loaded_wallet(r#"["default"]"#).unwrap());
+        assert!(!has_loaded_wallet("[\n]\n").unwrap());
+        assert!(has_loaded_wallet("error: no wallet").is_err());
+    }
+
+    #[test]
+    fn test_has_wallet_on_disk() {
+        let output = r#"{"wallets": [{"name": "other"}, {"name": "polar"}]}"#;
+        assert!(has_wallet_on_disk(output, "polar").unwrap());
+        assert!(!has_wallet_on_disk(output, "missing").unwrap());
+        assert!(!has_wallet_on_disk(r#"{"wallets": []}"#, "polar").unwrap());
+        assert!(has_wallet_on_disk("error: no wallet dir", "polar").is_err());
+    }
+
+    #[test]
+    fn test_parse_fee_estimate() {
+        let json = serde_json::json!({ "feerate": 0.000_25, "blocks": 2 });
+        let rate = parse_fee_estimate(&json).unwrap();
+        assert!((rate - 25.0).abs() < f64::EPSILON * 100.0);
+
+        let json = serde_json::json!({
+            "errors": ["Insufficient data or no feerate found"],
+            "blocks": 0
+        });
+        assert_eq!(parse_fee_estimate(&json), None);
+    }
+
+    #[test]
+    fn test_parse_bump_fee() {
+        let output = r#"{"txid": "cd34", "origfee": 0.00001, "fee": 0.00005, "errors": []}"#;
+        assert_eq!(parse_bump_fee("ab12", output).unwrap(), "cd34");
+
+        let output = "error code: -4\nerror message:\nTransaction is not BIP 125 replaceable\n";
+        let err = parse_bump_fee("ab12", output).unwrap_err().to_string();
+        assert!(err.contains("does not signal RBF"), "{err}");
+
+        let output = "error code: -8\nerror message:\nTransaction has been mined\n";
+        let err = parse_bump_fee("ab12", output).unwrap_err().to_string();
+        assert!(err.contains("Transaction has been mined"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_confirmations() {
+        let json = serde_json::json!({ "txid": "ab12", "confirmations": 6 });
+        assert_eq!(parse_confirmations(&json), 6);
+
+        // Raw mempool transactions have no confirmations field
+        let json = serde_json::json!({ "txid": "ab12" });
+        assert_eq!(parse_confirmations(&json), 0);
+
+        // Conflicted wallet transactions report a negative count
+        let json = serde_json::json!({ "txid": "ab12", "confirmations": -1 });
+        assert_eq!(parse_confirmations(&json), 0);
+    }
+
+    #[test]
+    fn test_daemon_command_uses_rpc_credentials() {
+        let node = BitcoinNode::new("btc").with_rpc(RpcCredentials::new("alice", "secret"));
+        let cmd = node.daemon_command().unwrap();
+        assert!(cmd.contains(&"-rpcuser=alice".to_string()));
+        assert!(cmd.contains(&"-rpcpassword=secret".to_string()));
+        assert!(!cmd.iter().any(|arg| arg.contains("polaruser")));
+    }
+}
@@ -0,0 +1,33 @@
+//! Reading values out of lncli JSON output.
+
+use serde_json::Value;
+
+/// Read an integer from lncli JSON output.
+///
+/// lncli prints int64 values as strings while smaller integers stay JSON
+/// numbers, so both are accepted. Missing or unparsable values read as zero.
+#[must_use]
+pub fn lncli_number<T: std::str::FromStr + Default>(value: &Value) -> T {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    }
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lncli_number() {
+        let json = serde_json::json!({ "amt": "2500", "hops": 3, "fee": "-1", "memo": "x" });
+        assert_eq!(lncli_number::<u64>(&json["amt"]), 2500);
+        assert_eq!(lncli_number::<i64>(&json["hops"]), 3);
+        assert_eq!(lncli_number::<i64>(&json["fee"]), -1);
+        assert_eq!(lncli_number::<u64>(&json["fee"]), 0);
+        assert_eq!(lncli_number::<u64>(&json["memo"]), 0);
+        assert_eq!(lncli_number::<u64>(&json["missing"]), 0);
+    }
+}
@@ -2,8 +2,20 @@
 //!
 //! This crate provides Bitcoin Core and LND node management.
 
+mod address;
+mod args;
 mod bitcoin;
+mod json;
 mod lnd;
+mod lndconnect;
+mod rest;
 
+pub use address::AddressType;
 pub use bitcoin::{BITCOIN_VERSIONS, BitcoinNode};
-pub use lnd::{LND_VERSIONS, LndNode};
+pub use json::lncli_number;
+pub use lnd::{
+    ChannelType, ExportedCredentials, LND_VERSIONS, LndCredential, LndNode, OpenChannelOptions,
+    PaymentOptions, set_route_payment_addr,
+};
+pub use lndconnect::lndconnect_url;
+pub use rest::LndRestClient;
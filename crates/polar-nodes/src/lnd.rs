@@ -1,7 +1,20 @@
 //! LND node implementation.
 
-use polar_core::{Node, NodeKind, Result};
-use polar_docker::{ContainerManager, PortMap};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use polar_core::{
+    ChannelPoint, DecodedInvoice, HtlcAttempt, MessageVerification, Node, NodeKind, PaymentUpdate,
+    Preimage, ResourceLimits, Result, RouteEstimate, RpcCredentials, UtxoInfo,
+};
+use polar_docker::{ContainerManager, PortMap, data_volume_name};
+
+use crate::address::AddressType;
+use crate::args::{append_extra_args, flag_value};
+use crate::bitcoin::BitcoinNode;
+use crate::json::lncli_number;
+use crate::rest::{LndRestClient, macaroon_hex};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Available LND versions.
 pub const LND_VERSIONS: &[&str] = &[
@@ -11,6 +24,303 @@ pub const LND_VERSIONS: &[&str] = &[
     "polarlightning/lnd:0.16.4-beta",
 ];
 
+/// Commitment type proposed when opening a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelType {
+    /// Static remote key commitments without anchor outputs.
+    Legacy,
+    /// Anchor output commitments, LND's default.
+    Anchors,
+    /// Simple taproot channels, which are always private.
+    SimpleTaproot,
+}
+
+/// Credential file an external wallet needs to talk to an LND node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LndCredential {
+    /// Self-signed TLS certificate of the gRPC and REST servers.
+    TlsCert,
+    /// Macaroon granting full access to the node.
+    AdminMacaroon,
+}
+
+impl LndCredential {
+    /// Path of the file inside the container.
+    #[must_use]
+    pub const fn container_path(self) -> &'static str {
+        match self {
+            Self::TlsCert => "/home/lnd/.lnd/tls.cert",
+            Self::AdminMacaroon => LndNode::ADMIN_MACAROON_PATH,
+        }
+    }
+
+    /// File name the credential is exported under.
+    #[must_use]
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::TlsCert => "tls.cert",
+            Self::AdminMacaroon => "admin.macaroon",
+        }
+    }
+}
+
+/// Host paths of the credentials written by [`LndNode::export_credentials`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedCredentials {
+    /// Path of the exported `tls.cert`.
+    pub tls_cert: PathBuf,
+    /// Path of the exported `admin.macaroon`.
+    pub admin_macaroon: PathBuf,
+}
+
+impl ChannelType {
+    /// Value for `lncli openchannel --channel_type`.
+    #[must_use]
+    pub const fn lncli_name(self) -> &'static str {
+        match self {
+            Self::Legacy => "tweakless",
+            Self::Anchors => "anchors",
+            Self::SimpleTaproot => "taproot",
+        }
+    }
+
+    /// `commitment_type` reported by `lncli listchannels` for this type.
+    #[must_use]
+    pub const fn commitment_type(self) -> &'static str {
+        match self {
+            Self::Legacy => "STATIC_REMOTE_KEY",
+            Self::Anchors => "ANCHORS",
+            Self::SimpleTaproot => "SIMPLE_TAPROOT",
+        }
+    }
+
+    /// Feature names a node advertises when it supports this type; any one
+    /// of them is enough.
+    #[must_use]
+    pub const fn feature_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Legacy => &["static-remote-key"],
+            Self::Anchors => &["anchors-zero-fee-htlc-tx", "anchor-commitments"],
+            Self::SimpleTaproot => &["simple-taproot-chans-x", "simple-taproot-chans"],
+        }
+    }
+
+    /// Whether a node advertising `features` can open this type of channel.
+    #[must_use]
+    pub fn is_supported_by(self, features: &[String]) -> bool {
+        self.feature_names()
+            .iter()
+            .any(|name| features.iter().any(|f| f == name))
+    }
+}
+
+impl std::fmt::Display for ChannelType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Legacy => "legacy",
+            Self::Anchors => "anchors",
+            Self::SimpleTaproot => "simple taproot",
+        })
+    }
+}
+
+/// Options for paying an invoice with `lncli payinvoice`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaymentOptions {
+    /// Maximum routing fee in satoshis (LND default if `None`).
+    pub fee_limit_sat: Option<u64>,
+    /// Allow the payment to be split into multiple parts (MPP).
+    pub allow_mpp: bool,
+    /// Maximum number of parts when MPP is allowed (LND default if `None`).
+    pub max_parts: Option<u32>,
+    /// Send as an atomic multi-path payment (AMP), which needs an AMP
+    /// invoice, see [`LndNode::create_amp_invoice`].
+    pub amp: bool,
+    /// Seconds to keep trying before giving up
+    /// ([`Self::DEFAULT_TIMEOUT_SECS`] if `None`).
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for PaymentOptions {
+    fn default() -> Self {
+        Self {
+            fee_limit_sat: None,
+            allow_mpp: true,
+            max_parts: None,
+            amp: false,
+            timeout_secs: None,
+        }
+    }
+}
+
+impl PaymentOptions {
+    /// Payment timeout used when none is set, matching lncli's default.
+    pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+    /// Extra wait past the payment timeout before abandoning the lncli call,
+    /// since lncli keeps waiting on HTLCs that are already in flight.
+    const TIMEOUT_GRACE: Duration = Duration::from_secs(10);
+
+    /// Set how long to keep trying the payment.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    /// Set the maximum routing fee in satoshis.
+    #[must_use]
+    pub const fn with_fee_limit(mut self, fee_limit_sat: u64) -> Self {
+        self.fee_limit_sat = Some(fee_limit_sat);
+        self
+    }
+
+    /// Allow or forbid splitting the payment, optionally capping the parts.
+    #[must_use]
+    pub const fn with_mpp(mut self, allow_mpp: bool, max_parts: Option<u32>) -> Self {
+        self.allow_mpp = allow_mpp;
+        self.max_parts = max_parts;
+        self
+    }
+
+    /// Send the payment as an atomic multi-path payment.
+    #[must_use]
+    pub const fn with_amp(mut self, amp: bool) -> Self {
+        self.amp = amp;
+        self
+    }
+
+    /// Extra `payinvoice` flags for these options.
+    fn payinvoice_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(fee_limit) = self.fee_limit_sat {
+            args.push("--fee_limit".to_string());
+            args.push(fee_limit.to_string());
+        }
+        // A single part disables MPP
+        let max_parts = if self.allow_mpp {
+            self.max_parts
+        } else {
+            Some(1)
+        };
+        if let Some(max_parts) = max_parts {
+            args.push("--max_parts".to_string());
+            args.push(max_parts.to_string());
+        }
+        if self.amp {
+            args.push("--amp".to_string());
+        }
+        if let Some(timeout) = self.timeout_secs {
+            args.push("--timeout".to_string());
+            args.push(format!("{timeout}s"));
+        }
+        args
+    }
+}
+
+/// Settings for opening a channel with `lncli openchannel`.
+///
+/// Unset fields leave LND's defaults in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenChannelOptions {
+    /// Satoshis pushed to the peer when the channel opens.
+    pub push_amount: Option<u64>,
+    /// Commitment type to propose; simple taproot channels are opened as
+    /// private.
+    pub channel_type: Option<ChannelType>,
+    /// Whether to keep the channel out of the graph (`--private`).
+    pub private: bool,
+    /// Outpoints to fund the channel from; empty lets LND choose.
+    pub utxos: Vec<String>,
+    /// Confirmations the funding inputs need; 0 spends unconfirmed outputs.
+    pub min_confs: Option<u32>,
+    /// Blocks the peer must wait to claim its funds after force closing.
+    pub remote_csv_delay: Option<u32>,
+    /// Smallest HTLC in millisatoshis this node accepts on the channel.
+    pub min_htlc_msat: Option<u64>,
+}
+
+impl OpenChannelOptions {
+    /// Set the amount pushed to the peer.
+    #[must_use]
+    pub const fn with_push_amount(mut self, push_amount: u64) -> Self {
+        self.push_amount = Some(push_amount);
+        self
+    }
+
+    /// Set the commitment type to propose.
+    #[must_use]
+    pub const fn with_channel_type(mut self, channel_type: ChannelType) -> Self {
+        self.channel_type = Some(channel_type);
+        self
+    }
+
+    /// Set whether the channel stays out of the graph.
+    #[must_use]
+    pub const fn with_private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+
+    /// Fund the channel from these outpoints only.
+    #[must_use]
+    pub fn with_utxos(mut self, utxos: Vec<String>) -> Self {
+        self.utxos = utxos;
+        self
+    }
+
+    /// Set the confirmations the funding inputs need.
+    #[must_use]
+    pub const fn with_min_confs(mut self, min_confs: u32) -> Self {
+        self.min_confs = Some(min_confs);
+        self
+    }
+
+    /// Set the CSV delay imposed on the peer.
+    #[must_use]
+    pub const fn with_remote_csv_delay(mut self, blocks: u32) -> Self {
+        self.remote_csv_delay = Some(blocks);
+        self
+    }
+
+    /// Set the smallest HTLC accepted on the channel.
+    #[must_use]
+    pub const fn with_min_htlc_msat(mut self, min_htlc_msat: u64) -> Self {
+        self.min_htlc_msat = Some(min_htlc_msat);
+        self
+    }
+
+    /// `openchannel` flags for these options, which must precede the
+    /// positional arguments.
+    fn openchannel_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for utxo in &self.utxos {
+            args.push("--utxo".to_string());
+            args.push(utxo.clone());
+        }
+        if let Some(channel_type) = self.channel_type {
+            args.push("--channel_type".to_string());
+            args.push(channel_type.lncli_name().to_string());
+        }
+        if self.private || self.channel_type == Some(ChannelType::SimpleTaproot) {
+            args.push("--private".to_string());
+        }
+        if let Some(min_confs) = self.min_confs {
+            args.push("--min_confs".to_string());
+            args.push(min_confs.to_string());
+        }
+        if let Some(delay) = self.remote_csv_delay {
+            args.push("--remote_csv_delay".to_string());
+            args.push(delay.to_string());
+        }
+        if let Some(min_htlc) = self.min_htlc_msat {
+            args.push("--min_htlc_msat".to_string());
+            args.push(min_htlc.to_string());
+        }
+        args
+    }
+}
+
 /// LND Lightning node configuration and management.
 pub struct LndNode {
     /// The underlying node data.
@@ -19,14 +329,65 @@ pub struct LndNode {
     pub image: String,
     /// Bitcoin backend node name.
     pub bitcoin_node: String,
+    /// Host the Bitcoin backend is reached at, when it is not the
+    /// `polar-btc-{bitcoin_node}` container polar started, e.g. an adopted one.
+    pub bitcoin_host: Option<String>,
     /// Node alias.
     pub alias: String,
+    /// RPC credentials of the Bitcoin backend.
+    pub rpc: RpcCredentials,
+    /// Additional lnd flags appended to the generated command line.
+    pub extra_args: Vec<String>,
 }
 
 impl LndNode {
     /// Default LND image.
     pub const DEFAULT_IMAGE: &'static str = "polarlightning/lnd:0.18.5-beta";
 
+    /// Directory lnd keeps its wallet, macaroons and channel state in inside
+    /// the image.
+    pub const DATA_DIR: &'static str = "/home/lnd/.lnd";
+
+    /// Default time to wait for lnd to shut down after `lncli stop`.
+    pub const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Most invoices or payments fetched for the payment history.
+    pub const HISTORY_LIMIT: u32 = 50;
+
+    /// Port the integrated watchtower server listens on.
+    pub const WATCHTOWER_PORT: u16 = 9911;
+
+    /// Largest channel LND accepts without wumbo channels, in satoshis.
+    pub const MAX_CHANNEL_SIZE: u64 = 16_777_215;
+
+    /// Largest channel LND accepts with wumbo channels enabled, in satoshis.
+    pub const MAX_WUMBO_CHANNEL_SIZE: u64 = 1_000_000_000;
+
+    /// Flag enabling channels above [`Self::MAX_CHANNEL_SIZE`].
+    ///
+    /// Bitcoin Core needs no matching option; the limit is purely LND's.
+    pub const WUMBO_FLAG: &'static str = "--protocol.wumbo-channels";
+
+    /// Flag enabling [`ChannelType::SimpleTaproot`] channels; both peers need it.
+    pub const TAPROOT_FLAG: &'static str = "--protocol.simple-taproot-chans";
+
+    /// Path of the admin macaroon inside the container.
+    const ADMIN_MACAROON_PATH: &'static str =
+        "/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon";
+
+    /// Flags extra args may not set, on top of those polar already passes.
+    const RESERVED_FLAGS: &'static [&'static str] = &[
+        "bitcoin.mainnet",
+        "bitcoin.testnet",
+        "bitcoin.testnet4",
+        "bitcoin.signet",
+        "bitcoin.simnet",
+        "lnddir",
+        "listen",
+        "rpclisten",
+        "restlisten",
+    ];
+
     /// Create a new LND node.
     pub fn new(name: impl Into<String>, bitcoin_node: impl Into<String>) -> Self {
         let name = name.into();
@@ -34,7 +395,10 @@ impl LndNode {
             node: Node::new(name.clone(), NodeKind::Lnd),
             image: Self::DEFAULT_IMAGE.to_string(),
             bitcoin_node: bitcoin_node.into(),
+            bitcoin_host: None,
             alias: name, // Default alias is the node name
+            rpc: RpcCredentials::default(),
+            extra_args: Vec::new(),
         }
     }
 
@@ -48,7 +412,10 @@ impl LndNode {
             node: Node::new(name, NodeKind::Lnd),
             image: Self::DEFAULT_IMAGE.to_string(),
             bitcoin_node: bitcoin_node.into(),
+            bitcoin_host: None,
             alias: alias.into(),
+            rpc: RpcCredentials::default(),
+            extra_args: Vec::new(),
         }
     }
 
@@ -58,6 +425,119 @@ impl LndNode {
         self
     }
 
+    /// Set the RPC credentials used to reach the Bitcoin backend.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: RpcCredentials) -> Self {
+        self.rpc = rpc;
+        self
+    }
+
+    /// Append extra lnd flags, e.g. `--protocol.wumbo-channels`.
+    #[must_use]
+    pub fn with_extra_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra_args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set a variable in the container's environment.
+    ///
+    /// Flags on the generated command line take precedence over anything
+    /// lnd also reads from the environment.
+    #[must_use]
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.node.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Check that the extra args don't override flags polar relies on.
+    pub fn check_extra_args(&self) -> Result<()> {
+        self.daemon_command(true).map(|_| ())
+    }
+
+    /// Check that this node subscribes to the ZMQ endpoints `bitcoin`
+    /// publishes on.
+    ///
+    /// LND does not complain about a wrong endpoint, it just never learns
+    /// about new blocks and stays unsynced, so this runs before starting.
+    pub fn check_zmq_backend(&self, bitcoin: &BitcoinNode) -> Result<()> {
+        let cmd = self.daemon_command(false)?;
+        let (block_port, tx_port) = bitcoin.zmq_ports()?;
+
+        for (flag, port) in [
+            ("bitcoind.zmqpubrawblock", block_port),
+            ("bitcoind.zmqpubrawtx", tx_port),
+        ] {
+            let host = self
+                .bitcoin_host
+                .clone()
+                .unwrap_or_else(|| bitcoin.container_name());
+            let expected = format!("tcp://{host}:{port}");
+            let actual = flag_value(&cmd, flag).unwrap_or_default();
+            if actual != expected {
+                return Err(polar_core::Error::Config(format!(
+                    "{} subscribes to {actual} for --{flag}, but {} publishes on {expected}",
+                    self.node.name, bitcoin.node.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the lnd command line for this node.
+    ///
+    /// With `watchtower` set the node runs both the watchtower server and
+    /// client, so it can protect other nodes or be protected by them.
+    fn daemon_command(&self, watchtower: bool) -> Result<Vec<String>> {
+        let bitcoin_host = self
+            .bitcoin_host
+            .clone()
+            .unwrap_or_else(|| format!("polar-btc-{}", self.bitcoin_node));
+        let mut cmd = vec![
+            "lnd".to_string(),
+            "--noseedbackup".to_string(),
+            "--trickledelay=5000".to_string(),
+            format!("--alias={}", self.alias),
+            "--debuglevel=info".to_string(),
+            "--bitcoin.active".to_string(),
+            "--bitcoin.regtest".to_string(),
+            "--bitcoin.node=bitcoind".to_string(),
+            format!("--bitcoind.rpchost={bitcoin_host}"),
+            format!("--bitcoind.rpcuser={}", self.rpc.user),
+            format!("--bitcoind.rpcpass={}", self.rpc.pass),
+            format!(
+                "--bitcoind.zmqpubrawblock=tcp://{bitcoin_host}:{}",
+                BitcoinNode::ZMQ_BLOCK_PORT
+            ),
+            format!(
+                "--bitcoind.zmqpubrawtx=tcp://{bitcoin_host}:{}",
+                BitcoinNode::ZMQ_TX_PORT
+            ),
+        ];
+        if watchtower {
+            cmd.push("--watchtower.active".to_string());
+            cmd.push("--wtclient.active".to_string());
+        }
+        append_extra_args(&mut cmd, &self.extra_args, Self::RESERVED_FLAGS)?;
+        Ok(cmd)
+    }
+
+    /// Name of the Docker container this node runs in.
+    #[must_use]
+    pub fn container_name(&self) -> String {
+        format!("polar-lnd-{}", self.node.id)
+    }
+
+    /// Name of the Docker volume holding [`Self::DATA_DIR`].
+    #[must_use]
+    pub fn volume_name(&self) -> String {
+        data_volume_name(&self.container_name())
+    }
+
     /// Start the LND container.
     pub async fn start(&mut self, manager: &ContainerManager) -> Result<()> {
         self.start_with_network(manager, None).await
@@ -69,7 +549,8 @@ impl LndNode {
         manager: &ContainerManager,
         network: Option<&str>,
     ) -> Result<()> {
-        self.start_with_ports(manager, network, None).await
+        self.start_with_ports(manager, network, None, false, ResourceLimits::default())
+            .await
     }
 
     /// Start the LND container with custom port mappings.
@@ -78,38 +559,28 @@ impl LndNode {
     /// * `manager` - Docker container manager
     /// * `network` - Optional Docker network name
     /// * `ports` - Optional port configuration (rest, grpc, p2p)
+    /// * `watchtower` - Run the watchtower server and client
+    /// * `limits` - CPU and memory caps for the container
+    #[tracing::instrument(skip_all, fields(node = %self.node.name, image = %self.image))]
     pub async fn start_with_ports(
         &mut self,
         manager: &ContainerManager,
         network: Option<&str>,
         ports: Option<(u16, u16, u16)>,
+        watchtower: bool,
+        limits: ResourceLimits,
     ) -> Result<()> {
         // Ensure the image exists locally
         manager.ensure_image(&self.image).await?;
 
-        let container_name = format!("polar-lnd-{}", self.node.id);
+        let container_name = self.container_name();
 
-        let cmd = vec![
-            "lnd".to_string(),
-            "--noseedbackup".to_string(),
-            "--trickledelay=5000".to_string(),
-            format!("--alias={}", self.alias),
-            "--debuglevel=info".to_string(),
-            "--bitcoin.active".to_string(),
-            "--bitcoin.regtest".to_string(),
-            "--bitcoin.node=bitcoind".to_string(),
-            format!("--bitcoind.rpchost=polar-btc-{}", self.bitcoin_node),
-            "--bitcoind.rpcuser=polaruser".to_string(),
-            "--bitcoind.rpcpass=polarpass".to_string(),
-            format!(
-                "--bitcoind.zmqpubrawblock=tcp://polar-btc-{}:28334",
-                self.bitcoin_node
-            ),
-            format!(
-                "--bitcoind.zmqpubrawtx=tcp://polar-btc-{}:28335",
-                self.bitcoin_node
-            ),
-        ];
+        let mut cmd = self.daemon_command(watchtower)?;
+        if ports.is_some() {
+            // lnd only serves REST on localhost by default, out of reach of
+            // the published port
+            cmd.push("--restlisten=0.0.0.0:8080".to_string());
+        }
 
         // Configure port mappings if ports are provided
         let port_map = ports.map(|(rest_port, grpc_port, p2p_port)| {
@@ -127,6 +598,9 @@ impl LndNode {
                 Some(cmd),
                 port_map,
                 network,
+                limits,
+                &self.node.env,
+                &[(self.volume_name(), Self::DATA_DIR.to_string())],
             )
             .await?;
 
@@ -138,42 +612,160 @@ impl LndNode {
 
     /// Stop the LND container.
     pub async fn stop(&mut self, manager: &ContainerManager) -> Result<()> {
-        if let Some(container_id) = &self.node.container_id {
-            manager.stop_container(container_id).await?;
-            manager.remove_container(container_id).await?;
-            self.node.container_id = None;
+        self.stop_with_timeout(manager, Self::DEFAULT_STOP_TIMEOUT)
+            .await
+            .map(|_| ())
+    }
+
+    /// Stop the LND container, letting lnd shut down cleanly first.
+    ///
+    /// Runs `lncli stop` so lnd can flush its channel database, then waits up
+    /// to `timeout` for the container to exit before forcing it down.
+    ///
+    /// # Returns
+    /// `true` if lnd exited on its own, `false` if the forced path was used
+    pub async fn stop_with_timeout(
+        &mut self,
+        manager: &ContainerManager,
+        timeout: Duration,
+    ) -> Result<bool> {
+        let Some(container_id) = self.node.container_id.clone() else {
+            return Ok(true);
+        };
+
+        // Ask lnd to shut down; it may already be unresponsive, which the
+        // timeout below handles
+        let _ = manager
+            .exec_command(&container_id, lncli_command(&["stop"]))
+            .await;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut graceful = false;
+        while tokio::time::Instant::now() < deadline {
+            if !manager.is_running(&container_id).await.unwrap_or(true) {
+                graceful = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
         }
-        Ok(())
+
+        if !graceful {
+            tracing::warn!(
+                "lnd in {} did not stop within {:?}, forcing container stop",
+                self.node.name,
+                timeout
+            );
+            manager.stop_container(&container_id).await?;
+        }
+
+        manager.remove_container(&container_id).await?;
+        self.node.container_id = None;
+
+        Ok(graceful)
+    }
+
+    /// Read the admin macaroon, hex encoded for REST requests.
+    pub async fn admin_macaroon_hex(&self, manager: &ContainerManager) -> Result<String> {
+        let macaroon = self
+            .read_credential(manager, LndCredential::AdminMacaroon)
+            .await?;
+        Ok(macaroon_hex(&macaroon))
+    }
+
+    /// Read a credential file from the container.
+    ///
+    /// Fails with `NodeNotRunning` for a stopped node, since LND regenerates
+    /// an expired TLS cert on startup and a stale copy would not verify.
+    pub async fn read_credential(
+        &self,
+        manager: &ContainerManager,
+        credential: LndCredential,
+    ) -> Result<Vec<u8>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+        if !manager.is_running(container_id).await? {
+            return Err(polar_core::Error::NodeNotRunning(self.node.name.clone()));
+        }
+
+        manager
+            .download_file(container_id, credential.container_path())
+            .await
+    }
+
+    /// Copy the TLS cert and admin macaroon out of the container into
+    /// `dest_dir`, creating it if needed.
+    pub async fn export_credentials(
+        &self,
+        manager: &ContainerManager,
+        dest_dir: &Path,
+    ) -> Result<ExportedCredentials> {
+        std::fs::create_dir_all(dest_dir)?;
+
+        let tls_cert = dest_dir.join(LndCredential::TlsCert.file_name());
+        let content = self
+            .read_credential(manager, LndCredential::TlsCert)
+            .await?;
+        std::fs::write(&tls_cert, content)?;
+
+        let admin_macaroon = dest_dir.join(LndCredential::AdminMacaroon.file_name());
+        let content = self
+            .read_credential(manager, LndCredential::AdminMacaroon)
+            .await?;
+        std::fs::write(&admin_macaroon, content)?;
+
+        Ok(ExportedCredentials {
+            tls_cert,
+            admin_macaroon,
+        })
+    }
+
+    /// Create a REST client for this node, reachable on `address` (`host:port`).
+    ///
+    /// The client trusts the node's current TLS cert only.
+    pub async fn rest_client(
+        &self,
+        manager: &ContainerManager,
+        address: impl Into<String>,
+    ) -> Result<LndRestClient> {
+        let macaroon_hex = self.admin_macaroon_hex(manager).await?;
+        let tls_cert = self
+            .read_credential(manager, LndCredential::TlsCert)
+            .await?;
+        LndRestClient::new(address, macaroon_hex, &tls_cert)
     }
 
     /// Get a new on-chain Bitcoin address for depositing funds.
-    pub async fn get_new_address(&self, manager: &ContainerManager) -> Result<String> {
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `address_type` - Script type (None for native segwit)
+    pub async fn get_new_address(
+        &self,
+        manager: &ContainerManager,
+        address_type: Option<AddressType>,
+    ) -> Result<String> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
         let output = manager
             .exec_command(
                 container_id,
-                vec![
-                    "lncli",
-                    "--network=regtest",
-                    "--tlscertpath=/home/lnd/.lnd/tls.cert",
-                    "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-                    "newaddress",
-                    "p2wkh",
-                ],
+                lncli_command(&["newaddress", address_type.unwrap_or_default().lncli_name()]),
             )
             .await?;
 
         let json: serde_json::Value = serde_json::from_str(&output)
-            .map_err(|e| polar_core::Error::Config(format!("Failed to parse address: {}", e)))?;
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse address: {}", e)))?;
 
         let address = json["address"]
             .as_str()
-            .ok_or_else(|| polar_core::Error::Config("No address in response".to_string()))?
+            .ok_or_else(|| polar_core::Error::RpcParse("No address in response".to_string()))?
             .to_string();
 
         Ok(address)
@@ -185,34 +777,50 @@ impl LndNode {
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
         let output = manager
-            .exec_command(
-                container_id,
-                vec![
-                    "lncli",
-                    "--network=regtest",
-                    "--tlscertpath=/home/lnd/.lnd/tls.cert",
-                    "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-                    "getinfo",
-                ],
-            )
+            .exec_command(container_id, lncli_command(&["getinfo"]))
             .await?;
 
         let json: serde_json::Value = serde_json::from_str(&output)
-            .map_err(|e| polar_core::Error::Config(format!("Failed to parse getinfo: {}", e)))?;
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse getinfo: {}", e)))?;
 
         let pubkey = json["identity_pubkey"]
             .as_str()
-            .ok_or_else(|| polar_core::Error::Config("No pubkey in response".to_string()))?
+            .ok_or_else(|| polar_core::Error::RpcParse("No pubkey in response".to_string()))?
             .to_string();
 
         Ok(pubkey)
     }
 
+    /// Get the node's best block height and whether it reports being synced
+    /// to the chain.
+    pub async fn chain_sync(&self, manager: &ContainerManager) -> Result<(u32, bool)> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command(container_id, lncli_command(&["getinfo"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse getinfo: {e}")))?;
+
+        let height = json["block_height"]
+            .as_u64()
+            .and_then(|height| u32::try_from(height).ok())
+            .unwrap_or(0);
+        Ok((height, json["synced_to_chain"].as_bool().unwrap_or(false)))
+    }
+
     /// Connect to another LND node as a peer.
     ///
+    /// Succeeds if the nodes are already connected.
+    ///
     /// # Arguments
     /// * `manager` - Docker container manager
     /// * `peer_pubkey` - Public key of the peer node
@@ -227,69 +835,227 @@ impl LndNode {
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
         let peer_address = format!("{}@{}", peer_pubkey, peer_host);
 
-        manager
-            .exec_command(
-                container_id,
-                vec![
-                    "lncli",
-                    "--network=regtest",
-                    "--tlscertpath=/home/lnd/.lnd/tls.cert",
-                    "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-                    "connect",
-                    &peer_address,
-                ],
-            )
+        let result = manager
+            .exec_command(container_id, lncli_command(&["connect", &peer_address]))
+            .await;
+
+        match result {
+            Err(e) if e.to_string().contains("already connected") => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// Get the public key of this node's watchtower server.
+    ///
+    /// The tower key is distinct from the node's identity pubkey.
+    pub async fn tower_pubkey(&self, manager: &ContainerManager) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command_unchecked(container_id, lncli_command(&["tower", "info"]))
             .await?;
 
-        Ok(())
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Watchtower not available", &output))?;
+
+        let pubkey = json["pubkey"]
+            .as_str()
+            .ok_or_else(|| polar_core::Error::RpcParse("No tower pubkey in response".to_string()))?
+            .to_string();
+
+        Ok(pubkey)
     }
 
-    /// Open a Lightning channel to another node.
+    /// Register a watchtower with this node's watchtower client.
     ///
     /// # Arguments
     /// * `manager` - Docker container manager
-    /// * `peer_pubkey` - Public key of the peer to open channel with
-    /// * `amount` - Channel capacity in satoshis
-    /// * `push_amount` - Amount to push to peer in satoshis (optional)
-    pub async fn open_channel(
+    /// * `pubkey` - Public key of the watchtower
+    /// * `address` - Address of the watchtower (format: "host:port")
+    pub async fn add_watchtower(
         &self,
         manager: &ContainerManager,
-        peer_pubkey: &str,
-        amount: u64,
-        push_amount: Option<u64>,
-    ) -> Result<String> {
+        pubkey: &str,
+        address: &str,
+    ) -> Result<()> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
-        let amount_str = amount.to_string();
-        let push_str = push_amount.map(|p| p.to_string());
-
-        let mut args = vec![
-            "lncli",
-            "--network=regtest",
-            "--tlscertpath=/home/lnd/.lnd/tls.cert",
-            "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-            "openchannel",
-            peer_pubkey,
-            &amount_str,
-        ];
+        let tower_address = format!("{pubkey}@{address}");
 
-        if let Some(ref push) = push_str {
-            args.push(push);
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["wtclient", "add", &tower_address]),
+            )
+            .await?;
+
+        // lncli prints an rpc error instead of JSON when wtclient is inactive
+        if serde_json::from_str::<serde_json::Value>(&output).is_err() {
+            return Err(lncli_error("Failed to add watchtower", &output));
         }
 
-        let output = manager.exec_command(container_id, args).await?;
+        Ok(())
+    }
+
+    /// Disconnect from a peer.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `peer_pubkey` - Public key of the peer to disconnect from
+    pub async fn disconnect_peer(
+        &self,
+        manager: &ContainerManager,
+        peer_pubkey: &str,
+    ) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command_unchecked(container_id, lncli_command(&["disconnect", peer_pubkey]))
+            .await?;
+
+        // lncli prints an rpc error instead of JSON when the peer is unknown
+        if serde_json::from_str::<serde_json::Value>(&output).is_err() {
+            return Err(lncli_error("Failed to disconnect peer", &output));
+        }
+
+        Ok(())
+    }
+
+    /// List all peers connected to this node.
+    pub async fn list_peers(&self, manager: &ContainerManager) -> Result<serde_json::Value> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command(container_id, lncli_command(&["listpeers"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse peers: {e}")))?;
+
+        Ok(json)
+    }
+
+    /// List the confirmed unspent outputs of the node's wallet.
+    pub async fn list_unspent(&self, manager: &ContainerManager) -> Result<Vec<UtxoInfo>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command_unchecked(container_id, lncli_command(&["listunspent"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Failed to list unspent outputs", &output))?;
+
+        Ok(parse_utxos(&json))
+    }
+
+    /// Get the names of the feature bits this node advertises.
+    pub async fn get_features(&self, manager: &ContainerManager) -> Result<Vec<String>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command(container_id, lncli_command(&["getinfo"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse getinfo: {e}")))?;
+
+        Ok(parse_feature_names(&json))
+    }
+
+    /// Open a Lightning channel to another node.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `peer_pubkey` - Public key of the peer to open channel with
+    /// * `amount` - Channel capacity in satoshis
+    /// * `push_amount` - Amount to push to peer in satoshis (optional)
+    pub async fn open_channel(
+        &self,
+        manager: &ContainerManager,
+        peer_pubkey: &str,
+        amount: u64,
+        push_amount: Option<u64>,
+    ) -> Result<String> {
+        let options = OpenChannelOptions {
+            push_amount,
+            ..OpenChannelOptions::default()
+        };
+        self.open_channel_with_options(manager, peer_pubkey, amount, &options)
+            .await
+    }
+
+    /// Open a Lightning channel with a commitment type, funding outpoints or
+    /// acceptance settings.
+    ///
+    /// Takes the same arguments as [`Self::open_channel`], with the push
+    /// amount moved into `options`, whose set fields are passed on to
+    /// `lncli openchannel`.
+    pub async fn open_channel_with_options(
+        &self,
+        manager: &ContainerManager,
+        peer_pubkey: &str,
+        amount: u64,
+        options: &OpenChannelOptions,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let amount_str = amount.to_string();
+        let push_str = options.push_amount.map(|p| p.to_string());
+
+        let mut args = lncli_command(&["openchannel"]);
+
+        if !options.utxos.is_empty() {
+            let available = self.list_unspent(manager).await?;
+            check_selected_utxos(&self.node.name, &available, &options.utxos, amount)?;
+        }
+
+        let option_args = options.openchannel_args();
+        args.extend(option_args.iter().map(String::as_str));
+
+        args.push(peer_pubkey);
+        args.push(&amount_str);
+        if let Some(ref push) = push_str {
+            args.push(push);
+        }
+
+        let output = manager.exec_command(container_id, args).await?;
 
         // Parse the funding txid from the output
         let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
-            polar_core::Error::Config(format!(
+            polar_core::Error::RpcParse(format!(
                 "Failed to parse channel open response: {}. Output was: {}",
                 e, output
             ))
@@ -298,7 +1064,7 @@ impl LndNode {
         let funding_txid = json["funding_txid"]
             .as_str()
             .ok_or_else(|| {
-                polar_core::Error::Config(format!(
+                polar_core::Error::RpcParse(format!(
                     "No funding_txid in response. Full response: {}",
                     output
                 ))
@@ -319,35 +1085,56 @@ impl LndNode {
         manager: &ContainerManager,
         amount: u64,
         memo: Option<&str>,
+    ) -> Result<String> {
+        self.add_invoice(manager, amount, memo, false).await
+    }
+
+    /// Create an invoice that accepts atomic multi-path payments (AMP).
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `amount` - Amount in satoshis
+    /// * `memo` - Optional description for the invoice
+    pub async fn create_amp_invoice(
+        &self,
+        manager: &ContainerManager,
+        amount: u64,
+        memo: Option<&str>,
+    ) -> Result<String> {
+        self.add_invoice(manager, amount, memo, true).await
+    }
+
+    /// Create an invoice with `lncli addinvoice`.
+    async fn add_invoice(
+        &self,
+        manager: &ContainerManager,
+        amount: u64,
+        memo: Option<&str>,
+        amp: bool,
     ) -> Result<String> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
         let amount_str = amount.to_string();
         let memo_str = memo.map(|m| m.to_string());
 
-        let mut args = vec![
-            "lncli",
-            "--network=regtest",
-            "--tlscertpath=/home/lnd/.lnd/tls.cert",
-            "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-            "addinvoice",
-            "--amt",
-            &amount_str,
-        ];
+        let mut args = lncli_command(&["addinvoice", "--amt", &amount_str]);
 
         if let Some(ref m) = memo_str {
             args.push("--memo");
             args.push(m);
         }
+        if amp {
+            args.push("--amp");
+        }
 
         let output = manager.exec_command(container_id, args).await?;
 
         let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
-            polar_core::Error::Config(format!(
+            polar_core::Error::RpcParse(format!(
                 "Failed to parse invoice: {}. Output was: {}",
                 e, output
             ))
@@ -356,7 +1143,7 @@ impl LndNode {
         let payment_request = json["payment_request"]
             .as_str()
             .ok_or_else(|| {
-                polar_core::Error::Config(format!(
+                polar_core::Error::RpcParse(format!(
                     "No payment_request in response. Full response: {}",
                     output
                 ))
@@ -366,150 +1153,1566 @@ impl LndNode {
         Ok(payment_request)
     }
 
-    /// Pay a Lightning invoice.
+    /// Create a hold invoice for a caller-supplied payment hash.
+    ///
+    /// Incoming HTLCs are held until the invoice is settled with the matching
+    /// preimage or cancelled. The caller must generate the preimage/hash pair
+    /// (see [`polar_core::Preimage`]) and keep the preimage to settle.
     ///
     /// # Arguments
     /// * `manager` - Docker container manager
-    /// * `payment_request` - The bolt11 invoice string
-    pub async fn pay_invoice(
+    /// * `payment_hash` - Hex-encoded SHA-256 of the preimage
+    /// * `amount` - Invoice amount in satoshis
+    pub async fn add_hold_invoice(
         &self,
         manager: &ContainerManager,
-        payment_request: &str,
+        payment_hash: &str,
+        amount: u64,
     ) -> Result<String> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let amount_str = amount.to_string();
 
-        // Use payinvoice with --force and --json flags for non-interactive execution
         let output = manager
-            .exec_command(
+            .exec_command_unchecked(
                 container_id,
-                vec![
-                    "lncli",
-                    "--network=regtest",
-                    "--tlscertpath=/home/lnd/.lnd/tls.cert",
-                    "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-                    "payinvoice",
-                    "--force",
-                    "--json",
-                    payment_request,
-                ],
+                lncli_command(&["addholdinvoice", "--amt", &amount_str, payment_hash]),
             )
             .await?;
 
-        let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
-            polar_core::Error::Config(format!(
-                "Failed to parse payment response: {}. Output was: {}",
-                e, output
-            ))
-        })?;
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Failed to add hold invoice", &output))?;
 
-        let payment_hash = json["payment_hash"]
+        json["payment_request"]
             .as_str()
+            .map(ToString::to_string)
             .ok_or_else(|| {
-                polar_core::Error::Config(format!(
-                    "No payment_hash in response. Full response: {}",
-                    output
+                polar_core::Error::RpcParse(format!(
+                    "No payment_request in response. Full response: {output}"
                 ))
-            })?
-            .to_string();
+            })
+    }
+
+    /// Settle a held invoice by revealing its preimage.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `preimage` - Hex-encoded preimage of the invoice's payment hash
+    pub async fn settle_invoice(&self, manager: &ContainerManager, preimage: &str) -> Result<()> {
+        self.run_invoice_command(manager, "settleinvoice", preimage)
+            .await
+    }
 
-        Ok(payment_hash)
+    /// Cancel an open or held invoice, failing any in-flight HTLCs back.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `payment_hash` - Hex-encoded payment hash of the invoice
+    pub async fn cancel_invoice(
+        &self,
+        manager: &ContainerManager,
+        payment_hash: &str,
+    ) -> Result<()> {
+        self.run_invoice_command(manager, "cancelinvoice", payment_hash)
+            .await
     }
 
-    /// List all channels for this node.
-    pub async fn list_channels(&self, manager: &ContainerManager) -> Result<serde_json::Value> {
+    /// Look up the state of an invoice (`OPEN`, `ACCEPTED`, `SETTLED` or `CANCELED`).
+    ///
+    /// A hold invoice moves to `ACCEPTED` once the payer's HTLC has arrived.
+    pub async fn invoice_state(
+        &self,
+        manager: &ContainerManager,
+        payment_hash: &str,
+    ) -> Result<String> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
         let output = manager
-            .exec_command(
+            .exec_command_unchecked(
                 container_id,
-                vec![
-                    "lncli",
-                    "--network=regtest",
-                    "--tlscertpath=/home/lnd/.lnd/tls.cert",
-                    "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-                    "listchannels",
-                ],
+                lncli_command(&["lookupinvoice", payment_hash]),
             )
             .await?;
 
         let json: serde_json::Value = serde_json::from_str(&output)
-            .map_err(|e| polar_core::Error::Config(format!("Failed to parse channels: {}", e)))?;
+            .map_err(|_| lncli_error("Failed to look up invoice", &output))?;
 
-        Ok(json)
+        Ok(json["state"].as_str().unwrap_or("UNKNOWN").to_string())
     }
 
-    /// Close a Lightning channel.
-    ///
-    /// # Arguments
-    /// * `manager` - Docker container manager
-    /// * `channel_point` - Channel point in format "funding_txid:output_index"
-    /// * `force` - Whether to force close the channel (default: false for cooperative close)
-    pub async fn close_channel(
+    /// Run an `invoicesrpc` subcommand that takes a single hex argument.
+    async fn run_invoice_command(
         &self,
         manager: &ContainerManager,
-        channel_point: &str,
-        force: bool,
-    ) -> Result<String> {
+        subcommand: &str,
+        arg: &str,
+    ) -> Result<()> {
         let container_id = self
             .node
             .container_id
             .as_ref()
-            .ok_or_else(|| polar_core::Error::Config("LND node not running".to_string()))?;
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
-        // Parse channel_point into funding_txid and output_index
-        let parts: Vec<&str> = channel_point.split(':').collect();
-        if parts.len() != 2 {
-            return Err(polar_core::Error::Config(format!(
-                "Invalid channel_point format. Expected 'txid:index', got: {}",
-                channel_point
-            )));
+        let output = manager
+            .exec_command_unchecked(container_id, lncli_command(&[subcommand, arg]))
+            .await?;
+
+        // Success prints an empty JSON object, failures print an rpc error
+        if serde_json::from_str::<serde_json::Value>(&output).is_err() {
+            return Err(lncli_error(&format!("{subcommand} failed"), &output));
         }
 
-        let funding_txid = parts[0];
-        let output_index = parts[1];
+        Ok(())
+    }
 
-        let mut args = vec![
-            "lncli",
-            "--network=regtest",
-            "--tlscertpath=/home/lnd/.lnd/tls.cert",
-            "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-            "closechannel",
-        ];
+    /// Pay a Lightning invoice.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `payment_request` - The bolt11 invoice string
+    /// * `timeout_secs` - Seconds before giving up (60 if `None`)
+    pub async fn pay_invoice(
+        &self,
+        manager: &ContainerManager,
+        payment_request: &str,
+        timeout_secs: Option<u64>,
+    ) -> Result<String> {
+        let options = PaymentOptions {
+            timeout_secs,
+            ..PaymentOptions::default()
+        };
+        self.pay_invoice_with_options(manager, payment_request, options)
+            .await
+    }
 
-        if force {
-            args.push("--force");
-        }
+    /// Pay a Lightning invoice with custom options.
+    ///
+    /// Payments are routed over the full channel graph known to the node, so
+    /// multi-hop payments work once the graph has been synced.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `payment_request` - The bolt11 invoice string
+    /// * `options` - Fee limit, multi-part payment and timeout settings
+    ///
+    /// # Errors
+    ///
+    /// Returns [`polar_core::Error::PaymentTimeout`] if the payment is still
+    /// unresolved shortly after its timeout, e.g. because an HTLC is stuck in
+    /// flight, and [`polar_core::Error::PaymentFailed`] if LND gives up on it
+    pub async fn pay_invoice_with_options(
+        &self,
+        manager: &ContainerManager,
+        payment_request: &str,
+        options: PaymentOptions,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
 
-        args.push(funding_txid);
-        args.push(output_index);
+        let option_args = options.payinvoice_args();
+        let args = payinvoice_command(&option_args, payment_request);
 
-        let output = manager.exec_command(container_id, args).await?;
+        let timeout_secs = options
+            .timeout_secs
+            .unwrap_or(PaymentOptions::DEFAULT_TIMEOUT_SECS);
+        let deadline = Duration::from_secs(timeout_secs) + PaymentOptions::TIMEOUT_GRACE;
+        let output =
+            tokio::time::timeout(deadline, manager.exec_command_unchecked(container_id, args))
+                .await
+                .map_err(|_| polar_core::Error::PaymentTimeout(timeout_secs))??;
 
         let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
-            polar_core::Error::Config(format!(
-                "Failed to parse close channel response: {}. Output was: {}",
+            polar_core::Error::RpcParse(format!(
+                "Failed to parse payment response: {}. Output was: {}",
                 e, output
             ))
         })?;
+        payment_hash(&json, &output, timeout_secs)
+    }
 
-        let closing_txid = json["closing_txid"]
-            .as_str()
-            .ok_or_else(|| {
-                polar_core::Error::Config(format!(
-                    "No closing_txid in response. Full response: {}",
-                    output
-                ))
-            })?
-            .to_string();
+    /// Pay a Lightning invoice, reporting its progress as it is routed.
+    ///
+    /// Works like [`Self::pay_invoice_with_options`], but reads lncli's output
+    /// as it arrives and passes each JSON payment update, with the HTLC
+    /// attempts made so far, to `on_update`. lncli versions that only print
+    /// the final payment report just that update, and output holding no
+    /// update at all, such as an rpc error, is handled like the blocking path.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `payment_request` - The bolt11 invoice string
+    /// * `options` - Fee limit, multi-part payment and timeout settings
+    /// * `on_update` - Called with every payment update
+    pub async fn pay_invoice_streaming(
+        &self,
+        manager: &ContainerManager,
+        payment_request: &str,
+        options: PaymentOptions,
+        mut on_update: impl FnMut(&PaymentUpdate) + Send,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let option_args = options.payinvoice_args();
+        let args = payinvoice_command(&option_args, payment_request);
+
+        let timeout_secs = options
+            .timeout_secs
+            .unwrap_or(PaymentOptions::DEFAULT_TIMEOUT_SECS);
+        let deadline = Duration::from_secs(timeout_secs) + PaymentOptions::TIMEOUT_GRACE;
+        let mut objects = JsonObjects::default();
+        let mut last_update = None;
+        let output = tokio::time::timeout(
+            deadline,
+            manager.exec_command_streaming(container_id, args, |line| {
+                if let Some(json) = objects.push_line(line) {
+                    on_update(&parse_payment_update(&json));
+                    last_update = Some(json);
+                }
+            }),
+        )
+        .await
+        .map_err(|_| polar_core::Error::PaymentTimeout(timeout_secs))??
+        .output;
+
+        let json = match last_update {
+            Some(json) => json,
+            None => {
+                serde_json::from_str(&output).map_err(|_| lncli_error("Payment failed", &output))?
+            }
+        };
+        payment_hash(&json, &output, timeout_secs)
+    }
 
-        Ok(closing_txid)
+    /// Pay one of this node's own invoices around a loop to rebalance channels.
+    ///
+    /// The payment leaves through `outgoing_chan_id` and must arrive back via
+    /// `last_hop_pubkey`, the peer on the inbound side of the loop.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `payment_request` - Invoice created by this node
+    /// * `outgoing_chan_id` - Channel ID to send the payment out through
+    /// * `last_hop_pubkey` - Public key of the peer the payment must return from
+    ///
+    /// # Returns
+    /// The routing fee paid in satoshis
+    pub async fn pay_circular(
+        &self,
+        manager: &ContainerManager,
+        payment_request: &str,
+        outgoing_chan_id: &str,
+        last_hop_pubkey: &str,
+    ) -> Result<u64> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&[
+                    "payinvoice",
+                    "--force",
+                    "--json",
+                    "--allow_self_payment",
+                    "--outgoing_chan_id",
+                    outgoing_chan_id,
+                    "--last_hop",
+                    last_hop_pubkey,
+                    payment_request,
+                ]),
+            )
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Circular payment failed", &output))?;
+
+        parse_payment_fee(&json)
+    }
+
+    /// Decode a bolt11 payment request without paying it.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `payment_request` - The bolt11 invoice string
+    pub async fn decode_invoice(
+        &self,
+        manager: &ContainerManager,
+        payment_request: &str,
+    ) -> Result<DecodedInvoice> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["decodepayreq", payment_request]),
+            )
+            .await?;
+
+        // lncli prints an rpc error instead of JSON for malformed invoices
+        let json: serde_json::Value = serde_json::from_str(&output).map_err(|_| {
+            polar_core::Error::InvalidArgument(format!(
+                "Invalid invoice: {}",
+                lncli_error_message(&output)
+            ))
+        })?;
+
+        Ok(parse_decoded_invoice(&json))
+    }
+
+    /// Find candidate routes for a payment with `lncli queryroutes`.
+    ///
+    /// Returns an empty list when LND cannot find a path to the destination.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `dest_pubkey` - Public key of the destination node
+    /// * `amount` - Amount to send in satoshis
+    pub async fn query_routes(
+        &self,
+        manager: &ContainerManager,
+        dest_pubkey: &str,
+        amount: u64,
+    ) -> Result<Vec<RouteEstimate>> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let dest_arg = format!("--dest={dest_pubkey}");
+        let amt_arg = format!("--amt={amount}");
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["queryroutes", &dest_arg, &amt_arg]),
+            )
+            .await?;
+
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&output) else {
+            let message = lncli_error_message(&output);
+            if message.contains("unable to find a path") {
+                return Ok(Vec::new());
+            }
+            return Err(lncli_error("Failed to query routes", &output));
+        };
+
+        Ok(parse_routes(&json))
+    }
+
+    /// Build a route through the given hops with `lncli buildroute`.
+    ///
+    /// Returns the `{"route": ...}` JSON lncli prints, ready for
+    /// [`LndNode::send_to_route`].
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `amount` - Amount the destination receives in satoshis
+    /// * `hop_pubkeys` - Public keys of every hop after this node, ending with
+    ///   the destination
+    pub async fn build_route(
+        &self,
+        manager: &ContainerManager,
+        amount: u64,
+        hop_pubkeys: &[String],
+    ) -> Result<serde_json::Value> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let amt_arg = format!("--amt={amount}");
+        let hops_arg = format!("--hops={}", hop_pubkeys.join(","));
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["buildroute", &amt_arg, &hops_arg]),
+            )
+            .await?;
+
+        serde_json::from_str(&output).map_err(|_| lncli_error("Failed to build route", &output))
+    }
+
+    /// Pay along a fixed route with `lncli sendtoroute`.
+    ///
+    /// `route` is either `lncli queryroutes` or `lncli buildroute` output; an
+    /// invoice's payment secret must already be on the final hop, see
+    /// [`set_route_payment_addr`].
+    ///
+    /// # Returns
+    /// The hex encoded payment preimage
+    pub async fn send_to_route(
+        &self,
+        manager: &ContainerManager,
+        payment_hash: &str,
+        route: &serde_json::Value,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let hash_arg = format!("--payment_hash={payment_hash}");
+        let routes_arg = format!("--routes={route}");
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["sendtoroute", &hash_arg, &routes_arg]),
+            )
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Payment along route failed", &output))?;
+
+        parse_route_preimage(&json)
+    }
+
+    /// Sign a message with the node's identity key.
+    ///
+    /// Returns the zbase32 encoded signature.
+    pub async fn sign_message(&self, manager: &ContainerManager, message: &str) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let msg_arg = format!("--msg={message}");
+        let output = manager
+            .exec_command_unchecked(container_id, lncli_command(&["signmessage", &msg_arg]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Failed to sign message", &output))?;
+
+        json["signature"]
+            .as_str()
+            .map(ToString::to_string)
+            .ok_or_else(|| polar_core::Error::RpcParse("No signature in response".to_string()))
+    }
+
+    /// Verify a signature made by [`Self::sign_message`] on any node.
+    ///
+    /// LND only reports a signature as valid when the signer is in its
+    /// channel graph, so the recovered public key is returned either way.
+    pub async fn verify_message(
+        &self,
+        manager: &ContainerManager,
+        message: &str,
+        signature: &str,
+    ) -> Result<MessageVerification> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let msg_arg = format!("--msg={message}");
+        let sig_arg = format!("--sig={signature}");
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["verifymessage", &msg_arg, &sig_arg]),
+            )
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Failed to verify message", &output))?;
+
+        Ok(parse_message_verification(&json))
+    }
+
+    /// List all channels for this node.
+    pub async fn list_channels(&self, manager: &ContainerManager) -> Result<serde_json::Value> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command(container_id, lncli_command(&["listchannels"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse channels: {}", e)))?;
+
+        Ok(json)
+    }
+
+    /// List the most recent invoices created by this node.
+    ///
+    /// At most [`Self::HISTORY_LIMIT`] invoices are returned, oldest first.
+    pub async fn list_invoices(&self, manager: &ContainerManager) -> Result<serde_json::Value> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let limit = Self::HISTORY_LIMIT.to_string();
+        let output = manager
+            .exec_command(
+                container_id,
+                lncli_command(&["listinvoices", "--max_invoices", &limit]),
+            )
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse invoices: {e}")))?;
+
+        Ok(json)
+    }
+
+    /// List the most recent outgoing payments of this node, including
+    /// in-flight and failed ones.
+    ///
+    /// At most [`Self::HISTORY_LIMIT`] payments are returned, oldest first.
+    pub async fn list_payments(&self, manager: &ContainerManager) -> Result<serde_json::Value> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let limit = Self::HISTORY_LIMIT.to_string();
+        let output = manager
+            .exec_command(
+                container_id,
+                lncli_command(&[
+                    "listpayments",
+                    "--include_incomplete",
+                    "--max_payments",
+                    &limit,
+                ]),
+            )
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse payments: {e}")))?;
+
+        Ok(json)
+    }
+
+    /// Send on-chain funds from this node's wallet.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `address` - Destination address
+    /// * `amount_sat` - Amount in satoshis (ignored when sweeping)
+    /// * `sweep` - Send the entire wallet balance minus fees
+    ///
+    /// # Returns
+    /// The transaction ID
+    pub async fn send_coins(
+        &self,
+        manager: &ContainerManager,
+        address: &str,
+        amount_sat: u64,
+        sweep: bool,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let amount_str = amount_sat.to_string();
+
+        let mut args = lncli_command(&["sendcoins", "--addr", address]);
+
+        if sweep {
+            args.push("--sweepall");
+        } else {
+            args.push("--amt");
+            args.push(&amount_str);
+        }
+
+        let output = manager.exec_command_unchecked(container_id, args).await?;
+
+        // lncli prints an rpc error instead of JSON, e.g. when funds are short
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&output) else {
+            let message = lncli_error_message(&output);
+            if message.contains("insufficient funds") {
+                return Err(polar_core::Error::InsufficientFunds {
+                    node: self.node.name.clone(),
+                    have: self.wallet_balance(manager).await?,
+                    need: amount_sat,
+                });
+            }
+            return Err(lncli_error("Failed to send coins", &output));
+        };
+
+        json["txid"]
+            .as_str()
+            .map(ToString::to_string)
+            .ok_or_else(|| {
+                polar_core::Error::RpcParse(format!("No txid in response. Full response: {output}"))
+            })
+    }
+
+    /// Raise the fee of an unconfirmed transaction with `lncli wallet bumpfee`.
+    ///
+    /// LND hands the output to its sweeper, which spends it in a child
+    /// transaction paying for both (CPFP), so this works for transactions
+    /// that do not signal RBF, such as a stuck channel open.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `outpoint` - Unconfirmed output owned by this wallet, as `txid:index`
+    /// * `sat_per_vbyte` - Fee rate in sat/vB for the sweep
+    pub async fn bump_fee(
+        &self,
+        manager: &ContainerManager,
+        outpoint: &str,
+        sat_per_vbyte: u64,
+    ) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let fee_arg = format!("--sat_per_vbyte={sat_per_vbyte}");
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&["wallet", "bumpfee", &fee_arg, outpoint]),
+            )
+            .await?;
+
+        if serde_json::from_str::<serde_json::Value>(&output).is_err() {
+            let message = lncli_error_message(&output);
+            if message.contains("not belong to the wallet") || message.contains("not found") {
+                return Err(polar_core::Error::ContainerExec(format!(
+                    "Cannot bump {outpoint}: {} does not own this unconfirmed output",
+                    self.node.name
+                )));
+            }
+            return Err(lncli_error("Failed to bump fee", &output));
+        }
+
+        Ok(())
+    }
+
+    /// Get the confirmed on-chain wallet balance in satoshis.
+    pub async fn wallet_balance(&self, manager: &ContainerManager) -> Result<u64> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command(container_id, lncli_command(&["walletbalance"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
+            polar_core::Error::RpcParse(format!("Failed to parse wallet balance: {e}"))
+        })?;
+
+        Ok(lncli_number(&json["confirmed_balance"]))
+    }
+
+    /// Describe the channel graph as known to this node.
+    pub async fn describe_graph(&self, manager: &ContainerManager) -> Result<serde_json::Value> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output = manager
+            .exec_command(container_id, lncli_command(&["describegraph"]))
+            .await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| polar_core::Error::RpcParse(format!("Failed to parse graph: {e}")))?;
+
+        Ok(json)
+    }
+
+    /// Update the fee and time lock policy with `lncli updatechanpolicy`.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `chan_point` - Channel to update, or `None` to update every channel
+    /// * `base_fee_msat` - Fixed fee per forwarded HTLC in millisatoshis
+    /// * `fee_rate_ppm` - Proportional fee in parts per million
+    /// * `time_lock_delta` - CLTV delta required to forward over the channel
+    pub async fn update_channel_policy(
+        &self,
+        manager: &ContainerManager,
+        chan_point: Option<&str>,
+        base_fee_msat: u64,
+        fee_rate_ppm: u64,
+        time_lock_delta: u32,
+    ) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let base_fee_arg = format!("--base_fee_msat={base_fee_msat}");
+        let fee_rate_arg = format!("--fee_rate_ppm={fee_rate_ppm}");
+        let time_lock_arg = format!("--time_lock_delta={time_lock_delta}");
+        let chan_point_arg = chan_point.map(|point| format!("--chan_point={point}"));
+
+        let mut args = lncli_command(&[
+            "updatechanpolicy",
+            &base_fee_arg,
+            &fee_rate_arg,
+            &time_lock_arg,
+        ]);
+        args.push(chan_point_arg.as_deref().unwrap_or("--global"));
+
+        let output = manager.exec_command_unchecked(container_id, args).await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| lncli_error("Failed to update channel policy", &output))?;
+
+        // Per-channel failures are reported in the response, not as an rpc error
+        if let Some(failed) = json["failed_updates"].as_array().and_then(|f| f.first()) {
+            return Err(polar_core::Error::ContainerExec(format!(
+                "Failed to update channel policy for {}: {}",
+                failed["outpoint"]["txid_str"].as_str().unwrap_or("channel"),
+                failed["update_error"].as_str().unwrap_or("unknown error")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Close a Lightning channel.
+    ///
+    /// # Arguments
+    /// * `manager` - Docker container manager
+    /// * `channel_point` - Funding outpoint of the channel
+    /// * `force` - Whether to force close the channel (default: false for cooperative close)
+    pub async fn close_channel(
+        &self,
+        manager: &ContainerManager,
+        channel_point: &ChannelPoint,
+        force: bool,
+    ) -> Result<String> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output_index = channel_point.output_index.to_string();
+        let mut args = lncli_command(&["closechannel"]);
+
+        if force {
+            args.push("--force");
+        }
+
+        args.push(&channel_point.funding_txid);
+        args.push(&output_index);
+
+        let output = manager.exec_command(container_id, args).await?;
+
+        let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
+            polar_core::Error::RpcParse(format!(
+                "Failed to parse close channel response: {}. Output was: {}",
+                e, output
+            ))
+        })?;
+
+        let closing_txid = json["closing_txid"]
+            .as_str()
+            .ok_or_else(|| {
+                polar_core::Error::RpcParse(format!(
+                    "No closing_txid in response. Full response: {}",
+                    output
+                ))
+            })?
+            .to_string();
+
+        Ok(closing_txid)
+    }
+
+    /// Remove a channel from this node's local state with `lncli abandonchannel`.
+    ///
+    /// Meant for pending channels stuck in regtest that can neither be
+    /// cooperatively nor force closed. Nothing is broadcast, so funds locked
+    /// in the channel stay unspendable unless the funding transaction is
+    /// later dealt with on-chain.
+    pub async fn abandon_channel(
+        &self,
+        manager: &ContainerManager,
+        channel_point: &ChannelPoint,
+    ) -> Result<()> {
+        let container_id = self
+            .node
+            .container_id
+            .as_ref()
+            .ok_or_else(|| polar_core::Error::NodeNotRunning(self.node.name.clone()))?;
+
+        let output_index = channel_point.output_index.to_string();
+        let output = manager
+            .exec_command_unchecked(
+                container_id,
+                lncli_command(&[
+                    "abandonchannel",
+                    "--funding_txid",
+                    &channel_point.funding_txid,
+                    "--output_index",
+                    &output_index,
+                    "--i_know_what_i_am_doing",
+                ]),
+            )
+            .await?;
+
+        // lncli prints an rpc error instead of JSON when the channel is unknown
+        if serde_json::from_str::<serde_json::Value>(&output).is_err() {
+            return Err(polar_core::Error::ContainerExec(format!(
+                "Failed to abandon channel: {}. Nothing was broadcast; only {}'s local channel state is affected",
+                lncli_error_message(&output),
+                self.node.name
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Turn lncli error output into the error its gRPC status code names,
+/// with `context` describing the failed operation.
+fn lncli_error(context: &str, output: &str) -> polar_core::Error {
+    let message = format!("{context}: {}", lncli_error_message(output));
+    match lncli_error_code(output) {
+        Some("InvalidArgument" | "OutOfRange") => polar_core::Error::InvalidArgument(message),
+        Some("DeadlineExceeded") => polar_core::Error::Timeout(message),
+        _ => polar_core::Error::ContainerExec(message),
+    }
+}
+
+/// Extract the gRPC status code of an lncli error, e.g. `InvalidArgument`.
+fn lncli_error_code(output: &str) -> Option<&str> {
+    output
+        .split_once("code = ")
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+}
+
+/// Extract the human readable part of an lncli error.
+///
+/// lncli errors look like `[lncli] rpc error: code = Unknown desc = <message>`.
+fn lncli_error_message(output: &str) -> &str {
+    let output = output.trim();
+    output
+        .split_once("desc = ")
+        .map_or(output, |(_, message)| message.trim())
+}
+
+/// Build an `lncli` command running `args` against the node's regtest
+/// admin RPC.
+fn lncli_command<'a>(args: &[&'a str]) -> Vec<&'a str> {
+    let mut command = vec![
+        "lncli",
+        "--network=regtest",
+        "--tlscertpath=/home/lnd/.lnd/tls.cert",
+        "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
+    ];
+    command.extend_from_slice(args);
+    command
+}
+
+/// Build the `lncli payinvoice` command for `payment_request`.
+///
+/// `--force` skips the confirmation prompt and `--json` prints payment
+/// updates as JSON, for non-interactive execution.
+fn payinvoice_command<'a>(option_args: &'a [String], payment_request: &'a str) -> Vec<&'a str> {
+    let mut args = lncli_command(&["payinvoice", "--force", "--json"]);
+    args.extend(option_args.iter().map(String::as_str));
+    args.push(payment_request);
+    args
+}
+
+/// Get the hash of a final `payinvoice` payment, failing unless it succeeded.
+fn payment_hash(json: &serde_json::Value, output: &str, timeout_secs: u64) -> Result<String> {
+    if json["failure_reason"] == "FAILURE_REASON_TIMEOUT" {
+        return Err(polar_core::Error::PaymentTimeout(timeout_secs));
+    }
+    check_payment_status(json)?;
+
+    let payment_hash = json["payment_hash"]
+        .as_str()
+        .ok_or_else(|| {
+            polar_core::Error::RpcParse(format!(
+                "No payment_hash in response. Full response: {output}"
+            ))
+        })?
+        .to_string();
+
+    Ok(payment_hash)
+}
+
+/// Reassembles the JSON objects lncli prints one after another, each pretty
+/// printed over several lines.
+#[derive(Debug, Default)]
+struct JsonObjects {
+    /// Lines of the object read so far.
+    pending: String,
+}
+
+impl JsonObjects {
+    /// Add a line of output, returning the object it completes, if any.
+    ///
+    /// Lines outside an object, such as progress text, are skipped.
+    fn push_line(&mut self, line: &str) -> Option<serde_json::Value> {
+        if self.pending.is_empty() && !line.starts_with('{') {
+            return None;
+        }
+        self.pending.push_str(line);
+        self.pending.push('\n');
+
+        // Only a brace at the start of a line can close the top-level object
+        if line.starts_with(char::is_whitespace) || !line.trim_end().ends_with('}') {
+            return None;
+        }
+        let json = serde_json::from_str(&self.pending).ok()?;
+        self.pending.clear();
+        Some(json)
+    }
+}
+
+/// Parse a payment update printed by `lncli payinvoice --json`.
+fn parse_payment_update(json: &serde_json::Value) -> PaymentUpdate {
+    let attempts = json["htlcs"]
+        .as_array()
+        .map(|htlcs| {
+            htlcs
+                .iter()
+                .map(|htlc| HtlcAttempt {
+                    status: htlc["status"].as_str().unwrap_or("UNKNOWN").to_string(),
+                    hops: htlc["route"]["hops"].as_array().map_or(0, Vec::len),
+                    total_amt_msat: lncli_number(&htlc["route"]["total_amt_msat"]),
+                    failure: htlc["failure"]["code"].as_str().map(str::to_string),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PaymentUpdate {
+        payment_hash: json["payment_hash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        status: json["status"].as_str().unwrap_or("UNKNOWN").to_string(),
+        failure_reason: json["failure_reason"]
+            .as_str()
+            .filter(|reason| *reason != "FAILURE_REASON_NONE")
+            .map(str::to_string),
+        attempts,
+    }
+}
+
+/// Fail unless `lncli payinvoice --json` output reports a successful payment.
+fn check_payment_status(json: &serde_json::Value) -> Result<()> {
+    let status = json["status"].as_str().unwrap_or("UNKNOWN");
+    if status == "SUCCEEDED" {
+        return Ok(());
+    }
+
+    let reason = json["failure_reason"].as_str().unwrap_or(status);
+    Err(polar_core::Error::PaymentFailed(reason.to_string()))
+}
+
+/// Put an invoice's payment secret on the final hop of a built route.
+///
+/// LND rejects payments to its invoices without it, and `lncli buildroute`
+/// cannot add it on every supported version.
+pub fn set_route_payment_addr(route: &mut serde_json::Value, payment_addr: &str) {
+    let Some(last_hop) = route["route"]["hops"]
+        .as_array_mut()
+        .and_then(|hops| hops.last_mut())
+    else {
+        return;
+    };
+    let total_amt_msat = last_hop["amt_to_forward_msat"].clone();
+    last_hop["mpp_record"] = serde_json::json!({
+        "payment_addr": payment_addr,
+        "total_amt_msat": total_amt_msat,
+    });
+}
+
+/// Extract the preimage from `lncli sendtoroute` output.
+///
+/// Newer lncli prints an HTLC attempt with `status` and `preimage`, older
+/// versions a send response with `payment_error` and `payment_preimage`.
+/// Bytes are hex encoded by recent lncli and base64 encoded by older ones.
+fn parse_route_preimage(json: &serde_json::Value) -> Result<String> {
+    let failure = json["failure"]["code"]
+        .as_str()
+        .filter(|_| json["status"] == "FAILED")
+        .or_else(|| json["payment_error"].as_str().filter(|e| !e.is_empty()));
+    if let Some(failure) = failure {
+        return Err(polar_core::Error::ContainerExec(format!(
+            "Payment along route failed: {failure}"
+        )));
+    }
+
+    let preimage = json["preimage"]
+        .as_str()
+        .or_else(|| json["payment_preimage"].as_str())
+        .unwrap_or_default();
+    if preimage.len() == 64 && preimage.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(preimage.to_string());
+    }
+
+    STANDARD
+        .decode(preimage)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(|bytes| Preimage::from_bytes(&bytes).preimage)
+        .ok_or_else(|| {
+            polar_core::Error::RpcParse(format!("No preimage in sendtoroute response: {json}"))
+        })
+}
+
+/// Extract the fee of a successful payment from `lncli payinvoice --json` output.
+fn parse_payment_fee(json: &serde_json::Value) -> Result<u64> {
+    check_payment_status(json)?;
+
+    Ok(lncli_number(&json["fee_sat"]))
+}
+
+/// Build a `MessageVerification` from `lncli verifymessage` output.
+///
+/// Older lncli versions omit `valid` when it is false.
+fn parse_message_verification(json: &serde_json::Value) -> MessageVerification {
+    MessageVerification {
+        valid: json["valid"].as_bool().unwrap_or(false),
+        pubkey: json["pubkey"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+/// Build a `DecodedInvoice` from `lncli decodepayreq` output.
+fn parse_decoded_invoice(json: &serde_json::Value) -> DecodedInvoice {
+    DecodedInvoice {
+        destination: json["destination"].as_str().unwrap_or_default().to_string(),
+        payment_hash: json["payment_hash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        payment_addr: json["payment_addr"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        num_satoshis: lncli_number(&json["num_satoshis"]),
+        description: json["description"].as_str().unwrap_or_default().to_string(),
+        timestamp: lncli_number(&json["timestamp"]),
+        expiry: lncli_number(&json["expiry"]),
+    }
+}
+
+/// Parse the output of `lncli queryroutes`.
+fn parse_routes(json: &serde_json::Value) -> Vec<RouteEstimate> {
+    json["routes"]
+        .as_array()
+        .map(|routes| {
+            routes
+                .iter()
+                .map(|route| RouteEstimate {
+                    total_fees_msat: lncli_number(&route["total_fees_msat"]),
+                    total_amt_msat: lncli_number(&route["total_amt_msat"]),
+                    hops: route["hops"].as_array().map_or(0, Vec::len),
+                    total_time_lock: lncli_number(&route["total_time_lock"]),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the output of `lncli listunspent`.
+fn parse_utxos(json: &serde_json::Value) -> Vec<UtxoInfo> {
+    json["utxos"]
+        .as_array()
+        .map(|utxos| {
+            utxos
+                .iter()
+                .map(|utxo| {
+                    let outpoint = &utxo["outpoint"];
+                    UtxoInfo {
+                        outpoint: outpoint.as_str().map_or_else(
+                            || {
+                                format!(
+                                    "{}:{}",
+                                    outpoint["txid_str"].as_str().unwrap_or_default(),
+                                    lncli_number::<u32>(&outpoint["output_index"])
+                                )
+                            },
+                            ToString::to_string,
+                        ),
+                        address: utxo["address"].as_str().unwrap_or_default().to_string(),
+                        amount_sat: lncli_number(&utxo["amount_sat"]),
+                        confirmations: lncli_number(&utxo["confirmations"]),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Check that every selected outpoint is in the wallet and that together
+/// they cover `amount`.
+fn check_selected_utxos(
+    node_name: &str,
+    available: &[UtxoInfo],
+    selected: &[String],
+    amount: u64,
+) -> Result<()> {
+    let mut total = 0;
+    for outpoint in selected {
+        let utxo = available
+            .iter()
+            .find(|u| &u.outpoint == outpoint)
+            .ok_or_else(|| {
+                polar_core::Error::Config(format!(
+                    "UTXO {outpoint} is not an unspent output of {node_name}"
+                ))
+            })?;
+        total += utxo.amount_sat;
+    }
+
+    if total < amount {
+        return Err(polar_core::Error::InsufficientFunds {
+            node: node_name.to_string(),
+            have: total,
+            need: amount,
+        });
+    }
+    Ok(())
+}
+
+/// Collect feature names from the `features` map of `lncli getinfo`.
+fn parse_feature_names(json: &serde_json::Value) -> Vec<String> {
+    json["features"]
+        .as_object()
+        .map(|features| {
+            features
+                .values()
+                .filter_map(|feature| feature["name"].as_str())
+                .map(ToString::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_payment_addr_and_preimage() {
+        let mut route = serde_json::json!({
+            "route": {"hops": [
+                {"pub_key": "02aa", "amt_to_forward_msat": "10001000"},
+                {"pub_key": "03bb", "amt_to_forward_msat": "10000000"}
+            ]}
+        });
+        set_route_payment_addr(&mut route, "ab".repeat(32).as_str());
+        assert_eq!(route["route"]["hops"][0].get("mpp_record"), None);
+        assert_eq!(
+            route["route"]["hops"][1]["mpp_record"]["total_amt_msat"],
+            "10000000"
+        );
+
+        let hex = "01".repeat(32);
+        let attempt = serde_json::json!({"status": "SUCCEEDED", "preimage": hex});
+        assert_eq!(parse_route_preimage(&attempt).unwrap(), hex);
+
+        // Older lncli base64 encodes the preimage
+        let legacy = serde_json::json!({
+            "payment_error": "",
+            "payment_preimage": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE="
+        });
+        assert_eq!(parse_route_preimage(&legacy).unwrap(), hex);
+
+        let failed = serde_json::json!({
+            "status": "FAILED",
+            "failure": {"code": "TEMPORARY_CHANNEL_FAILURE"}
+        });
+        assert!(
+            parse_route_preimage(&failed)
+                .unwrap_err()
+                .to_string()
+                .contains("TEMPORARY_CHANNEL_FAILURE")
+        );
+    }
+
+    #[test]
+    fn test_parse_utxos() {
+        let json = serde_json::json!({
+            "utxos": [
+                {
+                    "address": "bcrt1qa",
+                    "amount_sat": "50000000",
+                    "confirmations": "6",
+                    "outpoint": {"txid_str": "ab12", "output_index": 1}
+                },
+                {
+                    "address": "bcrt1qb",
+                    "amount_sat": "25000000",
+                    "confirmations": "3",
+                    "outpoint": "cd34:0"
+                }
+            ]
+        });
+
+        let utxos = parse_utxos(&json);
+        assert_eq!(utxos.len(), 2);
+        assert_eq!(utxos[0].outpoint, "ab12:1");
+        assert_eq!(utxos[0].amount_sat, 50_000_000);
+        assert_eq!(utxos[1].outpoint, "cd34:0");
+        assert_eq!(utxos[1].confirmations, 3);
+
+        let selected = ["ab12:1".to_string()];
+        assert!(check_selected_utxos("lnd-1", &utxos, &selected, 1_000_000).is_ok());
+        assert!(matches!(
+            check_selected_utxos("lnd-1", &utxos, &selected, 60_000_000),
+            Err(polar_core::Error::InsufficientFunds {
+                have: 50_000_000,
+                need: 60_000_000,
+                ..
+            })
+        ));
+        let missing = ["ef56:0".to_string()];
+        assert!(check_selected_utxos("lnd-1", &utxos, &missing, 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_feature_names() {
+        let json = serde_json::json!({
+            "features": {
+                "12": {"name": "static-remote-key", "is_required": true, "is_known": true},
+                "23": {"name": "anchors-zero-fee-htlc-tx", "is_required": false, "is_known": true}
+            }
+        });
+
+        let names = parse_feature_names(&json);
+        assert_eq!(names.len(), 2);
+        assert!(ChannelType::Anchors.is_supported_by(&names));
+        assert!(ChannelType::Legacy.is_supported_by(&names));
+        assert!(!ChannelType::SimpleTaproot.is_supported_by(&names));
+        assert!(parse_feature_names(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_parse_decoded_invoice() {
+        let json = serde_json::json!({
+            "destination": "02aa",
+            "payment_hash": "beef",
+            "num_satoshis": "1500",
+            "timestamp": "1700000000",
+            "expiry": "3600",
+            "description": "coffee"
+        });
+
+        let invoice = parse_decoded_invoice(&json);
+        assert_eq!(invoice.destination, "02aa");
+        assert_eq!(invoice.num_satoshis, 1500);
+        assert_eq!(invoice.description, "coffee");
+        assert_eq!(invoice.expires_at(), 1_700_003_600);
+        assert!(!invoice.is_expired(1_700_000_100));
+        assert!(invoice.is_expired(1_700_003_600));
+    }
+
+    #[test]
+    fn test_parse_message_verification() {
+        let json = serde_json::json!({ "valid": true, "pubkey": "02aa" });
+        let verification = parse_message_verification(&json);
+        assert!(verification.valid);
+        assert_eq!(verification.pubkey, "02aa");
+
+        // Older lncli versions leave out `valid` when it is false
+        let json = serde_json::json!({ "pubkey": "03bb" });
+        assert!(!parse_message_verification(&json).valid);
+    }
+
+    #[test]
+    fn test_payment_options_args() {
+        assert!(PaymentOptions::default().payinvoice_args().is_empty());
+        assert_eq!(
+            PaymentOptions::default()
+                .with_fee_limit(100)
+                .with_mpp(false, Some(8))
+                .payinvoice_args(),
+            ["--fee_limit", "100", "--max_parts", "1"]
+        );
+        assert_eq!(
+            PaymentOptions::default()
+                .with_mpp(true, Some(8))
+                .payinvoice_args(),
+            ["--max_parts", "8"]
+        );
+        assert_eq!(
+            PaymentOptions::default().with_timeout(5).payinvoice_args(),
+            ["--timeout", "5s"]
+        );
+        assert_eq!(
+            PaymentOptions::default()
+                .with_mpp(true, Some(4))
+                .with_amp(true)
+                .payinvoice_args(),
+            ["--max_parts", "4", "--amp"]
+        );
+    }
+
+    #[test]
+    fn test_open_channel_options_args() {
+        assert!(OpenChannelOptions::default().openchannel_args().is_empty());
+        assert_eq!(
+            OpenChannelOptions::default()
+                .with_min_confs(0)
+                .with_remote_csv_delay(144)
+                .with_min_htlc_msat(1000)
+                .openchannel_args(),
+            [
+                "--min_confs",
+                "0",
+                "--remote_csv_delay",
+                "144",
+                "--min_htlc_msat",
+                "1000"
+            ]
+        );
+        assert_eq!(
+            OpenChannelOptions::default()
+                .with_min_htlc_msat(5000)
+                .openchannel_args(),
+            ["--min_htlc_msat", "5000"]
+        );
+        assert_eq!(
+            OpenChannelOptions::default()
+                .with_push_amount(1000)
+                .with_utxos(vec!["abc:0".to_string()])
+                .with_channel_type(ChannelType::SimpleTaproot)
+                .openchannel_args(),
+            [
+                "--utxo",
+                "abc:0",
+                "--channel_type",
+                ChannelType::SimpleTaproot.lncli_name(),
+                "--private"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_payment_fee() {
+        let ok = serde_json::json!({ "status": "SUCCEEDED", "fee_sat": "3" });
+        assert_eq!(parse_payment_fee(&ok).unwrap(), 3);
+
+        let failed = serde_json::json!({
+            "status": "FAILED",
+            "failure_reason": "FAILURE_REASON_NO_ROUTE"
+        });
+        let err = parse_payment_fee(&failed).unwrap_err().to_string();
+        assert!(err.contains("FAILURE_REASON_NO_ROUTE"));
+    }
+
+    #[test]
+    fn test_daemon_command_uses_rpc_credentials() {
+        let node = LndNode::new("lnd-1", "btc").with_rpc(RpcCredentials::new("alice", "secret"));
+        let cmd = node.daemon_command(false).unwrap();
+        assert!(cmd.contains(&"--bitcoind.rpcuser=alice".to_string()));
+        assert!(cmd.contains(&"--bitcoind.rpcpass=secret".to_string()));
+        assert!(!cmd.iter().any(|arg| arg.contains("polaruser")));
+    }
+
+    #[test]
+    fn test_daemon_command_bitcoin_host() {
+        let mut node = LndNode::new("lnd-1", "btc");
+        let cmd = node.daemon_command(false).unwrap();
+        assert!(cmd.contains(&"--bitcoind.rpchost=polar-btc-btc".to_string()));
+
+        node.bitcoin_host = Some("my-bitcoind".to_string());
+        let cmd = node.daemon_command(false).unwrap();
+        assert!(cmd.contains(&"--bitcoind.rpchost=my-bitcoind".to_string()));
+        assert!(cmd.contains(&format!(
+            "--bitcoind.zmqpubrawblock=tcp://my-bitcoind:{}",
+            BitcoinNode::ZMQ_BLOCK_PORT
+        )));
+    }
+
+    #[test]
+    fn test_daemon_command_watchtower_flags() {
+        let node = LndNode::new("lnd-1", "btc");
+        let plain = node.daemon_command(false).unwrap();
+        assert!(!plain.iter().any(|arg| arg.starts_with("--wtclient")));
+
+        let cmd = node.daemon_command(true).unwrap();
+        assert!(cmd.contains(&"--watchtower.active".to_string()));
+        assert!(cmd.contains(&"--wtclient.active".to_string()));
+    }
+
+    #[test]
+    fn test_daemon_command_extra_args() {
+        let node = LndNode::new("lnd-1", "btc").with_extra_args(["--protocol.wumbo-channels"]);
+        let cmd = node.daemon_command(false).unwrap();
+        assert_eq!(
+            cmd.last().map(String::as_str),
+            Some("--protocol.wumbo-channels")
+        );
+
+        let node = LndNode::new("lnd-1", "btc").with_extra_args(["--bitcoin.mainnet"]);
+        assert!(node.check_extra_args().is_err());
+        let node = LndNode::new("lnd-1", "btc").with_extra_args(["--bitcoind.rpchost=elsewhere"]);
+        assert!(node.check_extra_args().is_err());
+    }
+
+    #[test]
+    fn test_parse_routes() {
+        let json = serde_json::json!({
+            "routes": [{
+                "total_time_lock": 250,
+                "total_fees": "1",
+                "total_amt": "10001",
+                "total_fees_msat": "1010",
+                "total_amt_msat": "10001010",
+                "hops": [{"chan_id": "1"}, {"chan_id": "2"}]
+            }],
+            "success_prob": 1
+        });
+        let routes = parse_routes(&json);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].total_fees_msat, 1010);
+        assert_eq!(routes[0].total_fees_sat(), 2);
+        assert_eq!(routes[0].total_amt_msat, 10_001_010);
+        assert_eq!(routes[0].hops, 2);
+        assert_eq!(routes[0].total_time_lock, 250);
+
+        assert!(parse_routes(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_json_objects() {
+        let mut objects = JsonObjects::default();
+        assert!(objects.push_line("Payment hash: abc").is_none());
+        assert!(objects.push_line("{").is_none());
+        assert!(objects.push_line("    \"htlcs\": [{").is_none());
+        assert!(objects.push_line("    }],").is_none());
+        assert!(objects.push_line("    \"status\": \"IN_FLIGHT\"").is_none());
+        let json = objects.push_line("}").unwrap();
+        assert_eq!(json["status"], "IN_FLIGHT");
+
+        // A compact object completes on its own line
+        let json = objects.push_line("{\"status\": \"SUCCEEDED\"}").unwrap();
+        assert_eq!(json["status"], "SUCCEEDED");
+    }
+
+    #[test]
+    fn test_parse_payment_update() {
+        let json = serde_json::json!({
+            "payment_hash": "ab12",
+            "status": "IN_FLIGHT",
+            "failure_reason": "FAILURE_REASON_NONE",
+            "htlcs": [
+                {
+                    "status": "FAILED",
+                    "route": {"total_amt_msat": "10001000", "hops": [{}, {}]},
+                    "failure": {"code": "TEMPORARY_CHANNEL_FAILURE"}
+                },
+                {
+                    "status": "IN_FLIGHT",
+                    "route": {"total_amt_msat": "10002000", "hops": [{}, {}, {}]},
+                    "failure": null
+                }
+            ]
+        });
+        let update = parse_payment_update(&json);
+        assert_eq!(update.payment_hash, "ab12");
+        assert_eq!(update.failure_reason, None);
+        assert_eq!(update.attempts.len(), 2);
+        assert_eq!(update.attempts[1].hops, 3);
+        assert_eq!(update.attempts[1].total_amt_msat, 10_002_000);
+        assert_eq!(update.failed_attempts(), 1);
+        assert_eq!(
+            update.to_string(),
+            "IN_FLIGHT, attempt 2 over 3 hops, 1 failed (last: TEMPORARY_CHANNEL_FAILURE)"
+        );
+
+        let failed = parse_payment_update(&serde_json::json!({
+            "status": "FAILED",
+            "failure_reason": "FAILURE_REASON_NO_ROUTE"
+        }));
+        assert_eq!(failed.to_string(), "FAILED: FAILURE_REASON_NO_ROUTE");
+    }
+
+    #[test]
+    fn test_lncli_error_message() {
+        let output = "[lncli] rpc error: code = Unknown desc = invalid bech32 string length 7\n";
+        assert_eq!(
+            lncli_error_message(output),
+            "invalid bech32 string length 7"
+        );
+        assert_eq!(lncli_error_message("  boom \n"), "boom");
+    }
+
+    #[test]
+    fn test_lncli_error_kind() {
+        let output = "[lncli] rpc error: code = InvalidArgument desc = invalid pubkey\n";
+        assert_eq!(lncli_error_code(output), Some("InvalidArgument"));
+        let err = lncli_error("Failed to connect", output);
+        assert!(
+            matches!(err, polar_core::Error::InvalidArgument(_)),
+            "{err}"
+        );
+        assert!(
+            err.to_string()
+                .ends_with("Failed to connect: invalid pubkey")
+        );
+
+        let output = "[lncli] rpc error: code = DeadlineExceeded desc = context deadline exceeded";
+        assert!(matches!(
+            lncli_error("Failed to pay", output),
+            polar_core::Error::Timeout(_)
+        ));
+        assert!(matches!(
+            lncli_error(
+                "Failed to pay",
+                "[lncli] rpc error: code = Unknown desc = boom"
+            ),
+            polar_core::Error::ContainerExec(_)
+        ));
+        assert_eq!(lncli_error_code("boom"), None);
+    }
+
+    #[test]
+    fn test_check_zmq_backend() {
+        let bitcoin = BitcoinNode::new("bitcoin-1");
+        let lnd = LndNode::new("lnd-1", bitcoin.node.id.to_string());
+        assert!(lnd.check_zmq_backend(&bitcoin).is_ok());
+
+        let other = BitcoinNode::new("bitcoin-2");
+        let err = lnd.check_zmq_backend(&other).unwrap_err().to_string();
+        assert!(err.contains("bitcoin-2 publishes on"), "{err}");
     }
 }
@@ -0,0 +1,53 @@
+//! `lndconnect` URLs for pairing wallets with an LND node.
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use polar_core::{Error, Result};
+
+/// Build an `lndconnect://host:port?cert=...&macaroon=...` URL.
+///
+/// The cert is passed as PEM, as LND writes it, and embedded as its
+/// base64url encoded DER body; the macaroon is base64url encoded as is.
+///
+/// # Arguments
+/// * `host` - `host:port` the wallet connects to, reachable from outside Docker
+/// * `tls_cert_pem` - Contents of the node's `tls.cert`
+/// * `macaroon` - Contents of the macaroon to authenticate with
+pub fn lndconnect_url(host: &str, tls_cert_pem: &[u8], macaroon: &[u8]) -> Result<String> {
+    let der = pem_to_der(tls_cert_pem)?;
+
+    Ok(format!(
+        "lndconnect://{host}?cert={}&macaroon={}",
+        URL_SAFE_NO_PAD.encode(der),
+        URL_SAFE_NO_PAD.encode(macaroon)
+    ))
+}
+
+/// Decode the DER body of a PEM certificate, as LND writes `tls.cert`.
+pub fn pem_to_der(tls_cert_pem: &[u8]) -> Result<Vec<u8>> {
+    let pem = std::str::from_utf8(tls_cert_pem)
+        .map_err(|e| Error::RpcParse(format!("TLS cert is not PEM: {e}")))?;
+    let body: String = pem
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    STANDARD
+        .decode(body)
+        .map_err(|e| Error::RpcParse(format!("TLS cert is not PEM: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lndconnect_url() {
+        let pem = b"-----BEGIN CERTIFICATE-----\nAAEC/+8=\n-----END CERTIFICATE-----\n";
+        assert_eq!(
+            lndconnect_url("127.0.0.1:10009", pem, &[0x02, 0x01]).unwrap(),
+            "lndconnect://127.0.0.1:10009?cert=AAEC_-8&macaroon=AgE"
+        );
+        assert!(lndconnect_url("127.0.0.1:10009", b"not a cert", &[0x02]).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Client for LND's REST API.
+//!
+//! Reading node info over REST avoids starting an `lncli` process inside the
+//! container for every call. LND serves REST over TLS with a self-signed
+//! cert, so the client trusts exactly the cert read from the node.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Empty};
+use hyper::Request;
+use hyper::body::Bytes;
+use hyper::header::HOST;
+use hyper_util::rt::TokioIo;
+use polar_core::{Error, Result};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{
+    WebPkiSupportedAlgorithms, ring, verify_tls12_signature, verify_tls13_signature,
+};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{
+    self, CertificateError, ClientConfig, DigitallySignedStruct, SignatureScheme,
+};
+
+use crate::lndconnect::pem_to_der;
+
+/// Header LND reads the hex encoded macaroon from.
+const MACAROON_HEADER: &str = "Grpc-Metadata-macaroon";
+
+/// Client for the REST API of one LND node.
+#[derive(Debug, Clone)]
+pub struct LndRestClient {
+    /// `host:port` the REST API is reachable on.
+    address: String,
+    /// Hex encoded admin macaroon.
+    macaroon_hex: String,
+    /// TLS settings trusting only the node's cert.
+    tls: Arc<ClientConfig>,
+}
+
+impl LndRestClient {
+    /// Longest a single request may take, including connecting.
+    pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Create a client for the REST API at `address` (`host:port`).
+    ///
+    /// # Arguments
+    /// * `address` - `host:port` the REST API is published on
+    /// * `macaroon_hex` - Hex encoded admin macaroon
+    /// * `tls_cert_pem` - Contents of the node's `tls.cert`, the only cert
+    ///   the client accepts
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `tls_cert_pem` is not a PEM certificate.
+    pub fn new(
+        address: impl Into<String>,
+        macaroon_hex: impl Into<String>,
+        tls_cert_pem: &[u8],
+    ) -> Result<Self> {
+        let cert = CertificateDer::from(pem_to_der(tls_cert_pem)?);
+        let provider = Arc::new(ring::default_provider());
+        let verifier = PinnedCert {
+            cert,
+            algorithms: provider.signature_verification_algorithms,
+        };
+        let tls = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(http_error)?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth();
+
+        Ok(Self {
+            address: address.into(),
+            macaroon_hex: macaroon_hex.into(),
+            tls: Arc::new(tls),
+        })
+    }
+
+    /// Address the client talks to.
+    #[must_use]
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Node info, as returned by `lncli getinfo`.
+    pub async fn get_info(&self) -> Result<serde_json::Value> {
+        self.get("/v1/getinfo").await
+    }
+
+    /// On-chain wallet balance, as returned by `lncli walletbalance`.
+    pub async fn wallet_balance(&self) -> Result<serde_json::Value> {
+        self.get("/v1/balance/blockchain").await
+    }
+
+    /// Open channels, as returned by `lncli listchannels`.
+    pub async fn list_channels(&self) -> Result<serde_json::Value> {
+        self.get("/v1/channels").await
+    }
+
+    /// Send a GET request for `path` and parse the JSON response.
+    async fn get(&self, path: &str) -> Result<serde_json::Value> {
+        tokio::time::timeout(Self::TIMEOUT, self.request(path))
+            .await
+            .map_err(|_| Error::Timeout(format!("GET {path} on {}", self.address)))?
+    }
+
+    async fn request(&self, path: &str) -> Result<serde_json::Value> {
+        let stream = TcpStream::connect(&self.address).await?;
+        // The cert is pinned, so the name only goes out as SNI
+        let server_name = ServerName::try_from("localhost").map_err(http_error)?;
+        let stream = TlsConnector::from(Arc::clone(&self.tls))
+            .connect(server_name, stream)
+            .await?;
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(http_error)?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::debug!("LND REST connection closed: {e}");
+            }
+        });
+
+        let request = Request::get(path)
+            .header(HOST, &self.address)
+            .header(MACAROON_HEADER, &self.macaroon_hex)
+            .body(Empty::<Bytes>::new())
+            .map_err(http_error)?;
+        let response = sender.send_request(request).await.map_err(http_error)?;
+        let status = response.status();
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(http_error)?
+            .to_bytes();
+
+        parse_response(status.as_u16(), &body)
+            .map_err(|e| Error::Http(format!("GET {path} on {}: {e}", self.address)))
+    }
+}
+
+fn http_error(e: impl std::fmt::Display) -> Error {
+    Error::Http(e.to_string())
+}
+
+/// Accepts only the one cert LND generated for the node.
+///
+/// LND's cert is self-signed and names the container rather than the host
+/// its port is published on, so it is compared byte for byte instead of
+/// being checked against a root store and the server name.
+#[derive(Debug)]
+struct PinnedCert {
+    cert: CertificateDer<'static>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl ServerCertVerifier for PinnedCert {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        if end_entity.as_ref() == self.cert.as_ref() {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::UnknownIssuer,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+/// Parse a REST response body, turning error statuses into LND's message.
+fn parse_response(status: u16, body: &[u8]) -> std::result::Result<serde_json::Value, String> {
+    let json: serde_json::Value = serde_json::from_slice(body)
+        .map_err(|_| format!("status {status}: {}", String::from_utf8_lossy(body).trim()))?;
+    if (200..300).contains(&status) {
+        return Ok(json);
+    }
+    Err(json["message"]
+        .as_str()
+        .map_or_else(|| format!("status {status}"), ToString::to_string))
+}
+
+/// Hex encode a macaroon for the `Grpc-Metadata-macaroon` header.
+#[must_use]
+pub fn macaroon_hex(macaroon: &[u8]) -> String {
+    use std::fmt::Write;
+
+    macaroon.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let json = parse_response(200, br#"{"alias":"alice"}"#).unwrap();
+        assert_eq!(json["alias"], "alice");
+
+        let err = parse_response(500, br#"{"code":2,"message":"wallet locked"}"#).unwrap_err();
+        assert_eq!(err, "wallet locked");
+
+        let err = parse_response(404, b"Not Found").unwrap_err();
+        assert_eq!(err, "status 404: Not Found");
+    }
+
+    #[test]
+    fn test_new_requires_pem_cert() {
+        let pem = b"-----BEGIN CERTIFICATE-----\nAAEC/+8=\n-----END CERTIFICATE-----\n";
+        let client = LndRestClient::new("127.0.0.1:8080", "0201", pem).unwrap();
+        assert_eq!(client.address(), "127.0.0.1:8080");
+        assert!(LndRestClient::new("127.0.0.1:8080", "0201", b"not a cert!").is_err());
+    }
+
+    #[test]
+    fn test_macaroon_hex() {
+        assert_eq!(
+            macaroon_hex(&[0x02, 0x01, 0x03, 0x6c, 0x6e, 0x64, 0x02, 0xf8, 0x01]),
+            "0201036c6e6402f801"
+        );
+        assert_eq!(macaroon_hex(&[]), "");
+    }
+}
@@ -2,9 +2,169 @@
 //!
 //! This crate contains integration tests for network operations,
 //! Docker container management, and node lifecycle.
+//!
+//! Tests that need a whole network use [`TestNetwork`], which deletes the
+//! network again when it goes out of scope, so a failing assertion never
+//! leaves containers behind.
+
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use polar_core::ExtraArgs;
+use polar_nodes::{BitcoinNode, LndNode};
+use polar_tui::NetworkManager;
+
+/// Time given to freshly started nodes before a test talks to them.
+pub const STARTUP_DELAY: Duration = Duration::from_secs(5);
+
+/// A network created for a single test and deleted when dropped.
+///
+/// Dereferences to the [`NetworkManager`] that owns the network.
+pub struct TestNetwork {
+    manager: NetworkManager,
+    name: String,
+}
+
+impl TestNetwork {
+    /// Create a stopped network of `lnd_count` LND and `btc_count` Bitcoin
+    /// nodes on the default images.
+    ///
+    /// A network of the same name left behind by an aborted run is removed
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the leftover network cannot be removed or the
+    /// network cannot be created
+    pub async fn create(name: &str, lnd_count: usize, btc_count: usize) -> Result<Self> {
+        Self::create_with_extra_args(name, lnd_count, btc_count, ExtraArgs::default()).await
+    }
+
+    /// Create a stopped network whose nodes get `extra_args`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `extra_args` are rejected, see [`Self::create`]
+    pub async fn create_with_extra_args(
+        name: &str,
+        lnd_count: usize,
+        btc_count: usize,
+        extra_args: ExtraArgs,
+    ) -> Result<Self> {
+        let mut manager = NetworkManager::new()?;
+        teardown(&mut manager, name).await?;
+        manager.create_network_with_extra_args(
+            name,
+            lnd_count,
+            btc_count,
+            name,
+            LndNode::DEFAULT_IMAGE,
+            BitcoinNode::DEFAULT_IMAGE,
+            extra_args,
+        )?;
+        Ok(Self {
+            manager,
+            name: name.to_string(),
+        })
+    }
+
+    /// Create a network and start it, see [`Self::create`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the network cannot be created or started
+    pub async fn started(name: &str, lnd_count: usize, btc_count: usize) -> Result<Self> {
+        let mut network = Self::create(name, lnd_count, btc_count).await?;
+        network.start().await?;
+        Ok(network)
+    }
+
+    /// Start the network and give its nodes [`STARTUP_DELAY`] to come up.
+    ///
+    /// Containers started before a failure are stopped at once, since the
+    /// partially started state is not saved for the cleanup on drop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any node fails to start
+    pub async fn start(&mut self) -> Result<()> {
+        if let Err(e) = self.manager.start_network(&self.name).await {
+            let _ = self.manager.stop_network(&self.name).await;
+            return Err(e.into());
+        }
+        tokio::time::sleep(STARTUP_DELAY).await;
+        Ok(())
+    }
+
+    /// Name of the network.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-#![allow(dead_code)]
+    /// Replace the manager with a fresh one that loads the networks from
+    /// disk, as after restarting the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the saved networks cannot be loaded
+    pub fn reload(&mut self) -> Result<()> {
+        let data_dir = self.manager.data_dir().to_path_buf();
+        self.manager = NetworkManager::with_data_dir(Some(data_dir))?;
+        Ok(())
+    }
+}
+
+impl Deref for TestNetwork {
+    type Target = NetworkManager;
+
+    fn deref(&self) -> &NetworkManager {
+        &self.manager
+    }
+}
+
+impl DerefMut for TestNetwork {
+    fn deref_mut(&mut self) -> &mut NetworkManager {
+        &mut self.manager
+    }
+}
+
+impl Drop for TestNetwork {
+    fn drop(&mut self) {
+        println!("  - Cleaning up {}...", self.name);
+        let name = std::mem::take(&mut self.name);
+        let data_dir = self.manager.data_dir().to_path_buf();
+
+        // The test's runtime is blocked in this drop and cannot drive the
+        // cleanup, so it runs on its own runtime with a manager reloaded
+        // from disk
+        let cleanup = std::thread::spawn(move || -> Result<()> {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(teardown_saved(data_dir, &name))
+        });
+        match cleanup.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("  ! Failed to clean up test network: {e}"),
+            Err(_) => eprintln!("  ! Test network cleanup panicked"),
+        }
+    }
+}
+
+/// Delete the network `name` saved under `data_dir`.
+async fn teardown_saved(data_dir: PathBuf, name: &str) -> Result<()> {
+    let mut manager = NetworkManager::with_data_dir(Some(data_dir))?;
+    teardown(&mut manager, name).await
+}
 
-pub fn placeholder() {
-    // This is just a placeholder to make the crate compile
+/// Stop the network `name` if it is running or errored, then delete it.
+async fn teardown(manager: &mut NetworkManager, name: &str) -> Result<()> {
+    if manager.get_network(name).is_some() {
+        manager.stop_network(name).await?;
+        manager.delete_network(name).await?;
+    }
+    Ok(())
 }
@@ -1,12 +1,43 @@
 //! Integration tests for polar-tests
 
 mod integration {
+    mod address_types;
+    mod auto_maturity;
+    mod bitcoin_peers;
+    mod bitcoin_wallet;
+    mod block_generator;
     mod channel_close;
+    mod channel_fees;
     mod channel_info_display;
     mod channel_operations;
+    mod channel_options;
+    mod channel_types;
+    mod coin_control;
+    mod concurrency;
+    mod credential_export;
+    mod data_dir;
+    mod external_network;
+    mod extra_args;
+    mod fund_all;
     mod funding_flow;
+    mod hold_invoice;
+    mod mesh;
+    mod message_signing;
+    mod mpp;
     mod network_operations;
+    mod network_summary;
     mod node_deletion;
+    mod node_env;
     mod node_info;
+    mod node_lifecycle;
+    mod onchain_transfer;
     mod payment_operations;
+    mod peer_management;
+    mod port_persistence;
+    mod private_channel;
+    mod rebalance;
+    mod routing;
+    mod topology;
+    mod watchtower;
+    mod wumbo;
 }
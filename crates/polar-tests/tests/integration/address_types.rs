@@ -0,0 +1,51 @@
+//! Tests for choosing the script type of new addresses.
+
+use anyhow::Result;
+use polar_docker::ContainerManager;
+use polar_nodes::{AddressType, BitcoinNode, LndNode};
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_new_address_types() -> Result<()> {
+    println!("\nTesting new address types...");
+
+    let network_name = "polar-test-address-types";
+
+    println!("  - Starting network with 1 LND node...");
+    let network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    let container_id = |name: &str| {
+        network_manager
+            .get_network(network_name)
+            .and_then(|n| n.nodes.iter().find(|n| n.name == name))
+            .and_then(|n| n.container_id.clone())
+    };
+    let manager = ContainerManager::new()?;
+    let mut lnd = LndNode::new("lnd-1", String::new());
+    lnd.node.container_id = container_id("lnd-1");
+    let mut btc = BitcoinNode::new("bitcoin-1");
+    btc.node.container_id = container_id("bitcoin-1");
+
+    println!("  - Requesting addresses of each type...");
+    let lnd_default = lnd.get_new_address(&manager, None).await;
+    let lnd_nested = lnd
+        .get_new_address(&manager, Some(AddressType::Np2wkh))
+        .await;
+    let lnd_taproot = lnd.get_new_address(&manager, Some(AddressType::P2tr)).await;
+    let btc_taproot = btc.get_new_address(&manager, Some(AddressType::P2tr)).await;
+
+    let lnd_default = lnd_default?;
+    assert!(lnd_default.starts_with("bcrt1q"), "{lnd_default}");
+    println!("    ✓ LND default: {lnd_default}");
+    let lnd_nested = lnd_nested?;
+    assert!(lnd_nested.starts_with('2'), "{lnd_nested}");
+    println!("    ✓ LND np2wkh: {lnd_nested}");
+    let lnd_taproot = lnd_taproot?;
+    assert!(lnd_taproot.starts_with("bcrt1p"), "{lnd_taproot}");
+    println!("    ✓ LND p2tr: {lnd_taproot}");
+    let btc_taproot = btc_taproot?;
+    assert!(btc_taproot.starts_with("bcrt1p"), "{btc_taproot}");
+    println!("    ✓ Bitcoin Core bech32m: {btc_taproot}");
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Tests for mining coinbase maturity automatically before funding.
+
+use anyhow::Result;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+use polar_tui::network_manager::FundOptions;
+
+/// Fund lnd-1 on a fresh chain, where the Bitcoin wallet starts empty.
+async fn fund_from_empty_wallet(
+    network_manager: &NetworkManager,
+    network_name: &str,
+) -> Result<()> {
+    println!("  - Funding without auto maturity mining...");
+    let result = network_manager
+        .fund_lnd_wallet_with_options(network_name, "lnd-1", 1.0, FundOptions::default())
+        .await;
+    assert!(result.is_err(), "Empty wallet should not fund lnd-1");
+
+    println!("  - Rejecting an amount that cannot be mined...");
+    let result = network_manager
+        .fund_lnd_wallet_with_options(
+            network_name,
+            "lnd-1",
+            1_000_000.0,
+            FundOptions::default().with_auto_mine_maturity(true),
+        )
+        .await;
+    assert!(result.is_err(), "Unminable amount should be rejected");
+
+    println!("  - Funding with auto maturity mining...");
+    let txid = network_manager
+        .fund_lnd_wallet_with_options(
+            network_name,
+            "lnd-1",
+            1.0,
+            FundOptions::default().with_auto_mine_maturity(true),
+        )
+        .await?;
+    println!("    ✓ Funded lnd-1 in {txid}");
+
+    let utxos = network_manager.list_utxos(network_name, "lnd-1").await?;
+    assert!(
+        utxos.iter().any(|u| u.amount_sat == 100_000_000),
+        "lnd-1 should hold the 1 BTC output, got {utxos:?}"
+    );
+    println!("    ✓ lnd-1 received 1 BTC");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fund_with_auto_maturity_mining() -> Result<()> {
+    println!("\nTesting funding with automatic coinbase maturity mining...");
+
+    let network_name = "polar-test-auto-maturity";
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    fund_from_empty_wallet(&network_manager, network_name).await
+}
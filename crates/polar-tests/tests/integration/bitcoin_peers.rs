@@ -0,0 +1,104 @@
+//! Tests for networks with multiple connected Bitcoin Core nodes.
+
+use anyhow::Result;
+use polar_core::{NodeKind, RpcCredentials};
+use polar_docker::ContainerManager;
+use polar_nodes::BitcoinNode;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_reorg_after_partition_heals() -> Result<()> {
+    println!("\nTesting reorg between two partitioned Bitcoin nodes...");
+
+    let network_name = "polar-test-btc-peers";
+
+    println!("  - Starting network with 2 Bitcoin nodes...");
+    let network_manager = TestNetwork::started(network_name, 0, 2).await?;
+
+    let nodes: Vec<BitcoinNode> = network_manager
+        .get_network(network_name)
+        .expect("network exists")
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::BitcoinCore)
+        .map(|n| BitcoinNode {
+            node: n.clone(),
+            image: BitcoinNode::DEFAULT_IMAGE.to_string(),
+            rpc: RpcCredentials::default(),
+            extra_args: Vec::new(),
+        })
+        .collect();
+    let manager = ContainerManager::new()?;
+    let (btc1, btc2) = (&nodes[0], &nodes[1]);
+
+    println!("  - Connecting Bitcoin peers...");
+    let connected = network_manager.connect_bitcoin_peers(network_name).await?;
+    assert_eq!(connected, 2);
+    let peers = btc1.get_peer_info(&manager).await?;
+    assert!(!peers.is_empty(), "bitcoin-1 should list bitcoin-2 as a peer");
+
+    println!("  - Mining a shared block...");
+    btc1.mine_blocks(&manager, 1, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let shared_tip = btc2.get_best_block_hash(&manager).await?;
+    assert_eq!(shared_tip, btc1.get_best_block_hash(&manager).await?);
+
+    println!("  - Partitioning and mining competing chains...");
+    network_manager
+        .disconnect_bitcoin_peer(network_name, "bitcoin-1", "bitcoin-2")
+        .await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    btc1.mine_blocks(&manager, 2, None).await?;
+    btc2.mine_blocks(&manager, 5, None).await?;
+    let short_tip = btc1.get_best_block_hash(&manager).await?;
+    let long_tip = btc2.get_best_block_hash(&manager).await?;
+    assert_ne!(
+        short_tip, long_tip,
+        "Chains should diverge while partitioned"
+    );
+
+    println!("  - Reconnecting peers...");
+    network_manager.connect_bitcoin_peers(network_name).await?;
+
+    // Give bitcoin-1 time to download the longer chain and reorg
+    let mut reorged = false;
+    for _ in 0..10 {
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        if btc1.get_best_block_hash(&manager).await? == long_tip {
+            reorged = true;
+            break;
+        }
+    }
+
+    assert!(reorged, "bitcoin-1 should reorg onto the longer chain");
+    println!("    ✓ Reorg observed after partition healed");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_simulate_reorg() -> Result<()> {
+    println!("\nTesting one-call reorg simulation...");
+
+    let network_name = "polar-test-simulate-reorg";
+
+    println!("  - Starting network with 2 Bitcoin nodes...");
+    let network_manager = TestNetwork::started(network_name, 1, 2).await?;
+
+    println!("  - Simulating a 3 block reorg...");
+    let zero_depth = network_manager.simulate_reorg(network_name, 0).await;
+    let reorg = network_manager.simulate_reorg(network_name, 3).await;
+
+    assert!(zero_depth.is_err(), "A reorg needs at least one block");
+    let reorg = reorg?;
+    assert_eq!(reorg.node, "bitcoin-1");
+    assert_eq!(reorg.peer, "bitcoin-2");
+    assert_eq!(reorg.depth, 3);
+    assert_ne!(reorg.before, reorg.after, "bitcoin-1 should switch tips");
+    println!(
+        "    ✓ {} reorged from {} to {}",
+        reorg.node, reorg.before, reorg.after
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Tests for loading the Bitcoin Core wallet after restarts.
+
+use anyhow::Result;
+use polar_docker::ContainerManager;
+use polar_nodes::BitcoinNode;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_wallet_reloaded_after_restart() -> Result<()> {
+    println!("\nTesting Bitcoin Core wallet reloading...");
+
+    let network_name = "polar-test-bitcoin-wallet";
+
+    println!("  - Starting network with 1 LND node...");
+    let mut network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    let container_id = || {
+        network_manager
+            .get_network(network_name)
+            .and_then(|n| n.nodes.iter().find(|n| n.name == "bitcoin-1"))
+            .and_then(|n| n.container_id.clone())
+    };
+    let manager = ContainerManager::new()?;
+    let mut btc = BitcoinNode::new("bitcoin-1");
+    btc.node.container_id = container_id();
+    let address = btc.get_new_address(&manager, None).await?;
+
+    // Leave the wallet on disk but unloaded, as after a bitcoind restart
+    println!("  - Unloading the wallet...");
+    let unload_cmd = btc
+        .rpc
+        .bitcoin_cli_command(&["unloadwallet", BitcoinNode::WALLET_NAME]);
+    let unloaded = match &btc.node.container_id {
+        Some(id) => manager
+            .exec_command(id, unload_cmd.iter().map(String::as_str).collect())
+            .await
+            .map(|_| ()),
+        None => Err(polar_core::Error::NodeNotRunning("bitcoin-1".to_string())),
+    };
+    println!("  - Reading the node info, which loads the wallet again...");
+    let info = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await;
+    let owned = match &btc.node.container_id {
+        Some(id) => {
+            let cmd = btc.rpc.bitcoin_cli_command(&["getaddressinfo", &address]);
+            manager
+                .exec_command(id, cmd.iter().map(String::as_str).collect())
+                .await
+        }
+        None => Err(polar_core::Error::NodeNotRunning("bitcoin-1".to_string())),
+    };
+    let balance = btc.get_balance(&manager).await;
+
+    println!("  - Restarting bitcoin-1 and mining again...");
+    let restarted = match network_manager.stop_node(network_name, "bitcoin-1").await {
+        Ok(()) => network_manager.start_node(network_name, "bitcoin-1").await,
+        Err(e) => Err(e),
+    };
+    let mined = network_manager.mine_blocks(network_name, 1, None).await;
+
+    unloaded?;
+    info?;
+    let owned: serde_json::Value = serde_json::from_str(&owned?)?;
+    assert_eq!(
+        owned["ismine"],
+        serde_json::Value::Bool(true),
+        "The wallet should be reloaded with its old addresses, not recreated"
+    );
+    let balance = balance?;
+    assert!(
+        balance > 0.0,
+        "The reloaded wallet should keep its coinbase funds, got {balance}"
+    );
+    println!("    ✓ Unloaded wallet reloaded with {balance} BTC");
+
+    restarted?;
+    assert_eq!(mined?.len(), 1, "Mining should work after a restart");
+    println!("    ✓ Mined after restarting bitcoin-1");
+
+    Ok(())
+}
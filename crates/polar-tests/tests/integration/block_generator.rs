@@ -0,0 +1,141 @@
+//! Tests for timed background block generation.
+
+use anyhow::Result;
+use polar_core::NodeInfo;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+use polar_tui::network_manager::GeneratorMode;
+
+/// Get the current block height of the Bitcoin node.
+async fn block_height(manager: &NetworkManager, network: &str) -> Result<u64> {
+    match manager.get_node_info(network, "bitcoin-1").await? {
+        NodeInfo::Bitcoin(info) => Ok(info.blocks),
+        NodeInfo::Lnd(_) => anyhow::bail!("bitcoin-1 is not a Bitcoin node"),
+    }
+}
+
+#[tokio::test]
+async fn test_block_generator_mines_until_stopped() -> Result<()> {
+    println!("\nTesting timed block generation...");
+
+    let network_name = "polar-test-block-generator";
+
+    println!("  - Starting network with 1 LND node...");
+    let mut network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    let start_height = block_height(&network_manager, network_name).await;
+
+    println!("  - Mining 1 block every second for 5 seconds...");
+    let started = network_manager.start_block_generator(network_name, 1, 1);
+    let running = network_manager.is_block_generator_running(network_name);
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let stopped = network_manager.stop_block_generator(network_name);
+
+    // Let any in-flight tick finish before sampling the final height
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    let mid_height = block_height(&network_manager, network_name).await;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let end_height = block_height(&network_manager, network_name).await;
+
+    started?;
+    assert!(running, "Generator should report running after start");
+    assert!(stopped, "Stopping should find the running generator");
+    let (start_height, mid_height, end_height) = (start_height?, mid_height?, end_height?);
+    assert!(
+        mid_height >= start_height + 3,
+        "Generator should have mined blocks ({start_height} -> {mid_height})"
+    );
+    assert_eq!(
+        mid_height, end_height,
+        "No blocks should be mined after stopping"
+    );
+    println!(
+        "    ✓ Mined {} blocks, then stopped",
+        mid_height - start_height
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_block_height_follows_mining() -> Result<()> {
+    println!("\nTesting the block height indicator...");
+
+    let network_name = "polar-test-block-height";
+
+    println!("  - Creating network with 1 LND node...");
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+    let stopped_height = network_manager.block_height(network_name).await;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    let start_height = network_manager.block_height(network_name).await;
+    println!("  - Mining 3 blocks...");
+    let mined = network_manager.mine_blocks(network_name, 3, None).await;
+    let end_height = network_manager.block_height(network_name).await;
+
+    assert_eq!(
+        stopped_height?, None,
+        "A stopped network should have no height"
+    );
+    mined?;
+    let start_height = start_height?.expect("running network should report a height");
+    let end_height = end_height?.expect("running network should report a height");
+    assert_eq!(end_height, start_height + 3);
+    println!("    ✓ Height went from {start_height} to {end_height}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_block_generator_mode_switch() -> Result<()> {
+    println!("\nTesting switching the block generator mode...");
+
+    let network_name = "polar-test-block-generator-mode";
+
+    println!("  - Starting network with 1 LND node...");
+    let mut network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    let mempool = network_manager.mempool_size(network_name).await;
+    let start_height = block_height(&network_manager, network_name).await;
+
+    println!("  - Mining only on mempool activity for 4 seconds...");
+    let started = network_manager.start_block_generator_with_mode(
+        network_name,
+        1,
+        1,
+        GeneratorMode::OnMempool,
+    );
+    tokio::time::sleep(tokio::time::Duration::from_secs(4)).await;
+    let idle_height = block_height(&network_manager, network_name).await;
+
+    println!("  - Switching to a fixed interval for 4 seconds...");
+    let switched =
+        network_manager.set_block_generator_mode(network_name, GeneratorMode::FixedInterval);
+    let mode = network_manager.block_generator_mode(network_name);
+    tokio::time::sleep(tokio::time::Duration::from_secs(4)).await;
+    network_manager.stop_block_generator(network_name);
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    let end_height = block_height(&network_manager, network_name).await;
+
+    assert_eq!(mempool?, 0, "A fresh network should have an empty mempool");
+    started?;
+    switched?;
+    assert_eq!(mode, Some(GeneratorMode::FixedInterval));
+    let (start_height, idle_height, end_height) = (start_height?, idle_height?, end_height?);
+    assert_eq!(
+        start_height, idle_height,
+        "No blocks should be mined with an empty mempool"
+    );
+    assert!(
+        end_height >= idle_height + 2,
+        "Fixed interval mode should mine ({idle_height} -> {end_height})"
+    );
+    println!(
+        "    ✓ Idle on an empty mempool, then mined {} blocks",
+        end_height - idle_height
+    );
+
+    Ok(())
+}
@@ -43,7 +43,7 @@ async fn test_cooperative_channel_close() -> Result<()> {
 
     // Fund LND1 wallet
     println!("  - Funding LND1 wallet...");
-    let lnd1_address = lnd1.get_new_address(&manager).await?;
+    let lnd1_address = lnd1.get_new_address(&manager, None).await?;
     btc_node
         .send_to_address(&manager, &lnd1_address, 1.0)
         .await?;
@@ -83,7 +83,9 @@ async fn test_cooperative_channel_close() -> Result<()> {
 
     // Close channel cooperatively
     println!("  - Closing channel cooperatively...");
-    let closing_txid = lnd1.close_channel(&manager, channel_point, false).await?;
+    let closing_txid = lnd1
+        .close_channel(&manager, &channel_point.parse()?, false)
+        .await?;
     println!("    ✓ Channel closing initiated. Txid: {}", closing_txid);
 
     // Mine blocks to confirm close
@@ -0,0 +1,119 @@
+//! Tests for updating channel fee policies.
+
+use anyhow::Result;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Fee lnd-2 charges after the update, high enough to make its route lose.
+const HIGH_BASE_FEE_MSAT: u64 = 100_000;
+const HIGH_FEE_RATE_PPM: u64 = 50_000;
+
+/// Wait until the graph, as seen by lnd-1, carries lnd-2's new policy.
+async fn wait_for_high_fee_policy(manager: &NetworkManager, network_name: &str) -> Result<bool> {
+    for _ in 0..10 {
+        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        let graph = manager.describe_graph(network_name).await?;
+        let seen = graph.edges.iter().any(|edge| {
+            [edge.node1_policy, edge.node2_policy]
+                .into_iter()
+                .flatten()
+                .any(|policy| policy.base_fee_msat == HIGH_BASE_FEE_MSAT)
+        });
+        if seen {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[tokio::test]
+async fn test_high_fee_route_is_avoided() -> Result<()> {
+    println!("\nTesting fee policy in a diamond A -> (B | C) -> D...");
+
+    let network_name = "polar-test-channel-fees";
+
+    println!("  - Starting network with 4 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 4, 1).await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1, lnd-2 and lnd-3...");
+    for node in ["lnd-1", "lnd-2", "lnd-3"] {
+        network_manager
+            .fund_lnd_wallet(network_name, node, 0.1)
+            .await?;
+    }
+
+    println!("  - Opening diamond channels...");
+    for (from, to) in [
+        ("lnd-1", "lnd-2"),
+        ("lnd-1", "lnd-3"),
+        ("lnd-2", "lnd-4"),
+        ("lnd-3", "lnd-4"),
+    ] {
+        network_manager
+            .open_channel(network_name, from, to, 1_000_000, None)
+            .await?;
+    }
+
+    println!("  - Mining 10 blocks to confirm and announce channels...");
+    network_manager.mine_blocks(network_name, 10, None).await?;
+    network_manager.sync_graph(network_name).await?;
+
+    println!("  - Raising lnd-2's fees on all its channels...");
+    network_manager
+        .set_channel_fees(
+            network_name,
+            "lnd-2",
+            None,
+            HIGH_BASE_FEE_MSAT,
+            HIGH_FEE_RATE_PPM,
+            40,
+        )
+        .await?;
+
+    println!("  - Waiting for the policy to reach the graph...");
+    let policy_seen = wait_for_high_fee_policy(&network_manager, network_name).await?;
+
+    println!("  - Paying lnd-4 from lnd-1...");
+    let mut payment = None;
+    for attempt in 1..=10 {
+        match network_manager
+            .send_payment(network_name, "lnd-1", "lnd-4", 10_000, Some("cheap route"))
+            .await
+        {
+            Ok(hash) => {
+                payment = Some(hash);
+                break;
+            }
+            Err(e) => {
+                println!("    … attempt {attempt} failed: {e}");
+                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            }
+        }
+    }
+    let history = network_manager.payment_history(network_name, "lnd-1").await;
+
+    assert!(
+        policy_seen,
+        "Updated policy should show up in describegraph"
+    );
+    let payment_hash = payment.expect("lnd-1 should be able to pay lnd-4");
+    let history = history?;
+    let sent = history
+        .payments
+        .iter()
+        .find(|p| p.payment_hash == payment_hash)
+        .expect("Payment should be in lnd-1's history");
+
+    // Routing via lnd-2 would cost over 600 sats, lnd-3 charges about 1
+    assert!(
+        sent.fee_sat < 10,
+        "Payment should avoid lnd-2, paid {} sats in fees",
+        sent.fee_sat
+    );
+    println!("    ✓ Payment took the cheap route via lnd-3");
+
+    Ok(())
+}
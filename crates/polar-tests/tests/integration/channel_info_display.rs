@@ -45,7 +45,7 @@ async fn test_channel_list_in_node_info() -> Result<()> {
 
     // Fund and open channel
     println!("  - Funding LND1 wallet...");
-    let address = lnd1.get_new_address(&manager).await?;
+    let address = lnd1.get_new_address(&manager, None).await?;
     btc_node.send_to_address(&manager, &address, 1.0).await?;
     btc_node.mine_blocks(&manager, 6, None).await?;
     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
@@ -57,7 +57,7 @@ async fn test_channel_list_in_node_info() -> Result<()> {
         .await?;
 
     println!("  - Opening channel...");
-    lnd1.open_channel(&manager, &lnd2_pubkey, 1000000, Some(0))
+    lnd1.open_channel(&manager, &lnd2_pubkey, 1_000_000, Some(0))
         .await?;
     btc_node.mine_blocks(&manager, 6, None).await?;
     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
@@ -74,7 +74,7 @@ async fn test_open_channel_basic() -> Result<()> {
 
     // Fund LND node 1 (the one that will open the channel)
     println!("  - Funding LND node 1...");
-    let addr1 = lnd_node_1.get_new_address(&manager).await?;
+    let addr1 = lnd_node_1.get_new_address(&manager, None).await?;
     let txid = btc_node.send_to_address(&manager, &addr1, 1.0).await?;
     println!("    ✓ Funding TXID: {}", txid);
 
@@ -181,7 +181,7 @@ async fn test_open_channel_with_push() -> Result<()> {
     println!("  - Mining and funding nodes...");
     btc_node.mine_blocks(&manager, 101, None).await?;
 
-    let addr1 = lnd_node_1.get_new_address(&manager).await?;
+    let addr1 = lnd_node_1.get_new_address(&manager, None).await?;
     btc_node.send_to_address(&manager, &addr1, 1.0).await?;
     btc_node.mine_blocks(&manager, 6, None).await?;
     tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
@@ -285,7 +285,7 @@ async fn test_open_multiple_channels() -> Result<()> {
     println!("  - Mining and funding node 1...");
     btc_node.mine_blocks(&manager, 101, None).await?;
 
-    let addr1 = lnd_node_1.get_new_address(&manager).await?;
+    let addr1 = lnd_node_1.get_new_address(&manager, None).await?;
     btc_node.send_to_address(&manager, &addr1, 2.0).await?;
     btc_node.mine_blocks(&manager, 6, None).await?;
     tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
@@ -0,0 +1,69 @@
+//! Tests for channel-open acceptance options.
+
+use anyhow::Result;
+use polar_nodes::OpenChannelOptions;
+use polar_tests::TestNetwork;
+use polar_tui::network_manager::FundOptions;
+
+#[tokio::test]
+async fn test_open_channel_from_unconfirmed_funds() -> Result<()> {
+    println!("\nTesting channel open with --min_confs 0...");
+
+    let network_name = "polar-test-channel-options";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1 without confirming the transaction...");
+    network_manager
+        .fund_lnd_wallet_with_options(
+            network_name,
+            "lnd-1",
+            1.0,
+            FundOptions::default().with_auto_mine(false),
+        )
+        .await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+    println!("  - Opening with default options...");
+    let default_open = network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", 500_000, None)
+        .await;
+
+    println!("  - Opening with --min_confs 0...");
+    let options = OpenChannelOptions::default()
+        .with_min_confs(0)
+        .with_remote_csv_delay(288)
+        .with_min_htlc_msat(5_000);
+    let zero_conf_open = network_manager
+        .open_channel_with_options(network_name, "lnd-1", "lnd-2", 500_000, &options)
+        .await;
+
+    network_manager.mine_blocks(network_name, 6, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let channels = network_manager.list_channels(network_name, "lnd-1").await;
+
+    assert!(
+        default_open.is_err(),
+        "Default options should refuse unconfirmed funds"
+    );
+    println!("    ✓ Default open refused unconfirmed funds");
+
+    let funding_txid = zero_conf_open?;
+    assert_eq!(funding_txid.len(), 64, "Should return a funding txid");
+    println!("    ✓ Opened from unconfirmed funds: {funding_txid}");
+
+    let channels = channels?;
+    assert!(
+        channels
+            .iter()
+            .any(|c| c.channel_point.starts_with(&funding_txid)),
+        "Channel should be open after confirming"
+    );
+    println!("    ✓ Channel confirmed and active");
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Tests for opening channels with an explicit commitment type.
+
+use anyhow::Result;
+use polar_core::NodeInfo;
+use polar_nodes::{ChannelType, LndNode, OpenChannelOptions};
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_open_anchors_channel() -> Result<()> {
+    println!("\nTesting anchors channel type...");
+
+    let network_name = "polar-test-channel-types";
+    let mut network_manager = TestNetwork::create(network_name, 2, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1 with 1 BTC...");
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    println!("  - Rejecting taproot channel without the protocol flag...");
+    let taproot = network_manager
+        .open_channel_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            1_000_000,
+            &OpenChannelOptions::default().with_channel_type(ChannelType::SimpleTaproot),
+        )
+        .await;
+
+    println!("  - Opening anchors channel lnd-1 -> lnd-2...");
+    let opened = network_manager
+        .open_channel_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            1_000_000,
+            &OpenChannelOptions::default().with_channel_type(ChannelType::Anchors),
+        )
+        .await;
+    if opened.is_ok() {
+        network_manager.mine_blocks(network_name, 6, None).await?;
+        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    }
+    let info = network_manager.get_node_info(network_name, "lnd-1").await;
+
+    let err = taproot.expect_err("Taproot channel should need the protocol flag");
+    assert!(
+        err.to_string().contains(LndNode::TAPROOT_FLAG),
+        "Error should name the taproot flag: {err}"
+    );
+
+    opened?;
+    let NodeInfo::Lnd(info) = info? else {
+        panic!("lnd-1 should be an LND node");
+    };
+    let channel = info.channels.first().expect("Channel should be open");
+    assert_eq!(
+        channel.commitment_type,
+        ChannelType::Anchors.commitment_type()
+    );
+    println!("    ✓ Anchors channel opened");
+
+    Ok(())
+}
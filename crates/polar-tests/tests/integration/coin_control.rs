@@ -0,0 +1,86 @@
+//! Tests for funding a channel from selected UTXOs.
+
+use anyhow::{Context, Result};
+use polar_nodes::OpenChannelOptions;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Fund lnd-1 with two UTXOs and open a channel from the smaller one.
+async fn open_from_selected_utxo(
+    network_manager: &NetworkManager,
+    network_name: &str,
+) -> Result<()> {
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1 with 0.5 and 0.2 BTC...");
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 0.5)
+        .await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 0.2)
+        .await?;
+
+    let utxos = network_manager.list_utxos(network_name, "lnd-1").await?;
+    let outpoint_of = |amount_sat| {
+        utxos
+            .iter()
+            .find(|u| u.amount_sat == amount_sat)
+            .map(|u| u.outpoint.clone())
+            .with_context(|| format!("No {amount_sat} sat UTXO in {utxos:?}"))
+    };
+    let small = outpoint_of(20_000_000)?;
+    let large = outpoint_of(50_000_000)?;
+    println!("    ✓ Found UTXOs {small} and {large}");
+
+    println!("  - Rejecting an outpoint not in the wallet...");
+    let unknown = network_manager
+        .open_channel_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            1_000_000,
+            &OpenChannelOptions::default().with_utxos(vec![small.clone(), "00:0".to_string()]),
+        )
+        .await;
+    assert!(unknown.is_err(), "Unknown outpoint should be rejected");
+
+    println!("  - Opening channel from the 0.2 BTC UTXO only...");
+    network_manager
+        .open_channel_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            1_000_000,
+            &OpenChannelOptions::default().with_utxos(vec![small.clone()]),
+        )
+        .await?;
+    network_manager.mine_blocks(network_name, 6, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+
+    let remaining = network_manager.list_utxos(network_name, "lnd-1").await?;
+    assert!(
+        remaining.iter().all(|u| u.outpoint != small),
+        "Selected UTXO should be spent"
+    );
+    assert!(
+        remaining.iter().any(|u| u.outpoint == large),
+        "Other UTXO should be untouched"
+    );
+    println!("    ✓ Channel funded from the selected UTXO only");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_open_channel_from_selected_utxo() -> Result<()> {
+    println!("\nTesting channel funding from a selected UTXO...");
+
+    let network_name = "polar-test-coin-control";
+    let mut network_manager = TestNetwork::create(network_name, 2, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    open_from_selected_utxo(&network_manager, network_name).await
+}
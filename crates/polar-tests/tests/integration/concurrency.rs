@@ -0,0 +1,163 @@
+//! Tests for serializing concurrent operations on one network.
+
+use anyhow::Result;
+use polar_core::{NetworkStatus, NodeInfo};
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Get the current block height of the Bitcoin node.
+async fn block_height(manager: &NetworkManager, network: &str) -> Result<u64> {
+    match manager.get_node_info(network, "bitcoin-1").await? {
+        NodeInfo::Bitcoin(info) => Ok(info.blocks),
+        NodeInfo::Lnd(_) => anyhow::bail!("bitcoin-1 is not a Bitcoin node"),
+    }
+}
+
+/// Environment that tells apart the saves of racing writer `writer`.
+fn writer_env(writer: usize, round: usize) -> Vec<(String, String)> {
+    vec![("POLAR_WRITER".to_string(), format!("{writer}-{round}"))]
+}
+
+/// Save `network` `rounds` times from `manager`, as writer `writer`.
+fn save_repeatedly(
+    manager: &mut NetworkManager,
+    network: &str,
+    writer: usize,
+    rounds: usize,
+) -> Result<()> {
+    for round in 0..rounds {
+        manager.set_node_env(network, "lnd-1", writer_env(writer, round))?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_mine_blocks_on_one_network() -> Result<()> {
+    println!("\nTesting concurrent mining on one network...");
+
+    let network_name = "polar-test-concurrent-mining";
+
+    println!("  - Starting network with 1 LND node...");
+    let network_manager = TestNetwork::started(network_name, 1, 1).await?;
+    let start_height = block_height(&network_manager, network_name).await;
+
+    println!("  - Mining 5 and 7 blocks at the same time...");
+    let (first, second) = tokio::join!(
+        network_manager.mine_blocks(network_name, 5, None),
+        network_manager.mine_blocks(network_name, 7, None),
+    );
+    let end_height = block_height(&network_manager, network_name).await;
+
+    let (first, second) = (first?, second?);
+    assert_eq!(first.len(), 5, "First batch should mine 5 blocks");
+    assert_eq!(second.len(), 7, "Second batch should mine 7 blocks");
+    assert!(
+        first.iter().all(|hash| !second.contains(hash)),
+        "Batches should not share block hashes"
+    );
+    let (start_height, end_height) = (start_height?, end_height?);
+    assert_eq!(
+        end_height,
+        start_height + 12,
+        "Both batches should land on the chain"
+    );
+    println!("    ✓ Mined {start_height} -> {end_height} from two concurrent calls");
+
+    Ok(())
+}
+
+#[test]
+fn test_racing_saves_leave_one_complete_file() -> Result<()> {
+    const ROUNDS: usize = 50;
+
+    println!("\nTesting racing saves of one network file...");
+    let root = std::env::temp_dir().join(format!("polar-test-saves-{}", std::process::id()));
+    let network_name = "racing";
+
+    println!("  - Creating a network and two managers on the same data dir...");
+    let mut first = NetworkManager::with_data_dir(Some(root.clone()))?;
+    first.create_network_with_config(
+        network_name,
+        1,
+        1,
+        "race",
+        polar_nodes::LndNode::DEFAULT_IMAGE,
+        polar_nodes::BitcoinNode::DEFAULT_IMAGE,
+    )?;
+    let mut second = NetworkManager::with_data_dir(Some(root.clone()))?;
+
+    println!("  - Saving {ROUNDS} times from each manager at once...");
+    let (saved_first, saved_second) = std::thread::scope(|scope| {
+        let first = scope.spawn(|| save_repeatedly(&mut first, network_name, 0, ROUNDS));
+        let second = scope.spawn(|| save_repeatedly(&mut second, network_name, 1, ROUNDS));
+        (
+            first.join().expect("first writer panicked"),
+            second.join().expect("second writer panicked"),
+        )
+    });
+
+    let reloaded = NetworkManager::with_data_dir(Some(root.clone()));
+    let leftovers: Vec<_> = std::fs::read_dir(root.join("networks"))?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "tmp"))
+        .collect();
+    let _ = std::fs::remove_dir_all(&root);
+
+    saved_first?;
+    saved_second?;
+    let reloaded = reloaded?;
+    let env = reloaded
+        .get_network(network_name)
+        .and_then(|network| network.nodes.iter().find(|n| n.name == "lnd-1"))
+        .map(|node| node.env.clone())
+        .expect("saved network should load with its lnd-1 node");
+    assert!(
+        env == writer_env(0, ROUNDS - 1) || env == writer_env(1, ROUNDS - 1),
+        "The file should hold the last save of one writer, got {env:?}"
+    );
+    assert!(
+        leftovers.is_empty(),
+        "No temporary files should be left, got {leftovers:?}"
+    );
+    println!("    ✓ Racing saves left one complete network file");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_and_start_race_block_generator() -> Result<()> {
+    println!("\nTesting stop and start while the block generator mines...");
+
+    let network_name = "polar-test-concurrency";
+
+    println!("  - Starting network with 1 LND node...");
+    let mut network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    for round in 0..3 {
+        network_manager.start_block_generator(network_name, 1, 1)?;
+        let before = block_height(&network_manager, network_name).await?;
+        tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+
+        println!("  - Round {round}: stopping while mining...");
+        network_manager.stop_network(network_name).await?;
+        assert_eq!(
+            network_manager
+                .get_network(network_name)
+                .map(|network| network.status),
+            Some(NetworkStatus::Stopped),
+            "Network should be stopped after round {round}"
+        );
+
+        println!("  - Round {round}: starting again...");
+        network_manager.start().await?;
+        let after = block_height(&network_manager, network_name).await?;
+        assert!(
+            after > before,
+            "The generator should have mined before the stop ({before} -> {after})"
+        );
+    }
+    println!("    ✓ Stop and start never collided with mining");
+
+    Ok(())
+}
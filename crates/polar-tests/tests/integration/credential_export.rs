@@ -0,0 +1,112 @@
+//! Tests for exporting LND credentials for external wallets.
+
+use anyhow::Result;
+use polar_core::Error;
+use polar_nodes::{BitcoinNode, LndNode};
+use polar_tui::NetworkManager;
+
+#[tokio::test]
+async fn test_export_lnd_credentials() -> Result<()> {
+    println!("\nTesting LND credential export...");
+
+    let network_name = "polar-test-credentials";
+    let data_dir =
+        std::env::temp_dir().join(format!("polar-test-credentials-{}", std::process::id()));
+
+    println!("  - Creating network with 1 LND node...");
+    let mut network_manager = NetworkManager::with_data_dir(Some(data_dir.clone()))?;
+    network_manager.create_network_with_config(
+        network_name,
+        1,
+        1,
+        "creds",
+        LndNode::DEFAULT_IMAGE,
+        BitcoinNode::DEFAULT_IMAGE,
+    )?;
+
+    println!("  - Starting network...");
+    network_manager.start_network(network_name).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+    println!("  - Exporting the TLS cert and admin macaroon of lnd-1...");
+    let exported = network_manager
+        .export_node_credentials(network_name, "lnd-1")
+        .await;
+    let cert_content = exported
+        .as_ref()
+        .ok()
+        .map(|e| std::fs::read_to_string(&e.tls_cert));
+    let macaroon_content = exported
+        .as_ref()
+        .ok()
+        .map(|e| std::fs::read(&e.admin_macaroon));
+
+    println!("  - Building the lndconnect URL of lnd-1...");
+    let lndconnect = network_manager.lndconnect_url(network_name, "lnd-1").await;
+
+    println!("  - Exporting from a stopped node...");
+    network_manager.stop_node(network_name, "lnd-1").await?;
+    let stopped = network_manager
+        .export_node_credentials(network_name, "lnd-1")
+        .await;
+
+    println!("  - Exporting from the Bitcoin node...");
+    let from_bitcoin = network_manager
+        .export_node_credentials(network_name, "bitcoin-1")
+        .await;
+
+    println!("  - Cleaning up...");
+    network_manager.delete_network(network_name).await?;
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let exported = exported?;
+    let dir = data_dir
+        .join("credentials")
+        .join(network_name)
+        .join("lnd-1");
+    assert_eq!(exported.tls_cert, dir.join("tls.cert"));
+    assert_eq!(exported.admin_macaroon, dir.join("admin.macaroon"));
+    let cert_content = cert_content.expect("cert was exported")?;
+    assert!(
+        cert_content.starts_with("-----BEGIN CERTIFICATE-----"),
+        "Exported cert should be PEM: {cert_content}"
+    );
+    println!("    ✓ TLS cert exported to {}", exported.tls_cert.display());
+
+    let macaroon_content = macaroon_content.expect("macaroon was exported")?;
+    assert_eq!(
+        macaroon_content.first(),
+        Some(&0x02),
+        "Exported macaroon should be a binary V2 macaroon"
+    );
+    println!(
+        "    ✓ Admin macaroon exported ({} bytes)",
+        macaroon_content.len()
+    );
+
+    let lndconnect = lndconnect?;
+    assert!(
+        lndconnect.starts_with("lndconnect://127.0.0.1:"),
+        "URL should point at the Docker host: {lndconnect}"
+    );
+    assert!(
+        !lndconnect.contains(":10009?"),
+        "URL should use the published gRPC port, not the container one"
+    );
+    assert!(lndconnect.contains("?cert=") && lndconnect.contains("&macaroon=Ag"));
+    println!("    ✓ lndconnect URL built ({} chars)", lndconnect.len());
+
+    assert!(
+        matches!(stopped, Err(Error::NodeNotRunning(ref node)) if node == "lnd-1"),
+        "Stopped node should be reported as not running: {stopped:?}"
+    );
+    println!("    ✓ Exporting from a stopped node fails clearly");
+
+    assert!(
+        from_bitcoin.is_err(),
+        "Bitcoin nodes have no LND credentials"
+    );
+    println!("    ✓ Exporting from a Bitcoin node fails");
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Tests for isolating networks in separate data directories.
+
+use anyhow::Result;
+use polar_tui::NetworkManager;
+
+#[test]
+fn test_data_dirs_are_isolated() -> Result<()> {
+    println!("\nTesting network isolation between data directories...");
+
+    let root = std::env::temp_dir().join(format!("polar-test-data-dir-{}", std::process::id()));
+    let (dir_a, dir_b) = (root.join("a"), root.join("b"));
+
+    println!("  - Creating a network in the first data dir...");
+    let mut manager_a = NetworkManager::with_data_dir(Some(dir_a.clone()))?;
+    manager_a.create_network_with_config(
+        "isolated",
+        1,
+        1,
+        "iso",
+        polar_nodes::LndNode::DEFAULT_IMAGE,
+        polar_nodes::BitcoinNode::DEFAULT_IMAGE,
+    )?;
+
+    println!("  - Loading both data dirs...");
+    let reloaded_a = NetworkManager::with_data_dir(Some(dir_a.clone()))?;
+    let manager_b = NetworkManager::with_data_dir(Some(dir_b.clone()))?;
+
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(reloaded_a.data_dir(), dir_a);
+    assert_eq!(manager_b.data_dir(), dir_b);
+    assert!(reloaded_a.get_network("isolated").is_some());
+    assert!(
+        manager_b.get_network("isolated").is_none(),
+        "Networks must not leak between data dirs"
+    );
+    println!("    ✓ Data dirs are isolated");
+
+    Ok(())
+}
+
+#[test]
+fn test_clean_network_data() -> Result<()> {
+    println!("\nTesting data dir usage and cleanup...");
+
+    let root = std::env::temp_dir().join(format!("polar-test-clean-data-{}", std::process::id()));
+    let write = |path: std::path::PathBuf, content: &[u8]| -> Result<()> {
+        std::fs::create_dir_all(path.parent().expect("path has a parent"))?;
+        std::fs::write(path, content)?;
+        Ok(())
+    };
+
+    println!("  - Creating networks and stale artifacts...");
+    let mut manager = NetworkManager::with_data_dir(Some(root.clone()))?;
+    for name in ["alpha", "beta"] {
+        manager.create_network_with_config(
+            name,
+            1,
+            1,
+            name,
+            polar_nodes::LndNode::DEFAULT_IMAGE,
+            polar_nodes::BitcoinNode::DEFAULT_IMAGE,
+        )?;
+        manager.export_network(name, &manager.exports_dir().join(format!("{name}.json")))?;
+        write(
+            manager.credentials_dir(name).join("lnd-1").join("tls.cert"),
+            b"-----BEGIN CERTIFICATE-----",
+        )?;
+    }
+    write(
+        manager
+            .credentials_dir("gone")
+            .join("lnd-1")
+            .join("admin.macaroon"),
+        &[0x02, 0x01],
+    )?;
+    write(manager.exports_dir().join("gone.json"), b"{}")?;
+    write(manager.exports_dir().join("notes.txt"), b"keep me")?;
+
+    let usage = manager.data_usage()?;
+    let freed_alpha = manager.clean_network_data("alpha");
+    let freed_gone = manager.clean_network_data("gone");
+    let invalid = manager.clean_network_data("..");
+
+    let alpha_export_left = manager.exports_dir().join("alpha.json").exists();
+    let alpha_creds_left = manager.credentials_dir("alpha").exists();
+    let gone_creds_left = manager.credentials_dir("gone").exists();
+    let gone_export_left = manager.exports_dir().join("gone.json").exists();
+    let beta_kept = manager.exports_dir().join("beta.json").exists()
+        && manager
+            .credentials_dir("beta")
+            .join("lnd-1")
+            .join("tls.cert")
+            .exists();
+    let notes_kept = manager.exports_dir().join("notes.txt").exists();
+    let reloaded = NetworkManager::with_data_dir(Some(root.clone()))?;
+    let usage_after = reloaded.data_usage()?;
+
+    let _ = std::fs::remove_dir_all(&root);
+
+    let names: Vec<_> = usage.iter().map(|u| u.network.as_str()).collect();
+    assert_eq!(names, ["alpha", "beta", "gone"]);
+    let alpha = &usage[0];
+    assert!(alpha.loaded && alpha.network_file > 0);
+    assert!(alpha.exports > 0 && alpha.credentials > 0);
+    let gone = &usage[2];
+    assert!(!gone.loaded);
+    assert_eq!(
+        (gone.network_file, gone.exports, gone.credentials),
+        (0, 2, 2)
+    );
+    println!("    ✓ Usage reported for loaded and deleted networks");
+
+    assert_eq!(freed_alpha?, alpha.reclaimable());
+    assert_eq!(freed_gone?, 4);
+    assert!(!alpha_creds_left && !gone_creds_left && !gone_export_left);
+    println!("    ✓ Stale exports and credentials removed");
+    assert!(alpha_export_left, "A loaded network's export must be kept");
+    println!("    ✓ Export of a loaded network kept");
+
+    assert!(beta_kept, "Other networks' artifacts must be kept");
+    assert!(notes_kept, "Unrelated files must be kept");
+    assert!(
+        reloaded.get_network("alpha").is_some(),
+        "The network file must survive cleanup"
+    );
+    let names_after: Vec<_> = usage_after.iter().map(|u| u.network.as_str()).collect();
+    assert_eq!(names_after, ["alpha", "beta"]);
+    assert_eq!(usage_after[0].reclaimable(), 0);
+    println!("    ✓ Network files and other artifacts kept");
+
+    assert!(invalid.is_err(), "Path-like names must be rejected");
+
+    Ok(())
+}
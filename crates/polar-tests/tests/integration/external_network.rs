@@ -0,0 +1,81 @@
+//! Tests for attaching networks to an existing Docker network.
+
+use anyhow::Result;
+use polar_core::NodeInfo;
+use polar_docker::ContainerManager;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_network_on_external_docker_network() -> Result<()> {
+    println!("\nTesting a network attached to an external Docker network...");
+
+    let network_name = "polar-test-external-network";
+    let docker_network = "polar-test-external-net";
+    let docker = ContainerManager::new()?;
+
+    println!("  - Creating Docker network {docker_network}...");
+    let _ = docker.remove_network(docker_network).await;
+    docker.create_network(docker_network).await?;
+
+    println!("  - Creating network with 1 LND node...");
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+    network_manager.set_external_network(network_name, Some(docker_network.to_string()))?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    // lnd only answers once it reached bitcoind over the shared network
+    let info = network_manager.get_node_info(network_name, "lnd-1").await;
+    let container_id = network_manager
+        .get_network(network_name)
+        .and_then(|n| n.nodes.iter().find(|n| n.name == "lnd-1"))
+        .and_then(|n| n.container_id.clone())
+        .unwrap_or_default();
+    let inspect = docker.inspect_container(&container_id).await;
+    let kept = docker.network_exists(docker_network).await;
+    docker.remove_network(docker_network).await?;
+
+    let networks = inspect?
+        .network_settings
+        .and_then(|s| s.networks)
+        .unwrap_or_default();
+    assert!(
+        networks.contains_key(docker_network),
+        "lnd-1 should be attached to {docker_network}, got {:?}",
+        networks.keys().collect::<Vec<_>>()
+    );
+    match info? {
+        NodeInfo::Lnd(info) => assert!(
+            info.synced_to_chain,
+            "lnd-1 should sync over {docker_network}"
+        ),
+        NodeInfo::Bitcoin(_) => anyhow::bail!("lnd-1 is not an LND node"),
+    }
+    assert!(
+        kept?,
+        "Deleting the network should keep the external Docker network"
+    );
+    println!("    ✓ Nodes reached each other over {docker_network}, which outlived the network");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_external_network_fails_start() -> Result<()> {
+    println!("\nTesting a missing external Docker network...");
+
+    let network_name = "polar-test-missing-external-network";
+
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+    network_manager
+        .set_external_network(network_name, Some("polar-test-no-such-net".to_string()))?;
+
+    println!("  - Starting network...");
+    let started = network_manager.start_network(network_name).await;
+
+    let err = started.expect_err("start should fail without the Docker network");
+    assert!(err.to_string().contains("does not exist"), "{err}");
+    println!("    ✓ Start refused: {err}");
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Tests for starting nodes with extra daemon arguments.
+
+use anyhow::Result;
+use polar_core::{ExtraArgs, NodeInfo};
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_network_with_extra_args() -> Result<()> {
+    println!("\nTesting network with extra daemon arguments...");
+
+    let network_name = "polar-test-extra-args";
+
+    println!("  - Rejecting args that override regtest flags...");
+    let rejected = TestNetwork::create_with_extra_args(
+        network_name,
+        1,
+        1,
+        ExtraArgs {
+            lnd: vec!["--bitcoin.mainnet".to_string()],
+            bitcoin: Vec::new(),
+        },
+    )
+    .await;
+    assert!(rejected.is_err(), "Mainnet flag should be rejected");
+
+    println!("  - Creating network with wumbo channels and txindex...");
+    let mut network_manager = TestNetwork::create_with_extra_args(
+        network_name,
+        1,
+        1,
+        ExtraArgs {
+            lnd: vec!["--protocol.wumbo-channels".to_string()],
+            bitcoin: vec!["-txindex=1".to_string()],
+        },
+    )
+    .await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    let info = network_manager.get_node_info(network_name, "lnd-1").await?;
+    assert!(
+        matches!(info, NodeInfo::Lnd(_)),
+        "LND should start with extra args"
+    );
+    println!("    ✓ Nodes started with extra args");
+
+    Ok(())
+}
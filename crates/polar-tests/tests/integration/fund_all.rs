@@ -0,0 +1,53 @@
+//! Tests for funding every LND node of a network at once.
+
+use anyhow::Result;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Fund all nodes of a fresh network, which first has to mine its coins.
+async fn fund_every_node(network_manager: &NetworkManager, network_name: &str) -> Result<()> {
+    println!("  - Rejecting an amount that cannot be mined...");
+    let too_much = network_manager
+        .fund_all_nodes(network_name, 1_000_000.0)
+        .await;
+    assert!(too_much.is_err(), "Unminable amount should be rejected");
+
+    println!("  - Funding every node with 0.5 BTC...");
+    let txids = network_manager.fund_all_nodes(network_name, 0.5).await?;
+    assert_eq!(txids.len(), 3, "Every LND node should be funded");
+
+    let mut unique: Vec<&String> = txids.values().collect();
+    unique.dedup();
+    assert_eq!(
+        unique.len(),
+        1,
+        "Nodes should share one funding transaction"
+    );
+    println!("    ✓ Funded {} nodes in {}", txids.len(), unique[0]);
+
+    for node_name in txids.keys() {
+        let utxos = network_manager.list_utxos(network_name, node_name).await?;
+        assert!(
+            utxos
+                .iter()
+                .any(|u| u.amount_sat == 50_000_000 && u.confirmations > 0),
+            "{node_name} should have a confirmed 0.5 BTC UTXO, got {utxos:?}"
+        );
+    }
+    println!("    ✓ Every node has a confirmed 0.5 BTC UTXO");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fund_all_nodes() -> Result<()> {
+    println!("\nTesting funding every LND node at once...");
+
+    let network_name = "polar-test-fund-all";
+    let mut network_manager = TestNetwork::create(network_name, 3, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    fund_every_node(&network_manager, network_name).await
+}
@@ -80,7 +80,7 @@ async fn test_fund_lnd_wallet_basic() -> Result<()> {
 
     // Step 3: Get new address from LND wallet
     println!("  - Getting new address from LND wallet...");
-    let lnd_address = lnd_node.get_new_address(&manager).await?;
+    let lnd_address = lnd_node.get_new_address(&manager, None).await?;
     println!("    ✓ LND address: {}", lnd_address);
     assert!(!lnd_address.is_empty(), "LND address should not be empty");
     assert!(
@@ -203,7 +203,7 @@ async fn test_fund_lnd_wallet_insufficient_balance() -> Result<()> {
 
     // Try to send funds - this should fail
     println!("  - Attempting to send 1.0 BTC with zero balance...");
-    let lnd_address = lnd_node.get_new_address(&manager).await?;
+    let lnd_address = lnd_node.get_new_address(&manager, None).await?;
 
     let result = btc_node.send_to_address(&manager, &lnd_address, 1.0).await;
 
@@ -262,7 +262,7 @@ async fn test_fund_lnd_wallet_multiple_times() -> Result<()> {
     for i in 1..=num_fundings {
         println!("  - Funding transaction {} of {}...", i, num_fundings);
 
-        let lnd_address = lnd_node.get_new_address(&manager).await?;
+        let lnd_address = lnd_node.get_new_address(&manager, None).await?;
         let txid = btc_node
             .send_to_address(&manager, &lnd_address, amount_per_funding)
             .await?;
@@ -369,13 +369,13 @@ async fn test_fund_multiple_lnd_wallets() -> Result<()> {
 
     // Fund first LND node
     println!("  - Funding LND node 1...");
-    let addr1 = lnd_node_1.get_new_address(&manager).await?;
+    let addr1 = lnd_node_1.get_new_address(&manager, None).await?;
     let txid1 = btc_node.send_to_address(&manager, &addr1, 1.0).await?;
     println!("    ✓ TXID: {}", txid1);
 
     // Fund second LND node
     println!("  - Funding LND node 2...");
-    let addr2 = lnd_node_2.get_new_address(&manager).await?;
+    let addr2 = lnd_node_2.get_new_address(&manager, None).await?;
     let txid2 = btc_node.send_to_address(&manager, &addr2, 2.0).await?;
     println!("    ✓ TXID: {}", txid2);
 
@@ -462,7 +462,7 @@ async fn test_lnd_address_generation_uniqueness() -> Result<()> {
     println!("  - Generating 5 addresses...");
     let mut addresses = Vec::new();
     for i in 1..=5 {
-        let addr = lnd_node.get_new_address(&manager).await?;
+        let addr = lnd_node.get_new_address(&manager, None).await?;
         println!("    Address {}: {}", i, addr);
         addresses.push(addr);
     }
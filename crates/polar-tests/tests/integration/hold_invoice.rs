@@ -0,0 +1,121 @@
+//! Tests for hold invoices and in-flight HTLC handling.
+
+use anyhow::Result;
+use polar_core::{Error, Preimage};
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Poll an invoice until it reaches the expected state.
+async fn wait_for_state(
+    manager: &NetworkManager,
+    network: &str,
+    node: &str,
+    payment_hash: &str,
+    expected: &str,
+) -> Result<()> {
+    for _ in 0..20 {
+        if manager.invoice_state(network, node, payment_hash).await? == expected {
+            return Ok(());
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+    anyhow::bail!("invoice {payment_hash} never reached state {expected}")
+}
+
+/// Pay a hold invoice while resolving it from a concurrent task.
+async fn pay_and_resolve(
+    manager: &NetworkManager,
+    network: &str,
+    amount: u64,
+    settle: bool,
+) -> Result<String> {
+    let pair = Preimage::generate();
+    let payment_request = manager
+        .add_hold_invoice(network, "lnd-2", &pair.payment_hash, amount)
+        .await?;
+
+    let resolve = async {
+        // The payment is in flight once the receiver has accepted the HTLC
+        wait_for_state(manager, network, "lnd-2", &pair.payment_hash, "ACCEPTED").await?;
+        if settle {
+            manager
+                .settle_invoice(network, "lnd-2", &pair.preimage)
+                .await?;
+        } else {
+            manager
+                .cancel_invoice(network, "lnd-2", &pair.payment_hash)
+                .await?;
+        }
+        anyhow::Ok(())
+    };
+
+    // The payment result is not asserted: a cancelled HTLC is a failed payment
+    let (_payment, resolved) = tokio::join!(
+        manager.pay_invoice(network, "lnd-1", &payment_request, None, None),
+        resolve
+    );
+    resolved?;
+
+    Ok(manager
+        .invoice_state(network, "lnd-2", &pair.payment_hash)
+        .await?)
+}
+
+#[tokio::test]
+async fn test_hold_invoice_settle_and_cancel() -> Result<()> {
+    println!("\nTesting hold invoices...");
+
+    let network_name = "polar-test-hold-invoice";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Mining 101 blocks and funding lnd-1...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    println!("  - Opening channel lnd-1 -> lnd-2...");
+    network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", 1_000_000, None)
+        .await?;
+    network_manager.mine_blocks(network_name, 6, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+
+    println!("  - Paying and settling a hold invoice...");
+    let settled = pay_and_resolve(&network_manager, network_name, 10_000, true).await;
+
+    println!("  - Paying and cancelling a hold invoice...");
+    let cancelled = pay_and_resolve(&network_manager, network_name, 10_000, false).await;
+
+    println!("  - Paying a hold invoice nobody resolves, with a 5s timeout...");
+    let stuck = Preimage::generate();
+    let stuck_request = network_manager
+        .add_hold_invoice(network_name, "lnd-2", &stuck.payment_hash, 10_000)
+        .await?;
+    let started = std::time::Instant::now();
+    let timed_out = network_manager
+        .pay_invoice(network_name, "lnd-1", &stuck_request, None, Some(5))
+        .await;
+    let elapsed = started.elapsed();
+    network_manager
+        .cancel_invoice(network_name, "lnd-2", &stuck.payment_hash)
+        .await?;
+
+    assert_eq!(settled?, "SETTLED");
+    assert_eq!(cancelled?, "CANCELED");
+    println!("    ✓ Hold invoices settled and cancelled as expected");
+
+    assert!(
+        matches!(timed_out, Err(Error::PaymentTimeout(5))),
+        "Stuck payment should time out: {timed_out:?}"
+    );
+    assert!(
+        elapsed < std::time::Duration::from_secs(30),
+        "Timeout should fire promptly, took {elapsed:?}"
+    );
+    println!("    ✓ Stuck payment timed out after {elapsed:?}");
+
+    Ok(())
+}
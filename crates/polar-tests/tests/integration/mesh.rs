@@ -0,0 +1,52 @@
+//! Tests for opening a full channel mesh between LND nodes.
+
+use anyhow::Result;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_open_mesh_skips_existing_channels() -> Result<()> {
+    println!("\nTesting channel mesh across 3 funded LND nodes...");
+
+    let network_name = "polar-test-mesh";
+
+    println!("  - Starting network with 3 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 3, 1).await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding all LND nodes...");
+    for node in ["lnd-1", "lnd-2", "lnd-3"] {
+        network_manager
+            .fund_lnd_wallet(network_name, node, 0.1)
+            .await?;
+    }
+
+    println!("  - Opening mesh...");
+    let first = network_manager.open_mesh(network_name, 500_000).await;
+
+    println!("  - Mining 6 blocks to confirm channels...");
+    network_manager.mine_blocks(network_name, 6, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+
+    println!("  - Opening mesh again...");
+    let second = network_manager.open_mesh(network_name, 500_000).await;
+
+    let first = first?;
+    assert_eq!(first.opened.len(), 3, "Three pairs should get channels");
+    assert!(
+        first.failed.is_empty(),
+        "No channel should fail: {:?}",
+        first.failed
+    );
+
+    let second = second?;
+    assert!(
+        second.opened.is_empty(),
+        "Existing channels should not be reopened"
+    );
+    assert_eq!(second.skipped.len(), 3, "All three pairs should be skipped");
+    println!("    ✓ Mesh opened and existing channels skipped");
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Tests for signing and verifying messages with LND node keys.
+
+use anyhow::{Result, bail};
+use polar_core::NodeInfo;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Sign with lnd-1 and check the signer lnd-2 recovers.
+async fn sign_and_verify(network_manager: &NetworkManager, network_name: &str) -> Result<()> {
+    let NodeInfo::Lnd(info) = network_manager.get_node_info(network_name, "lnd-1").await? else {
+        bail!("lnd-1 should be an LND node");
+    };
+
+    println!("  - Signing a message with lnd-1...");
+    let message = "polar says hello";
+    let signature = network_manager
+        .sign_message(network_name, "lnd-1", message)
+        .await?;
+    assert!(!signature.is_empty(), "Signature should not be empty");
+    println!("    ✓ Signature: {signature}");
+
+    println!("  - Verifying the signature on lnd-2...");
+    let verification = network_manager
+        .verify_message(network_name, "lnd-2", message, &signature)
+        .await?;
+    assert_eq!(
+        verification.pubkey, info.identity_pubkey,
+        "Recovered key should be lnd-1's identity key"
+    );
+    println!("    ✓ Recovered lnd-1's public key");
+
+    println!("  - Verifying a different message...");
+    let tampered = network_manager
+        .verify_message(network_name, "lnd-2", "polar says goodbye", &signature)
+        .await?;
+    assert_ne!(
+        tampered.pubkey, info.identity_pubkey,
+        "A changed message should not recover lnd-1's key"
+    );
+    println!("    ✓ Changed message recovers a different key");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_and_verify_message() -> Result<()> {
+    println!("\nTesting message signing...");
+
+    let network_name = "polar-test-sign-message";
+    let mut network_manager = TestNetwork::create(network_name, 2, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    sign_and_verify(&network_manager, network_name).await
+}
@@ -0,0 +1,96 @@
+//! Tests for multi-part payments split across parallel channels.
+
+use anyhow::Result;
+use polar_nodes::PaymentOptions;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_mpp_splits_payment_across_small_channels() -> Result<()> {
+    println!("\nTesting multi-part payments...");
+
+    let network_name = "polar-test-mpp";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Mining 101 blocks and funding lnd-1...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    // Two parallel channels, each too small for the payment on its own
+    println!("  - Opening two 100,000 sat channels lnd-1 -> lnd-2...");
+    for _ in 0..2 {
+        network_manager
+            .open_channel(network_name, "lnd-1", "lnd-2", 100_000, None)
+            .await?;
+    }
+    network_manager.mine_blocks(network_name, 6, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+    let amount = 150_000;
+
+    println!("  - Paying {amount} sats with MPP disabled...");
+    let single_part = network_manager
+        .send_payment_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            amount,
+            Some("single part"),
+            PaymentOptions::default().with_mpp(false, None),
+        )
+        .await;
+
+    println!("  - Paying {amount} sats with MPP enabled...");
+    let multi_part = network_manager
+        .send_payment_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            amount,
+            Some("multi part"),
+            PaymentOptions::default().with_mpp(true, Some(4)),
+        )
+        .await;
+
+    println!("  - Paying {amount} sats with AMP...");
+    let atomic_multi_part = network_manager
+        .send_payment_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            amount,
+            Some("atomic multi part"),
+            PaymentOptions::default()
+                .with_mpp(true, Some(4))
+                .with_amp(true),
+        )
+        .await;
+
+    assert!(
+        single_part.is_err(),
+        "A single part cannot fit in either channel"
+    );
+
+    let history = network_manager
+        .payment_history(network_name, "lnd-1")
+        .await?;
+    for (label, payment_hash) in [("MPP", multi_part?), ("AMP", atomic_multi_part?)] {
+        let payment = history
+            .payments
+            .iter()
+            .find(|p| p.payment_hash == payment_hash)
+            .expect("Payment should be listed");
+        assert_eq!(payment.status, "SUCCEEDED");
+        assert!(
+            payment.parts >= 2,
+            "{label} payment should be split, got {} parts",
+            payment.parts
+        );
+        println!("    ✓ {label} payment settled in {} parts", payment.parts);
+    }
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 //! Integration tests for network operations.
 
-use polar_core::{NetworkStatus};
+use anyhow::Result;
+use polar_core::{NetworkStatus, NodeKind, ResourceLimits};
 use polar_docker::ContainerManager;
 use polar_nodes::{BitcoinNode, LndNode};
-use anyhow::Result;
+use polar_tests::TestNetwork;
 
 #[tokio::test]
 async fn test_docker_connectivity() -> Result<()> {
@@ -26,8 +27,14 @@ async fn test_create_bitcoin_container() -> Result<()> {
     println!("  - Creating Bitcoin Core container...");
     btc_node.start(&manager).await?;
 
-    assert!(btc_node.node.container_id.is_some(), "Container ID should be set");
-    println!("  ✓ Bitcoin Core container created: {:?}", btc_node.node.container_id);
+    assert!(
+        btc_node.node.container_id.is_some(),
+        "Container ID should be set"
+    );
+    println!(
+        "  ✓ Bitcoin Core container created: {:?}",
+        btc_node.node.container_id
+    );
 
     // Give it a moment to start
     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
@@ -35,12 +42,56 @@ async fn test_create_bitcoin_container() -> Result<()> {
     println!("  - Stopping Bitcoin Core container...");
     btc_node.stop(&manager).await?;
 
-    assert!(btc_node.node.container_id.is_none(), "Container ID should be cleared");
+    assert!(
+        btc_node.node.container_id.is_none(),
+        "Container ID should be cleared"
+    );
     println!("  ✓ Bitcoin Core container stopped and removed");
 
     Ok(())
 }
 
+#[tokio::test]
+async fn test_container_resource_limits() -> Result<()> {
+    println!("\nTesting container resource limits...");
+
+    let manager = ContainerManager::new()?;
+    let mut btc_node = BitcoinNode::new("test-bitcoin-limits");
+    let limits = ResourceLimits::new(0.5, 512);
+
+    println!("  - Creating Bitcoin Core container with limits...");
+    btc_node
+        .start_with_ports(&manager, None, None, limits)
+        .await?;
+
+    let container_id = btc_node
+        .node
+        .container_id
+        .clone()
+        .expect("Container ID should be set");
+    let inspect = manager.inspect_container(&container_id).await;
+
+    println!("  - Stopping Bitcoin Core container...");
+    btc_node.stop(&manager).await?;
+
+    let host_config = inspect?
+        .host_config
+        .expect("Container should have a host config");
+    assert_eq!(
+        host_config.nano_cpus,
+        Some(500_000_000),
+        "CPU limit should be applied"
+    );
+    assert_eq!(
+        host_config.memory,
+        Some(512 * 1024 * 1024),
+        "Memory limit should be applied"
+    );
+    println!("  ✓ CPU and memory limits applied");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_lnd_container() -> Result<()> {
     println!("\nTesting LND container with Bitcoin backend...");
@@ -62,8 +113,14 @@ async fn test_create_lnd_container() -> Result<()> {
     println!("  - Starting LND node...");
     lnd_node.start(&manager).await?;
 
-    assert!(lnd_node.node.container_id.is_some(), "LND container ID should be set");
-    println!("  ✓ LND container created: {:?}", lnd_node.node.container_id);
+    assert!(
+        lnd_node.node.container_id.is_some(),
+        "LND container ID should be set"
+    );
+    println!(
+        "  ✓ LND container created: {:?}",
+        lnd_node.node.container_id
+    );
 
     // Wait a bit for LND to start
     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
@@ -121,7 +178,8 @@ async fn test_network_creation_and_lifecycle() -> Result<()> {
     tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
 
     // Get Bitcoin node ID for LND nodes
-    let btc_id = network.nodes
+    let btc_id = network
+        .nodes
         .iter()
         .find(|n| n.kind == NodeKind::BitcoinCore)
         .map(|n| n.id.to_string())
@@ -144,7 +202,11 @@ async fn test_network_creation_and_lifecycle() -> Result<()> {
 
     // Verify all nodes have container IDs
     for node in &network.nodes {
-        assert!(node.container_id.is_some(), "Node '{}' should have container ID", node.name);
+        assert!(
+            node.container_id.is_some(),
+            "Node '{}' should have container ID",
+            node.name
+        );
     }
 
     // Wait a bit to let things stabilize
@@ -184,7 +246,11 @@ async fn test_network_creation_and_lifecycle() -> Result<()> {
 
     // Verify all containers are removed
     for node in &network.nodes {
-        assert!(node.container_id.is_none(), "Node '{}' container should be removed", node.name);
+        assert!(
+            node.container_id.is_none(),
+            "Node '{}' container should be removed",
+            node.name
+        );
     }
 
     Ok(())
@@ -208,3 +274,146 @@ async fn test_docker_network_creation() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_stop_all_networks() -> Result<()> {
+    println!("\nTesting stopping every running network...");
+
+    let names = ["polar-test-stop-all-1", "polar-test-stop-all-2"];
+
+    // The second manager loads the first network from disk, so it sees both
+    println!("  - Starting '{}'...", names[0]);
+    let _first = TestNetwork::started(names[0], 1, 1).await?;
+    println!("  - Starting '{}'...", names[1]);
+    let mut network_manager = TestNetwork::started(names[1], 1, 1).await?;
+
+    let running_before = network_manager
+        .running_networks()
+        .iter()
+        .filter(|n| names.contains(&n.as_str()))
+        .count();
+
+    println!("  - Stopping all networks...");
+    let stopped = network_manager.stop_all_networks().await;
+    let running_after = network_manager.running_networks();
+
+    assert_eq!(running_before, 2, "Both networks should be running");
+    assert!(stopped? >= 2, "Both networks should be stopped");
+    assert!(
+        running_after.is_empty(),
+        "No network should be left running: {running_after:?}"
+    );
+    println!("    ✓ All running networks stopped");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_network_with_missing_container() -> Result<()> {
+    println!("\nTesting stopping a network with a vanished container...");
+
+    let network_name = "polar-test-stop-partial";
+    let mut network_manager = TestNetwork::create(network_name, 2, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    println!("  - Removing lnd-1's container behind the manager's back...");
+    let lnd1_id = network_manager
+        .get_network(network_name)
+        .and_then(|n| n.nodes.iter().find(|n| n.name == "lnd-1"))
+        .and_then(|n| n.container_id.clone())
+        .expect("lnd-1 should have a container");
+    ContainerManager::new()?.remove_container(&lnd1_id).await?;
+
+    println!("  - Stopping the network...");
+    network_manager.stop_network(network_name).await?;
+
+    let network = network_manager
+        .get_network(network_name)
+        .expect("Network should still exist");
+    assert_eq!(network.status, NetworkStatus::Stopped);
+    for node in &network.nodes {
+        assert!(
+            node.container_id.is_none(),
+            "{} should have no container ID",
+            node.name
+        );
+    }
+    println!("    ✓ Vanished container treated as already stopped");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_attach_external_container() -> Result<()> {
+    println!("\nTesting adopting a manually started container...");
+
+    let manager = ContainerManager::new()?;
+
+    println!("  - Starting bitcoind outside of any network...");
+    let mut external = BitcoinNode::new("external-btc");
+    external.start(&manager).await?;
+    let external_id = external.node.container_id.clone().unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+
+    let network_name = "polar-test-attach";
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+    network_manager.start().await?;
+    let network = network_manager
+        .get_network(network_name)
+        .expect("Network should exist");
+    let docker_network = network.docker_network_name();
+    let replaced_id = network
+        .nodes
+        .iter()
+        .find(|n| n.name == "bitcoin-1")
+        .and_then(|n| n.container_id.clone())
+        .expect("bitcoin-1 should have a container");
+
+    println!("  - Rejecting the container as an LND node...");
+    let wrong_kind = network_manager
+        .attach_external_container(network_name, "manual-lnd", &external_id, NodeKind::Lnd)
+        .await;
+
+    println!("  - Attaching it as bitcoin-1...");
+    let attached = network_manager
+        .attach_external_container(
+            network_name,
+            "bitcoin-1",
+            &external_id,
+            NodeKind::BitcoinCore,
+        )
+        .await;
+    let mined = network_manager.mine_blocks(network_name, 1, None).await;
+    let replaced_gone = manager.inspect_container(&replaced_id).await.is_err();
+    let connected = manager
+        .inspect_container(&external_id)
+        .await?
+        .network_settings
+        .and_then(|s| s.networks)
+        .is_some_and(|networks| networks.contains_key(&docker_network));
+
+    println!("  - Deleting the network...");
+    network_manager.delete_network(network_name).await?;
+    let still_running = manager.is_running(&external_id).await;
+
+    println!("  - Cleaning up...");
+    external.stop(&manager).await?;
+
+    assert!(wrong_kind.is_err(), "A bitcoind image is not an LND node");
+    println!("    ✓ Kind mismatch rejected");
+    attached?;
+    assert_eq!(mined?.len(), 1, "Mining should use the adopted container");
+    println!("    ✓ Adopted container mined a block");
+    assert!(replaced_gone, "bitcoin-1's own container should be removed");
+    assert!(connected, "Adopted container should join the network");
+    println!("    ✓ Replaced container removed, adopted one connected");
+    assert!(
+        still_running?,
+        "Deleting the network must not stop the adopted container"
+    );
+    println!("    ✓ Adopted container left running");
+
+    Ok(())
+}
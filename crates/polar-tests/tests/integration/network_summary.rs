@@ -0,0 +1,59 @@
+//! Tests for the network liquidity summary.
+
+use anyhow::Result;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_network_summary() -> Result<()> {
+    println!("\nTesting network liquidity summary...");
+
+    let network_name = "polar-test-summary";
+
+    println!("  - Creating network with 2 LND nodes...");
+    let mut network_manager = TestNetwork::create(network_name, 2, 1).await?;
+
+    println!("  - Summarizing the stopped network...");
+    let stopped = network_manager.network_summary(network_name).await;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    println!("  - Mining 101 blocks and funding lnd-1...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    println!("  - Opening channel lnd-1 -> lnd-2...");
+    network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", 1_000_000, None)
+        .await?;
+    network_manager.mine_blocks(network_name, 6, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+    println!("  - Summarizing the running network...");
+    let running = network_manager.network_summary(network_name).await;
+
+    assert!(
+        stopped?.is_none(),
+        "A stopped network should have no summary"
+    );
+    println!("    ✓ Stopped network reported as not running");
+
+    let summary = running?.expect("Running network should have a summary");
+    assert_eq!(summary.nodes, 2, "Both LND nodes should be counted");
+    assert_eq!(summary.unreachable, 0);
+    assert_eq!(summary.active_channels, 1, "The channel should count once");
+    assert_eq!(summary.capacity, 1_000_000);
+    assert!(summary.onchain > 0, "lnd-1 should keep its change on-chain");
+    assert!(
+        summary.local > 0 && summary.remote > 0,
+        "Each side should see the other's balance as remote"
+    );
+    println!(
+        "    ✓ {} sats on-chain, {} sats capacity, {} local / {} remote",
+        summary.onchain, summary.capacity, summary.local, summary.remote
+    );
+
+    Ok(())
+}
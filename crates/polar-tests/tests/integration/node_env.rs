@@ -0,0 +1,51 @@
+//! Tests for setting environment variables on node containers.
+
+use anyhow::Result;
+use polar_docker::ContainerManager;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_node_env_reaches_container() -> Result<()> {
+    println!("\nTesting node environment variables...");
+
+    let network_name = "polar-test-node-env";
+
+    println!("  - Creating network with 1 LND node...");
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+
+    println!("  - Rejecting an invalid variable name...");
+    let rejected = network_manager.set_node_env(
+        network_name,
+        "lnd-1",
+        vec![("A=B".to_string(), "1".to_string())],
+    );
+
+    println!("  - Setting GRPC_VERBOSITY on lnd-1...");
+    network_manager.set_node_env(
+        network_name,
+        "lnd-1",
+        vec![("GRPC_VERBOSITY".to_string(), "debug".to_string())],
+    )?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    let container_id = network_manager
+        .get_network(network_name)
+        .and_then(|n| n.nodes.iter().find(|n| n.name == "lnd-1"))
+        .and_then(|n| n.container_id.clone())
+        .unwrap_or_default();
+    let inspect = ContainerManager::new()?
+        .inspect_container(&container_id)
+        .await;
+
+    assert!(rejected.is_err(), "Names containing '=' should be rejected");
+    let env = inspect?.config.and_then(|c| c.env).unwrap_or_default();
+    assert!(
+        env.iter().any(|var| var == "GRPC_VERBOSITY=debug"),
+        "Container env should contain GRPC_VERBOSITY=debug, got {env:?}"
+    );
+    println!("    ✓ GRPC_VERBOSITY=debug set in the lnd-1 container");
+
+    Ok(())
+}
@@ -3,6 +3,7 @@
 use anyhow::Result;
 use polar_docker::ContainerManager;
 use polar_nodes::{BitcoinNode, LndNode};
+use polar_tests::TestNetwork;
 
 #[tokio::test]
 async fn test_bitcoin_exec_command() -> Result<()> {
@@ -26,17 +27,9 @@ async fn test_bitcoin_exec_command() -> Result<()> {
 
     // Try to execute a command
     println!("  - Executing bitcoin-cli getblockchaininfo...");
+    let cmd = btc_node.rpc.bitcoin_cli_command(&["getblockchaininfo"]);
     match manager
-        .exec_command(
-            container_id,
-            vec![
-                "bitcoin-cli",
-                "-regtest",
-                "-rpcuser=polaruser",
-                "-rpcpassword=polarpass",
-                "getblockchaininfo",
-            ],
-        )
+        .exec_command(container_id, cmd.iter().map(String::as_str).collect())
         .await
     {
         Ok(output) => {
@@ -71,6 +64,40 @@ async fn test_bitcoin_exec_command() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_exec_command_non_zero_exit() -> Result<()> {
+    println!("Testing exec commands that exit non-zero...");
+
+    let manager = ContainerManager::new()?;
+    let mut btc_node = BitcoinNode::new("test-exec-exit-code");
+
+    println!("  - Starting Bitcoin Core container...");
+    btc_node.start(&manager).await?;
+    let container_id = btc_node.node.container_id.clone().unwrap_or_default();
+
+    println!("  - Running a command that fails with exit code 3...");
+    let cmd = vec!["sh", "-c", "echo boom >&2; exit 3"];
+    let checked = manager.exec_command(&container_id, cmd.clone()).await;
+    let output = manager
+        .exec_command_output(&container_id, cmd.clone())
+        .await;
+    let unchecked = manager.exec_command_unchecked(&container_id, cmd).await;
+
+    println!("  - Stopping Bitcoin Core container...");
+    btc_node.stop(&manager).await?;
+
+    let err = checked.expect_err("non-zero exit should fail").to_string();
+    assert!(err.contains("exited with code 3"), "{err}");
+    assert!(err.contains("boom"), "{err}");
+    let output = output?;
+    assert_eq!(output.exit_code, Some(3));
+    assert_eq!(output.stderr.trim(), "boom");
+    assert_eq!(unchecked?.trim(), "boom");
+    println!("    ✓ Exit code 3 reported with stderr: {err}");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_lnd_exec_command() -> Result<()> {
     println!("Testing LND CLI exec command...");
@@ -183,3 +210,94 @@ async fn test_lnd_exec_command() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_get_all_node_info_tolerates_stopped_node() -> Result<()> {
+    println!("\nTesting concurrent node info fetching...");
+
+    let network_name = "polar-test-all-node-info";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    // Kill one node behind the manager's back so its fetch fails
+    println!("  - Stopping lnd-2 container directly...");
+    let lnd2_container = network_manager
+        .get_network(network_name)
+        .and_then(|n| n.nodes.iter().find(|n| n.name == "lnd-2"))
+        .and_then(|n| n.container_id.clone());
+    let manager = ContainerManager::new()?;
+    if let Some(container_id) = &lnd2_container {
+        manager.stop_container(container_id).await?;
+    }
+
+    println!("  - Fetching info for all nodes...");
+    let results = network_manager.get_all_node_info(network_name).await;
+
+    let results = results?;
+    assert_eq!(
+        results.len(),
+        3,
+        "All three running nodes should be reported"
+    );
+    assert!(
+        results["bitcoin-1"].is_ok(),
+        "bitcoin-1 info should succeed"
+    );
+    assert!(results["lnd-1"].is_ok(), "lnd-1 info should succeed");
+    assert!(results["lnd-2"].is_err(), "lnd-2 info should fail");
+    println!("    ✓ Partial results returned with a per-node error");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_node_info_cache_and_force_refresh() -> Result<()> {
+    println!("\nTesting node info caching...");
+
+    let network_name = "polar-test-info-cache";
+
+    println!("  - Starting network with 1 LND node...");
+    let network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    let blocks = |info: polar_core::NodeInfo| match info {
+        polar_core::NodeInfo::Bitcoin(info) => info.blocks,
+        polar_core::NodeInfo::Lnd(_) => 0,
+    };
+
+    println!("  - Fetching info, mining a block and fetching again...");
+    let result = async {
+        let before = blocks(
+            network_manager
+                .get_node_info(network_name, "bitcoin-1")
+                .await?,
+        );
+        network_manager.mine_blocks(network_name, 1, None).await?;
+        let cached = blocks(
+            network_manager
+                .get_node_info(network_name, "bitcoin-1")
+                .await?,
+        );
+        let refreshed = blocks(
+            network_manager
+                .get_node_info_with_options(network_name, "bitcoin-1", true)
+                .await?,
+        );
+        anyhow::Ok((before, cached, refreshed))
+    }
+    .await;
+
+    let (before, cached, refreshed) = result?;
+    assert_eq!(
+        cached, before,
+        "Info within the TTL should come from the cache"
+    );
+    assert_eq!(
+        refreshed,
+        before + 1,
+        "A forced refresh should see the new block"
+    );
+    println!("    ✓ Cached info reused and forced refresh bypassed it");
+
+    Ok(())
+}
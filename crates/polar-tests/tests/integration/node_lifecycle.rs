@@ -0,0 +1,71 @@
+//! Tests for stopping and starting a single node in a running network.
+
+use anyhow::Result;
+use polar_core::NodeInfo;
+use polar_tests::TestNetwork;
+
+/// Extract the reported REST endpoint of an LND node.
+fn rest_host(info: &NodeInfo) -> Option<String> {
+    match info {
+        NodeInfo::Lnd(info) => Some(info.rest_host.clone()),
+        NodeInfo::Bitcoin(_) => None,
+    }
+}
+
+#[tokio::test]
+async fn test_stop_and_start_single_node() -> Result<()> {
+    println!("\nTesting stopping and starting a single node...");
+
+    let network_name = "polar-test-node-lifecycle";
+    let mut network_manager = TestNetwork::create(network_name, 2, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+    let before = network_manager.get_node_info(network_name, "lnd-1").await?;
+
+    println!("  - Refusing to stop the Bitcoin node under running LND nodes...");
+    let btc_stop = network_manager.stop_node(network_name, "bitcoin-1").await;
+
+    println!("  - Stopping lnd-1 only...");
+    network_manager.stop_node(network_name, "lnd-1").await?;
+    let stopped = network_manager.get_node_info(network_name, "lnd-1").await;
+    let other = network_manager.get_node_info(network_name, "lnd-2").await;
+
+    println!("  - Starting lnd-1 again...");
+    network_manager.start_node(network_name, "lnd-1").await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let after = network_manager.get_node_info(network_name, "lnd-1").await;
+
+    assert!(btc_stop.is_err(), "Bitcoin node should stay up for LND");
+    assert!(stopped.is_err(), "lnd-1 should be stopped");
+    assert!(other.is_ok(), "lnd-2 should keep running");
+    assert_eq!(
+        rest_host(&after?),
+        rest_host(&before),
+        "lnd-1 should come back on its allocated ports"
+    );
+    println!("    ✓ Single node restarted on the same ports");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_lnd_backend() -> Result<()> {
+    println!("\nTesting LND block notifications from bitcoind...");
+
+    let network_name = "polar-test-lnd-backend";
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    println!("  - Mining a block and waiting for lnd-1 to follow...");
+    let verified = network_manager
+        .verify_lnd_backend(network_name, "lnd-1")
+        .await;
+
+    verified?;
+    println!("    ✓ lnd-1 received the block notification");
+
+    Ok(())
+}
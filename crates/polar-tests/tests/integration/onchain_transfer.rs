@@ -0,0 +1,122 @@
+//! Tests for moving on-chain funds out of LND wallets.
+
+use anyhow::Result;
+use polar_core::{Error, NodeInfo};
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+use polar_tui::network_manager::FundOptions;
+
+/// Get the on-chain wallet balance of an LND node in satoshis.
+async fn wallet_balance(manager: &NetworkManager, network: &str, node: &str) -> Result<i64> {
+    match manager.get_node_info(network, node).await? {
+        NodeInfo::Lnd(info) => Ok(info.wallet_confirmed),
+        NodeInfo::Bitcoin(_) => anyhow::bail!("{node} is not an LND node"),
+    }
+}
+
+#[tokio::test]
+async fn test_send_onchain_and_sweep() -> Result<()> {
+    println!("\nTesting on-chain sends between wallets...");
+
+    let network_name = "polar-test-onchain";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Mining 101 blocks and funding lnd-1...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    println!("  - Sending 100,000 sats from lnd-1 to lnd-2...");
+    let send = network_manager
+        .send_onchain(network_name, "lnd-1", "lnd-2", Some(100_000))
+        .await;
+    network_manager.mine_blocks(network_name, 1, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let lnd2_balance = wallet_balance(&network_manager, network_name, "lnd-2").await;
+
+    println!("  - Sending more than lnd-2 holds...");
+    let overdraft = network_manager
+        .send_onchain(network_name, "lnd-2", "lnd-1", Some(10_000_000))
+        .await;
+
+    println!("  - Sweeping lnd-1 back to bitcoin-1...");
+    let sweep = network_manager
+        .send_onchain(network_name, "lnd-1", "bitcoin-1", None)
+        .await;
+    network_manager.mine_blocks(network_name, 1, None).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let lnd1_balance = wallet_balance(&network_manager, network_name, "lnd-1").await;
+
+    println!("    ✓ Send TXID: {}", send?);
+    assert_eq!(lnd2_balance?, 100_000, "lnd-2 should hold the sent amount");
+    let overdraft = overdraft.expect_err("Overdraft should fail");
+    assert!(
+        matches!(overdraft, Error::InsufficientFunds { .. }),
+        "Unexpected error: {overdraft}"
+    );
+    println!("    ✓ Sweep TXID: {}", sweep?);
+    assert_eq!(lnd1_balance?, 0, "lnd-1 should be empty after sweeping");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bump_transaction() -> Result<()> {
+    println!("\nTesting fee bumps of unconfirmed transactions...");
+
+    let network_name = "polar-test-bumpfee";
+
+    println!("  - Starting network with 1 LND node...");
+    let network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1 without mining...");
+    let funding = network_manager
+        .fund_lnd_wallet_with_options(
+            network_name,
+            "lnd-1",
+            0.5,
+            FundOptions::default()
+                .with_auto_mine(false)
+                .with_fee_rate(1),
+        )
+        .await;
+
+    println!("  - Bumping the funding transaction on bitcoin-1...");
+    let bumped = match &funding {
+        Ok(txid) => Some(
+            network_manager
+                .bump_transaction(network_name, "bitcoin-1", txid, 20)
+                .await,
+        ),
+        Err(_) => None,
+    };
+
+    println!("  - Bumping with a txid instead of an outpoint on lnd-1...");
+    let bad_target = network_manager
+        .bump_transaction(network_name, "lnd-1", "ab12", 20)
+        .await;
+
+    let funding = funding?;
+    let replacement = bumped.expect("bump should have run")?;
+    let replacement = replacement.expect("Bitcoin Core should return a replacement txid");
+    assert_ne!(
+        replacement, funding,
+        "Replacement should be a new transaction"
+    );
+    println!("    ✓ Replaced {funding} with {replacement}");
+
+    let bad_target = bad_target.expect_err("LND needs an outpoint");
+    assert!(
+        matches!(bad_target, Error::Config(_)),
+        "Unexpected error: {bad_target}"
+    );
+    println!("    ✓ LND rejects a bare txid");
+
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 use anyhow::Result;
 use polar_docker::ContainerManager;
-use polar_nodes::{BitcoinNode, LndNode};
+use polar_nodes::{BitcoinNode, LndNode, PaymentOptions};
 
 #[tokio::test]
 async fn test_payment_between_two_nodes_with_direct_channel() -> Result<()> {
@@ -43,7 +43,7 @@ async fn test_payment_between_two_nodes_with_direct_channel() -> Result<()> {
 
     // Fund LND1 wallet (will open channel)
     println!("  - Funding LND1 wallet...");
-    let lnd1_address = lnd1.get_new_address(&manager).await?;
+    let lnd1_address = lnd1.get_new_address(&manager, None).await?;
     btc_node
         .send_to_address(&manager, &lnd1_address, 1.0)
         .await?;
@@ -102,7 +102,7 @@ async fn test_payment_between_two_nodes_with_direct_channel() -> Result<()> {
     println!("    ✓ Created invoice");
 
     println!("  - Paying invoice from LND1...");
-    let payment_hash = lnd1.pay_invoice(&manager, &invoice).await?;
+    let payment_hash = lnd1.pay_invoice(&manager, &invoice, None).await?;
     println!("    ✓ Payment successful! Hash: {}", payment_hash);
 
     // Test payment in reverse direction (lnd2 to lnd1)
@@ -114,12 +114,35 @@ async fn test_payment_between_two_nodes_with_direct_channel() -> Result<()> {
     println!("    ✓ Created reverse invoice");
 
     println!("  - Paying reverse invoice from LND2...");
-    let reverse_payment_hash = lnd2.pay_invoice(&manager, &reverse_invoice).await?;
+    let reverse_payment_hash = lnd2.pay_invoice(&manager, &reverse_invoice, None).await?;
     println!(
         "    ✓ Reverse payment successful! Hash: {}",
         reverse_payment_hash
     );
 
+    // Stream the attempts of a third payment
+    println!("  - Paying an invoice with streamed progress...");
+    let streamed_invoice = lnd2
+        .create_invoice(&manager, 1_000, Some("streamed payment"))
+        .await?;
+    let mut updates = Vec::new();
+    let streamed_hash = lnd1
+        .pay_invoice_streaming(
+            &manager,
+            &streamed_invoice,
+            PaymentOptions::default(),
+            |update| updates.push(update.clone()),
+        )
+        .await?;
+    let last = updates.last().expect("payment should report an update");
+    assert_eq!(last.payment_hash, streamed_hash);
+    assert_eq!(last.status, "SUCCEEDED");
+    assert!(
+        !last.attempts.is_empty(),
+        "update should list HTLC attempts"
+    );
+    println!("    ✓ {} update(s), last: {last}", updates.len());
+
     println!("  ✓ All payments completed successfully!");
 
     // Cleanup
@@ -174,7 +197,7 @@ async fn test_payment_fails_without_channel() -> Result<()> {
         .create_invoice(&manager, payment_amount, Some("should fail"))
         .await?;
 
-    let result = lnd1.pay_invoice(&manager, &invoice).await;
+    let result = lnd1.pay_invoice(&manager, &invoice, None).await;
     assert!(result.is_err(), "Payment should fail without a channel");
     println!("    ✓ Payment correctly failed: {:?}", result.unwrap_err());
 
@@ -0,0 +1,47 @@
+//! Tests for listing and disconnecting Lightning peers.
+
+use anyhow::Result;
+use polar_core::NodeInfo;
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Get the current peer count of an LND node.
+async fn num_peers(manager: &NetworkManager, network: &str, node: &str) -> Result<u32> {
+    match manager.get_node_info(network, node).await? {
+        NodeInfo::Lnd(info) => Ok(info.num_peers),
+        NodeInfo::Bitcoin(_) => anyhow::bail!("{node} is not an LND node"),
+    }
+}
+
+#[tokio::test]
+async fn test_disconnect_peer_reduces_peer_count() -> Result<()> {
+    println!("\nTesting peer disconnect...");
+
+    let network_name = "polar-test-peers";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Connecting peers...");
+    network_manager.sync_graph(network_name).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    let before = num_peers(&network_manager, network_name, "lnd-1").await;
+
+    println!("  - Disconnecting lnd-1 from lnd-2...");
+    let disconnect = network_manager
+        .disconnect_peer(network_name, "lnd-1", "lnd-2")
+        .await;
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    let after = num_peers(&network_manager, network_name, "lnd-1").await;
+
+    disconnect?;
+    let (before, after) = (before?, after?);
+    assert!(before >= 1, "lnd-1 should have lnd-2 as a peer");
+    assert!(
+        after < before,
+        "Peer count should drop after disconnect ({before} -> {after})"
+    );
+    println!("    ✓ Peer count dropped from {before} to {after}");
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//! Tests for port mapping persistence across network restarts.
+
+use anyhow::Result;
+use polar_core::{NodeInfo, NodePorts};
+use polar_tests::TestNetwork;
+
+/// Extract the reported RPC/REST endpoint for a node.
+fn reported_host(info: &NodeInfo) -> String {
+    match info {
+        NodeInfo::Bitcoin(info) => info.rpc_host.clone(),
+        NodeInfo::Lnd(info) => info.rest_host.clone(),
+    }
+}
+
+#[tokio::test]
+async fn test_ports_stable_across_restart() -> Result<()> {
+    println!("\nTesting port mappings persist across restarts...");
+
+    let network_name = "polar-test-port-persistence";
+
+    println!("  - Starting network...");
+    let mut network_manager = TestNetwork::started(network_name, 1, 1).await?;
+
+    // Record the allocated ports
+    let network = network_manager
+        .get_network(network_name)
+        .expect("Network should exist");
+    let btc_node = network
+        .nodes
+        .iter()
+        .find(|n| n.name == "bitcoin-1")
+        .expect("Bitcoin node should exist");
+    let allocated_rpc = match network.port_mappings[&btc_node.id].ports {
+        NodePorts::BitcoinCore { rpc, .. } => rpc,
+        NodePorts::Lnd { .. } => panic!("Bitcoin node should have Bitcoin ports"),
+    };
+
+    let btc_before = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await?;
+    let lnd_before = network_manager.get_node_info(network_name, "lnd-1").await?;
+    println!("    ✓ Bitcoin RPC: {}", reported_host(&btc_before));
+    println!("    ✓ LND REST:    {}", reported_host(&lnd_before));
+    assert!(reported_host(&btc_before).ends_with(&format!(":{allocated_rpc}")));
+
+    println!("  - Stopping network...");
+    network_manager.stop_network(network_name).await?;
+
+    // Reload from disk and start again
+    println!("  - Reloading network from disk and restarting...");
+    network_manager.reload()?;
+    network_manager.start().await?;
+
+    let btc_after = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await?;
+    let lnd_after = network_manager.get_node_info(network_name, "lnd-1").await?;
+
+    assert_eq!(reported_host(&btc_before), reported_host(&btc_after));
+    assert_eq!(reported_host(&lnd_before), reported_host(&lnd_after));
+    println!("  ✓ Ports are stable across restarts!");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reset_wipes_chain_but_keeps_ports() -> Result<()> {
+    println!("\nTesting network reset...");
+
+    let network_name = "polar-test-reset";
+
+    println!("  - Creating network...");
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+
+    println!("  - Starting network and mining 10 blocks...");
+    network_manager.start().await?;
+    network_manager.mine_blocks(network_name, 10, None).await?;
+
+    let btc_before = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await?;
+
+    println!("  - Resetting network...");
+    let reset = network_manager.reset_network(network_name).await;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let btc_after = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await;
+
+    reset?;
+    let btc_after = btc_after?;
+    let NodeInfo::Bitcoin(after) = &btc_after else {
+        panic!("bitcoin-1 should be a Bitcoin node");
+    };
+    assert_eq!(after.blocks, 0, "Chain should restart from block zero");
+    assert_eq!(reported_host(&btc_before), reported_host(&btc_after));
+    println!("  ✓ Chain wiped and ports kept!");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restart_keeps_chain_data() -> Result<()> {
+    println!("\nTesting network restart keeps chain data...");
+
+    let network_name = "polar-test-restart";
+
+    println!("  - Creating network...");
+    let mut network_manager = TestNetwork::create(network_name, 1, 1).await?;
+
+    println!("  - Starting network and mining 10 blocks...");
+    network_manager.start().await?;
+    network_manager.mine_blocks(network_name, 10, None).await?;
+
+    let btc_before = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await?;
+    let lnd_before = network_manager.get_node_info(network_name, "lnd-1").await?;
+
+    println!("  - Restarting network...");
+    let restart = network_manager.restart_network(network_name).await;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let btc_after = network_manager
+        .get_node_info(network_name, "bitcoin-1")
+        .await;
+    let lnd_after = network_manager.get_node_info(network_name, "lnd-1").await;
+
+    restart?;
+    let (NodeInfo::Bitcoin(before), NodeInfo::Bitcoin(after)) = (&btc_before, &btc_after?) else {
+        panic!("bitcoin-1 should be a Bitcoin node");
+    };
+    assert_eq!(after.blocks, before.blocks, "Block height should persist");
+    println!("    ✓ Block height {} kept", after.blocks);
+
+    let (NodeInfo::Lnd(before), NodeInfo::Lnd(after)) = (&lnd_before, &lnd_after?) else {
+        panic!("lnd-1 should be an LND node");
+    };
+    assert_eq!(
+        after.identity_pubkey, before.identity_pubkey,
+        "LND should keep its wallet and identity"
+    );
+    println!("  ✓ Chain data survives a restart!");
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! Tests for private (unannounced) channels.
+
+use anyhow::Result;
+use polar_core::NodeInfo;
+use polar_docker::ContainerManager;
+use polar_nodes::{LndNode, OpenChannelOptions};
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_private_channel_is_unannounced_but_usable() -> Result<()> {
+    println!("\nTesting private channel visibility...");
+
+    let network_name = "polar-test-private-channel";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1...");
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    println!("  - Opening private channel lnd-1 -> lnd-2...");
+    network_manager
+        .open_channel_with_options(
+            network_name,
+            "lnd-1",
+            "lnd-2",
+            1_000_000,
+            &OpenChannelOptions::default().with_private(true),
+        )
+        .await?;
+
+    // Mine past the announcement depth so a public channel would be gossiped
+    println!("  - Mining 10 blocks...");
+    network_manager.mine_blocks(network_name, 10, None).await?;
+    network_manager.sync_graph(network_name).await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+    println!("  - Checking lnd-1 channel list...");
+    let info = network_manager.get_node_info(network_name, "lnd-1").await;
+
+    println!("  - Reading lnd-2's view of the graph...");
+    let mut lnd2 = LndNode::new("lnd-2", String::new());
+    lnd2.node.container_id = network_manager
+        .get_network(network_name)
+        .and_then(|n| n.nodes.iter().find(|n| n.name == "lnd-2"))
+        .and_then(|n| n.container_id.clone());
+    let graph = lnd2.describe_graph(&ContainerManager::new()?).await;
+
+    println!("  - Paying lnd-2 over the private channel...");
+    let mut payment_result = None;
+    for attempt in 1..=5 {
+        match network_manager
+            .send_payment(network_name, "lnd-1", "lnd-2", 10_000, Some("private"))
+            .await
+        {
+            Ok(payment_hash) => {
+                payment_result = Some(payment_hash);
+                break;
+            }
+            Err(e) => {
+                println!("    … attempt {attempt} failed: {e}");
+                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            }
+        }
+    }
+
+    let NodeInfo::Lnd(info) = info? else {
+        panic!("lnd-1 should report LND info");
+    };
+    assert_eq!(info.channels.len(), 1, "lnd-1 should have one channel");
+    assert!(info.channels[0].private, "Channel should be private");
+    println!("    ✓ Channel listed as private");
+    assert_eq!(
+        info.channels[0].remote_name.as_deref(),
+        Some("lnd-2"),
+        "Counterparty should be resolved to its node name"
+    );
+    println!("    ✓ Counterparty shown as lnd-2");
+
+    let graph = graph?;
+    let edges = graph["edges"].as_array().cloned().unwrap_or_default();
+    assert!(
+        edges.is_empty(),
+        "Private channel should not appear in lnd-2's graph: {edges:?}"
+    );
+    println!("    ✓ Channel absent from the counterparty's graph");
+
+    assert!(
+        payment_result.is_some(),
+        "Direct payment over the private channel should succeed"
+    );
+    println!("    ✓ Direct payment succeeded");
+
+    Ok(())
+}
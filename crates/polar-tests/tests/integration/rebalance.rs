@@ -0,0 +1,94 @@
+//! Tests for rebalancing channels with circular payments.
+
+use anyhow::Result;
+use polar_core::{ChannelInfo, NodeInfo};
+use polar_tests::TestNetwork;
+use polar_tui::NetworkManager;
+
+/// Get the pubkey and channels of an LND node.
+async fn lnd_info(
+    manager: &NetworkManager,
+    network: &str,
+    node: &str,
+) -> Result<(String, Vec<ChannelInfo>)> {
+    match manager.get_node_info(network, node).await? {
+        NodeInfo::Lnd(info) => Ok((info.identity_pubkey, info.channels)),
+        NodeInfo::Bitcoin(_) => anyhow::bail!("{node} is not an LND node"),
+    }
+}
+
+#[tokio::test]
+async fn test_rebalance_around_triangle() -> Result<()> {
+    println!("\nTesting circular rebalance lnd-1 -> lnd-2 -> lnd-3 -> lnd-1...");
+
+    let network_name = "polar-test-rebalance";
+
+    println!("  - Starting network with 3 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 3, 1).await?;
+
+    println!("  - Mining 101 blocks and funding all nodes...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    for node in ["lnd-1", "lnd-2", "lnd-3"] {
+        network_manager
+            .fund_lnd_wallet(network_name, node, 1.0)
+            .await?;
+    }
+
+    // Build the loop: lnd-1 -> lnd-2 -> lnd-3 -> lnd-1
+    println!("  - Opening the triangle of channels...");
+    for (from, to) in [("lnd-1", "lnd-2"), ("lnd-2", "lnd-3"), ("lnd-3", "lnd-1")] {
+        network_manager
+            .open_channel(network_name, from, to, 1_000_000, None)
+            .await?;
+    }
+
+    // Channels are only announced to the graph after 6 confirmations
+    println!("  - Mining 10 blocks to confirm and announce channels...");
+    network_manager.mine_blocks(network_name, 10, None).await?;
+    network_manager.sync_graph(network_name).await?;
+
+    let (_, lnd1_channels) = lnd_info(&network_manager, network_name, "lnd-1").await?;
+    let (lnd2_pubkey, _) = lnd_info(&network_manager, network_name, "lnd-2").await?;
+    let out_channel = lnd1_channels
+        .iter()
+        .find(|c| c.remote_pubkey == lnd2_pubkey)
+        .map(|c| c.chan_id.clone());
+    let in_channel = lnd1_channels
+        .iter()
+        .find(|c| c.remote_pubkey != lnd2_pubkey)
+        .map(|c| c.chan_id.clone());
+
+    // Gossip propagation takes a few seconds, retry until a loop is found
+    println!("  - Rebalancing 50,000 sats around the loop...");
+    let mut rebalance_result = None;
+    if let (Some(out_channel), Some(in_channel)) = (&out_channel, &in_channel) {
+        for attempt in 1..=10 {
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            match network_manager
+                .rebalance(network_name, "lnd-1", out_channel, in_channel, 50_000)
+                .await
+            {
+                Ok(fee) => {
+                    rebalance_result = Some(fee);
+                    break;
+                }
+                Err(e) => println!("    … attempt {attempt} failed: {e}"),
+            }
+        }
+    }
+
+    println!("  - Rebalancing through the same channel twice...");
+    let same_channel = network_manager
+        .rebalance(network_name, "lnd-1", "1", "1", 1_000)
+        .await;
+
+    assert!(
+        out_channel.is_some() && in_channel.is_some(),
+        "lnd-1 should have two channels"
+    );
+    let fee = rebalance_result.expect("lnd-1 should be able to pay itself around the loop");
+    assert!(same_channel.is_err(), "A single channel cannot form a loop");
+    println!("    ✓ Rebalanced around the loop for {fee} sats in fees");
+
+    Ok(())
+}
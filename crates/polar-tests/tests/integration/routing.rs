@@ -0,0 +1,185 @@
+//! Tests for multi-hop payments routed across the channel graph.
+
+use anyhow::Result;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_routed_payment_across_three_node_line() -> Result<()> {
+    println!("\nTesting routed payment across A -> B -> C...");
+
+    let network_name = "polar-test-routing";
+
+    println!("  - Starting network with 3 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 3, 1).await?;
+
+    // Mine initial blocks so the Bitcoin node has mature funds
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    // Fund the two nodes that will open channels
+    println!("  - Funding lnd-1 and lnd-2...");
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-2", 1.0)
+        .await?;
+
+    // Build the line topology: lnd-1 -> lnd-2 -> lnd-3
+    println!("  - Opening channel lnd-1 -> lnd-2...");
+    network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", 1_000_000, None)
+        .await?;
+    println!("  - Opening channel lnd-2 -> lnd-3...");
+    network_manager
+        .open_channel(network_name, "lnd-2", "lnd-3", 1_000_000, None)
+        .await?;
+
+    // Channels are only announced to the graph after 6 confirmations
+    println!("  - Mining 10 blocks to confirm and announce channels...");
+    network_manager.mine_blocks(network_name, 10, None).await?;
+
+    println!("  - Syncing graph...");
+    let synced = network_manager.sync_graph(network_name).await?;
+    assert_eq!(synced, 3, "All three LND nodes should be synced");
+
+    // Gossip propagation takes a few seconds, wait until every node sees both channels
+    println!("  - Waiting for gossip to reach every node...");
+    let waited = network_manager
+        .wait_for_graph_sync(network_name, std::time::Duration::from_secs(60))
+        .await;
+
+    println!("  - Paying lnd-3 from lnd-1 via lnd-2...");
+    let payment_result = match waited {
+        Ok(_) => network_manager
+            .send_payment_with_options(
+                network_name,
+                "lnd-1",
+                "lnd-3",
+                10_000,
+                Some("routed payment"),
+                polar_nodes::PaymentOptions::default().with_fee_limit(100),
+            )
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    let payment_hash = payment_result.expect("lnd-1 should be able to pay lnd-3 via lnd-2");
+    println!("    ✓ Routed payment successful! Hash: {payment_hash}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_probe_route_before_and_after_channel() -> Result<()> {
+    println!("\nTesting route probing...");
+
+    let network_name = "polar-test-probe-route";
+
+    println!("  - Starting network with 2 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 2, 1).await?;
+
+    println!("  - Probing lnd-1 -> lnd-2 without a channel...");
+    let before = network_manager
+        .probe_route(network_name, "lnd-1", "lnd-2", 10_000)
+        .await;
+
+    println!("  - Opening channel lnd-1 -> lnd-2...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+    network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", 1_000_000, None)
+        .await?;
+    network_manager.mine_blocks(network_name, 10, None).await?;
+    network_manager.sync_graph(network_name).await?;
+
+    println!("  - Probing lnd-1 -> lnd-2 over the channel...");
+    let mut after = None;
+    for attempt in 1..=10 {
+        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        match network_manager
+            .probe_route(network_name, "lnd-1", "lnd-2", 10_000)
+            .await
+        {
+            Ok(route) => {
+                after = Some(route);
+                break;
+            }
+            Err(e) => println!("    … attempt {attempt} failed: {e}"),
+        }
+    }
+
+    assert!(
+        matches!(before, Err(polar_core::Error::NoRoute { .. })),
+        "Probing without a channel should report no route: {before:?}"
+    );
+    println!("    ✓ No route reported before the channel opened");
+
+    let route = after.expect("lnd-1 should find a route to lnd-2");
+    assert_eq!(route.hops, 1, "Direct channel should be a single hop");
+    assert_eq!(route.total_fees_msat, 0, "Direct payments pay no fee");
+    println!("    ✓ Direct route found with no fee");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_along_fixed_route() -> Result<()> {
+    println!("\nTesting payment along a fixed route...");
+
+    let network_name = "polar-test-fixed-route";
+
+    println!("  - Starting network with 3 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 3, 1).await?;
+
+    println!("  - Building lnd-1 -> lnd-2 -> lnd-3...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-2", 1.0)
+        .await?;
+    network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", 1_000_000, None)
+        .await?;
+    network_manager
+        .open_channel(network_name, "lnd-2", "lnd-3", 1_000_000, None)
+        .await?;
+    network_manager.mine_blocks(network_name, 10, None).await?;
+    network_manager.sync_graph(network_name).await?;
+    let waited = network_manager
+        .wait_for_graph_sync(network_name, std::time::Duration::from_secs(60))
+        .await;
+
+    // lnd-1 has no channel to lnd-3, so this path is rejected up front
+    println!("  - Paying lnd-2 via lnd-3...");
+    let invalid = network_manager
+        .send_along_route(network_name, "lnd-1", "lnd-2", 10_000, vec!["lnd-3"])
+        .await;
+
+    println!("  - Paying lnd-3 via lnd-2...");
+    let valid = network_manager
+        .send_along_route(network_name, "lnd-1", "lnd-3", 10_000, vec!["lnd-2"])
+        .await;
+
+    waited?;
+    assert!(
+        matches!(invalid, Err(polar_core::Error::NoRoute { .. })),
+        "a hop without a channel should be rejected, got {invalid:?}"
+    );
+    println!("    ✓ Path without a channel rejected");
+
+    let preimage = valid?;
+    assert_eq!(
+        preimage.len(),
+        64,
+        "preimage should be 32 hex encoded bytes"
+    );
+    println!("    ✓ Paid along the fixed route, preimage {preimage}");
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Tests for wiring channel topologies between LND nodes.
+
+use anyhow::Result;
+use polar_core::Topology;
+use polar_tests::TestNetwork;
+
+#[tokio::test]
+async fn test_apply_ring_topology() -> Result<()> {
+    println!("\nTesting ring topology across 3 unfunded LND nodes...");
+
+    let network_name = "polar-test-topology";
+
+    println!("  - Starting network with 3 LND nodes...");
+    let network_manager = TestNetwork::started(network_name, 3, 1).await?;
+
+    println!("  - Applying ring topology...");
+    let first = network_manager
+        .apply_topology(network_name, Topology::Ring, 500_000)
+        .await;
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+
+    println!("  - Applying ring topology again...");
+    let second = network_manager
+        .apply_topology(network_name, Topology::Ring, 500_000)
+        .await;
+
+    let first = first?;
+    let opened: Vec<_> = first
+        .opened
+        .iter()
+        .map(|(from, to, _)| (from.as_str(), to.as_str()))
+        .collect();
+    assert_eq!(
+        opened,
+        vec![("lnd-1", "lnd-2"), ("lnd-2", "lnd-3"), ("lnd-3", "lnd-1")],
+        "Each node should open a channel to the next: {:?}",
+        first.failed
+    );
+
+    let second = second?;
+    assert!(
+        second.opened.is_empty(),
+        "Existing channels should not be reopened"
+    );
+    assert_eq!(second.skipped.len(), 3, "All three pairs should be skipped");
+    println!("    ✓ Ring wired and existing channels skipped");
+
+    Ok(())
+}
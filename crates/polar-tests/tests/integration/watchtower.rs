@@ -0,0 +1,70 @@
+//! Tests for registering LND watchtowers.
+
+use anyhow::Result;
+use polar_core::ResourceLimits;
+use polar_docker::ContainerManager;
+use polar_nodes::{BitcoinNode, LndNode};
+
+#[tokio::test]
+async fn test_add_watchtower() -> Result<()> {
+    println!("\nTesting watchtower registration...");
+
+    let manager = ContainerManager::new()?;
+    let network_name = "polar-test-watchtower";
+
+    println!("  - Creating Docker network...");
+    manager.create_network(network_name).await?;
+
+    let mut btc_node = BitcoinNode::new("bitcoin-1");
+    println!("  - Starting Bitcoin Core...");
+    btc_node
+        .start_with_network(&manager, Some(network_name))
+        .await?;
+    let btc_id = btc_node.node.id.to_string();
+
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    btc_node.mine_blocks(&manager, 101, None).await?;
+
+    // Both nodes run the watchtower server and client
+    let mut client = LndNode::new("lnd-1", btc_id.clone());
+    let mut tower = LndNode::new("lnd-2", btc_id);
+
+    println!("  - Starting LND nodes with watchtowers enabled...");
+    client
+        .start_with_ports(
+            &manager,
+            Some(network_name),
+            None,
+            true,
+            ResourceLimits::default(),
+        )
+        .await?;
+    tower
+        .start_with_ports(
+            &manager,
+            Some(network_name),
+            None,
+            true,
+            ResourceLimits::default(),
+        )
+        .await?;
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+    println!("  - Registering lnd-2 as watchtower for lnd-1...");
+    let tower_pubkey = tower.tower_pubkey(&manager).await?;
+    let tower_address = format!("polar-lnd-{}:{}", tower.node.id, LndNode::WATCHTOWER_PORT);
+    let result = client
+        .add_watchtower(&manager, &tower_pubkey, &tower_address)
+        .await;
+
+    println!("  - Cleaning up...");
+    client.stop(&manager).await?;
+    tower.stop(&manager).await?;
+    btc_node.stop(&manager).await?;
+    manager.remove_network(network_name).await?;
+
+    result?;
+    println!("  ✓ Watchtower registered");
+
+    Ok(())
+}
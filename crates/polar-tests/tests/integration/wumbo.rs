@@ -0,0 +1,60 @@
+//! Tests for opening channels above the default LND size limit.
+
+use anyhow::Result;
+use polar_core::ExtraArgs;
+use polar_nodes::LndNode;
+use polar_tests::TestNetwork;
+
+const WUMBO_CAPACITY: u64 = 50_000_000;
+
+#[tokio::test]
+async fn test_open_wumbo_channel() -> Result<()> {
+    println!("\nTesting 50M sat channel with wumbo channels...");
+
+    // Capacity is validated before any node is contacted, so this network
+    // never needs to start
+    let plain_name = "polar-test-wumbo-off";
+    println!("  - Rejecting 50M sat channel without wumbo...");
+    let plain = TestNetwork::create(plain_name, 2, 1).await?;
+    let rejected = plain
+        .open_channel(plain_name, "lnd-1", "lnd-2", WUMBO_CAPACITY, None)
+        .await;
+    drop(plain);
+    let err = rejected.expect_err("Channel above the limit should be rejected");
+    assert!(
+        err.to_string().contains("wumbo"),
+        "Error should suggest wumbo: {err}"
+    );
+
+    let network_name = "polar-test-wumbo";
+    println!("  - Creating network with wumbo channels...");
+    let mut network_manager = TestNetwork::create_with_extra_args(
+        network_name,
+        2,
+        1,
+        ExtraArgs {
+            lnd: vec![LndNode::WUMBO_FLAG.to_string()],
+            bitcoin: Vec::new(),
+        },
+    )
+    .await?;
+
+    println!("  - Starting network...");
+    network_manager.start().await?;
+
+    println!("  - Mining 101 blocks...");
+    network_manager.mine_blocks(network_name, 101, None).await?;
+
+    println!("  - Funding lnd-1 with 1 BTC...");
+    network_manager
+        .fund_lnd_wallet(network_name, "lnd-1", 1.0)
+        .await?;
+
+    println!("  - Opening 50M sat channel lnd-1 -> lnd-2...");
+    network_manager
+        .open_channel(network_name, "lnd-1", "lnd-2", WUMBO_CAPACITY, None)
+        .await?;
+    println!("    ✓ Wumbo channel opened");
+
+    Ok(())
+}
@@ -1,13 +1,71 @@
 use anyhow::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use polar_core::{LightningImpl, NetworkStatus, NodeInfo};
+use polar_core::{
+    ChannelInfo, ChannelPoint, Config, DecodedInvoice, Error, GraphInfo, LightningImpl,
+    NetworkStatus, NodeInfo, NodeStatus, PaymentHistory, RouteEstimate, Topology,
+};
+use polar_nodes::{ExportedCredentials, OpenChannelOptions};
 use ratatui::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, mpsc};
 
-use crate::network_manager::NetworkManager;
-use crate::ui;
+use crate::clipboard;
+use crate::format::DisplayUnit;
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::network_manager::{GRAPH_SYNC_TIMEOUT, LiquiditySummary, NetworkManager};
+use crate::settings::SettingsForm;
+use crate::ui::{self, truncate_str};
+
+/// Seconds between ticks of the timed block generator.
+const BLOCK_GENERATOR_INTERVAL_SECS: u64 = 10;
+/// Blocks mined on each tick of the timed block generator.
+const BLOCK_GENERATOR_BLOCKS_PER_TICK: u32 = 1;
+
+/// Capacity of each channel opened when a network's topology is wired up.
+const TOPOLOGY_CHANNEL_CAPACITY: u64 = 1_000_000;
+
+/// How often node container statuses are re-inspected.
+const NODE_STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often the status bar's block height is polled.
+const BLOCK_HEIGHT_REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Values the lightning operation forms start with, taken from the config.
+#[derive(Debug, Clone)]
+struct FormDefaults {
+    mine_blocks: String,
+    fund_amount: String,
+    channel_capacity: String,
+    push_amount: String,
+    payment_amount: String,
+}
+
+impl FormDefaults {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            mine_blocks: config.default_mine_blocks.to_string(),
+            // Debug keeps the decimal point on whole amounts, e.g. "1.0"
+            fund_amount: format!("{:?}", config.default_fund_amount),
+            channel_capacity: config.default_channel_capacity.to_string(),
+            push_amount: config.default_push_amount.to_string(),
+            payment_amount: config.default_payment_amount.to_string(),
+        }
+    }
+}
+
+/// A long-running operation executing in the background.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    /// What the operation is doing, e.g. "Starting network 'demo'"
+    pub label: String,
+    /// Latest log line the operation emitted
+    pub detail: Option<String>,
+    /// When the operation started
+    pub started: Instant,
+}
 
 /// Commands that can be sent to the app for async execution.
 #[derive(Debug, Clone)]
@@ -18,9 +76,11 @@ pub enum AppCommand {
         alias: String,
         lnd_version_idx: usize,
         btc_version_idx: usize,
+        topology: Option<Topology>,
     },
     StartNetwork,
     StopNetwork,
+    ResetNetwork,
     DeleteNetwork,
     AddLightningNode {
         implementation: LightningImpl,
@@ -29,32 +89,85 @@ pub enum AppCommand {
         node_name: String,
     },
     ViewNodeDetails,
+    RefreshNodeDetails,
+    ExportNodeCredentials,
+    ShowLndconnect,
     MineBlocks {
         num_blocks: u32,
+        to_node: Option<String>,
     },
     FundWallet {
         node_name: String,
         amount: f64,
     },
+    FundAllNodes {
+        amount: f64,
+    },
     OpenChannel {
         from_node: String,
         to_node: String,
         capacity: u64,
         push_amount: Option<u64>,
+        private: bool,
     },
     CloseChannel {
         node_name: String,
         channel_point: String,
         force: bool,
     },
+    LoadCloseChannels {
+        node_name: String,
+    },
+    AbandonChannel {
+        node_name: String,
+        channel_point: String,
+    },
     SendPayment {
         from_node: String,
         to_node: String,
         amount: u64,
         memo: Option<String>,
     },
+    ProbeRoute {
+        from_node: String,
+        to_node: String,
+        amount: u64,
+    },
+    DecodeInvoice {
+        from_node: String,
+        payment_request: String,
+    },
+    PayInvoice {
+        from_node: String,
+        payment_request: String,
+    },
     SyncGraph,
     SyncChain,
+    ViewGraph,
+    ViewNetworkSummary,
+    ViewPaymentHistory,
+    ExportNetwork,
+    RefreshNetworks,
+    ToggleBlockGenerator,
+    CycleBlockGeneratorMode,
+    PruneOrphans,
+    RetryDocker,
+    /// Apply settings saved from the settings screen to the network manager
+    ApplySettings(Box<Config>),
+    RenameNode {
+        old_name: String,
+        new_name: String,
+    },
+    StartNode {
+        node_name: String,
+    },
+    StopNode {
+        node_name: String,
+    },
+    SignMessage {
+        node_name: String,
+        message: String,
+    },
 }
 
 /// UI mode - what screen we're showing
@@ -71,12 +184,40 @@ pub enum UiMode {
     MineBlocks,
     /// Fund wallet dialog
     FundWallet,
+    /// Fund every LND node dialog
+    FundAllNodes,
+    /// Rename node dialog
+    RenameNode,
+    /// Sign message dialog
+    SignMessage,
     /// Open channel dialog
     OpenChannel,
     /// Close channel dialog
     CloseChannel,
     /// Send payment dialog
     SendPayment,
+    /// Channel graph view
+    GraphView,
+    /// Liquidity totals of the selected network
+    NetworkSummary,
+    /// Invoice and payment history of an LND node
+    PaymentHistory,
+    /// Confirmation dialog for destructive actions
+    ConfirmDelete,
+    /// Decoded invoice confirmation dialog
+    ConfirmInvoice,
+    /// Route estimate confirmation dialog for a keysend-style payment
+    ConfirmPayment,
+    /// Quit dialog asking whether to stop running networks
+    ConfirmQuit,
+    /// Filter input for the active list panel
+    Filter,
+    /// Key binding reference overlay
+    Help,
+    /// Config editor
+    Settings,
+    /// Docker could not be reached; nothing works until it responds
+    DockerUnavailable,
 }
 
 /// Active panel in the main UI
@@ -88,6 +229,34 @@ pub enum ActivePanel {
     Logs,
 }
 
+/// How the close channel dialog gets rid of a channel
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CloseMode {
+    #[default]
+    Cooperative,
+    Force,
+    /// Drop the channel from local state without broadcasting anything
+    Abandon,
+}
+
+impl CloseMode {
+    /// Mode to the left in the dialog.
+    const fn prev(self) -> Self {
+        match self {
+            Self::Cooperative | Self::Force => Self::Cooperative,
+            Self::Abandon => Self::Force,
+        }
+    }
+
+    /// Mode to the right in the dialog.
+    const fn next(self) -> Self {
+        match self {
+            Self::Cooperative => Self::Force,
+            Self::Force | Self::Abandon => Self::Abandon,
+        }
+    }
+}
+
 /// Application state
 pub struct App {
     /// Is the application running
@@ -108,8 +277,14 @@ pub struct App {
     pub selected_node: Option<usize>,
     /// Log scroll position
     pub log_scroll: usize,
+    /// Whether the Logs panel sticks to the newest line
+    pub log_follow: bool,
+    /// Size of the Logs panel text, recorded after each draw
+    pub log_viewport: Size,
     /// Cached log lines
-    pub logs: Vec<String>,
+    pub logs: Vec<LogEntry>,
+    /// Lowest level shown in the Logs panel
+    pub log_min_level: LogLevel,
     /// Status message
     pub status_message: Option<String>,
     /// Command sender for async operations
@@ -120,6 +295,12 @@ pub struct App {
     log_tx: mpsc::UnboundedSender<String>,
     /// Log channel receiver
     log_rx: mpsc::UnboundedReceiver<String>,
+    /// Operation running in the background, if any
+    pub progress: Option<Progress>,
+    /// Sender background operations report their final status on
+    task_tx: mpsc::UnboundedSender<String>,
+    /// Receiver for the final status of background operations
+    task_rx: mpsc::UnboundedReceiver<String>,
     /// Network creation form state
     pub create_network_name: String,
     /// Number of LND nodes to create
@@ -130,7 +311,9 @@ pub struct App {
     pub create_lnd_version_idx: usize,
     /// Selected Bitcoin version index
     pub create_btc_version_idx: usize,
-    /// Active field in create network form (0=name, 1=alias, 2=lnd_count, 3=lnd_version, 4=btc_version)
+    /// Selected topology index, where 0 means no channels and the rest index [`Topology::all`]
+    pub create_topology_idx: usize,
+    /// Active field in create network form (0=name, 1=alias, 2=LND count, 3=LND version, 4=Bitcoin version, 5=topology)
     pub create_form_field: usize,
     /// Current node info being displayed
     pub node_info: Option<NodeInfo>,
@@ -138,10 +321,36 @@ pub struct App {
     pub node_info_scroll: usize,
     /// Selected channel index in node details view (for copying)
     pub selected_channel_idx: Option<usize>,
+    /// Highlighted endpoint in node details view, indexes [`NodeInfo::endpoints`]
+    pub selected_endpoint_idx: usize,
+    /// Credentials exported from the LND node shown in node details
+    pub node_credentials: Option<ExportedCredentials>,
+    /// lndconnect URL of the LND node shown in node details
+    pub lndconnect_url: Option<String>,
+    /// Current channel graph being displayed
+    pub graph_info: Option<GraphInfo>,
+    /// Graph view scroll position
+    pub graph_scroll: usize,
+    /// Liquidity totals being displayed, None when the network is not running
+    pub network_summary: Option<LiquiditySummary>,
+    /// Payment history being displayed
+    pub payment_history: Option<PaymentHistory>,
+    /// Payment history scroll position
+    pub payment_history_scroll: usize,
+    /// Number of orphaned containers found by the last reconcile
+    pub orphan_count: usize,
+    /// Error from the last failed Docker ping
+    pub docker_error: Option<String>,
+    /// Unit balances and amounts are displayed in
+    pub display_unit: DisplayUnit,
 
     // Mine blocks form state
     /// Number of blocks to mine
     pub mine_blocks_count: String,
+    /// Selected coinbase target (0=Bitcoin node wallet, n=nodes[n-1])
+    pub mine_target_idx: usize,
+    /// Active field in mine blocks form (0=count, 1=target)
+    pub mine_form_field: usize,
 
     // Fund wallet form state
     /// Selected node index for funding
@@ -150,6 +359,22 @@ pub struct App {
     pub fund_amount: String,
     /// Active field in fund form (0=node, 1=amount)
     pub fund_form_field: usize,
+    /// Amount to fund each LND node with (BTC)
+    pub fund_all_amount: String,
+
+    // Rename node form state
+    /// Current name of the node being renamed
+    pub rename_from: String,
+    /// New name being typed
+    pub rename_input: String,
+
+    // Sign message form state
+    /// LND node signing the message
+    pub sign_node: String,
+    /// Message being typed
+    pub sign_message: String,
+    /// Signature of the message, once signed
+    pub sign_signature: Option<String>,
 
     // Open channel form state
     /// From node index
@@ -160,7 +385,9 @@ pub struct App {
     pub channel_capacity: String,
     /// Push amount (sats)
     pub channel_push_amount: String,
-    /// Active field in channel form (0=from, 1=to, 2=capacity, 3=push)
+    /// Whether to open the channel as private (unannounced)
+    pub channel_private: bool,
+    /// Active field in channel form (0=from, 1=to, 2=capacity, 3=push, 4=visibility)
     pub channel_form_field: usize,
 
     // Send payment form state
@@ -172,18 +399,71 @@ pub struct App {
     pub payment_amount: String,
     /// Payment memo
     pub payment_memo: String,
-    /// Active field in payment form (0=from, 1=to, 2=amount, 3=memo)
+    /// Raw bolt11 invoice to pay instead of a node
+    pub payment_invoice: String,
+    /// Active field in payment form (0=from, 1=to, 2=amount, 3=memo, 4=invoice)
     pub payment_form_field: usize,
+    /// Invoice decoded for confirmation before paying
+    pub decoded_invoice: Option<DecodedInvoice>,
+    /// Route estimated for confirmation before sending a payment
+    pub route_estimate: Option<RouteEstimate>,
 
     // Close channel form state
     /// Selected node index for channel close
     pub close_channel_node_idx: usize,
-    /// Channel point (txid:index)
+    /// Channel point (txid:index), picked from the list or typed
     pub close_channel_point: String,
-    /// Force close flag
-    pub close_channel_force: bool,
-    /// Active field in close channel form (0=node, 1=channel_point, 2=force)
+    /// Open channels of the selected node, None while they load
+    pub close_channels: Option<Vec<ChannelInfo>>,
+    /// Channel picked from `close_channels`
+    pub close_channel_idx: usize,
+    /// Cooperative, force or abandon
+    pub close_channel_mode: CloseMode,
+    /// Active field in close channel form (0=node, 1=channel point, 2=mode)
     pub close_channel_form_field: usize,
+
+    // Confirmation dialog state
+    /// Command to dispatch once the user confirms
+    pub confirm_command: Option<AppCommand>,
+    /// Description of the action awaiting confirmation
+    pub confirm_prompt: String,
+    /// Networks still running when quit was requested
+    pub quit_running_networks: Vec<String>,
+    /// Whether Enter in the quit dialog stops the running networks
+    pub quit_stop_default: bool,
+    /// Exit once the running background task finishes
+    quit_after_task: bool,
+    /// Quit was pressed and awaits the quit dialog
+    pub quit_requested: bool,
+
+    // List filter state
+    /// Case-insensitive substring filter for the networks panel
+    pub network_filter: String,
+    /// Case-insensitive substring filter for the nodes panel
+    pub node_filter: String,
+    /// Help overlay scroll position
+    pub help_scroll: usize,
+
+    // Node status state
+    /// Container status of each node in the selected network, keyed by node name
+    pub node_statuses: HashMap<String, NodeStatus>,
+    /// When node statuses were last refreshed
+    node_statuses_refreshed: Option<Instant>,
+    /// Chain tip height of the selected network while it runs, as
+    /// (network, height)
+    pub block_height: Option<(String, u64)>,
+    /// When the block height was last polled
+    block_height_polled: Option<Instant>,
+    /// Whether a block height poll is still waiting for its result
+    block_height_in_flight: bool,
+    /// Sender background block height polls report on
+    block_height_tx: mpsc::UnboundedSender<(String, Option<u64>)>,
+    /// Receiver for background block height polls
+    block_height_rx: mpsc::UnboundedReceiver<(String, Option<u64>)>,
+    /// Values the lightning operation forms are reset to when opened.
+    form_defaults: FormDefaults,
+    /// Settings screen form state
+    pub settings: SettingsForm,
 }
 
 impl Default for App {
@@ -195,12 +475,27 @@ impl Default for App {
 impl App {
     #[must_use]
     pub fn new() -> Self {
-        let mut network_manager = NetworkManager::new().expect("Failed to create network manager");
+        Self::with_data_dir(None)
+    }
+
+    /// Create the app with networks stored under `data_dir`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the network manager cannot be created.
+    #[must_use]
+    pub fn with_data_dir(data_dir: Option<PathBuf>) -> Self {
+        let mut network_manager =
+            NetworkManager::with_data_dir(data_dir).expect("Failed to create network manager");
         let (command_tx, command_rx) = mpsc::unbounded_channel();
         let (log_tx, log_rx) = mpsc::unbounded_channel();
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
+        let (block_height_tx, block_height_rx) = mpsc::unbounded_channel();
 
         // Set up logging for the network manager
         network_manager.set_logger(log_tx.clone());
+        let form_defaults = FormDefaults::from_config(network_manager.config());
+        let display_unit = DisplayUnit::from_btc(network_manager.config().display_btc);
 
         Self {
             running: true,
@@ -212,87 +507,268 @@ impl App {
             selected_network: None,
             selected_node: None,
             log_scroll: 0,
+            log_follow: true,
+            log_viewport: Size::default(),
             logs: Vec::new(),
+            log_min_level: LogLevel::Trace,
             status_message: None,
             command_tx,
             command_rx,
             log_tx,
             log_rx,
+            progress: None,
+            task_tx,
+            task_rx,
             create_network_name: String::new(),
             create_lnd_count: 2, // Default to 2 LND nodes
             create_node_alias: String::new(),
             create_lnd_version_idx: 0, // Default to first version
             create_btc_version_idx: 0, // Default to first version
+            create_topology_idx: 0,
             create_form_field: 0,
             node_info: None,
             node_info_scroll: 0,
             selected_channel_idx: None,
+            selected_endpoint_idx: 0,
+            node_credentials: None,
+            lndconnect_url: None,
+            graph_info: None,
+            graph_scroll: 0,
+            network_summary: None,
+            payment_history: None,
+            payment_history_scroll: 0,
+            orphan_count: 0,
+            docker_error: None,
+            display_unit,
             // Lightning operation form defaults
-            mine_blocks_count: "100".to_string(),
+            mine_blocks_count: form_defaults.mine_blocks.clone(),
+            mine_target_idx: 0,
+            mine_form_field: 0,
             fund_node_idx: 0,
-            fund_amount: "1.0".to_string(),
+            fund_amount: form_defaults.fund_amount.clone(),
             fund_form_field: 0,
+            fund_all_amount: form_defaults.fund_amount.clone(),
+            rename_from: String::new(),
+            rename_input: String::new(),
+            sign_node: String::new(),
+            sign_message: String::new(),
+            sign_signature: None,
             channel_from_idx: 0,
             channel_to_idx: 1,
-            channel_capacity: "1000000".to_string(),
-            channel_push_amount: "500000".to_string(),
+            channel_capacity: form_defaults.channel_capacity.clone(),
+            channel_push_amount: form_defaults.push_amount.clone(),
+            channel_private: false,
             channel_form_field: 0,
             payment_from_idx: 0,
             payment_to_idx: 1,
-            payment_amount: "10000".to_string(),
+            payment_amount: form_defaults.payment_amount.clone(),
             payment_memo: String::new(),
+            payment_invoice: String::new(),
             payment_form_field: 0,
+            decoded_invoice: None,
+            route_estimate: None,
             close_channel_node_idx: 0,
             close_channel_point: String::new(),
-            close_channel_force: false,
+            close_channels: None,
+            close_channel_idx: 0,
+            close_channel_mode: CloseMode::Cooperative,
             close_channel_form_field: 0,
+            confirm_command: None,
+            confirm_prompt: String::new(),
+            quit_running_networks: Vec::new(),
+            quit_stop_default: false,
+            quit_after_task: false,
+            quit_requested: false,
+            network_filter: String::new(),
+            node_filter: String::new(),
+            help_scroll: 0,
+            node_statuses: HashMap::new(),
+            node_statuses_refreshed: None,
+            block_height: None,
+            block_height_polled: None,
+            block_height_in_flight: false,
+            block_height_tx,
+            block_height_rx,
+            form_defaults,
+            settings: SettingsForm::default(),
         }
     }
 
     /// Initialize the app.
     pub async fn init(&mut self) -> Result<()> {
-        // Check if Docker is available
-        let manager = self.network_manager.lock().await;
+        // Load existing networks
+        self.refresh_networks().await?;
+        if !self.networks.is_empty() {
+            self.selected_network = Some(0);
+        }
+
+        self.connect_docker().await
+    }
+
+    /// Ping Docker and leave the Docker unavailable screen once it responds.
+    ///
+    /// Only the existing connection is pinged, so a retry picks up a daemon
+    /// that has started since without recreating the network manager.
+    async fn connect_docker(&mut self) -> Result<()> {
+        let mut manager = self.network_manager.lock().await;
         if let Err(e) = manager.check_docker().await {
-            self.status_message = Some(format!("Docker not available: {}", e));
-            self.ui_mode = UiMode::Main; // Skip to main even if Docker fails
+            self.docker_error = Some(e.to_string());
+            self.ui_mode = UiMode::DockerUnavailable;
+            return Ok(());
+        }
+        self.docker_error = None;
+        self.status_message = None;
+
+        // Match leftover containers to networks before anything is shown
+        match manager.reconcile().await {
+            Ok(summary) if !summary.orphaned.is_empty() => {
+                self.orphan_count = summary.orphaned.len();
+                self.status_message = Some(format!(
+                    "{} orphaned containers found - press O to remove them",
+                    summary.orphaned.len()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.status_message = Some(format!("Failed to reconcile containers: {e}"));
+            }
         }
         drop(manager);
 
-        // Load existing networks
         self.refresh_networks().await?;
 
         // If networks exist, start in Main view instead of CreateNetwork
-        if !self.networks.is_empty() {
-            self.ui_mode = UiMode::Main;
-            self.selected_network = Some(0);
-        }
+        self.ui_mode = if self.networks.is_empty() {
+            UiMode::CreateNetwork
+        } else {
+            UiMode::Main
+        };
 
         Ok(())
     }
 
     /// Refresh the cached network list.
+    ///
+    /// Active panel filters are applied here, and selections are re-resolved
+    /// by name so they keep pointing at the same item in the filtered list.
     async fn refresh_networks(&mut self) -> Result<()> {
         let manager = self.network_manager.lock().await;
-        self.networks = manager.networks().keys().cloned().collect();
-        self.networks.sort();
+
+        let prev_network = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned());
+        let mut networks: Vec<String> = manager.networks().keys().cloned().collect();
+        networks.sort();
+        self.networks = apply_filter(networks, &self.network_filter);
+        self.selected_network = reselect(
+            &self.networks,
+            prev_network.as_deref(),
+            self.selected_network,
+        );
 
         // Update nodes for selected network
-        if let Some(idx) = self.selected_network {
-            if let Some(network_name) = self.networks.get(idx) {
-                if let Some(network) = manager.get_network(network_name) {
-                    self.nodes = network
-                        .nodes
-                        .iter()
-                        .map(|n| format!("{} ({})", n.name, n.kind))
-                        .collect();
-                }
-            }
+        let prev_node = self
+            .selected_node
+            .and_then(|idx| self.nodes.get(idx).cloned());
+        if let Some(network) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx))
+            .and_then(|name| manager.get_network(name))
+        {
+            let nodes = network
+                .nodes
+                .iter()
+                .map(|n| format!("{} ({})", n.name, n.kind))
+                .collect();
+            self.nodes = apply_filter(nodes, &self.node_filter);
+        } else {
+            self.nodes.clear();
         }
+        drop(manager);
+        self.selected_node = reselect(&self.nodes, prev_node.as_deref(), self.selected_node);
 
         Ok(())
     }
 
+    /// Re-inspect the containers of the nodes in the selected network.
+    async fn refresh_node_statuses(&mut self) {
+        self.node_statuses_refreshed = Some(Instant::now());
+
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            self.node_statuses.clear();
+            return;
+        };
+
+        // Inspecting containers is slow, so it happens without the manager lock
+        let manager = self.network_manager.lock().await;
+        let containers = manager.node_containers(&network_name).unwrap_or_default();
+        let container_manager = manager.container_manager();
+        drop(manager);
+
+        let mut statuses = HashMap::new();
+        for node in &self.nodes {
+            let name = node.split(" (").next().unwrap_or(node);
+            let status = match containers.iter().find(|(n, _)| n == name) {
+                Some((_, container_id)) => {
+                    NetworkManager::container_node_status(
+                        &container_manager,
+                        container_id.as_deref(),
+                    )
+                    .await
+                }
+                None => NodeStatus::Unknown,
+            };
+            statuses.insert(name.to_string(), status);
+        }
+
+        self.node_statuses = statuses;
+    }
+
+    /// Poll the chain tip of the selected network in the background.
+    ///
+    /// The result arrives on `block_height_rx`, so a slow exec never holds
+    /// up drawing. A tick is skipped while the previous poll is still
+    /// running or the manager is busy, and stopped networks are not polled.
+    fn poll_block_height(&mut self) {
+        if self.block_height_in_flight {
+            return;
+        }
+        self.block_height_polled = Some(Instant::now());
+
+        let Some(network_name) = self.selected_network_name() else {
+            self.block_height = None;
+            return;
+        };
+        let Ok(running) = self.network_manager.try_lock().map(|manager| {
+            manager
+                .get_network(&network_name)
+                .is_some_and(|network| network.status == NetworkStatus::Running)
+        }) else {
+            return;
+        };
+        if !running {
+            self.block_height = None;
+            return;
+        }
+
+        self.block_height_in_flight = true;
+        let manager = Arc::clone(&self.network_manager);
+        let block_height_tx = self.block_height_tx.clone();
+        tokio::spawn(async move {
+            let height = manager
+                .lock()
+                .await
+                .block_height(&network_name)
+                .await
+                .ok()
+                .flatten();
+            let _ = block_height_tx.send((network_name, height));
+        });
+    }
+
     /// Run the main application loop
     ///
     /// # Errors
@@ -300,16 +776,68 @@ impl App {
     /// Returns an error if drawing or event handling fails
     pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         while self.running {
-            terminal.draw(|frame| ui::render(frame, self))?;
+            let frame = terminal.draw(|frame| ui::render(frame, self))?;
+            self.log_viewport = ui::logs_viewport(frame.area);
             self.handle_events()?;
 
             // Process any pending log messages
             while let Ok(log_msg) = self.log_rx.try_recv() {
-                self.logs.push(log_msg);
+                let entry = LogEntry::parse(&log_msg);
+                if let Some(progress) = &mut self.progress {
+                    progress.detail = Some(entry.message.clone());
+                }
+                self.logs.push(entry);
+            }
+
+            if let Ok(status) = self.task_rx.try_recv() {
+                self.progress = None;
+                self.status_message = Some(status);
+                self.refresh_networks().await?;
+                if self.quit_after_task {
+                    self.running = false;
+                }
             }
 
-            // Process any pending commands
-            while let Ok(cmd) = self.command_rx.try_recv() {
+            // Answer quit right away, even while a background operation
+            // holds the network manager
+            if self.quit_requested {
+                self.request_quit();
+            }
+
+            // Container inspection only fails while Docker is unreachable,
+            // and waits on the manager while a background operation holds it
+            if self.ui_mode != UiMode::DockerUnavailable
+                && self.progress.is_none()
+                && self
+                    .node_statuses_refreshed
+                    .is_none_or(|at| at.elapsed() >= NODE_STATUS_REFRESH_INTERVAL)
+            {
+                self.refresh_node_statuses().await;
+            }
+
+            // Heights polled for a network that is no longer selected are stale
+            while let Ok((network_name, height)) = self.block_height_rx.try_recv() {
+                self.block_height_in_flight = false;
+                let selected = self.selected_network_name();
+                self.block_height = height
+                    .filter(|_| selected.as_deref() == Some(network_name.as_str()))
+                    .map(|height| (network_name, height));
+            }
+            if self.ui_mode != UiMode::DockerUnavailable
+                && self.progress.is_none()
+                && self
+                    .block_height_polled
+                    .is_none_or(|at| at.elapsed() >= BLOCK_HEIGHT_REFRESH_INTERVAL)
+            {
+                self.poll_block_height();
+            }
+
+            // Process any pending commands, leaving them queued while a
+            // background operation holds the network manager
+            while self.progress.is_none() {
+                let Ok(cmd) = self.command_rx.try_recv() else {
+                    break;
+                };
                 match cmd {
                     AppCommand::CreateNetwork {
                         name,
@@ -317,6 +845,7 @@ impl App {
                         alias,
                         lnd_version_idx,
                         btc_version_idx,
+                        topology,
                     } => {
                         self.create_network(
                             name,
@@ -324,15 +853,19 @@ impl App {
                             alias,
                             lnd_version_idx,
                             btc_version_idx,
+                            topology,
                         )
                         .await?;
                     }
                     AppCommand::StartNetwork => {
-                        self.start_selected_network().await?;
+                        self.start_selected_network();
                     }
                     AppCommand::StopNetwork => {
                         self.stop_selected_network().await?;
                     }
+                    AppCommand::ResetNetwork => {
+                        self.reset_selected_network().await?;
+                    }
                     AppCommand::DeleteNetwork => {
                         self.delete_selected_network().await?;
                     }
@@ -343,22 +876,37 @@ impl App {
                         self.delete_lightning_node(&node_name).await?;
                     }
                     AppCommand::ViewNodeDetails => {
-                        self.view_node_details().await?;
+                        self.view_node_details(false).await?;
                     }
-                    AppCommand::MineBlocks { num_blocks } => {
-                        self.mine_blocks(num_blocks).await?;
+                    AppCommand::RefreshNodeDetails => {
+                        self.view_node_details(true).await?;
+                    }
+                    AppCommand::ExportNodeCredentials => {
+                        self.export_node_credentials().await;
+                    }
+                    AppCommand::ShowLndconnect => {
+                        self.show_lndconnect().await;
+                    }
+                    AppCommand::MineBlocks {
+                        num_blocks,
+                        to_node,
+                    } => {
+                        self.mine_blocks(num_blocks, to_node.as_deref()).await?;
                     }
                     AppCommand::FundWallet { node_name, amount } => {
-                        self.fund_wallet(&node_name, amount).await?;
+                        self.fund_wallet(&node_name, amount);
+                    }
+                    AppCommand::FundAllNodes { amount } => {
+                        self.fund_all_nodes(amount).await?;
                     }
                     AppCommand::OpenChannel {
                         from_node,
                         to_node,
                         capacity,
                         push_amount,
+                        private,
                     } => {
-                        self.open_channel(&from_node, &to_node, capacity, push_amount)
-                            .await?;
+                        self.open_channel(&from_node, &to_node, capacity, push_amount, private);
                     }
                     AppCommand::CloseChannel {
                         node_name,
@@ -368,6 +916,15 @@ impl App {
                         self.close_channel(&node_name, &channel_point, force)
                             .await?;
                     }
+                    AppCommand::LoadCloseChannels { node_name } => {
+                        self.load_close_channels(&node_name).await;
+                    }
+                    AppCommand::AbandonChannel {
+                        node_name,
+                        channel_point,
+                    } => {
+                        self.abandon_channel(&node_name, &channel_point).await;
+                    }
                     AppCommand::SendPayment {
                         from_node,
                         to_node,
@@ -377,15 +934,77 @@ impl App {
                         self.send_payment(&from_node, &to_node, amount, memo.as_deref())
                             .await?;
                     }
+                    AppCommand::ProbeRoute {
+                        from_node,
+                        to_node,
+                        amount,
+                    } => {
+                        self.probe_route(&from_node, &to_node, amount).await;
+                    }
+                    AppCommand::DecodeInvoice {
+                        from_node,
+                        payment_request,
+                    } => {
+                        self.decode_invoice(&from_node, &payment_request).await;
+                    }
+                    AppCommand::PayInvoice {
+                        from_node,
+                        payment_request,
+                    } => {
+                        self.pay_invoice(&from_node, &payment_request);
+                    }
                     AppCommand::SyncGraph => {
-                        self.sync_graph().await?;
+                        self.sync_graph();
                     }
                     AppCommand::SyncChain => {
                         self.sync_chain().await?;
                     }
+                    AppCommand::ViewGraph => {
+                        self.view_graph().await;
+                    }
+                    AppCommand::ViewNetworkSummary => {
+                        self.view_network_summary().await;
+                    }
+                    AppCommand::ViewPaymentHistory => {
+                        self.view_payment_history().await;
+                    }
+                    AppCommand::ExportNetwork => {
+                        self.export_network().await;
+                    }
+                    AppCommand::RefreshNetworks => {
+                        self.refresh_networks().await?;
+                    }
+                    AppCommand::ToggleBlockGenerator => {
+                        self.toggle_block_generator().await;
+                    }
+                    AppCommand::CycleBlockGeneratorMode => {
+                        self.cycle_block_generator_mode().await;
+                    }
+                    AppCommand::RetryDocker => {
+                        self.connect_docker().await?;
+                    }
+                    AppCommand::ApplySettings(config) => {
+                        self.network_manager.lock().await.apply_config(*config);
+                    }
+                    AppCommand::PruneOrphans => {
+                        self.prune_orphans().await;
+                    }
+                    AppCommand::RenameNode { old_name, new_name } => {
+                        self.rename_node(&old_name, &new_name).await?;
+                    }
+                    AppCommand::StartNode { node_name } => {
+                        self.set_node_running(&node_name, true).await?;
+                    }
+                    AppCommand::StopNode { node_name } => {
+                        self.set_node_running(&node_name, false).await?;
+                    }
+                    AppCommand::SignMessage { node_name, message } => {
+                        self.sign_message(&node_name, &message).await;
+                    }
                 }
                 // Redraw after processing command
-                terminal.draw(|frame| ui::render(frame, self))?;
+                let frame = terminal.draw(|frame| ui::render(frame, self))?;
+                self.log_viewport = ui::logs_viewport(frame.area);
             }
         }
         Ok(())
@@ -409,9 +1028,105 @@ impl App {
             UiMode::NodeDetails => self.handle_node_details_key(code),
             UiMode::MineBlocks => self.handle_mine_blocks_key(code),
             UiMode::FundWallet => self.handle_fund_wallet_key(code),
+            UiMode::FundAllNodes => self.handle_fund_all_nodes_key(code),
+            UiMode::RenameNode => self.handle_rename_node_key(code),
+            UiMode::SignMessage => self.handle_sign_message_key(code),
             UiMode::OpenChannel => self.handle_open_channel_key(code),
             UiMode::CloseChannel => self.handle_close_channel_key(code),
             UiMode::SendPayment => self.handle_send_payment_key(code),
+            UiMode::GraphView => self.handle_graph_view_key(code),
+            UiMode::NetworkSummary => self.handle_network_summary_key(code),
+            UiMode::PaymentHistory => self.handle_payment_history_key(code),
+            UiMode::ConfirmDelete => self.handle_confirm_key(code),
+            UiMode::ConfirmInvoice => self.handle_confirm_invoice_key(code),
+            UiMode::ConfirmPayment => self.handle_confirm_payment_key(code),
+            UiMode::ConfirmQuit => self.handle_confirm_quit_key(code),
+            UiMode::Filter => self.handle_filter_key(code),
+            UiMode::Help => self.handle_help_key(code),
+            UiMode::Settings => self.handle_settings_key(code),
+            UiMode::DockerUnavailable => self.handle_docker_unavailable_key(code),
+        }
+    }
+
+    /// Run a long operation in the background so the UI keeps drawing.
+    ///
+    /// `label` shows next to a spinner in the status bar until `task`
+    /// resolves to the final status message.
+    fn spawn_task<F, Fut>(&mut self, label: String, task: F)
+    where
+        F: FnOnce(Arc<Mutex<NetworkManager>>) -> Fut,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        self.progress = Some(Progress {
+            label,
+            detail: None,
+            started: Instant::now(),
+        });
+        let task = task(Arc::clone(&self.network_manager));
+        let task_tx = self.task_tx.clone();
+        tokio::spawn(async move {
+            let _ = task_tx.send(task.await);
+        });
+    }
+
+    /// Ask the user to confirm a destructive command before dispatching it.
+    fn request_confirmation(&mut self, prompt: String, command: AppCommand) {
+        self.confirm_prompt = prompt;
+        self.confirm_command = Some(command);
+        self.ui_mode = UiMode::ConfirmDelete;
+    }
+
+    /// Quit, first asking what to do with any running networks.
+    ///
+    /// While a background operation holds the network manager, the dialog
+    /// waits for it to finish, since it needs to know what is running.
+    fn request_quit(&mut self) {
+        let Ok(manager) = self.network_manager.try_lock() else {
+            return;
+        };
+        self.quit_requested = false;
+        self.quit_running_networks = manager.running_networks();
+        self.quit_stop_default = manager.stop_on_exit();
+        drop(manager);
+
+        if self.quit_running_networks.is_empty() {
+            self.running = false;
+        } else {
+            self.ui_mode = UiMode::ConfirmQuit;
+        }
+    }
+
+    /// Stop every running network in the background, then exit.
+    fn stop_all_and_quit(&mut self) {
+        self.ui_mode = UiMode::Main;
+        self.quit_after_task = true;
+        self.spawn_task(
+            "Stopping running networks...".to_string(),
+            |manager| async move {
+                match manager.lock().await.stop_all_networks().await {
+                    Ok(stopped) => format!("Stopped {stopped} networks"),
+                    Err(e) => format!("Failed to stop networks: {e}"),
+                }
+            },
+        );
+    }
+
+    fn handle_confirm_quit_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('s' | 'S') => self.stop_all_and_quit(),
+            KeyCode::Char('l' | 'L') => self.running = false,
+            KeyCode::Enter => {
+                if self.quit_stop_default {
+                    self.stop_all_and_quit();
+                } else {
+                    self.running = false;
+                }
+            }
+            KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => {
+                self.quit_running_networks.clear();
+                self.ui_mode = UiMode::Main;
+            }
+            _ => {}
         }
     }
 
@@ -419,13 +1134,15 @@ impl App {
         use polar_nodes::{BITCOIN_VERSIONS, LND_VERSIONS};
 
         match code {
-            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.quit_requested = true;
+            }
             KeyCode::Tab | KeyCode::Down => {
-                self.create_form_field = (self.create_form_field + 1) % 5;
+                self.create_form_field = (self.create_form_field + 1) % 6;
             }
             KeyCode::BackTab | KeyCode::Up => {
                 self.create_form_field = if self.create_form_field == 0 {
-                    4
+                    5
                 } else {
                     self.create_form_field - 1
                 };
@@ -466,6 +1183,10 @@ impl App {
                             self.create_btc_version_idx -= 1;
                         }
                     }
+                    5 => {
+                        // Topology
+                        self.create_topology_idx = self.create_topology_idx.saturating_sub(1);
+                    }
                     _ => {}
                 }
             }
@@ -489,6 +1210,10 @@ impl App {
                             self.create_btc_version_idx += 1;
                         }
                     }
+                    5 if self.create_topology_idx < Topology::all().len() => {
+                        // Topology
+                        self.create_topology_idx += 1;
+                    }
                     _ => {}
                 }
             }
@@ -505,6 +1230,7 @@ impl App {
                         },
                         lnd_version_idx: self.create_lnd_version_idx,
                         btc_version_idx: self.create_btc_version_idx,
+                        topology: self.create_topology(),
                     });
                 }
             }
@@ -512,9 +1238,40 @@ impl App {
         }
     }
 
+    /// Topology selected in the create network form, if any.
+    #[must_use]
+    pub fn create_topology(&self) -> Option<Topology> {
+        self.create_topology_idx
+            .checked_sub(1)
+            .and_then(|idx| Topology::all().get(idx))
+            .copied()
+    }
+
     fn handle_main_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            KeyCode::Esc if !self.active_filter().is_empty() => {
+                self.active_filter_mut().clear();
+                let _ = self.command_tx.send(AppCommand::RefreshNetworks);
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.quit_requested = true;
+            }
+            KeyCode::Char('w') if self.active_panel == ActivePanel::Logs => {
+                // Toggle hiding log lines below warn
+                self.log_min_level = if self.log_min_level == LogLevel::Warn {
+                    LogLevel::Trace
+                } else {
+                    LogLevel::Warn
+                };
+            }
+            KeyCode::Char('/') if self.active_panel != ActivePanel::Logs => {
+                self.ui_mode = UiMode::Filter;
+            }
+            KeyCode::Char('?') => {
+                self.help_scroll = 0;
+                self.ui_mode = UiMode::Help;
+            }
+            KeyCode::Char(',') => self.open_settings(),
             KeyCode::Tab => self.next_panel(),
             KeyCode::BackTab => self.prev_panel(),
             KeyCode::Up | KeyCode::Char('k') => self.select_prev(),
@@ -527,6 +1284,7 @@ impl App {
                 self.create_lnd_count = 2;
                 self.create_lnd_version_idx = 0;
                 self.create_btc_version_idx = 0;
+                self.create_topology_idx = 0;
                 self.create_form_field = 0;
             }
             KeyCode::Enter | KeyCode::Char('s') => {
@@ -557,13 +1315,44 @@ impl App {
                 }
             }
             KeyCode::Char('d') | KeyCode::Delete => {
-                // Delete network - send async command
+                // Delete network - confirm before sending async command
                 if self.active_panel == ActivePanel::Networks {
-                    if self.selected_network.is_some() {
-                        let _ = self.command_tx.send(AppCommand::DeleteNetwork);
+                    if let Some(network_name) = self
+                        .selected_network
+                        .and_then(|idx| self.networks.get(idx))
+                        .cloned()
+                    {
+                        self.request_confirmation(
+                            format!("Delete network '{network_name}' and all of its nodes?"),
+                            AppCommand::DeleteNetwork,
+                        );
                     }
                 }
             }
+            KeyCode::Char('R') if self.active_panel == ActivePanel::Networks => {
+                // Reset network - confirm before wiping chain data
+                if let Some(network_name) = self
+                    .selected_network
+                    .and_then(|idx| self.networks.get(idx))
+                    .cloned()
+                {
+                    self.request_confirmation(
+                        format!("Reset network '{network_name}' and wipe all chain data?"),
+                        AppCommand::ResetNetwork,
+                    );
+                }
+            }
+            KeyCode::Char('O') => {
+                // Remove containers that belong to no network
+                if self.orphan_count > 0 {
+                    self.request_confirmation(
+                        format!("Remove {} orphaned containers?", self.orphan_count),
+                        AppCommand::PruneOrphans,
+                    );
+                } else {
+                    self.status_message = Some("No orphaned containers".to_string());
+                }
+            }
             KeyCode::Char('a') => {
                 // Add Lightning node to selected network
                 if self.active_panel == ActivePanel::Networks {
@@ -592,9 +1381,10 @@ impl App {
                                 .to_string();
 
                             if !node_name.is_empty() {
-                                let _ = self
-                                    .command_tx
-                                    .send(AppCommand::DeleteLightningNode { node_name });
+                                self.request_confirmation(
+                                    format!("Delete node '{node_name}'?"),
+                                    AppCommand::DeleteLightningNode { node_name },
+                                );
                             }
                         }
                     }
@@ -604,7 +1394,9 @@ impl App {
                 // Mine blocks - only available when network is selected
                 if self.selected_network.is_some() {
                     self.ui_mode = UiMode::MineBlocks;
-                    self.mine_blocks_count = "100".to_string();
+                    self.mine_blocks_count = self.form_defaults.mine_blocks.clone();
+                    self.mine_target_idx = 0;
+                    self.mine_form_field = 0;
                 }
             }
             KeyCode::Char('f') => {
@@ -612,18 +1404,24 @@ impl App {
                 if self.selected_network.is_some() && !self.nodes.is_empty() {
                     self.ui_mode = UiMode::FundWallet;
                     self.fund_node_idx = 0;
-                    self.fund_amount = "1.0".to_string();
+                    self.fund_amount = self.form_defaults.fund_amount.clone();
                     self.fund_form_field = 0;
                 }
             }
+            KeyCode::Char('F') if self.selected_network.is_some() && !self.nodes.is_empty() => {
+                // Fund every LND node
+                self.ui_mode = UiMode::FundAllNodes;
+                self.fund_all_amount = self.form_defaults.fund_amount.clone();
+            }
             KeyCode::Char('c') => {
                 // Open channel - need at least 2 LND nodes
                 if self.selected_network.is_some() && self.nodes.len() >= 2 {
                     self.ui_mode = UiMode::OpenChannel;
                     self.channel_from_idx = 0;
                     self.channel_to_idx = 1;
-                    self.channel_capacity = "1000000".to_string();
-                    self.channel_push_amount = "500000".to_string();
+                    self.channel_capacity = self.form_defaults.channel_capacity.clone();
+                    self.channel_push_amount = self.form_defaults.push_amount.clone();
+                    self.channel_private = false;
                     self.channel_form_field = 0;
                 }
             }
@@ -632,9 +1430,9 @@ impl App {
                 if self.selected_network.is_some() && !self.nodes.is_empty() {
                     self.ui_mode = UiMode::CloseChannel;
                     self.close_channel_node_idx = 0;
-                    self.close_channel_point.clear();
-                    self.close_channel_force = false;
+                    self.close_channel_mode = CloseMode::Cooperative;
                     self.close_channel_form_field = 0;
+                    self.request_close_channels();
                 }
             }
             KeyCode::Char('p') => {
@@ -643,8 +1441,9 @@ impl App {
                     self.ui_mode = UiMode::SendPayment;
                     self.payment_from_idx = 0;
                     self.payment_to_idx = 1;
-                    self.payment_amount = "10000".to_string();
+                    self.payment_amount = self.form_defaults.payment_amount.clone();
                     self.payment_memo.clear();
+                    self.payment_invoice.clear();
                     self.payment_form_field = 0;
                 }
             }
@@ -660,6 +1459,77 @@ impl App {
                     let _ = self.command_tx.send(AppCommand::SyncChain);
                 }
             }
+            KeyCode::Char('v') if self.selected_network.is_some() => {
+                // View channel graph
+                let _ = self.command_tx.send(AppCommand::ViewGraph);
+            }
+            KeyCode::Char('L') if self.selected_network.is_some() => {
+                // View liquidity totals
+                let _ = self.command_tx.send(AppCommand::ViewNetworkSummary);
+            }
+            KeyCode::Char('e') if self.selected_network.is_some() => {
+                // Export network to a portable file
+                let _ = self.command_tx.send(AppCommand::ExportNetwork);
+            }
+            KeyCode::Char('b') if self.selected_network.is_some() => {
+                // Toggle timed block generation
+                let _ = self.command_tx.send(AppCommand::ToggleBlockGenerator);
+            }
+            KeyCode::Char('B') if self.selected_network.is_some() => {
+                // Switch when the running block generator mines
+                let _ = self.command_tx.send(AppCommand::CycleBlockGeneratorMode);
+            }
+            KeyCode::Char('h')
+                if self.active_panel == ActivePanel::Nodes && self.selected_node.is_some() =>
+            {
+                // View invoice and payment history of the selected node
+                let _ = self.command_tx.send(AppCommand::ViewPaymentHistory);
+            }
+            KeyCode::Char('N') if self.active_panel == ActivePanel::Nodes => {
+                // Rename the selected node
+                if let Some(node_name) = self
+                    .selected_node
+                    .and_then(|idx| self.nodes.get(idx))
+                    .and_then(|display| display.split(" (").next())
+                    .map(ToString::to_string)
+                {
+                    self.rename_input.clone_from(&node_name);
+                    self.rename_from = node_name;
+                    self.ui_mode = UiMode::RenameNode;
+                }
+            }
+            KeyCode::Char('M') if self.active_panel == ActivePanel::Nodes => {
+                // Sign a message with the selected LND node
+                if let Some(node_name) = self
+                    .selected_node
+                    .and_then(|idx| self.nodes.get(idx))
+                    .and_then(|display| display.strip_suffix(" (LND)"))
+                    .map(ToString::to_string)
+                {
+                    self.sign_node = node_name;
+                    self.sign_message.clear();
+                    self.sign_signature = None;
+                    self.ui_mode = UiMode::SignMessage;
+                } else {
+                    self.status_message = Some("Select an LND node to sign with".to_string());
+                }
+            }
+            KeyCode::Char(c @ ('S' | 'X')) if self.active_panel == ActivePanel::Nodes => {
+                // Start or stop only the selected node
+                if let Some(node_name) = self
+                    .selected_node
+                    .and_then(|idx| self.nodes.get(idx))
+                    .and_then(|display| display.split(" (").next())
+                    .map(ToString::to_string)
+                {
+                    let command = if c == 'S' {
+                        AppCommand::StartNode { node_name }
+                    } else {
+                        AppCommand::StopNode { node_name }
+                    };
+                    let _ = self.command_tx.send(command);
+                }
+            }
             _ => {}
         }
     }
@@ -672,6 +1542,9 @@ impl App {
                 self.node_info = None;
                 self.node_info_scroll = 0;
                 self.selected_channel_idx = None;
+                self.selected_endpoint_idx = 0;
+                self.node_credentials = None;
+                self.lndconnect_url = None;
             }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.node_info_scroll = self.node_info_scroll.saturating_sub(1);
@@ -679,6 +1552,33 @@ impl App {
             KeyCode::Down | KeyCode::Char('j') => {
                 self.node_info_scroll = self.node_info_scroll.saturating_add(1);
             }
+            KeyCode::Char('u') => self.display_unit = self.display_unit.toggle(),
+            KeyCode::Char('r') => {
+                let _ = self.command_tx.send(AppCommand::RefreshNodeDetails);
+            }
+            KeyCode::Tab => {
+                if let Some(ref info) = self.node_info {
+                    let count = info.endpoints().len().max(1);
+                    self.selected_endpoint_idx = (self.selected_endpoint_idx + 1) % count;
+                }
+            }
+            KeyCode::Char('y') => self.copy_selected_endpoint(),
+            KeyCode::Char('E') => {
+                if matches!(self.node_info, Some(NodeInfo::Lnd(_))) {
+                    let _ = self.command_tx.send(AppCommand::ExportNodeCredentials);
+                } else {
+                    self.status_message =
+                        Some("Only LND nodes have a TLS cert and macaroon to export".to_string());
+                }
+            }
+            KeyCode::Char('Q') => {
+                if matches!(self.node_info, Some(NodeInfo::Lnd(_))) {
+                    let _ = self.command_tx.send(AppCommand::ShowLndconnect);
+                } else {
+                    self.status_message =
+                        Some("Only LND nodes can be paired with lndconnect".to_string());
+                }
+            }
             KeyCode::Char('n') => {
                 // Next channel (select next)
                 if let Some(NodeInfo::Lnd(ref info)) = self.node_info {
@@ -716,31 +1616,21 @@ impl App {
                         if let Some(channel) = info.channels.get(idx) {
                             let channel_point = channel.channel_point.clone();
                             let channel_point_preview = if channel_point.len() > 20 {
-                                format!("{}...", &channel_point[..20])
+                                format!("{}...", truncate_str(&channel_point, 20))
                             } else {
                                 channel_point.clone()
                             };
 
                             // Try to copy to clipboard
-                            match arboard::Clipboard::new() {
-                                Ok(mut clipboard) => match clipboard.set_text(channel_point) {
-                                    Ok(_) => {
-                                        self.status_message = Some(format!(
-                                            "Copied to clipboard: {}",
-                                            channel_point_preview
-                                        ));
-                                    }
-                                    Err(e) => {
-                                        self.status_message = Some(format!(
-                                            "Clipboard copy failed: {}. Channel point is shown in full above for manual copy.",
-                                            e
-                                        ));
-                                    }
-                                },
+                            match clipboard::copy(&channel_point) {
+                                Ok(()) => {
+                                    self.status_message = Some(format!(
+                                        "Copied to clipboard: {channel_point_preview}"
+                                    ));
+                                }
                                 Err(e) => {
                                     self.status_message = Some(format!(
-                                        "Clipboard unavailable: {}. Channel point is shown in full above for manual copy.",
-                                        e
+                                        "{e}. Channel point is shown in full above for manual copy."
                                     ));
                                 }
                             }
@@ -755,30 +1645,330 @@ impl App {
         }
     }
 
-    fn handle_mine_blocks_key(&mut self, code: KeyCode) {
+    fn handle_filter_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Char('q') | KeyCode::Esc => {
+            KeyCode::Esc => {
+                self.active_filter_mut().clear();
                 self.ui_mode = UiMode::Main;
             }
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.mine_blocks_count.push(c);
+            KeyCode::Enter => {
+                self.ui_mode = UiMode::Main;
+                return;
             }
             KeyCode::Backspace => {
-                self.mine_blocks_count.pop();
+                self.active_filter_mut().pop();
             }
-            KeyCode::Enter => {
-                if let Ok(num_blocks) = self.mine_blocks_count.parse::<u32>() {
-                    let _ = self.command_tx.send(AppCommand::MineBlocks { num_blocks });
-                    self.ui_mode = UiMode::Main;
-                }
+            KeyCode::Char(c) => {
+                self.active_filter_mut().push(c);
             }
-            _ => {}
+            _ => return,
         }
+        let _ = self.command_tx.send(AppCommand::RefreshNetworks);
     }
 
-    fn handle_fund_wallet_key(&mut self, code: KeyCode) {
+    /// Filter query for the active panel.
+    fn active_filter(&self) -> &str {
+        match self.active_panel {
+            ActivePanel::Nodes => &self.node_filter,
+            ActivePanel::Networks | ActivePanel::Logs => &self.network_filter,
+        }
+    }
+
+    /// Mutable filter query for the active panel.
+    const fn active_filter_mut(&mut self) -> &mut String {
+        match self.active_panel {
+            ActivePanel::Nodes => &mut self.node_filter,
+            ActivePanel::Networks | ActivePanel::Logs => &mut self.network_filter,
+        }
+    }
+
+    fn handle_confirm_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Char('q') | KeyCode::Esc => {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                if let Some(command) = self.confirm_command.take() {
+                    let _ = self.command_tx.send(command);
+                }
+                self.confirm_prompt.clear();
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => {
+                self.confirm_command = None;
+                self.confirm_prompt.clear();
+                self.ui_mode = UiMode::Main;
+                self.status_message = Some("Cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_confirm_invoice_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                if self.decoded_invoice.take().is_some() {
+                    if let Some(from) = self.nodes.get(self.payment_from_idx).cloned() {
+                        let _ = self.command_tx.send(AppCommand::PayInvoice {
+                            from_node: from,
+                            payment_request: self.payment_invoice.trim().to_string(),
+                        });
+                    }
+                }
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => {
+                self.decoded_invoice = None;
+                self.ui_mode = UiMode::Main;
+                self.status_message = Some("Cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_confirm_payment_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                if self.route_estimate.take().is_some() {
+                    if let (Some(from), Some(to), Ok(amount)) = (
+                        self.nodes.get(self.payment_from_idx).cloned(),
+                        self.nodes.get(self.payment_to_idx).cloned(),
+                        self.payment_amount.parse::<u64>(),
+                    ) {
+                        let memo = if self.payment_memo.is_empty() {
+                            None
+                        } else {
+                            Some(self.payment_memo.clone())
+                        };
+                        let _ = self.command_tx.send(AppCommand::SendPayment {
+                            from_node: from,
+                            to_node: to,
+                            amount,
+                            memo,
+                        });
+                    }
+                }
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => {
+                self.route_estimate = None;
+                self.ui_mode = UiMode::Main;
+                self.status_message = Some("Cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the settings screen on the config saved on disk.
+    ///
+    /// The on-disk config is edited rather than the running one so a
+    /// `--data-dir` override is never written back.
+    fn open_settings(&mut self) {
+        match Config::load() {
+            Ok(config) => {
+                self.settings = SettingsForm::new(config);
+                self.ui_mode = UiMode::Settings;
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load config: {e}")),
+        }
+    }
+
+    fn handle_settings_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.ui_mode = UiMode::Main,
+            KeyCode::Tab | KeyCode::Down => self.settings.next_field(),
+            KeyCode::BackTab | KeyCode::Up => self.settings.prev_field(),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
+                if !SettingsForm::is_text(self.settings.field) =>
+            {
+                self.settings.toggle();
+            }
+            KeyCode::Char(c) => self.settings.push(c),
+            KeyCode::Backspace => self.settings.pop(),
+            KeyCode::Enter => self.save_settings(),
+            _ => {}
+        }
+    }
+
+    /// Validate and save the settings form, then apply what can change live.
+    fn save_settings(&mut self) {
+        let config = match self.settings.to_config() {
+            Ok(config) => config,
+            Err(e) => {
+                self.settings.error = Some(e);
+                return;
+            }
+        };
+        if let Err(e) = config.save() {
+            self.settings.error = Some(format!("Failed to save config: {e}"));
+            return;
+        }
+
+        self.form_defaults = FormDefaults::from_config(&config);
+        self.display_unit = DisplayUnit::from_btc(config.display_btc);
+        let _ = self
+            .command_tx
+            .send(AppCommand::ApplySettings(Box::new(config)));
+        self.ui_mode = UiMode::Main;
+        self.status_message =
+            Some("Settings saved; data dir and RPC credentials apply after a restart".to_string());
+    }
+
+    const fn handle_help_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('?' | 'q') | KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.help_scroll = self.help_scroll.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.help_scroll = self.help_scroll.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
+    const fn handle_network_summary_key(&mut self, code: KeyCode) {
+        if matches!(code, KeyCode::Char('q') | KeyCode::Esc) {
+            self.ui_mode = UiMode::Main;
+            self.network_summary = None;
+        }
+    }
+
+    fn handle_graph_view_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+                self.graph_info = None;
+                self.graph_scroll = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.graph_scroll = self.graph_scroll.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.graph_scroll = self.graph_scroll.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_payment_history_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+                self.payment_history = None;
+                self.payment_history_scroll = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.payment_history_scroll = self.payment_history_scroll.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.payment_history_scroll = self.payment_history_scroll.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_mine_blocks_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Tab | KeyCode::Down | KeyCode::BackTab | KeyCode::Up => {
+                self.mine_form_field = 1 - self.mine_form_field;
+            }
+            KeyCode::Left if self.mine_form_field == 1 => {
+                self.mine_target_idx = self.mine_target_idx.saturating_sub(1);
+            }
+            KeyCode::Right
+                if self.mine_form_field == 1 && self.mine_target_idx < self.nodes.len() =>
+            {
+                self.mine_target_idx += 1;
+            }
+            KeyCode::Char(c) if self.mine_form_field == 0 && c.is_ascii_digit() => {
+                self.mine_blocks_count.push(c);
+            }
+            KeyCode::Backspace if self.mine_form_field == 0 => {
+                self.mine_blocks_count.pop();
+            }
+            KeyCode::Enter => {
+                if let Ok(num_blocks) = self.mine_blocks_count.parse::<u32>() {
+                    let to_node = self
+                        .mine_target_idx
+                        .checked_sub(1)
+                        .and_then(|idx| self.nodes.get(idx))
+                        .cloned();
+                    let _ = self.command_tx.send(AppCommand::MineBlocks {
+                        num_blocks,
+                        to_node,
+                    });
+                    self.ui_mode = UiMode::Main;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_docker_unavailable_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('r') => {
+                self.docker_error = Some("Retrying...".to_string());
+                let _ = self.command_tx.send(AppCommand::RetryDocker);
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            _ => {}
+        }
+    }
+
+    fn handle_rename_node_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Enter => {
+                let new_name = self.rename_input.trim().to_string();
+                if !new_name.is_empty() && new_name != self.rename_from {
+                    let _ = self.command_tx.send(AppCommand::RenameNode {
+                        old_name: self.rename_from.clone(),
+                        new_name,
+                    });
+                }
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Backspace => {
+                self.rename_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.rename_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_sign_message_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Enter if !self.sign_message.is_empty() => {
+                let _ = self.command_tx.send(AppCommand::SignMessage {
+                    node_name: self.sign_node.clone(),
+                    message: self.sign_message.clone(),
+                });
+            }
+            KeyCode::Backspace => {
+                self.sign_message.pop();
+                self.sign_signature = None;
+            }
+            KeyCode::Char(c) => {
+                self.sign_message.push(c);
+                self.sign_signature = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_fund_wallet_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
                 self.ui_mode = UiMode::Main;
             }
             KeyCode::Tab | KeyCode::Down => {
@@ -819,17 +2009,40 @@ impl App {
         }
     }
 
+    fn handle_fund_all_nodes_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.ui_mode = UiMode::Main;
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+                self.fund_all_amount.push(c);
+            }
+            KeyCode::Backspace => {
+                self.fund_all_amount.pop();
+            }
+            KeyCode::Enter => {
+                if let Ok(amount) = self.fund_all_amount.parse::<f64>() {
+                    if amount > 0.0 {
+                        let _ = self.command_tx.send(AppCommand::FundAllNodes { amount });
+                        self.ui_mode = UiMode::Main;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn handle_open_channel_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Char('q') | KeyCode::Esc => {
                 self.ui_mode = UiMode::Main;
             }
             KeyCode::Tab | KeyCode::Down => {
-                self.channel_form_field = (self.channel_form_field + 1) % 4;
+                self.channel_form_field = (self.channel_form_field + 1) % 5;
             }
             KeyCode::BackTab | KeyCode::Up => {
                 self.channel_form_field = if self.channel_form_field == 0 {
-                    3
+                    4
                 } else {
                     self.channel_form_field - 1
                 };
@@ -837,6 +2050,7 @@ impl App {
             KeyCode::Left => match self.channel_form_field {
                 0 if self.channel_from_idx > 0 => self.channel_from_idx -= 1,
                 1 if self.channel_to_idx > 0 => self.channel_to_idx -= 1,
+                4 => self.channel_private = !self.channel_private,
                 _ => {}
             },
             KeyCode::Right => match self.channel_form_field {
@@ -846,8 +2060,12 @@ impl App {
                 1 if self.channel_to_idx < self.nodes.len().saturating_sub(1) => {
                     self.channel_to_idx += 1
                 }
+                4 => self.channel_private = !self.channel_private,
                 _ => {}
             },
+            KeyCode::Char(' ') if self.channel_form_field == 4 => {
+                self.channel_private = !self.channel_private;
+            }
             KeyCode::Char(c) if c.is_ascii_digit() => match self.channel_form_field {
                 2 => self.channel_capacity.push(c),
                 3 => self.channel_push_amount.push(c),
@@ -877,6 +2095,7 @@ impl App {
                             to_node: to,
                             capacity,
                             push_amount,
+                            private: self.channel_private,
                         });
                         self.ui_mode = UiMode::Main;
                     }
@@ -902,20 +2121,29 @@ impl App {
                 };
             }
             KeyCode::Left => match self.close_channel_form_field {
-                0 if self.close_channel_node_idx > 0 => self.close_channel_node_idx -= 1,
-                2 => self.close_channel_force = false,
+                0 if self.close_channel_node_idx > 0 => {
+                    self.close_channel_node_idx -= 1;
+                    self.request_close_channels();
+                }
+                1 => self.select_close_channel(self.close_channel_idx.checked_sub(1)),
+                2 => self.close_channel_mode = self.close_channel_mode.prev(),
                 _ => {}
             },
             KeyCode::Right => match self.close_channel_form_field {
                 0 if self.close_channel_node_idx < self.nodes.len().saturating_sub(1) => {
-                    self.close_channel_node_idx += 1
+                    self.close_channel_node_idx += 1;
+                    self.request_close_channels();
                 }
-                2 => self.close_channel_force = true,
+                1 => self.select_close_channel(Some(self.close_channel_idx + 1)),
+                2 => self.close_channel_mode = self.close_channel_mode.next(),
                 _ => {}
             },
             KeyCode::Char(c) if self.close_channel_form_field == 1 => {
-                // Allow typing channel point (txid:index)
+                // Allow typing channel point (txid:index), replacing a picked one
                 if c.is_ascii_hexdigit() || c == ':' {
+                    if self.picked_close_channel().is_some() {
+                        self.close_channel_point.clear();
+                    }
                     self.close_channel_point.push(c);
                 }
             }
@@ -924,13 +2152,48 @@ impl App {
             }
             KeyCode::Enter => {
                 if !self.close_channel_point.is_empty() {
+                    // Reject typos here rather than as an lncli error
+                    let channel_point = match self.close_channel_point.parse::<ChannelPoint>() {
+                        Ok(channel_point) => channel_point.to_string(),
+                        Err(e) => {
+                            self.status_message = Some(format!("Failed to close channel: {e}"));
+                            return;
+                        }
+                    };
                     if let Some(node_name) = self.nodes.get(self.close_channel_node_idx).cloned() {
-                        let _ = self.command_tx.send(AppCommand::CloseChannel {
-                            node_name,
-                            channel_point: self.close_channel_point.clone(),
-                            force: self.close_channel_force,
-                        });
-                        self.ui_mode = UiMode::Main;
+                        match self.close_channel_mode {
+                            CloseMode::Cooperative => {
+                                let _ = self.command_tx.send(AppCommand::CloseChannel {
+                                    node_name,
+                                    channel_point,
+                                    force: false,
+                                });
+                                self.ui_mode = UiMode::Main;
+                            }
+                            CloseMode::Force => {
+                                // Force closes lock funds on-chain, so ask first
+                                self.request_confirmation(
+                                    format!("Force close channel {channel_point}?"),
+                                    AppCommand::CloseChannel {
+                                        node_name,
+                                        channel_point,
+                                        force: true,
+                                    },
+                                );
+                            }
+                            CloseMode::Abandon => {
+                                // Abandoning forgets the channel for good, so ask first
+                                self.request_confirmation(
+                                    format!(
+                                        "Abandon channel {channel_point}? This only removes it from the node's local state and broadcasts nothing."
+                                    ),
+                                    AppCommand::AbandonChannel {
+                                        node_name,
+                                        channel_point,
+                                    },
+                                );
+                            }
+                        }
                     }
                 }
             }
@@ -938,17 +2201,57 @@ impl App {
         }
     }
 
+    /// Clear the close dialog's channel list and fetch the selected node's
+    /// channels. Only LND nodes have channels to list.
+    fn request_close_channels(&mut self) {
+        self.close_channel_point.clear();
+        self.close_channel_idx = 0;
+        match self.nodes.get(self.close_channel_node_idx) {
+            Some(node_name) if node_name.ends_with(" (LND)") => {
+                self.close_channels = None;
+                let _ = self.command_tx.send(AppCommand::LoadCloseChannels {
+                    node_name: node_name.clone(),
+                });
+            }
+            _ => self.close_channels = Some(Vec::new()),
+        }
+    }
+
+    /// The listed channel whose point is in the close dialog, None when the
+    /// point was typed in.
+    #[must_use]
+    pub fn picked_close_channel(&self) -> Option<&ChannelInfo> {
+        self.close_channels
+            .as_ref()?
+            .get(self.close_channel_idx)
+            .filter(|channel| channel.channel_point == self.close_channel_point)
+    }
+
+    /// Pick a listed channel to close, ignoring indexes past either end.
+    fn select_close_channel(&mut self, idx: Option<usize>) {
+        let Some((idx, channel)) = idx.and_then(|idx| {
+            self.close_channels
+                .as_ref()
+                .and_then(|channels| channels.get(idx))
+                .map(|channel| (idx, channel))
+        }) else {
+            return;
+        };
+        self.close_channel_point.clone_from(&channel.channel_point);
+        self.close_channel_idx = idx;
+    }
+
     fn handle_send_payment_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Char('q') | KeyCode::Esc => {
                 self.ui_mode = UiMode::Main;
             }
             KeyCode::Tab | KeyCode::Down => {
-                self.payment_form_field = (self.payment_form_field + 1) % 4;
+                self.payment_form_field = (self.payment_form_field + 1) % 5;
             }
             KeyCode::BackTab | KeyCode::Up => {
                 self.payment_form_field = if self.payment_form_field == 0 {
-                    3
+                    4
                 } else {
                     self.payment_form_field - 1
                 };
@@ -970,6 +2273,7 @@ impl App {
             KeyCode::Char(c) => match self.payment_form_field {
                 2 if c.is_ascii_digit() => self.payment_amount.push(c),
                 3 => self.payment_memo.push(c),
+                4 => self.payment_invoice.push(c),
                 _ => {}
             },
             KeyCode::Backspace => match self.payment_form_field {
@@ -979,24 +2283,32 @@ impl App {
                 3 => {
                     self.payment_memo.pop();
                 }
+                4 => {
+                    self.payment_invoice.pop();
+                }
                 _ => {}
             },
+            KeyCode::Enter if !self.payment_invoice.trim().is_empty() => {
+                // A raw invoice takes precedence over the node selection
+                if let Some(from) = self.nodes.get(self.payment_from_idx).cloned() {
+                    let _ = self.command_tx.send(AppCommand::DecodeInvoice {
+                        from_node: from,
+                        payment_request: self.payment_invoice.trim().to_string(),
+                    });
+                    self.ui_mode = UiMode::Main;
+                }
+            }
             KeyCode::Enter => {
                 if let Ok(amount) = self.payment_amount.parse::<u64>() {
                     if let (Some(from), Some(to)) = (
                         self.nodes.get(self.payment_from_idx).cloned(),
                         self.nodes.get(self.payment_to_idx).cloned(),
                     ) {
-                        let memo = if self.payment_memo.is_empty() {
-                            None
-                        } else {
-                            Some(self.payment_memo.clone())
-                        };
-                        let _ = self.command_tx.send(AppCommand::SendPayment {
+                        // Estimate the route first; the payment is sent once confirmed
+                        let _ = self.command_tx.send(AppCommand::ProbeRoute {
                             from_node: from,
                             to_node: to,
                             amount,
-                            memo,
                         });
                         self.ui_mode = UiMode::Main;
                     }
@@ -1035,7 +2347,14 @@ impl App {
                 }
             }
             ActivePanel::Logs => {
-                self.log_scroll = self.log_scroll.saturating_sub(1);
+                let max = ui::log_max_scroll(self, self.log_viewport);
+                let current = if self.log_follow {
+                    max
+                } else {
+                    self.log_scroll.min(max)
+                };
+                self.log_scroll = current.saturating_sub(1);
+                self.log_follow = max == 0;
             }
         }
     }
@@ -1057,7 +2376,9 @@ impl App {
                 );
             }
             ActivePanel::Logs => {
-                self.log_scroll = self.log_scroll.saturating_add(1);
+                let max = ui::log_max_scroll(self, self.log_viewport);
+                self.log_scroll = self.log_scroll.saturating_add(1).min(max);
+                self.log_follow = self.log_scroll >= max;
             }
         }
     }
@@ -1070,6 +2391,7 @@ impl App {
         alias: String,
         lnd_version_idx: usize,
         btc_version_idx: usize,
+        topology: Option<Topology>,
     ) -> Result<()> {
         use polar_nodes::{BITCOIN_VERSIONS, LND_VERSIONS};
 
@@ -1083,7 +2405,9 @@ impl App {
             .unwrap_or(&polar_nodes::BitcoinNode::DEFAULT_IMAGE);
 
         let mut manager = self.network_manager.lock().await;
-        match manager.create_network_with_config(&name, lnd_count, &alias, lnd_version, btc_version)
+        match manager
+            .create_network_with_config(&name, lnd_count, 1, &alias, lnd_version, btc_version)
+            .and_then(|()| manager.set_topology(&name, topology))
         {
             Ok(_) => {
                 self.status_message = Some(format!("Network '{}' created successfully", name));
@@ -1104,27 +2428,43 @@ impl App {
     }
 
     /// Start the selected network.
-    pub async fn start_selected_network(&mut self) -> Result<()> {
-        if let Some(idx) = self.selected_network {
-            if let Some(network_name) = self.networks.get(idx).cloned() {
-                self.status_message = Some(format!("Starting network '{}'...", network_name));
+    ///
+    /// A topology chosen when the network was created is wired up once the
+    /// nodes are running.
+    pub fn start_selected_network(&mut self) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
 
-                let mut manager = self.network_manager.lock().await;
-                match manager.start_network(&network_name).await {
-                    Ok(_) => {
-                        self.status_message =
-                            Some(format!("Network '{}' started successfully", network_name));
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Failed to start network: {}", e));
-                    }
+        self.spawn_task(
+            format!("Starting network '{network_name}'..."),
+            |manager| async move {
+                let mut manager = manager.lock().await;
+                if let Err(e) = manager.start_network(&network_name).await {
+                    return format!("Failed to start network: {e}");
                 }
-                drop(manager);
-
-                self.refresh_networks().await?;
-            }
-        }
-        Ok(())
+                let topology = match manager.take_topology(&network_name) {
+                    Ok(Some(topology)) => topology,
+                    Ok(None) => return format!("Network '{network_name}' started successfully"),
+                    Err(e) => return format!("Failed to wire network: {e}"),
+                };
+                match manager
+                    .apply_topology(&network_name, topology, TOPOLOGY_CHANNEL_CAPACITY)
+                    .await
+                {
+                    Ok(summary) => format!(
+                        "Network '{network_name}' started with a {topology} topology: \
+                         {} channels opened, {} failed",
+                        summary.opened.len(),
+                        summary.failed.len()
+                    ),
+                    Err(e) => format!("Failed to wire {topology} topology: {e}"),
+                }
+            },
+        );
     }
 
     /// Stop the selected network.
@@ -1151,6 +2491,33 @@ impl App {
         Ok(())
     }
 
+    /// Reset the selected network, wiping chain data but keeping its topology.
+    pub async fn reset_selected_network(&mut self) -> Result<()> {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return Ok(());
+        };
+
+        self.status_message = Some(format!("Resetting network '{network_name}'..."));
+
+        let mut manager = self.network_manager.lock().await;
+        match manager.reset_network(&network_name).await {
+            Ok(()) => {
+                self.status_message = Some(format!(
+                    "Network '{network_name}' reset and restarted from block zero"
+                ));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to reset network: {e}"));
+            }
+        }
+        drop(manager);
+
+        self.refresh_networks().await
+    }
+
     /// Get the status of the selected network.
     pub async fn get_selected_network_status(&self) -> Option<NetworkStatus> {
         if let Some(idx) = self.selected_network {
@@ -1256,21 +2623,39 @@ impl App {
     }
 
     /// View details for the selected node.
-    pub async fn view_node_details(&mut self) -> Result<()> {
+    ///
+    /// `force_refresh` bypasses the network manager's node info cache.
+    pub async fn view_node_details(&mut self, force_refresh: bool) -> Result<()> {
         if let Some(network_idx) = self.selected_network {
             if let Some(node_idx) = self.selected_node {
                 if let Some(network_name) = self.networks.get(network_idx) {
-                    let manager = self.network_manager.lock().await;
-
                     // Get the node name from the cached nodes list
                     if let Some(node_display) = self.nodes.get(node_idx) {
                         // Parse the node name from "name (type)" format
-                        let node_name = node_display.split(" (").next().unwrap_or("").to_string();
-
-                        match manager.get_node_info(network_name, &node_name).await {
+                        let node_name = node_display.split(" (").next().unwrap_or("");
+
+                        match NetworkManager::retry_while_starting(
+                            &self.network_manager,
+                            |manager| async move {
+                                manager
+                                    .get_node_info_with_options(
+                                        network_name,
+                                        node_name,
+                                        force_refresh,
+                                    )
+                                    .await
+                            },
+                        )
+                        .await
+                        {
                             Ok(info) => {
                                 self.node_info = Some(info);
-                                self.node_info_scroll = 0;
+                                if !force_refresh {
+                                    self.node_info_scroll = 0;
+                                    self.selected_endpoint_idx = 0;
+                                    self.node_credentials = None;
+                                    self.lndconnect_url = None;
+                                }
                                 self.ui_mode = UiMode::NodeDetails;
                                 self.status_message = None;
                             }
@@ -1286,14 +2671,109 @@ impl App {
         Ok(())
     }
 
-    pub async fn mine_blocks(&mut self, num_blocks: u32) -> Result<()> {
+    /// Copy the endpoint highlighted in node details to the clipboard.
+    fn copy_selected_endpoint(&mut self) {
+        let Some(ref info) = self.node_info else {
+            return;
+        };
+        let endpoints = info.endpoints();
+        let Some(&(label, host)) = endpoints.get(self.selected_endpoint_idx) else {
+            return;
+        };
+
+        self.status_message = Some(match clipboard::copy(host) {
+            Ok(()) => format!("Copied {label} endpoint to clipboard: {host}"),
+            Err(e) => format!("{e}. {label} endpoint is {host}"),
+        });
+    }
+
+    /// Name of the selected network.
+    fn selected_network_name(&self) -> Option<String> {
+        self.selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+    }
+
+    /// Network and node name of the selected node.
+    fn selected_node_name(&self) -> Option<(String, String)> {
+        let network_name = self.selected_network_name()?;
+        let node_name = self
+            .selected_node
+            .and_then(|idx| self.nodes.get(idx))
+            .map(|display| display.split(" (").next().unwrap_or(display).to_string())?;
+        Some((network_name, node_name))
+    }
+
+    /// Export the TLS cert and admin macaroon of the selected LND node so
+    /// node details can show where they were written, copying their
+    /// directory to the clipboard.
+    pub async fn export_node_credentials(&mut self) {
+        let Some((network_name, node_name)) = self.selected_node_name() else {
+            return;
+        };
+
+        let manager = self.network_manager.lock().await;
+        let result = manager
+            .export_node_credentials(&network_name, &node_name)
+            .await;
+        drop(manager);
+
+        match result {
+            Ok(exported) => {
+                let dir = exported
+                    .tls_cert
+                    .parent()
+                    .map_or_else(String::new, |dir| dir.display().to_string());
+                self.status_message = Some(match clipboard::copy(&dir) {
+                    Ok(()) => format!("Exported credentials of {node_name} and copied {dir}"),
+                    Err(e) => format!("Exported credentials of {node_name} to {dir} ({e})"),
+                });
+                self.node_credentials = Some(exported);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to export credentials: {e}"));
+            }
+        }
+    }
+
+    /// Build the lndconnect URL of the selected LND node for node details
+    /// to show, copying it to the clipboard as well.
+    pub async fn show_lndconnect(&mut self) {
+        let Some((network_name, node_name)) = self.selected_node_name() else {
+            return;
+        };
+
+        let manager = self.network_manager.lock().await;
+        let result = manager.lndconnect_url(&network_name, &node_name).await;
+        drop(manager);
+
+        match result {
+            Ok(url) => {
+                self.status_message = Some(match clipboard::copy(&url) {
+                    Ok(()) => format!("Copied lndconnect URL of {node_name} to clipboard"),
+                    Err(e) => format!("lndconnect URL of {node_name} is shown below ({e})"),
+                });
+                self.lndconnect_url = Some(url);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to build lndconnect URL: {e}"));
+            }
+        }
+    }
+
+    pub async fn mine_blocks(&mut self, num_blocks: u32, to_node: Option<&str>) -> Result<()> {
         if let Some(idx) = self.selected_network {
             if let Some(network_name) = self.networks.get(idx).cloned() {
+                // Parse node name from "name (type)" format if needed
+                let to_node = to_node.map(|name| name.split(" (").next().unwrap_or(name));
+
                 self.status_message = Some(format!("Mining {} blocks...", num_blocks));
 
                 let manager = self.network_manager.lock().await;
 
-                match manager.mine_blocks(&network_name, num_blocks).await {
+                match manager
+                    .mine_blocks(&network_name, num_blocks, to_node)
+                    .await
+                {
                     Ok(hashes) => {
                         self.status_message =
                             Some(format!("Mined {} blocks successfully", hashes.len()));
@@ -1311,81 +2791,188 @@ impl App {
         Ok(())
     }
 
-    pub async fn fund_wallet(&mut self, node_name: &str, amount: f64) -> Result<()> {
-        if let Some(idx) = self.selected_network {
-            if let Some(network_name) = self.networks.get(idx).cloned() {
-                // Parse node name from "name (type)" format if needed
-                let actual_node_name = node_name.split(" (").next().unwrap_or(node_name);
-
-                self.status_message = Some(format!(
-                    "Funding {} with {} BTC...",
-                    actual_node_name, amount
-                ));
-
-                let manager = self.network_manager.lock().await;
-                match manager
-                    .fund_lnd_wallet(&network_name, actual_node_name, amount)
-                    .await
+    pub fn fund_wallet(&mut self, node_name: &str, amount: f64) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+        // Parse node name from "name (type)" format if needed
+        let node_name = node_name
+            .split(" (")
+            .next()
+            .unwrap_or(node_name)
+            .to_string();
+
+        self.spawn_task(
+            format!("Funding {node_name} with {amount} BTC..."),
+            |manager| async move {
+                let (network_name, node_name) = (network_name.as_str(), node_name.as_str());
+                match NetworkManager::retry_while_starting(&manager, |manager| async move {
+                    manager
+                        .fund_lnd_wallet(network_name, node_name, amount)
+                        .await
+                })
+                .await
                 {
-                    Ok(txid) => {
-                        self.status_message = Some(format!("Funded wallet. TXID: {}", &txid[..8]));
+                    Ok(txid) => format!("Funded wallet. TXID: {}", truncate_str(&txid, 8)),
+                    Err(Error::InsufficientFunds { node, have, need }) => {
+                        format!("{node} has {have} of {need} sats needed. Try mining blocks first.")
                     }
-                    Err(e) => {
-                        self.status_message = Some(format!("Failed to fund wallet: {}", e));
+                    Err(Error::NodeNotRunning(node)) => {
+                        format!("{node} is not running. Start the network first.")
                     }
+                    Err(e) => format!("Failed to fund wallet: {e}"),
                 }
-                drop(manager);
+            },
+        );
+    }
 
-                // Refresh network state to update UI
-                self.refresh_networks().await?;
+    /// Fund the wallet of every LND node in the selected network with
+    /// `amount` BTC in one transaction.
+    pub async fn fund_all_nodes(&mut self, amount: f64) -> Result<()> {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx))
+            .cloned()
+        else {
+            self.status_message = Some("No network selected".to_string());
+            return Ok(());
+        };
+
+        self.status_message = Some(format!("Funding every LND node with {amount} BTC..."));
+
+        let network_name = network_name.as_str();
+        match NetworkManager::retry_while_starting(&self.network_manager, |manager| async move {
+            manager.fund_all_nodes(network_name, amount).await
+        })
+        .await
+        {
+            Ok(txids) => {
+                let txid = txids.values().next().map_or("", String::as_str);
+                self.status_message = Some(format!(
+                    "Funded {} nodes. TXID: {}",
+                    txids.len(),
+                    truncate_str(txid, 8)
+                ));
+            }
+            Err(Error::InsufficientFunds { node, have, need }) => {
+                self.status_message = Some(format!(
+                    "{node} has {have} of {need} sats needed after mining."
+                ));
+            }
+            Err(Error::NodeNotRunning(node)) => {
+                self.status_message =
+                    Some(format!("{node} is not running. Start the network first."));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to fund nodes: {e}"));
             }
         }
-        Ok(())
+
+        self.refresh_networks().await
     }
 
-    pub async fn open_channel(
+    pub fn open_channel(
         &mut self,
         from: &str,
         to: &str,
         capacity: u64,
         push_amount: Option<u64>,
-    ) -> Result<()> {
-        if let Some(idx) = self.selected_network {
-            if let Some(network_name) = self.networks.get(idx).cloned() {
-                // Parse node names from "name (type)" format if needed
-                let actual_from = from.split(" (").next().unwrap_or(from);
-                let actual_to = to.split(" (").next().unwrap_or(to);
-
-                let push_desc = if let Some(p) = push_amount {
-                    format!(" (push {})", p)
-                } else {
-                    String::new()
-                };
-                self.status_message = Some(format!(
-                    "Opening channel {} → {} capacity: {}{}",
-                    actual_from, actual_to, capacity, push_desc
-                ));
-
-                let manager = self.network_manager.lock().await;
+        private: bool,
+    ) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+        // Parse node names from "name (type)" format if needed
+        let from = from.split(" (").next().unwrap_or(from).to_string();
+        let to = to.split(" (").next().unwrap_or(to).to_string();
+
+        let push_desc = push_amount.map_or_else(String::new, |p| format!(" (push {p})"));
+        let visibility = if private { "private " } else { "" };
+        self.spawn_task(
+            format!("Opening {visibility}channel {from} → {to} capacity: {capacity}{push_desc}"),
+            |manager| async move {
+                let options = OpenChannelOptions {
+                    push_amount,
+                    ..OpenChannelOptions::default()
+                }
+                .with_private(private);
+                let manager = manager.lock().await;
                 match manager
-                    .open_channel(&network_name, actual_from, actual_to, capacity, push_amount)
+                    .open_channel_with_options(&network_name, &from, &to, capacity, &options)
                     .await
                 {
-                    Ok(txid) => {
-                        self.status_message =
-                            Some(format!("Channel opened. Funding TXID: {}", &txid[..8]));
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Failed to open channel: {}", e));
-                    }
+                    Ok(txid) => format!("Channel opened. Funding TXID: {}", truncate_str(&txid, 8)),
+                    Err(e) => format!("Failed to open channel: {e}"),
                 }
-                drop(manager);
+            },
+        );
+    }
 
-                // Refresh network state to update UI
-                self.refresh_networks().await?;
+    pub async fn abandon_channel(&mut self, node_name: &str, channel_point: &str) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+        let actual_node = node_name.split(" (").next().unwrap_or(node_name);
+
+        let manager = self.network_manager.lock().await;
+        match manager
+            .abandon_channel(&network_name, actual_node, channel_point)
+            .await
+        {
+            Ok(()) => {
+                self.status_message = Some(format!(
+                    "Abandoned channel on {actual_node} (local state only, nothing broadcast)"
+                ));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to abandon channel: {e}"));
             }
         }
-        Ok(())
+    }
+
+    /// Fill the close dialog with the open channels of `node_name`.
+    pub async fn load_close_channels(&mut self, node_name: &str) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+        let actual_node = node_name.split(" (").next().unwrap_or(node_name);
+
+        let manager = self.network_manager.lock().await;
+        let result = manager.list_channels(&network_name, actual_node).await;
+        drop(manager);
+
+        // The user may have moved on to another node or closed the dialog
+        let still_selected = self.ui_mode == UiMode::CloseChannel
+            && self
+                .nodes
+                .get(self.close_channel_node_idx)
+                .map(String::as_str)
+                == Some(node_name);
+        if !still_selected {
+            return;
+        }
+
+        let channels = result.unwrap_or_else(|e| {
+            self.status_message = Some(format!("Failed to list channels: {e}"));
+            Vec::new()
+        });
+        self.close_channels = Some(channels);
+        // Keep a channel point the user started typing while the list loaded
+        if self.close_channel_point.is_empty() {
+            self.select_close_channel(Some(0));
+        }
     }
 
     pub async fn close_channel(
@@ -1411,8 +2998,10 @@ impl App {
                     .await
                 {
                     Ok(txid) => {
-                        self.status_message =
-                            Some(format!("Channel closing. Closing TXID: {}", &txid[..8]));
+                        self.status_message = Some(format!(
+                            "Channel closing. Closing TXID: {}",
+                            truncate_str(&txid, 8)
+                        ));
                     }
                     Err(e) => {
                         self.status_message = Some(format!("Failed to close channel: {}", e));
@@ -1452,8 +3041,15 @@ impl App {
                     .await
                 {
                     Ok(payment_hash) => {
-                        self.status_message =
-                            Some(format!("Payment sent! Hash: {}", &payment_hash[..16]));
+                        self.status_message = Some(format!(
+                            "Payment sent! Hash: {}",
+                            truncate_str(&payment_hash, 16)
+                        ));
+                    }
+                    Err(Error::PaymentTimeout(secs)) => {
+                        self.status_message = Some(format!(
+                            "Payment timed out after {secs}s, an HTLC may still be in flight"
+                        ));
                     }
                     Err(e) => {
                         self.status_message = Some(format!("Failed to send payment: {}", e));
@@ -1468,26 +3064,132 @@ impl App {
         Ok(())
     }
 
-    pub async fn sync_graph(&mut self) -> Result<()> {
+    pub async fn probe_route(&mut self, from: &str, to: &str, amount: u64) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+        // Parse node names from "name (type)" format if needed
+        let actual_from = from.split(" (").next().unwrap_or(from);
+        let actual_to = to.split(" (").next().unwrap_or(to);
+
+        self.status_message = Some(format!("Finding route to {actual_to}..."));
+
+        let manager = self.network_manager.lock().await;
+        match manager
+            .probe_route(&network_name, actual_from, actual_to, amount)
+            .await
+        {
+            Ok(route) => {
+                self.route_estimate = Some(route);
+                self.ui_mode = UiMode::ConfirmPayment;
+                self.status_message = None;
+            }
+            Err(Error::NoRoute { from, to }) => {
+                self.status_message = Some(format!(
+                    "No route from {from} to {to} — sync graph or open channels"
+                ));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to find route: {e}"));
+            }
+        }
+    }
+
+    /// Decode an invoice with the selected network's `from` node and show
+    /// it for confirmation before paying.
+    pub async fn decode_invoice(&mut self, from: &str, payment_request: &str) {
         if let Some(idx) = self.selected_network {
             if let Some(network_name) = self.networks.get(idx).cloned() {
-                self.status_message = Some("Syncing Lightning Network graph...".to_string());
+                // Parse node name from "name (type)" format if needed
+                let actual_from = from.split(" (").next().unwrap_or(from);
+
+                self.status_message = Some("Decoding invoice...".to_string());
 
                 let manager = self.network_manager.lock().await;
-                match manager.sync_graph(&network_name).await {
-                    Ok(synced_nodes) => {
-                        self.status_message = Some(format!(
-                            "Graph synced! {} LND nodes synchronized",
-                            synced_nodes
-                        ));
+                match manager
+                    .decode_invoice(&network_name, actual_from, payment_request)
+                    .await
+                {
+                    Ok(invoice) => {
+                        self.decoded_invoice = Some(invoice);
+                        self.ui_mode = UiMode::ConfirmInvoice;
+                        self.status_message = None;
                     }
                     Err(e) => {
-                        self.status_message = Some(format!("Failed to sync graph: {}", e));
+                        self.status_message = Some(format!("Failed to decode invoice: {e}"));
                     }
                 }
             }
         }
-        Ok(())
+    }
+
+    /// Pay an invoice in the background so each HTLC attempt shows next to
+    /// the spinner while the payment is routed.
+    pub fn pay_invoice(&mut self, from: &str, payment_request: &str) {
+        let Some(network_name) = self.selected_network_name() else {
+            return;
+        };
+        // Parse node name from "name (type)" format if needed
+        let actual_from = from.split(" (").next().unwrap_or(from).to_string();
+        let payment_request = payment_request.to_string();
+
+        self.spawn_task(
+            format!("Paying invoice from {actual_from}..."),
+            |manager| async move {
+                let manager = manager.lock().await;
+                match manager
+                    .pay_invoice(&network_name, &actual_from, &payment_request, None, None)
+                    .await
+                {
+                    Ok(payment_hash) => {
+                        format!("Payment sent! Hash: {}", truncate_str(&payment_hash, 16))
+                    }
+                    Err(Error::PaymentTimeout(secs)) => {
+                        format!("Payment timed out after {secs}s, an HTLC may still be in flight")
+                    }
+                    Err(e) => format!("Failed to pay invoice: {e}"),
+                }
+            },
+        );
+    }
+
+    /// Sync the channel graph of the selected network in the background,
+    /// waiting until every LND node sees every channel.
+    pub fn sync_graph(&mut self) {
+        if let Some(idx) = self.selected_network {
+            if let Some(network_name) = self.networks.get(idx).cloned() {
+                self.spawn_task(
+                    "Syncing Lightning Network graph...".to_string(),
+                    |manager| async move {
+                        // The manager is only locked while polling, gossip
+                        // can take a while to spread
+                        let synced = manager.lock().await.sync_graph(&network_name).await;
+                        let result = match synced {
+                            Ok(synced_nodes) => NetworkManager::poll_graph_sync(
+                                &manager,
+                                &network_name,
+                                GRAPH_SYNC_TIMEOUT,
+                            )
+                            .await
+                            .map(|elapsed| (synced_nodes, elapsed)),
+                            Err(e) => Err(e),
+                        };
+
+                        match result {
+                            Ok((synced_nodes, elapsed)) => format!(
+                                "Graph synced! {} LND nodes see every channel after {:.1}s",
+                                synced_nodes,
+                                elapsed.as_secs_f64()
+                            ),
+                            Err(e) => format!("Failed to sync graph: {e}"),
+                        }
+                    },
+                );
+            }
+        }
     }
 
     pub async fn sync_chain(&mut self) -> Result<()> {
@@ -1511,4 +3213,282 @@ impl App {
         }
         Ok(())
     }
+
+    pub async fn export_network(&mut self) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+
+        let manager = self.network_manager.lock().await;
+        let path = manager.exports_dir().join(format!("{network_name}.json"));
+        match manager.export_network(&network_name, &path) {
+            Ok(()) => {
+                self.status_message = Some(format!("Exported to {}", path.display()));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to export network: {e}"));
+            }
+        }
+    }
+
+    pub async fn prune_orphans(&mut self) {
+        let mut manager = self.network_manager.lock().await;
+        let result = manager.prune_orphans().await;
+        self.orphan_count = manager.orphaned_containers().len();
+        drop(manager);
+
+        match result {
+            Ok(removed) => {
+                self.status_message = Some(format!("Removed {removed} orphaned containers"));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to remove orphans: {e}"));
+            }
+        }
+    }
+
+    pub async fn toggle_block_generator(&mut self) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+
+        let mut manager = self.network_manager.lock().await;
+        if manager.stop_block_generator(&network_name) {
+            self.status_message = Some(format!("Block generator stopped on '{network_name}'"));
+            return;
+        }
+
+        match manager.start_block_generator(
+            &network_name,
+            BLOCK_GENERATOR_INTERVAL_SECS,
+            BLOCK_GENERATOR_BLOCKS_PER_TICK,
+        ) {
+            Ok(()) => {
+                self.status_message = Some(format!(
+                    "Mining {BLOCK_GENERATOR_BLOCKS_PER_TICK} block(s) every {BLOCK_GENERATOR_INTERVAL_SECS}s on '{network_name}'"
+                ));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to start block generator: {e}"));
+            }
+        }
+    }
+
+    /// Move the running block generator of the selected network on to its
+    /// next mode.
+    pub async fn cycle_block_generator_mode(&mut self) {
+        let Some(network_name) = self.selected_network_name() else {
+            return;
+        };
+
+        let mut manager = self.network_manager.lock().await;
+        let result = manager.block_generator_mode(&network_name).map(|mode| {
+            let mode = mode.next();
+            manager
+                .set_block_generator_mode(&network_name, mode)
+                .map(|()| mode)
+        });
+        drop(manager);
+
+        self.status_message = Some(match result {
+            None => format!("No block generator on '{network_name}', press b to start one"),
+            Some(Ok(mode)) => format!("Block generator on '{network_name}' now mines {mode}"),
+            Some(Err(e)) => format!("Failed to change block generator mode: {e}"),
+        });
+    }
+
+    /// Show the channel graph of the selected network.
+    pub async fn view_graph(&mut self) {
+        if let Some(idx) = self.selected_network {
+            if let Some(network_name) = self.networks.get(idx).cloned() {
+                self.status_message = Some("Fetching channel graph...".to_string());
+
+                let manager = self.network_manager.lock().await;
+                match manager.describe_graph(&network_name).await {
+                    Ok(graph) => {
+                        self.graph_info = Some(graph);
+                        self.graph_scroll = 0;
+                        self.ui_mode = UiMode::GraphView;
+                        self.status_message = None;
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Failed to describe graph: {e}"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Show the liquidity totals of the selected network.
+    pub async fn view_network_summary(&mut self) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+
+        self.status_message = Some("Fetching network summary...".to_string());
+        let manager = self.network_manager.lock().await;
+        let result = manager.network_summary(&network_name).await;
+        drop(manager);
+
+        match result {
+            Ok(summary) => {
+                self.network_summary = summary;
+                self.ui_mode = UiMode::NetworkSummary;
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to summarize network: {e}"));
+            }
+        }
+    }
+
+    /// Rename a node of the selected network.
+    pub async fn rename_node(&mut self, old_name: &str, new_name: &str) -> Result<()> {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return Ok(());
+        };
+
+        let mut manager = self.network_manager.lock().await;
+        let result = manager.rename_node(&network_name, old_name, new_name);
+        drop(manager);
+
+        match result {
+            Ok(()) => {
+                self.status_message = Some(format!("Renamed {old_name} to {new_name}"));
+                self.refresh_networks().await?;
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to rename node: {e}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sign a message with an LND node, showing the signature in the dialog.
+    pub async fn sign_message(&mut self, node_name: &str, message: &str) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+
+        let manager = self.network_manager.lock().await;
+        let result = manager
+            .sign_message(&network_name, node_name, message)
+            .await;
+        drop(manager);
+
+        match result {
+            Ok(signature) => {
+                self.status_message = Some(format!("Signed message with {node_name}"));
+                self.sign_signature = Some(signature);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to sign message: {e}"));
+            }
+        }
+    }
+
+    /// Start or stop a single node of the selected network.
+    pub async fn set_node_running(&mut self, node_name: &str, running: bool) -> Result<()> {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return Ok(());
+        };
+
+        let (action, verb, state) = if running {
+            ("Starting", "start", "started")
+        } else {
+            ("Stopping", "stop", "stopped")
+        };
+        self.status_message = Some(format!("{action} node '{node_name}'..."));
+
+        let mut manager = self.network_manager.lock().await;
+        let result = if running {
+            manager.start_node(&network_name, node_name).await
+        } else {
+            manager.stop_node(&network_name, node_name).await
+        };
+        drop(manager);
+
+        match result {
+            Ok(()) => {
+                self.status_message = Some(format!("Node '{node_name}' {state}"));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to {verb} node: {e}"));
+            }
+        }
+        self.refresh_networks().await
+    }
+
+    /// Show the recent invoices and payments of the selected LND node.
+    pub async fn view_payment_history(&mut self) {
+        let Some(network_name) = self
+            .selected_network
+            .and_then(|idx| self.networks.get(idx).cloned())
+        else {
+            return;
+        };
+        let Some(node_name) = self
+            .selected_node
+            .and_then(|idx| self.nodes.get(idx))
+            .and_then(|display| display.split(" (").next())
+            .map(ToString::to_string)
+        else {
+            return;
+        };
+
+        let manager = self.network_manager.lock().await;
+        match manager.payment_history(&network_name, &node_name).await {
+            Ok(history) => {
+                self.payment_history = Some(history);
+                self.payment_history_scroll = 0;
+                self.ui_mode = UiMode::PaymentHistory;
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to get payment history: {e}"));
+            }
+        }
+    }
+}
+
+/// Keep only the items containing `query`, ignoring case.
+fn apply_filter(items: Vec<String>, query: &str) -> Vec<String> {
+    if query.is_empty() {
+        return items;
+    }
+    let query = query.to_lowercase();
+    items
+        .into_iter()
+        .filter(|item| item.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Resolve a selection against a refreshed list.
+///
+/// Prefers the previously selected item by name, then clamps the old index,
+/// and returns `None` for an empty list.
+fn reselect(items: &[String], prev: Option<&str>, prev_idx: Option<usize>) -> Option<usize> {
+    if items.is_empty() {
+        return None;
+    }
+    prev.and_then(|name| items.iter().position(|item| item == name))
+        .or_else(|| prev_idx.map(|idx| idx.min(items.len() - 1)))
 }
@@ -0,0 +1,29 @@
+//! System clipboard access.
+//!
+//! Behind the `clipboard` feature; without it, or without a clipboard to
+//! talk to (e.g. over SSH), copying fails with a message the caller can
+//! show instead.
+
+/// Copy `text` to the system clipboard.
+///
+/// # Errors
+///
+/// Returns a displayable reason when no clipboard is available.
+#[cfg(feature = "clipboard")]
+pub fn copy(text: &str) -> Result<(), String> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {e}"))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| format!("Clipboard copy failed: {e}"))
+}
+
+/// Copy `text` to the system clipboard.
+///
+/// # Errors
+///
+/// Always fails, this build has no clipboard support.
+#[cfg(not(feature = "clipboard"))]
+pub fn copy(_text: &str) -> Result<(), String> {
+    Err("Clipboard unavailable: built without the clipboard feature".to_string())
+}
@@ -0,0 +1,128 @@
+//! Amount formatting shared by the TUI views.
+
+/// Satoshis in one bitcoin.
+const SATS_PER_BTC: u64 = 100_000_000;
+
+/// Unit amounts are displayed in across the UI.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayUnit {
+    /// Whole satoshis, e.g. `1,250,000 sats`.
+    #[default]
+    Sats,
+    /// Bitcoin with eight decimals, e.g. `0.01250000 BTC`.
+    Btc,
+}
+
+impl DisplayUnit {
+    /// BTC when `btc` is set, otherwise sats.
+    #[must_use]
+    pub const fn from_btc(btc: bool) -> Self {
+        if btc { Self::Btc } else { Self::Sats }
+    }
+
+    /// The other unit.
+    #[must_use]
+    pub const fn toggle(self) -> Self {
+        match self {
+            Self::Sats => Self::Btc,
+            Self::Btc => Self::Sats,
+        }
+    }
+
+    /// Short label for the unit.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Sats => "sats",
+            Self::Btc => "BTC",
+        }
+    }
+}
+
+/// Convert a non-negative BTC amount to satoshis.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn btc_to_sats(btc: f64) -> u64 {
+    (btc * 100_000_000.0).round() as u64
+}
+
+/// Convert satoshis to BTC.
+#[allow(clippy::cast_precision_loss)]
+pub fn sats_to_btc(sats: u64) -> f64 {
+    sats as f64 / SATS_PER_BTC as f64
+}
+
+/// Format a satoshi amount in `unit` with thousands separators.
+pub fn format_sats(sats: i64, unit: DisplayUnit) -> String {
+    let sign = if sats < 0 { "-" } else { "" };
+    let abs = sats.unsigned_abs();
+    match unit {
+        DisplayUnit::Sats => format!("{sign}{} sats", group_thousands(abs)),
+        DisplayUnit::Btc => format!(
+            "{sign}{}.{:08} BTC",
+            group_thousands(abs / SATS_PER_BTC),
+            abs % SATS_PER_BTC
+        ),
+    }
+}
+
+/// Format a BTC amount as reported by bitcoind in `unit`.
+pub fn format_btc(btc: f64, unit: DisplayUnit) -> String {
+    let sats = i64::try_from(btc_to_sats(btc.abs())).unwrap_or(i64::MAX);
+    format_sats(if btc < 0.0 { -sats } else { sats }, unit)
+}
+
+/// Render `n` with a comma between each group of three digits.
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_btc_to_sats() {
+        assert_eq!(btc_to_sats(1.0), 100_000_000);
+        assert_eq!(btc_to_sats(0.001), 100_000);
+        assert_eq!(btc_to_sats(0.0), 0);
+        assert_eq!(btc_to_sats(sats_to_btc(123_456_789)), 123_456_789);
+    }
+
+    #[test]
+    fn test_format_sats() {
+        assert_eq!(format_sats(0, DisplayUnit::Sats), "0 sats");
+        assert_eq!(format_sats(999, DisplayUnit::Sats), "999 sats");
+        assert_eq!(format_sats(1_000, DisplayUnit::Sats), "1,000 sats");
+        assert_eq!(
+            format_sats(16_777_215, DisplayUnit::Sats),
+            "16,777,215 sats"
+        );
+        assert_eq!(format_sats(-1_500, DisplayUnit::Sats), "-1,500 sats");
+        assert_eq!(format_sats(1_250_000, DisplayUnit::Btc), "0.01250000 BTC");
+        assert_eq!(
+            format_sats(2_100_000_000_000_000, DisplayUnit::Btc),
+            "21,000,000.00000000 BTC"
+        );
+        assert_eq!(format_sats(-1, DisplayUnit::Btc), "-0.00000001 BTC");
+    }
+
+    #[test]
+    fn test_format_btc() {
+        assert_eq!(format_btc(1.5, DisplayUnit::Btc), "1.50000000 BTC");
+        assert_eq!(format_btc(0.001, DisplayUnit::Sats), "100,000 sats");
+    }
+
+    #[test]
+    fn test_toggle() {
+        assert_eq!(DisplayUnit::Sats.toggle(), DisplayUnit::Btc);
+        assert_eq!(DisplayUnit::Btc.toggle(), DisplayUnit::Sats);
+    }
+}
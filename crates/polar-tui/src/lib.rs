@@ -1,6 +1,12 @@
 mod app;
+mod clipboard;
 mod event;
+mod format;
+mod log_entry;
 pub mod network_manager;
+mod settings;
+#[cfg(feature = "status-server")]
+pub mod status_server;
 mod ui;
 
 pub use app::App;
@@ -13,13 +19,43 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::prelude::*;
+use std::path::PathBuf;
 
 /// Run the TUI application
 ///
+/// Networks are stored under `data_dir` when given, see
+/// [`NetworkManager::with_data_dir`].
+///
 /// # Errors
 ///
 /// Returns an error if terminal initialization fails or the app encounters an error
-pub async fn run() -> Result<()> {
+pub async fn run(data_dir: Option<PathBuf>) -> Result<()> {
+    let mut app = App::with_data_dir(data_dir);
+    app.init().await?;
+    run_app(app).await
+}
+
+/// Run the TUI application while serving network status over HTTP on
+/// `port`, see [`status_server`].
+///
+/// # Errors
+///
+/// Returns an error if the port cannot be bound, terminal initialization
+/// fails or the app encounters an error
+#[cfg(feature = "status-server")]
+pub async fn run_with_status_server(data_dir: Option<PathBuf>, port: u16) -> Result<()> {
+    let server = status_server::StatusServer::bind(port).await?;
+    tracing::info!("Serving network status on http://{}", server.local_addr()?);
+
+    let mut app = App::with_data_dir(data_dir);
+    app.init().await?;
+    let server = tokio::spawn(server.serve(std::sync::Arc::clone(&app.network_manager)));
+    let result = run_app(app).await;
+    server.abort();
+    result
+}
+
+async fn run_app(mut app: App) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
@@ -27,9 +63,6 @@ pub async fn run() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create and run app
-    let mut app = App::new();
-    app.init().await?;
     let result = app.run(&mut terminal).await;
 
     // Restore terminal
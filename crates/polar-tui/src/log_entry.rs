@@ -0,0 +1,183 @@
+//! Parsing of log lines shown in the Logs panel.
+
+/// Severity of a log line, ordered from least to most severe.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Trace output.
+    Trace,
+    /// Debug output.
+    Debug,
+    /// Informational output; also used for lines without a level.
+    #[default]
+    Info,
+    /// Warnings.
+    Warn,
+    /// Errors, including lnd's critical level.
+    Error,
+}
+
+impl LogLevel {
+    /// Map a level tag from lnd (`[WRN]`) or bitcoind (`[warning]`).
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag.to_ascii_lowercase().as_str() {
+            "trc" | "trace" => Some(Self::Trace),
+            "dbg" | "debug" => Some(Self::Debug),
+            "inf" | "info" => Some(Self::Info),
+            "wrn" | "warn" | "warning" => Some(Self::Warn),
+            "err" | "crt" | "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    /// Short label shown in front of the message.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Trace => "TRC",
+            Self::Debug => "DBG",
+            Self::Info => "INF",
+            Self::Warn => "WRN",
+            Self::Error => "ERR",
+        }
+    }
+}
+
+/// A log line split into its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Timestamp as written in the line, if it had one.
+    pub timestamp: Option<String>,
+    /// Severity of the line.
+    pub level: LogLevel,
+    /// Remainder of the line.
+    pub message: String,
+}
+
+impl LogEntry {
+    /// Parse a line in lnd or bitcoind format.
+    ///
+    /// Lines in neither format are kept whole as info, except polar's own
+    /// `Warning:` and `Error:` messages, which get the matching level.
+    pub fn parse(line: &str) -> Self {
+        let line = line.trim_end();
+        parse_lnd(line)
+            .or_else(|| parse_bitcoind(line))
+            .unwrap_or_else(|| Self {
+                timestamp: None,
+                level: plain_level(line),
+                message: line.to_string(),
+            })
+    }
+}
+
+/// Parse `2024-01-15 10:23:45.123 [INF] LTND: message`.
+fn parse_lnd(line: &str) -> Option<LogEntry> {
+    let (date, rest) = line.split_once(' ')?;
+    let (time, rest) = rest.split_once(' ')?;
+    if !is_date(date) || !time.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let (tag, message) = rest.strip_prefix('[')?.split_once("] ")?;
+    Some(LogEntry {
+        timestamp: Some(format!("{date} {time}")),
+        level: LogLevel::from_tag(tag)?,
+        message: message.to_string(),
+    })
+}
+
+/// Parse `2024-01-15T10:23:45Z message`, with an optional `[level]` or
+/// `[category:level]` tag in front of the message.
+fn parse_bitcoind(line: &str) -> Option<LogEntry> {
+    let (timestamp, message) = line.split_once(' ')?;
+    let (date, time) = timestamp.split_once('T')?;
+    if !is_date(date) || !time.ends_with('Z') {
+        return None;
+    }
+    let level = message
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(tag, _)| tag.rsplit(':').next())
+        .and_then(LogLevel::from_tag)
+        .unwrap_or_default();
+    Some(LogEntry {
+        timestamp: Some(timestamp.to_string()),
+        level,
+        message: message.to_string(),
+    })
+}
+
+/// Level of a line without a timestamp, such as polar's own messages.
+fn plain_level(line: &str) -> LogLevel {
+    if line.starts_with("Error") {
+        LogLevel::Error
+    } else if line.starts_with("Warning") {
+        LogLevel::Warn
+    } else {
+        LogLevel::Info
+    }
+}
+
+/// Whether `s` looks like `YYYY-MM-DD`.
+fn is_date(s: &str) -> bool {
+    s.len() == 10
+        && s.char_indices().all(|(i, c)| {
+            if i == 4 || i == 7 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lnd_line() {
+        let entry = LogEntry::parse("2024-01-15 10:23:45.123 [WRN] HSWC: link offline\n");
+        assert_eq!(entry.timestamp.as_deref(), Some("2024-01-15 10:23:45.123"));
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.message, "HSWC: link offline");
+
+        let entry = LogEntry::parse("2024-01-15 10:23:45.123 [CRT] LTND: unable to start");
+        assert_eq!(entry.level, LogLevel::Error);
+    }
+
+    #[test]
+    fn test_parse_bitcoind_line() {
+        let entry = LogEntry::parse("2024-01-15T10:23:45Z UpdateTip: new best=7a0c height=101");
+        assert_eq!(entry.timestamp.as_deref(), Some("2024-01-15T10:23:45Z"));
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.message, "UpdateTip: new best=7a0c height=101");
+
+        let entry = LogEntry::parse("2024-01-15T10:23:45Z [net:warning] peer misbehaving");
+        assert_eq!(entry.level, LogLevel::Warn);
+        let entry = LogEntry::parse("2024-01-15T10:23:45Z [error] disk full");
+        assert_eq!(entry.level, LogLevel::Error);
+        let entry = LogEntry::parse("2024-01-15T10:23:45Z [net] connected");
+        assert_eq!(entry.level, LogLevel::Info);
+    }
+
+    #[test]
+    fn test_parse_plain_line() {
+        let entry = LogEntry::parse("Pulled polarlightning/lnd:0.18.5-beta");
+        assert_eq!(entry.timestamp, None);
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.message, "Pulled polarlightning/lnd:0.18.5-beta");
+
+        let entry = LogEntry::parse("Warning: lnd-1 did not stop within 10s");
+        assert_eq!(entry.level, LogLevel::Warn);
+
+        // Unknown tags keep the whole line rather than dropping it
+        let entry = LogEntry::parse("2024-01-15 10:23:45.123 [???] something");
+        assert_eq!(entry.timestamp, None);
+        assert_eq!(entry.message, "2024-01-15 10:23:45.123 [???] something");
+    }
+
+    #[test]
+    fn test_level_ordering() {
+        assert!(LogLevel::Error > LogLevel::Warn);
+        assert!(LogLevel::Warn > LogLevel::Info);
+        assert!(LogLevel::Debug < LogLevel::Info);
+    }
+}
@@ -1,14 +1,377 @@
 //! Network lifecycle management.
 
+use crate::format::{btc_to_sats, sats_to_btc};
 use polar_core::{
-    BitcoinNodeInfo, Config, Error, LightningImpl, LndNodeInfo, Network, NetworkStatus, Node,
-    NodeInfo, NodeKind, NodePorts, Result,
+    BitcoinNodeInfo, ChannelInfo, ChannelPoint, Config, DecodedInvoice, Error, ExtraArgs,
+    GraphEdge, GraphInfo, GraphNode, InvoiceInfo, LightningImpl, LndNodeInfo, MempoolInfo,
+    MessageVerification, Network, NetworkStatus, Node, NodeInfo, NodeKind, NodePorts, NodeStatus,
+    PaymentHistory, PaymentInfo, PeerInfo, PendingChannelInfo, PendingChannelState, PortConfig,
+    Result, RouteEstimate, RoutingPolicy, Topology, TowerInfo, UtxoInfo,
 };
-use polar_docker::ContainerManager;
-use polar_nodes::{BitcoinNode, LndNode};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use polar_docker::{ContainerManager, data_volume_name, docker_host_name, is_retryable_exec_error};
+use polar_nodes::{
+    BITCOIN_VERSIONS, BitcoinNode, ChannelType, ExportedCredentials, LND_VERSIONS, LndCredential,
+    LndNode, LndRestClient, OpenChannelOptions, PaymentOptions, lncli_number, lndconnect_url,
+    set_route_payment_addr,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::task::{JoinHandle, JoinSet};
+
+/// On-chain sats held back per channel to cover funding fees.
+const CHANNEL_FEE_RESERVE_SAT: u64 = 10_000;
+
+/// Recent node info command output keyed by (container ID, command), with
+/// when it was fetched.
+type InfoCache = HashMap<(String, String), (Instant, String)>;
+
+/// How long [`NetworkManager::verify_lnd_backend`] waits for an LND node to
+/// see a freshly mined block.
+const BLOCK_NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// gRPC port LND listens on inside its container.
+const LND_GRPC_PORT: u16 = 10009;
+
+/// How long callers wait for gossip to spread after syncing the graph.
+pub const GRAPH_SYNC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often [`NetworkManager::wait_for_graph_sync`] polls the nodes.
+const GRAPH_SYNC_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Blocks mined to confirm channels opened by [`NetworkManager::apply_topology`].
+const CHANNEL_CONFIRMATION_BLOCKS: u32 = 6;
+
+/// How long [`NetworkManager::simulate_reorg`] waits for two Bitcoin nodes
+/// to agree on a chain tip.
+const CHAIN_SYNC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often [`NetworkManager::simulate_reorg`] compares chain tips.
+const CHAIN_SYNC_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Outcome of opening channels between pairs of LND nodes.
+#[derive(Debug, Clone, Default)]
+pub struct MeshSummary {
+    /// Opened channels as (from, to, funding txid).
+    pub opened: Vec<(String, String, String)>,
+    /// Pairs skipped because a channel already exists.
+    pub skipped: Vec<(String, String)>,
+    /// Pairs whose channel failed to open, with the error message.
+    pub failed: Vec<(String, String, String)>,
+}
+
+/// Outcome of matching `polar-*` Docker containers to the loaded networks.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileSummary {
+    /// Nodes whose container ID was restored, as (network, node).
+    pub reattached: Vec<(String, String)>,
+    /// Containers that belong to no loaded network, as (container ID, name).
+    pub orphaned: Vec<(String, String)>,
+}
+
+/// Chain tips around a reorg made by [`NetworkManager::simulate_reorg`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgSummary {
+    /// Primary Bitcoin node that was reorged.
+    pub node: String,
+    /// Bitcoin node whose longer chain won.
+    pub peer: String,
+    /// Blocks replaced on the primary node.
+    pub depth: u32,
+    /// Primary node's best block hash before reconnecting, now orphaned.
+    pub before: String,
+    /// Best block hash both nodes agree on afterwards.
+    pub after: String,
+}
+
+/// Disk space a network's files take up in the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataUsage {
+    /// Network name.
+    pub network: String,
+    /// Whether the network is still loaded; artifacts of deleted networks
+    /// are reported too, so they can be cleaned up.
+    pub loaded: bool,
+    /// Bytes of the saved network file under `networks/`.
+    pub network_file: u64,
+    /// Bytes of the network's export under `exports/`.
+    pub exports: u64,
+    /// Bytes of credentials extracted under `credentials/`.
+    pub credentials: u64,
+}
+
+impl DataUsage {
+    /// Bytes across all of the network's files.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.network_file + self.exports + self.credentials
+    }
+
+    /// Bytes [`NetworkManager::clean_network_data`] would free; the export
+    /// of a loaded network is kept.
+    #[must_use]
+    pub const fn reclaimable(&self) -> u64 {
+        if self.loaded {
+            self.credentials
+        } else {
+            self.exports + self.credentials
+        }
+    }
+}
+
+/// Liquidity totals across the LND nodes of a network.
+///
+/// Channel count and capacity count each channel once, while the local and
+/// remote balances are summed from every node's own point of view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LiquiditySummary {
+    /// LND nodes whose info was fetched.
+    pub nodes: usize,
+    /// LND nodes that failed to answer and are missing from the totals.
+    pub unreachable: usize,
+    /// Confirmed on-chain wallet balance in satoshis.
+    pub onchain: i64,
+    /// Capacity of all open channels in satoshis.
+    pub capacity: i64,
+    /// Balance on the nodes' own side of their channels in satoshis.
+    pub local: i64,
+    /// Balance on the counterparties' side of the nodes' channels in satoshis.
+    pub remote: i64,
+    /// Open channels that are currently active.
+    pub active_channels: usize,
+}
+
+/// Options for [`NetworkManager::fund_lnd_wallet_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FundOptions {
+    /// Mine `funding_confirmations` blocks and wait until the transaction
+    /// has that many confirmations and the LND node has synced to the new
+    /// tip.
+    pub auto_mine: bool,
+    /// First mine just enough blocks for coinbase rewards to cover the
+    /// amount when the Bitcoin wallet is short.
+    pub auto_mine_maturity: bool,
+    /// Fee rate in sat/vB for the funding transaction (bitcoind's estimate
+    /// if `None`).
+    pub fee_rate_sat_vb: Option<u64>,
+}
+
+impl Default for FundOptions {
+    fn default() -> Self {
+        Self {
+            auto_mine: true,
+            auto_mine_maturity: false,
+            fee_rate_sat_vb: None,
+        }
+    }
+}
+
+impl FundOptions {
+    /// Mine and wait for confirmations after sending, or not.
+    #[must_use]
+    pub const fn with_auto_mine(mut self, auto_mine: bool) -> Self {
+        self.auto_mine = auto_mine;
+        self
+    }
+
+    /// Mine coinbase rewards to maturity first when the wallet is short.
+    #[must_use]
+    pub const fn with_auto_mine_maturity(mut self, auto_mine_maturity: bool) -> Self {
+        self.auto_mine_maturity = auto_mine_maturity;
+        self
+    }
+
+    /// Set the fee rate of the funding transaction in sat/vB.
+    #[must_use]
+    pub const fn with_fee_rate(mut self, fee_rate_sat_vb: u64) -> Self {
+        self.fee_rate_sat_vb = Some(fee_rate_sat_vb);
+        self
+    }
+}
+
+/// Whether an image [`NetworkManager::start_network`] needs is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageStatus {
+    /// Present locally, nothing to pull.
+    Local,
+    /// Found in its registry and will be pulled.
+    Pull,
+    /// Neither local nor in its registry; starting would fail.
+    Missing,
+}
+
+/// When a block generator mines on each of its ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorMode {
+    /// Mine on every tick, whatever is in the mempool.
+    FixedInterval,
+    /// Mine only while the mempool holds transactions.
+    OnMempool,
+    /// Mine while the mempool holds transactions, and an empty block once
+    /// `empty_block_ticks` ticks passed without one.
+    Hybrid {
+        /// Ticks without a block before an empty one is mined.
+        empty_block_ticks: u32,
+    },
+}
+
+impl GeneratorMode {
+    /// Idle ticks before [`Self::Hybrid`] mines an empty block when picked
+    /// with [`Self::next`].
+    pub const DEFAULT_EMPTY_BLOCK_TICKS: u32 = 10;
+
+    /// Whether a tick mines, given the transactions in the mempool and the
+    /// ticks since the generator last mined.
+    #[must_use]
+    pub const fn should_mine(self, mempool_txs: u64, idle_ticks: u32) -> bool {
+        match self {
+            Self::FixedInterval => true,
+            Self::OnMempool => mempool_txs > 0,
+            Self::Hybrid { empty_block_ticks } => {
+                mempool_txs > 0 || idle_ticks >= empty_block_ticks
+            }
+        }
+    }
+
+    /// Whether [`Self::should_mine`] looks at the mempool.
+    #[must_use]
+    pub const fn needs_mempool(self) -> bool {
+        !matches!(self, Self::FixedInterval)
+    }
+
+    /// The mode after this one, for cycling through them.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::FixedInterval => Self::OnMempool,
+            Self::OnMempool => Self::Hybrid {
+                empty_block_ticks: Self::DEFAULT_EMPTY_BLOCK_TICKS,
+            },
+            Self::Hybrid { .. } => Self::FixedInterval,
+        }
+    }
+}
+
+impl std::fmt::Display for GeneratorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FixedInterval => f.write_str("fixed interval"),
+            Self::OnMempool => f.write_str("on mempool"),
+            Self::Hybrid { empty_block_ticks } => {
+                write!(
+                    f,
+                    "hybrid, empty block after {empty_block_ticks} idle ticks"
+                )
+            }
+        }
+    }
+}
+
+/// A running block generator task.
+struct BlockGenerator {
+    handle: JoinHandle<()>,
+    /// Read by the task on every tick, so it can change while running.
+    mode: Arc<Mutex<GeneratorMode>>,
+}
+
+/// A host port a container will publish.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortBinding {
+    /// Service the port belongs to, e.g. "rpc" or "grpc".
+    pub service: &'static str,
+    /// Port bound on the host.
+    pub host: u16,
+    /// Port inside the container.
+    pub container: u16,
+}
+
+/// A container [`NetworkManager::start_network`] would create.
+#[derive(Debug, Clone)]
+pub struct ContainerPlan {
+    /// Name of the node in the network.
+    pub node: String,
+    /// Kind of node.
+    pub kind: NodeKind,
+    /// Docker container name.
+    pub container_name: String,
+    /// Image the container runs.
+    pub image: String,
+    /// Published ports.
+    pub ports: Vec<PortBinding>,
+}
+
+/// What [`NetworkManager::start_network`] would do, computed without side effects.
+#[derive(Debug, Clone)]
+pub struct StartPlan {
+    /// Name of the network.
+    pub network: String,
+    /// Whether the network is already running, making a start a no-op.
+    pub already_running: bool,
+    /// Docker network the containers join.
+    pub docker_network: String,
+    /// Images to use and whether they need pulling.
+    pub images: Vec<(String, ImageStatus)>,
+    /// Containers in the order they are started.
+    pub containers: Vec<ContainerPlan>,
+    /// Host ports that are already in use by another process. Left empty
+    /// for a remote Docker host, whose ports can't be probed locally.
+    pub port_conflicts: Vec<u16>,
+}
+
+impl StartPlan {
+    /// Whether starting would fail on a missing image or a taken port.
+    #[must_use]
+    pub fn has_problems(&self) -> bool {
+        !self.port_conflicts.is_empty()
+            || self
+                .images
+                .iter()
+                .any(|(_, status)| *status == ImageStatus::Missing)
+    }
+}
+
+impl std::fmt::Display for StartPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Plan for starting network '{}':", self.network)?;
+        if self.already_running {
+            writeln!(f, "  Network is already running; nothing would change.")?;
+        }
+        writeln!(f, "  Docker network: {}", self.docker_network)?;
+        writeln!(f, "  Images:")?;
+        for (image, status) in &self.images {
+            let action = match status {
+                ImageStatus::Local => "present locally",
+                ImageStatus::Pull => "will be pulled",
+                ImageStatus::Missing => "NOT FOUND",
+            };
+            writeln!(f, "    {image} ({action})")?;
+        }
+        writeln!(f, "  Containers:")?;
+        for container in &self.containers {
+            writeln!(
+                f,
+                "    {} [{}] {} from {}",
+                container.container_name, container.kind, container.node, container.image
+            )?;
+            for binding in &container.ports {
+                let conflict = if self.port_conflicts.contains(&binding.host) {
+                    " (IN USE)"
+                } else {
+                    ""
+                };
+                writeln!(
+                    f,
+                    "      {:<10} {} -> {}{conflict}",
+                    binding.service, binding.host, binding.container
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
 
 /// Manages network lifecycle and operations.
 pub struct NetworkManager {
@@ -20,17 +383,49 @@ pub struct NetworkManager {
     config: Config,
     /// Log channel sender (optional).
     log_tx: Option<mpsc::UnboundedSender<String>>,
+    /// Background block generators keyed by network name.
+    block_generators: HashMap<String, BlockGenerator>,
+    /// Containers found by the last reconcile that match no node, as (ID, name).
+    orphaned_containers: Vec<(String, String)>,
+    /// LND REST clients keyed by container ID.
+    rest_clients: Mutex<HashMap<String, LndRestClient>>,
+    /// Node info command output, shared with detached managers.
+    info_cache: Arc<Mutex<InfoCache>>,
+    /// LND identity pubkeys keyed by container ID, shared with detached
+    /// managers. A node keeps its key for the life of its container.
+    pubkeys: Arc<Mutex<HashMap<String, String>>>,
+    /// Per-network operation locks keyed by network name, shared with
+    /// detached managers so block generators wait their turn too.
+    network_locks: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
 }
 
 impl NetworkManager {
     /// Create a new network manager.
     pub fn new() -> Result<Self> {
-        let config = Config::load()?;
+        Self::with_data_dir(None)
+    }
+
+    /// Create a network manager storing its networks under `data_dir`.
+    ///
+    /// Falls back to `POLAR_DATA_DIR` and then the configured `data_dir`
+    /// when `None`, so independent sets of networks can be kept apart.
+    pub fn with_data_dir(data_dir: Option<PathBuf>) -> Result<Self> {
+        let config = Config::load_with_data_dir(data_dir)?;
         let mut manager = Self {
-            container_manager: ContainerManager::new()?,
+            container_manager: ContainerManager::from_host(config.docker_host().as_deref())?
+                .with_pull_retries(
+                    config.image_pull_retries,
+                    Duration::from_millis(config.image_pull_retry_delay_ms),
+                ),
             networks: HashMap::new(),
             config,
             log_tx: None,
+            block_generators: HashMap::new(),
+            orphaned_containers: Vec::new(),
+            rest_clients: Mutex::new(HashMap::new()),
+            info_cache: Arc::default(),
+            pubkeys: Arc::default(),
+            network_locks: Arc::default(),
         };
 
         // Load existing networks from disk
@@ -54,11 +449,58 @@ impl NetworkManager {
         }
     }
 
+    /// Get the directory networks and exports are stored under.
+    #[must_use]
+    pub fn data_dir(&self) -> &Path {
+        &self.config.data_dir
+    }
+
+    /// Get the loaded configuration.
+    #[must_use]
+    pub const fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Replace the configuration with settings saved from the TUI.
+    ///
+    /// Networks and containers were set up with the current data directory,
+    /// Docker host and RPC credentials, so those are kept until a restart.
+    pub fn apply_config(&mut self, mut config: Config) {
+        config.data_dir.clone_from(&self.config.data_dir);
+        config.docker_host.clone_from(&self.config.docker_host);
+        config.docker_socket.clone_from(&self.config.docker_socket);
+        config.rpc_user.clone_from(&self.config.rpc_user);
+        config.rpc_pass.clone_from(&self.config.rpc_pass);
+        self.container_manager = self.container_manager.clone().with_pull_retries(
+            config.image_pull_retries,
+            Duration::from_millis(config.image_pull_retry_delay_ms),
+        );
+        self.config = config;
+    }
+
+    /// Whether quitting should stop running networks by default.
+    #[must_use]
+    pub const fn stop_on_exit(&self) -> bool {
+        self.config.stop_on_exit
+    }
+
     /// Get the networks directory path.
     fn networks_dir(&self) -> PathBuf {
         self.config.data_dir.join("networks")
     }
 
+    /// Get the default directory for exported networks.
+    #[must_use]
+    pub fn exports_dir(&self) -> PathBuf {
+        self.config.data_dir.join("exports")
+    }
+
+    /// Get the directory credentials of a network's nodes are exported to.
+    #[must_use]
+    pub fn credentials_dir(&self, network_name: &str) -> PathBuf {
+        self.config.data_dir.join("credentials").join(network_name)
+    }
+
     /// Get the path to a network file.
     fn network_file_path(&self, network_id: &str) -> PathBuf {
         self.networks_dir().join(format!("{}.json", network_id))
@@ -77,17 +519,46 @@ impl NetworkManager {
     }
 
     /// Save a single network to disk.
+    ///
+    /// The file is written next to the target and renamed over it, so a
+    /// reader or a racing save never sees half-written JSON. Every save
+    /// writes its own temporary file, so saves from several managers or
+    /// threads in one process do not clobber each other.
     fn save_network(&self, network: &Network) -> Result<()> {
+        static SAVE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
         let networks_dir = self.networks_dir();
         std::fs::create_dir_all(&networks_dir)?;
 
         let file_path = self.network_file_path(&network.id.to_string());
+        let tmp_path = file_path.with_extension(format!(
+            "json.{}.{}.tmp",
+            std::process::id(),
+            SAVE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
         let content = serde_json::to_string_pretty(network)?;
-        std::fs::write(&file_path, content)?;
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, &file_path)?;
 
         Ok(())
     }
 
+    /// Wait until no other operation holds `network_name`, then hold it
+    /// until the returned guard is dropped.
+    ///
+    /// Operations on the same network run one at a time while different
+    /// networks proceed in parallel.
+    async fn lock_network(&self, network_name: &str) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = Arc::clone(
+            self.network_locks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(network_name.to_string())
+                .or_default(),
+        );
+        lock.lock_owned().await
+    }
+
     /// Load all networks from disk.
     fn load_networks(&mut self) -> Result<()> {
         let networks_dir = self.networks_dir();
@@ -121,12 +592,317 @@ impl NetworkManager {
     }
 
     /// Load a single network from a file.
+    ///
+    /// Files saved with an older schema are migrated and written back, so
+    /// the upgrade only happens once.
     fn load_network(&self, path: &PathBuf) -> Result<Network> {
         let content = std::fs::read_to_string(path)?;
-        let network: Network = serde_json::from_str(&content)?;
+        let (network, migrated_from) = parse_network(&content)?;
+        if let Some(version) = migrated_from {
+            self.log(format!(
+                "Migrated network '{}' from schema v{version} to v{}",
+                network.name, network.schema_version
+            ));
+            self.save_network(&network)?;
+        }
         Ok(network)
     }
 
+    /// Adopt a running container started outside polar as a node.
+    ///
+    /// The container is inspected to check that it runs and that its image
+    /// fits `kind`, then recorded on the node named `node_name`, which is
+    /// created if needed. A container polar started for that node is stopped
+    /// and removed first, and the adopted one joins the network's Docker
+    /// network when it exists. Node operations then use the container, but
+    /// polar never recreates, stops or removes it.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the new or existing node
+    /// * `container_id` - ID or name of the container
+    /// * `kind` - Kind of node the container runs
+    pub async fn attach_external_container(
+        &mut self,
+        network_name: &str,
+        node_name: &str,
+        container_id: &str,
+        kind: NodeKind,
+    ) -> Result<()> {
+        if !self.networks.contains_key(network_name) {
+            return Err(Error::NetworkNotFound(network_name.to_string()));
+        }
+
+        let info = self
+            .container_manager
+            .inspect_container(container_id)
+            .await?;
+        if !info.state.as_ref().and_then(|s| s.running).unwrap_or(false) {
+            return Err(Error::Config(format!(
+                "Container {container_id} is not running"
+            )));
+        }
+        let image = info
+            .config
+            .as_ref()
+            .and_then(|c| c.image.as_deref())
+            .unwrap_or_default();
+        if !image_matches_kind(image, kind) {
+            return Err(Error::Config(format!(
+                "Container {container_id} runs '{image}', which is not a {kind} image"
+            )));
+        }
+        let container_id = info.id.unwrap_or_else(|| container_id.to_string());
+
+        let _guard = self.lock_network(network_name).await;
+        self.invalidate_info(network_name);
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let replaced = network
+            .nodes
+            .iter()
+            .find(|n| n.name == node_name && n.kind == kind && !n.external)
+            .and_then(|n| n.container_id.clone())
+            .filter(|id| *id != container_id);
+        if let Some(replaced) = replaced {
+            self.container_manager.stop_container(&replaced).await?;
+            self.container_manager.remove_container(&replaced).await?;
+        }
+
+        let docker_network_name = network.docker_network_name();
+        if self
+            .container_manager
+            .network_exists(&docker_network_name)
+            .await?
+        {
+            self.container_manager
+                .connect_network(&docker_network_name, &container_id)
+                .await?;
+        }
+        network.attach_container(node_name, kind, container_id.clone())?;
+        let network_clone = network.clone();
+        self.save_network(&network_clone)?;
+
+        self.log(format!(
+            "Attached container {} as {node_name} in network '{network_name}'",
+            container_id.get(..12).unwrap_or(&container_id)
+        ));
+        Ok(())
+    }
+
+    /// Match `polar-btc-*` and `polar-lnd-*` containers to the loaded networks.
+    ///
+    /// Containers are matched to nodes by the node UUID embedded in their
+    /// name. Nodes that lost track of their container, e.g. after a crash
+    /// mid-operation, get the container ID back. Containers matching no node
+    /// are recorded as orphans for [`Self::prune_orphans`].
+    pub async fn reconcile(&mut self) -> Result<ReconcileSummary> {
+        let mut summary = ReconcileSummary::default();
+        let mut changed = Vec::new();
+
+        for (container_id, name) in self
+            .container_manager
+            .list_containers_with_prefix("polar-")
+            .await?
+        {
+            let Some((kind, node_id)) = parse_container_name(&name) else {
+                continue;
+            };
+
+            let owner = self.networks.values_mut().find_map(|network| {
+                let network_name = network.name.clone();
+                network
+                    .nodes
+                    .iter_mut()
+                    .find(|n| n.kind == kind && n.id.to_string() == node_id)
+                    .map(|node| (network_name, node))
+            });
+
+            match owner {
+                Some((network_name, node)) => {
+                    if node.container_id.as_deref() != Some(container_id.as_str()) {
+                        node.container_id = Some(container_id);
+                        summary
+                            .reattached
+                            .push((network_name.clone(), node.name.clone()));
+                        changed.push(network_name);
+                    }
+                }
+                None => summary.orphaned.push((container_id, name)),
+            }
+        }
+
+        for network_name in changed {
+            if let Some(network) = self.networks.get(&network_name) {
+                self.save_network(network)?;
+            }
+        }
+
+        for (network, node) in &summary.reattached {
+            self.log(format!(
+                "Reattached container for {node} in network '{network}'"
+            ));
+        }
+        if !summary.orphaned.is_empty() {
+            self.log(format!(
+                "Found {} orphaned polar containers",
+                summary.orphaned.len()
+            ));
+        }
+        self.orphaned_containers.clone_from(&summary.orphaned);
+
+        Ok(summary)
+    }
+
+    /// Get the orphaned containers found by the last [`Self::reconcile`].
+    #[must_use]
+    pub fn orphaned_containers(&self) -> &[(String, String)] {
+        &self.orphaned_containers
+    }
+
+    /// Remove the orphaned containers found by the last [`Self::reconcile`]
+    /// together with their data volumes.
+    ///
+    /// # Returns
+    /// The number of containers removed
+    pub async fn prune_orphans(&mut self) -> Result<usize> {
+        let mut removed = 0;
+        for (container_id, name) in std::mem::take(&mut self.orphaned_containers) {
+            match self.container_manager.remove_container(&container_id).await {
+                Ok(()) => {
+                    self.log(format!("Removed orphaned container {name}"));
+                    removed += 1;
+                    let volume = data_volume_name(&name);
+                    if let Err(e) = self.container_manager.remove_volume(&volume).await {
+                        self.log(format!("Warning: Failed to remove volume {volume}: {e}"));
+                    }
+                }
+                Err(e) => {
+                    self.log(format!("Warning: Failed to remove {name}: {e}"));
+                    self.orphaned_containers.push((container_id, name));
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Rename a node in a network and persist the change.
+    ///
+    /// Containers are named after the node ID rather than its name, so a
+    /// running node keeps its container and needs no restart.
+    pub fn rename_node(
+        &mut self,
+        network_name: &str,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<()> {
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        network.rename_node(old_name, new_name)?;
+
+        let network = network.clone();
+        self.save_network(&network)?;
+        self.log(format!(
+            "Renamed {old_name} to {new_name} in network '{network_name}'"
+        ));
+        Ok(())
+    }
+
+    /// Set the topology to wire up the next time a network starts.
+    pub fn set_topology(&mut self, network_name: &str, topology: Option<Topology>) -> Result<()> {
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        network.topology = topology;
+
+        let network = network.clone();
+        self.save_network(&network)
+    }
+
+    /// Set the environment variables of a node's container and persist them.
+    ///
+    /// Containers are created with their environment, so the variables take
+    /// effect the next time the node starts. They override variables of the
+    /// same name from the image; flags polar generates and extra args still
+    /// take precedence for settings the daemon reads from both.
+    pub fn set_node_env(
+        &mut self,
+        network_name: &str,
+        node_name: &str,
+        env: Vec<(String, String)>,
+    ) -> Result<()> {
+        check_env(&env)?;
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let node = network
+            .nodes
+            .iter_mut()
+            .find(|n| n.name == node_name)
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
+        if node.external {
+            return Err(Error::Config(format!(
+                "{node_name} runs in an adopted container, its environment cannot be changed"
+            )));
+        }
+        node.env = env;
+
+        let network = network.clone();
+        self.save_network(&network)
+    }
+
+    /// Attach a network's containers to an existing Docker network, or go
+    /// back to a `polar-<id>` network of its own with `None`.
+    ///
+    /// The Docker network is only looked up when the network starts, and
+    /// polar never removes it.
+    pub fn set_external_network(
+        &mut self,
+        network_name: &str,
+        external_network: Option<String>,
+    ) -> Result<()> {
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        if !matches!(
+            network.status,
+            NetworkStatus::Stopped | NetworkStatus::Error
+        ) {
+            return Err(Error::Config(format!(
+                "Stop network '{network_name}' before changing its Docker network"
+            )));
+        }
+        network.external_network = external_network.filter(|name| !name.trim().is_empty());
+
+        let network = network.clone();
+        self.save_network(&network)
+    }
+
+    /// Take the topology waiting to be wired up in a network, if any.
+    ///
+    /// The topology is cleared and the change persisted, so it is applied
+    /// only once.
+    pub fn take_topology(&mut self, network_name: &str) -> Result<Option<Topology>> {
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let Some(topology) = network.topology.take() else {
+            return Ok(None);
+        };
+
+        let network = network.clone();
+        self.save_network(&network)?;
+        Ok(Some(topology))
+    }
+
     /// Delete a network from disk.
     fn delete_network_file(&self, network_id: &str) -> Result<()> {
         let file_path = self.network_file_path(network_id);
@@ -143,6 +919,7 @@ impl NetworkManager {
         self.create_network_with_config(
             name,
             2,
+            1,
             "polar-node",
             polar_nodes::LndNode::DEFAULT_IMAGE,
             polar_nodes::BitcoinNode::DEFAULT_IMAGE,
@@ -150,13 +927,47 @@ impl NetworkManager {
     }
 
     /// Create a new network with custom configuration.
+    ///
+    /// The first of the `btc_count` Bitcoin Core nodes is the primary backend
+    /// that every LND node connects to. Versions outside [`LND_VERSIONS`] and
+    /// [`BITCOIN_VERSIONS`] are rejected unless `allow_custom_images` is set
+    /// in the config. Nodes start with an empty environment; see
+    /// [`Self::set_node_env`].
     pub fn create_network_with_config(
         &mut self,
         name: impl Into<String>,
         lnd_count: usize,
+        btc_count: usize,
         alias_prefix: &str,
         lnd_version: &str,
         btc_version: &str,
+    ) -> Result<()> {
+        self.create_network_with_extra_args(
+            name,
+            lnd_count,
+            btc_count,
+            alias_prefix,
+            lnd_version,
+            btc_version,
+            ExtraArgs::default(),
+        )
+    }
+
+    /// Create a network whose nodes start with extra daemon flags.
+    ///
+    /// The flags are stored with the network and validated up front, so
+    /// arguments that override the regtest setup are rejected here rather
+    /// than when the network starts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_network_with_extra_args(
+        &mut self,
+        name: impl Into<String>,
+        lnd_count: usize,
+        btc_count: usize,
+        alias_prefix: &str,
+        lnd_version: &str,
+        btc_version: &str,
+        extra_args: ExtraArgs,
     ) -> Result<()> {
         let name = name.into();
 
@@ -164,16 +975,31 @@ impl NetworkManager {
             return Err(Error::Config(format!("Network '{}' already exists", name)));
         }
 
+        if !self.config.allow_custom_images {
+            check_supported_image("LND", lnd_version, LND_VERSIONS)?;
+            check_supported_image("Bitcoin Core", btc_version, BITCOIN_VERSIONS)?;
+        }
+
+        LndNode::new("lnd", "bitcoin")
+            .with_extra_args(extra_args.lnd.iter().cloned())
+            .check_extra_args()?;
+        BitcoinNode::new("bitcoin")
+            .with_extra_args(extra_args.bitcoin.iter().cloned())
+            .check_extra_args()?;
+
         let mut network = Network::new(name.clone());
+        network.extra_args = extra_args;
 
         // Store versions and alias
         network.lnd_version = Some(lnd_version.to_string());
         network.btc_version = Some(btc_version.to_string());
         network.alias_prefix = Some(alias_prefix.to_string());
 
-        // Add a Bitcoin Core node
-        let btc_node = Node::new("bitcoin-1", NodeKind::BitcoinCore);
-        network.add_node(btc_node);
+        // Add Bitcoin Core nodes (at least one is required as the LND backend)
+        for i in 1..=btc_count.max(1) {
+            let btc_node = Node::new(format!("bitcoin-{i}"), NodeKind::BitcoinCore);
+            network.add_node(btc_node);
+        }
 
         // Add LND nodes
         for i in 1..=lnd_count {
@@ -190,24 +1016,29 @@ impl NetworkManager {
     }
 
     /// Start a network.
+    #[tracing::instrument(skip(self))]
     pub async fn start_network(&mut self, name: &str) -> Result<()> {
-        let network = self
+        let _guard = self.lock_network(name).await;
+        self.invalidate_info(name);
+        let mut network = self
             .networks
-            .get_mut(name)
+            .get(name)
+            .cloned()
             .ok_or_else(|| Error::NetworkNotFound(name.to_string()))?;
 
+        // Keep the status and containers reached, even when a node fails
+        let result = self.start_network_nodes(&mut network).await;
+        self.networks.insert(name.to_string(), network);
+        result
+    }
+
+    /// Pull images and start the containers of `network`, recording their
+    /// IDs and the network status on it.
+    async fn start_network_nodes(&self, network: &mut Network) -> Result<()> {
         if network.status == NetworkStatus::Running {
             return Ok(());
         }
 
-        network.status = NetworkStatus::Starting;
-
-        // Create a Docker network for this polar network
-        let docker_network_name = format!("polar-{}", network.id);
-        self.container_manager
-            .create_network(&docker_network_name)
-            .await?;
-
         // Get stored versions and alias
         let btc_version = network
             .btc_version
@@ -217,6 +1048,45 @@ impl NetworkManager {
             .lnd_version
             .clone()
             .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string());
+
+        // Fail before starting any container if an image cannot be pulled
+        for image in [&btc_version, &lnd_version] {
+            if !self.container_manager.image_available_remote(image).await? {
+                return Err(Error::Docker(format!(
+                    "Image '{image}' was not found locally or in its registry"
+                )));
+            }
+        }
+
+        // Pull images up front so download progress shows in the log
+        for image in [&btc_version, &lnd_version] {
+            self.container_manager
+                .ensure_image_with_progress(image, |message| self.log(message))
+                .await?;
+        }
+
+        network.status = NetworkStatus::Starting;
+
+        // Create a Docker network for this polar network, or check that the
+        // external one to attach to exists
+        let docker_network_name = network.docker_network_name();
+        if network.external_network.is_some() {
+            if !self
+                .container_manager
+                .network_exists(&docker_network_name)
+                .await?
+            {
+                network.status = NetworkStatus::Error;
+                return Err(Error::Config(format!(
+                    "Docker network '{docker_network_name}' does not exist; create it with `docker network create {docker_network_name}`"
+                )));
+            }
+        } else {
+            self.container_manager
+                .create_network(&docker_network_name)
+                .await?;
+        }
+        connect_external_nodes(&self.container_manager, network).await?;
         let alias_prefix = network
             .alias_prefix
             .clone()
@@ -234,12 +1104,16 @@ impl NetworkManager {
             network.allocate_ports(node_id, node_kind);
         }
 
-        // Start Bitcoin Core nodes first
+        // Start Bitcoin Core nodes first; adopted containers are already running
         for node in &mut network.nodes {
-            if node.kind == NodeKind::BitcoinCore {
+            if node.kind == NodeKind::BitcoinCore && !node.external {
+                self.log(format!("Starting {}...", node.name));
                 let mut btc_node = BitcoinNode::new(node.name.clone());
                 btc_node.node.id = node.id;
                 btc_node.image = btc_version.clone();
+                btc_node.rpc = self.config.rpc_credentials();
+                btc_node.extra_args.clone_from(&network.extra_args.bitcoin);
+                btc_node.node.env.clone_from(&node.env);
 
                 // Get the allocated port configuration
                 let port_config = network.port_mappings.get(&node.id).unwrap().clone();
@@ -256,7 +1130,12 @@ impl NetworkManager {
                 };
 
                 match btc_node
-                    .start_with_ports(&self.container_manager, Some(&docker_network_name), ports)
+                    .start_with_ports(
+                        &self.container_manager,
+                        Some(&docker_network_name),
+                        ports,
+                        self.config.resource_limits.bitcoin,
+                    )
                     .await
                 {
                     Ok(_) => {
@@ -273,23 +1152,29 @@ impl NetworkManager {
         // Wait a bit for Bitcoin Core to be ready
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 
-        // Find the Bitcoin node ID first
-        let btc_node_id = network
-            .nodes
-            .iter()
-            .find(|n| n.kind == NodeKind::BitcoinCore)
-            .map(|n| n.id.to_string())
-            .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+        // Find the primary Bitcoin node first
+        let backend = lnd_backend(network)?;
+        let btc_node_id = backend.node.id.to_string();
+        let bitcoin_host = lnd_backend_host(&self.container_manager, network).await?;
 
         // Then start LND nodes with custom aliases
         let mut lnd_counter = 1;
         for node in &mut network.nodes {
-            if node.kind == NodeKind::Lnd {
+            if node.kind == NodeKind::Lnd && !node.external {
+                self.log(format!("Starting {}...", node.name));
                 let node_alias = format!("{}-{}", alias_prefix, lnd_counter);
                 let mut lnd_node =
                     LndNode::with_alias(node.name.clone(), btc_node_id.clone(), node_alias);
                 lnd_node.node.id = node.id;
+                lnd_node.bitcoin_host.clone_from(&bitcoin_host);
                 lnd_node.image = lnd_version.clone();
+                lnd_node.rpc = self.config.rpc_credentials();
+                lnd_node.extra_args = lnd_extra_args(&self.config, &network.extra_args);
+                lnd_node.node.env.clone_from(&node.env);
+                if let Err(e) = lnd_node.check_zmq_backend(&backend) {
+                    network.status = NetworkStatus::Error;
+                    return Err(e);
+                }
 
                 // Get the allocated port configuration
                 let port_config = network.port_mappings.get(&node.id).unwrap().clone();
@@ -301,7 +1186,13 @@ impl NetworkManager {
                 };
 
                 match lnd_node
-                    .start_with_ports(&self.container_manager, Some(&docker_network_name), ports)
+                    .start_with_ports(
+                        &self.container_manager,
+                        Some(&docker_network_name),
+                        ports,
+                        self.config.lnd_watchtowers,
+                        self.config.resource_limits.lnd,
+                    )
                     .await
                 {
                     Ok(_) => {
@@ -317,16 +1208,168 @@ impl NetworkManager {
         }
 
         network.status = NetworkStatus::Running;
+        self.save_network(network)
+    }
 
-        // Clone network for persistence to avoid borrow issues
-        let network_clone = network.clone();
-        self.save_network(&network_clone)?;
+    /// Describe what [`Self::start_network`] would do without doing it.
+    ///
+    /// Ports are allocated on a copy of the network exactly as a real start
+    /// would, so the plan lists the bindings that start would use. Images are
+    /// only looked up, never pulled, and nothing is persisted.
+    pub async fn plan_start(&self, name: &str) -> Result<StartPlan> {
+        let mut network = self
+            .get_network(name)
+            .cloned()
+            .ok_or_else(|| Error::NetworkNotFound(name.to_string()))?;
+        let already_running = network.status == NetworkStatus::Running;
 
-        Ok(())
+        let btc_version = network
+            .btc_version
+            .clone()
+            .unwrap_or_else(|| BitcoinNode::DEFAULT_IMAGE.to_string());
+        let lnd_version = network
+            .lnd_version
+            .clone()
+            .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string());
+
+        let mut images = Vec::new();
+        for image in [&btc_version, &lnd_version] {
+            let status = if self.container_manager.image_exists(image).await? {
+                ImageStatus::Local
+            } else if self.container_manager.image_in_registry(image).await? {
+                ImageStatus::Pull
+            } else {
+                ImageStatus::Missing
+            };
+            images.push((image.clone(), status));
+        }
+
+        let nodes_needing_ports: Vec<_> = network
+            .nodes
+            .iter()
+            .filter(|n| !network.port_mappings.contains_key(&n.id))
+            .map(|n| (n.id, n.kind))
+            .collect();
+        for (node_id, node_kind) in nodes_needing_ports {
+            network.allocate_ports(node_id, node_kind);
+        }
+
+        // Bitcoin Core nodes start before LND nodes
+        let mut containers = Vec::new();
+        for kind in [NodeKind::BitcoinCore, NodeKind::Lnd] {
+            for node in network.nodes.iter().filter(|n| n.kind == kind) {
+                let (container_name, image) = match kind {
+                    NodeKind::BitcoinCore => {
+                        let mut btc_node = BitcoinNode::new(node.name.clone());
+                        btc_node.node.id = node.id;
+                        (btc_node.container_name(), btc_version.clone())
+                    }
+                    NodeKind::Lnd => {
+                        let mut lnd_node = LndNode::new(node.name.clone(), String::new());
+                        lnd_node.node.id = node.id;
+                        (lnd_node.container_name(), lnd_version.clone())
+                    }
+                };
+                containers.push(ContainerPlan {
+                    node: node.name.clone(),
+                    kind,
+                    container_name,
+                    image,
+                    ports: network
+                        .port_mappings
+                        .get(&node.id)
+                        .map(|config| port_bindings(&config.ports))
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        // A running network holds its own ports, so only check stopped ones.
+        // Ports of a remote Docker host can't be probed from here.
+        let port_conflicts = if already_running || self.is_remote_docker() {
+            Vec::new()
+        } else {
+            containers
+                .iter()
+                .flat_map(|c| c.ports.iter().map(|p| p.host))
+                .filter(|port| std::net::TcpListener::bind(("0.0.0.0", *port)).is_err())
+                .collect()
+        };
+
+        Ok(StartPlan {
+            network: name.to_string(),
+            already_running,
+            docker_network: network.docker_network_name(),
+            images,
+            containers,
+            port_conflicts,
+        })
+    }
+
+    /// Whether containers run on a remote Docker host rather than this
+    /// machine.
+    fn is_remote_docker(&self) -> bool {
+        self.config
+            .docker_host()
+            .as_deref()
+            .and_then(docker_host_name)
+            .is_some()
+    }
+
+    /// Host that published container ports are reached at: the remote
+    /// Docker host, or the loopback address for a local daemon.
+    fn published_host(&self) -> String {
+        self.config
+            .docker_host()
+            .as_deref()
+            .and_then(docker_host_name)
+            .unwrap_or("127.0.0.1")
+            .to_string()
+    }
+
+    /// Names of the running networks, sorted.
+    #[must_use]
+    pub fn running_networks(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .networks
+            .values()
+            .filter(|n| n.status == NetworkStatus::Running)
+            .map(|n| n.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Stop every running network, e.g. before quitting.
+    ///
+    /// A failure does not stop the remaining networks from being stopped;
+    /// the first error is returned once all have been tried. Returns the
+    /// number of networks stopped.
+    pub async fn stop_all_networks(&mut self) -> Result<usize> {
+        let mut stopped = 0;
+        let mut first_error = None;
+        for name in self.running_networks() {
+            match self.stop_network(&name).await {
+                Ok(()) => stopped += 1,
+                Err(e) => {
+                    self.log(format!("Failed to stop network '{name}': {e}"));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(stopped), Err)
     }
 
     /// Stop a network.
+    ///
+    /// Every node is attempted even when some fail to stop. Nodes that fail
+    /// keep their container ID so stopping again retries them, the network
+    /// is marked as errored, and [`Error::StopFailed`] lists them.
     pub async fn stop_network(&mut self, name: &str) -> Result<()> {
+        let _guard = self.lock_network(name).await;
+        self.invalidate_info(name);
+        self.stop_block_generator(name);
+
         let network = self
             .networks
             .get_mut(name)
@@ -338,39 +1381,96 @@ impl NetworkManager {
 
         network.status = NetworkStatus::Stopping;
 
-        // Stop LND nodes first
+        // Stop LND nodes first, giving lnd a chance to flush its channel state
+        let stop_timeout = Duration::from_secs(self.config.lnd_stop_timeout_secs);
+        let mut forced_stops = Vec::new();
+        let mut failures = Vec::new();
         for node in &mut network.nodes {
-            if node.kind == NodeKind::Lnd {
-                if let Some(container_id) = &node.container_id {
-                    self.container_manager.stop_container(container_id).await?;
-                    self.container_manager
-                        .remove_container(container_id)
-                        .await?;
-                    node.container_id = None;
+            if node.kind == NodeKind::Lnd && node.container_id.is_some() && !node.external {
+                let mut lnd_node = LndNode::new(node.name.clone(), String::new());
+                lnd_node.node = node.clone();
+                match lnd_node
+                    .stop_with_timeout(&self.container_manager, stop_timeout)
+                    .await
+                {
+                    Ok(graceful) => {
+                        if !graceful {
+                            forced_stops.push(node.name.clone());
+                        }
+                        node.container_id = None;
+                    }
+                    Err(e) => failures.push((node.name.clone(), e.to_string())),
                 }
             }
         }
 
         // Then stop Bitcoin Core nodes
         for node in &mut network.nodes {
-            if node.kind == NodeKind::BitcoinCore {
+            if node.kind == NodeKind::BitcoinCore && !node.external {
                 if let Some(container_id) = &node.container_id {
-                    self.container_manager.stop_container(container_id).await?;
-                    self.container_manager
-                        .remove_container(container_id)
-                        .await?;
-                    node.container_id = None;
+                    let stopped = match self.container_manager.stop_container(container_id).await {
+                        Ok(()) => self.container_manager.remove_container(container_id).await,
+                        Err(e) => Err(e),
+                    };
+                    match stopped {
+                        Ok(()) => node.container_id = None,
+                        Err(e) => failures.push((node.name.clone(), e.to_string())),
+                    }
                 }
             }
         }
 
-        network.status = NetworkStatus::Stopped;
+        network.status = if failures.is_empty() {
+            NetworkStatus::Stopped
+        } else {
+            NetworkStatus::Error
+        };
 
         // Clone network for persistence to avoid borrow issues
         let network_clone = network.clone();
 
-        // Remove the Docker network
-        let docker_network_name = format!("polar-{}", network_clone.id);
+        for node_name in forced_stops {
+            self.log(format!(
+                "Warning: {node_name} did not stop within {}s, container was force stopped",
+                stop_timeout.as_secs()
+            ));
+        }
+
+        // Remove the Docker network polar created; external ones are left alone
+        self.remove_docker_network(&network_clone).await;
+
+        self.save_network(&network_clone)?;
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::StopFailed {
+                network: name.to_string(),
+                failures,
+            })
+        }
+    }
+
+    /// Remove the Docker network polar created for a network.
+    ///
+    /// External networks belong to the user and are kept. Adopted containers
+    /// keep running, so they are disconnected from the network first.
+    async fn remove_docker_network(&self, network: &Network) {
+        if network.external_network.is_some() {
+            return;
+        }
+        let docker_network_name = network.docker_network_name();
+        for node in network.nodes.iter().filter(|n| n.external) {
+            if let Some(container_id) = &node.container_id {
+                if let Err(e) = self
+                    .container_manager
+                    .disconnect_network(&docker_network_name, container_id)
+                    .await
+                {
+                    self.log(format!("Warning: {e}"));
+                }
+            }
+        }
         if let Err(e) = self
             .container_manager
             .remove_network(&docker_network_name)
@@ -378,14 +1478,80 @@ impl NetworkManager {
         {
             // Log but don't fail - network might not exist
             self.log(format!(
-                "Warning: Failed to remove network {}: {}",
-                docker_network_name, e
+                "Warning: Failed to remove network {docker_network_name}: {e}"
             ));
         }
+    }
+
+    /// Restart a network's containers, keeping their chain data.
+    ///
+    /// Each node's data volume outlives its container, so block height,
+    /// wallets and channels survive and the containers come back on the
+    /// network's current images. Networks created before nodes had named
+    /// volumes start from block zero on their first restart. The block
+    /// generator is stopped like in [`Self::stop_network`]. Use
+    /// [`Self::reset_network`] to wipe the chain data instead.
+    pub async fn restart_network(&mut self, name: &str) -> Result<()> {
+        self.stop_network(name).await?;
+        self.start_network(name).await?;
+        self.log(format!(
+            "Restarted network '{name}' with its chain data kept"
+        ));
+        Ok(())
+    }
+
+    /// Remove a managed node's data volume, logging rather than failing.
+    async fn remove_node_volume(&self, node: &Node) {
+        if node.external {
+            return;
+        }
+        let volume = node_volume_name(node);
+        if let Err(e) = self.container_manager.remove_volume(&volume).await {
+            self.log(format!("Warning: Failed to remove volume {volume}: {e}"));
+        }
+    }
+
+    /// Wipe a network's chain data and start it again from block zero.
+    ///
+    /// Containers are force removed together with their data volumes, but
+    /// the saved topology and port mappings are kept so endpoints stay
+    /// stable.
+    /// Unlike [`Self::delete_network`], the network itself is not removed.
+    pub async fn reset_network(&mut self, name: &str) -> Result<()> {
+        let guard = self.lock_network(name).await;
+        self.invalidate_info(name);
+        self.stop_block_generator(name);
+
+        let network = self
+            .networks
+            .get_mut(name)
+            .ok_or_else(|| Error::NetworkNotFound(name.to_string()))?;
+
+        let container_ids: Vec<String> = network
+            .nodes
+            .iter_mut()
+            .filter(|node| !node.external)
+            .filter_map(|node| node.container_id.take())
+            .collect();
+        network.status = NetworkStatus::Stopped;
+        let network_clone = network.clone();
+
+        for container_id in &container_ids {
+            self.container_manager
+                .remove_container_with_volumes(container_id)
+                .await?;
+        }
+        for node in &network_clone.nodes {
+            self.remove_node_volume(node).await;
+        }
+
+        self.remove_docker_network(&network_clone).await;
 
         self.save_network(&network_clone)?;
+        self.log(format!("Wiped chain data for network '{name}'"));
+        drop(guard);
 
-        Ok(())
+        self.start_network(name).await
     }
 
     /// Get all networks.
@@ -403,7 +1569,7 @@ impl NetworkManager {
         self.networks.get_mut(name)
     }
 
-    /// Delete a network.
+    /// Delete a network along with its nodes' data volumes.
     pub async fn delete_network(&mut self, name: &str) -> Result<()> {
         // Check if network exists and get its status and ID
         let (should_stop, network_id) = if let Some(network) = self.networks.get(name) {
@@ -419,114 +1585,277 @@ impl NetworkManager {
         if should_stop {
             self.stop_network(name).await?;
         }
+        let _guard = self.lock_network(name).await;
 
         // Remove from in-memory map
-        self.networks.remove(name);
+        if let Some(network) = self.networks.remove(name) {
+            for node in &network.nodes {
+                self.remove_node_volume(node).await;
+            }
+        }
 
         // Delete the network file from disk
         self.delete_network_file(&network_id)?;
 
+        // Drop the lock entry so deleted networks do not pile up. The entry
+        // and our guard are its only handles unless an operation is waiting,
+        // in which case it stays so a re-created network shares its mutex.
+        let mut locks = self
+            .network_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if locks
+            .get(name)
+            .is_some_and(|lock| Arc::strong_count(lock) <= 2)
+        {
+            locks.remove(name);
+        }
+        drop(locks);
+
+        Ok(())
+    }
+
+    /// Export a network to a portable file that can be shared and imported elsewhere.
+    pub fn export_network(&self, name: &str, path: &Path) -> Result<()> {
+        let network = self
+            .networks
+            .get(name)
+            .ok_or_else(|| Error::NetworkNotFound(name.to_string()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(&network.to_portable())?;
+        std::fs::write(path, content)?;
+
+        self.log(format!("Exported network '{name}' to {}", path.display()));
         Ok(())
     }
 
+    /// Import a network from a file created by [`Self::export_network`].
+    ///
+    /// The imported network gets a fresh ID and its port mappings are cleared
+    /// so they are re-allocated on next start. Returns the network name.
+    pub fn import_network(&mut self, path: &Path) -> Result<String> {
+        let content = std::fs::read_to_string(path)?;
+        let (mut network, _) = parse_network(&content)?;
+
+        if self.networks.contains_key(&network.name) {
+            return Err(Error::Config(format!(
+                "A network named '{}' already exists",
+                network.name
+            )));
+        }
+
+        network.prepare_import();
+        self.save_network(&network)?;
+
+        let name = network.name.clone();
+        self.networks.insert(name.clone(), network);
+
+        self.log(format!("Imported network '{name}' from {}", path.display()));
+        Ok(name)
+    }
+
+    /// Report the disk space each network takes up in the data directory.
+    ///
+    /// Walks `networks/`, `exports/` and `credentials/`, so networks that
+    /// were deleted but left exports or credentials behind show up as well.
+    /// Sorted by network name.
+    pub fn data_usage(&self) -> Result<Vec<DataUsage>> {
+        let mut usage = BTreeMap::new();
+        for network in self.networks.values() {
+            let network_file = path_size(&self.network_file_path(&network.id.to_string()))?;
+            usage.insert(
+                network.name.clone(),
+                DataUsage {
+                    network: network.name.clone(),
+                    loaded: true,
+                    network_file,
+                    ..DataUsage::default()
+                },
+            );
+        }
+
+        let unloaded = |name: &String| DataUsage {
+            network: name.clone(),
+            ..DataUsage::default()
+        };
+        for (name, size) in dir_entries(&self.exports_dir(), Some("json"))? {
+            usage
+                .entry(name.clone())
+                .or_insert_with(|| unloaded(&name))
+                .exports = size;
+        }
+        let credentials_root = self.config.data_dir.join("credentials");
+        for (name, size) in dir_entries(&credentials_root, None)? {
+            usage
+                .entry(name.clone())
+                .or_insert_with(|| unloaded(&name))
+                .credentials = size;
+        }
+
+        Ok(usage.into_values().collect())
+    }
+
+    /// Remove the exported copy and extracted credentials of a network.
+    ///
+    /// Meant for networks that were deleted or reset, whose exports and
+    /// credentials are stale. The saved network file under `networks/` is
+    /// never touched, and neither is the export of a network that is still
+    /// loaded, which may be the user's only portable copy of it.
+    ///
+    /// # Returns
+    /// The number of bytes freed
+    pub fn clean_network_data(&self, name: &str) -> Result<u64> {
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(Error::Config(format!("Invalid network name '{name}'")));
+        }
+
+        let export = self.exports_dir().join(format!("{name}.json"));
+        let credentials = self.credentials_dir(name);
+        let mut freed = path_size(&credentials)?;
+        if self.networks.contains_key(name) {
+            if export.is_file() {
+                self.log(format!(
+                    "Keeping {} of loaded network '{name}'",
+                    export.display()
+                ));
+            }
+        } else if export.is_file() {
+            freed += path_size(&export)?;
+            std::fs::remove_file(&export)?;
+        }
+        if credentials.is_dir() {
+            std::fs::remove_dir_all(&credentials)?;
+        }
+
+        self.log(format!("Removed {freed} bytes of stale data of '{name}'"));
+        Ok(freed)
+    }
+
     /// Get information about a Bitcoin Core node.
     pub async fn get_bitcoin_node_info(&self, container_id: &str) -> Result<BitcoinNodeInfo> {
+        self.get_bitcoin_node_info_with_ports(container_id, None)
+            .await
+    }
+
+    /// Make sure the bitcoind in `container_id` has its wallet loaded.
+    async fn ensure_bitcoin_wallet(&self, container_id: &str) -> Result<()> {
+        BitcoinNode::ensure_wallet_in(
+            &self.container_manager,
+            &self.config.rpc_credentials(),
+            container_id,
+        )
+        .await
+    }
+
+    /// Get the chain tip height of a network's primary Bitcoin node.
+    ///
+    /// Returns `None` when the network is not running. The block count goes
+    /// through the node info cache, so frequent polling stays cheap.
+    pub async fn block_height(&self, network_name: &str) -> Result<Option<u64>> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        if network.status != NetworkStatus::Running {
+            return Ok(None);
+        }
+        let Some(container_id) = network
+            .primary_bitcoin_node()
+            .and_then(|node| node.container_id.clone())
+        else {
+            return Ok(None);
+        };
+
+        let output = self
+            .cached_bitcoin_cli(&container_id, &["getblockcount"])
+            .await?;
+        output
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse block count: {e}")))
+    }
+
+    /// Get information about a Bitcoin Core node, reporting endpoints from the
+    /// persisted port configuration when available.
+    ///
+    /// # Arguments
+    /// * `container_id` - Docker container ID of the node
+    /// * `port_config` - Persisted port configuration (falls back to container inspection)
+    #[tracing::instrument(level = "debug", skip(self, port_config))]
+    pub async fn get_bitcoin_node_info_with_ports(
+        &self,
+        container_id: &str,
+        port_config: Option<&PortConfig>,
+    ) -> Result<BitcoinNodeInfo> {
         // Execute bitcoin-cli getblockchaininfo
         let blockchain_info = self
-            .container_manager
-            .exec_command(
-                container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "getblockchaininfo",
-                ],
-            )
+            .cached_bitcoin_cli(container_id, &["getblockchaininfo"])
             .await?;
 
         // Execute bitcoin-cli getnetworkinfo
         let network_info = self
-            .container_manager
-            .exec_command(
-                container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "getnetworkinfo",
-                ],
-            )
+            .cached_bitcoin_cli(container_id, &["getnetworkinfo"])
             .await?;
 
-        // Execute bitcoin-cli getbalance
+        // Execute bitcoin-cli getbalance, loading the wallet first if a
+        // restart left it unloaded
+        self.ensure_bitcoin_wallet(container_id).await?;
         let balance_info = self
-            .container_manager
-            .exec_command(
-                container_id,
-                vec![
-                    "bitcoin-cli",
-                    "-regtest",
-                    "-rpcuser=polaruser",
-                    "-rpcpassword=polarpass",
-                    "getbalance",
-                ],
-            )
+            .cached_bitcoin_cli(container_id, &["getbalance"])
             .await?;
 
-        // Parse JSON responses
-        let blockchain_json: serde_json::Value = serde_json::from_str(&blockchain_info)
-            .map_err(|e| Error::Config(format!("Failed to parse blockchain info: {}", e)))?;
+        // Execute bitcoin-cli getchaintips
+        let chain_tips = self
+            .cached_bitcoin_cli(container_id, &["getchaintips"])
+            .await?;
 
-        let network_json: serde_json::Value = serde_json::from_str(&network_info)
-            .map_err(|e| Error::Config(format!("Failed to parse network info: {}", e)))?;
+        // Execute bitcoin-cli getpeerinfo
+        let peer_info = self
+            .cached_bitcoin_cli(container_id, &["getpeerinfo"])
+            .await?;
 
-        // Get container info for ports
-        let container_info = self
-            .container_manager
-            .inspect_container(container_id)
+        // Execute bitcoin-cli getmempoolinfo and getrawmempool
+        let mempool_info = self
+            .cached_bitcoin_cli(container_id, &["getmempoolinfo"])
+            .await?;
+        let mempool = self
+            .cached_bitcoin_cli(container_id, &["getrawmempool", "true"])
             .await?;
 
-        let ports = container_info
-            .network_settings
-            .as_ref()
-            .and_then(|ns| ns.ports.as_ref())
-            .cloned()
-            .unwrap_or_default();
+        // Parse JSON responses
+        let blockchain_json: serde_json::Value = serde_json::from_str(&blockchain_info)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse blockchain info: {}", e)))?;
 
-        // Extract RPC port (18443 for regtest)
-        let rpc_host = ports
-            .get("18443/tcp")
-            .and_then(|bindings| bindings.as_ref())
-            .and_then(|b| b.first())
-            .map(|binding| {
-                format!(
-                    "{}:{}",
-                    binding.host_ip.as_deref().unwrap_or("0.0.0.0"),
-                    binding.host_port.as_deref().unwrap_or("18443")
-                )
-            })
-            .unwrap_or_else(|| "18443".to_string());
-
-        // Extract P2P port (18444 for regtest)
-        let p2p_host = ports
-            .get("18444/tcp")
-            .and_then(|bindings| bindings.as_ref())
-            .and_then(|b| b.first())
-            .map(|binding| {
-                format!(
-                    "{}:{}",
-                    binding.host_ip.as_deref().unwrap_or("0.0.0.0"),
-                    binding.host_port.as_deref().unwrap_or("18444")
-                )
-            })
-            .unwrap_or_else(|| "18444".to_string());
+        let network_json: serde_json::Value = serde_json::from_str(&network_info)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse network info: {}", e)))?;
+
+        // Prefer the persisted port configuration, fall back to container inspection
+        let [rpc_host, p2p_host] =
+            if let Some(NodePorts::BitcoinCore { rpc, p2p, .. }) = port_config.map(|c| &c.ports) {
+                [format!("0.0.0.0:{rpc}"), format!("0.0.0.0:{p2p}")]
+            } else {
+                self.inspect_host_endpoints(container_id, [18443, 18444])
+                    .await?
+            };
 
         // Parse balance
         let balance: f64 = balance_info.trim().parse().unwrap_or(0.0);
 
+        let chain_tips = serde_json::from_str(&chain_tips)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse chain tips: {e}")))?;
+
+        let peers = serde_json::from_str(&peer_info)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse peer info: {e}")))?;
+
+        let mempool_info: MempoolInfo = serde_json::from_str(&mempool_info)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse mempool info: {e}")))?;
+        let mempool_json: serde_json::Value = serde_json::from_str(&mempool)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse mempool: {e}")))?;
+
         Ok(BitcoinNodeInfo {
             version: network_json["subversion"]
                 .as_str()
@@ -545,63 +1874,323 @@ impl NetworkManager {
             balance,
             rpc_host,
             p2p_host,
+            chain_tips,
+            peers,
+            mempool_size: mempool_info.size,
+            mempool: BitcoinNode::parse_mempool(&mempool_json),
         })
     }
 
-    /// Get information about an LND node.
-    pub async fn get_lnd_node_info(&self, container_id: &str) -> Result<LndNodeInfo> {
-        // LND commands with proper network flag and TLS cert path
-        let lncli_args = vec![
-            "lncli",
-            "--network=regtest",
-            "--tlscertpath=/home/lnd/.lnd/tls.cert",
-            "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-        ];
-
-        // Execute lncli getinfo
-        let mut getinfo_cmd = lncli_args.clone();
-        getinfo_cmd.push("getinfo");
+    /// Run `lncli getinfo`, `walletbalance` and `listchannels` in an LND
+    /// container.
+    async fn lnd_exec_info(
+        &self,
+        container_id: &str,
+        lncli_args: &[&str],
+    ) -> Result<[serde_json::Value; 3]> {
         let getinfo = self
-            .container_manager
-            .exec_command(container_id, getinfo_cmd)
+            .cached_info(
+                container_id,
+                "getinfo",
+                self.container_manager
+                    .exec_command(container_id, [lncli_args, &["getinfo"]].concat()),
+            )
             .await?;
-
-        // Execute lncli walletbalance
-        let mut wallet_cmd = lncli_args.clone();
-        wallet_cmd.push("walletbalance");
         let wallet_balance = self
-            .container_manager
-            .exec_command(container_id, wallet_cmd)
+            .cached_info(
+                container_id,
+                "walletbalance",
+                self.container_manager
+                    .exec_command(container_id, [lncli_args, &["walletbalance"]].concat()),
+            )
+            .await?;
+        let list_channels = self
+            .cached_info(
+                container_id,
+                "listchannels",
+                self.container_manager
+                    .exec_command(container_id, [lncli_args, &["listchannels"]].concat()),
+            )
+            .await?;
+
+        let info_json = serde_json::from_str(&getinfo)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse getinfo: {e}")))?;
+        let wallet_json = serde_json::from_str(&wallet_balance)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse wallet balance: {e}")))?;
+        let channels_json = serde_json::from_str(&list_channels)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse channels list: {e}")))?;
+        Ok([info_json, wallet_json, channels_json])
+    }
+
+    /// Fetch getinfo, wallet balance and channels over the REST API published
+    /// on `port` of the Docker host, reusing the client of earlier calls.
+    async fn lnd_rest_info(&self, container_id: &str, port: u16) -> Result<[serde_json::Value; 3]> {
+        let cached = self.rest_clients().get(container_id).cloned();
+        let client = if let Some(client) = cached {
+            client
+        } else {
+            let mut lnd = LndNode::new(container_id, "");
+            lnd.node.container_id = Some(container_id.to_string());
+            let client = lnd
+                .rest_client(
+                    &self.container_manager,
+                    format!("{}:{port}", self.published_host()),
+                )
+                .await?;
+            self.rest_clients()
+                .insert(container_id.to_string(), client.clone());
+            client
+        };
+
+        let result = tokio::try_join!(
+            client.get_info(),
+            client.wallet_balance(),
+            client.list_channels()
+        );
+        if result.is_err() {
+            // Read the credentials again next time, in case the node was reset
+            self.rest_clients().remove(container_id);
+        }
+        Ok(result?.into())
+    }
+
+    /// Run a node info command, reusing output younger than
+    /// `info_cache_ttl_ms` so repeated requests for the same node do not
+    /// exec into its container again.
+    ///
+    /// `fetch` only runs on a miss, and failed commands are not cached.
+    async fn cached_info(
+        &self,
+        container_id: &str,
+        command: &str,
+        fetch: impl Future<Output = Result<String>>,
+    ) -> Result<String> {
+        let ttl = Duration::from_millis(self.config.info_cache_ttl_ms);
+        let key = (container_id.to_string(), command.to_string());
+        let cached = self
+            .info_cache()
+            .get(&key)
+            .filter(|(fetched, _)| fetched.elapsed() < ttl)
+            .map(|(_, output)| output.clone());
+        if let Some(output) = cached {
+            return Ok(output);
+        }
+
+        let output = fetch.await?;
+        if !ttl.is_zero() {
+            let mut cache = self.info_cache();
+            // Expired entries, e.g. of removed containers, are never read again
+            cache.retain(|_, (fetched, _)| fetched.elapsed() < ttl);
+            cache.insert(key, (Instant::now(), output.clone()));
+        }
+        Ok(output)
+    }
+
+    /// Run `lncli getinfo` in an LND container through the info cache.
+    async fn lnd_getinfo(&self, container_id: &str) -> Result<serde_json::Value> {
+        let output = self
+            .cached_info(
+                container_id,
+                "getinfo",
+                self.container_manager.exec_command(
+                    container_id,
+                    vec![
+                        "lncli",
+                        "--network=regtest",
+                        "--tlscertpath=/home/lnd/.lnd/tls.cert",
+                        "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
+                        "getinfo",
+                    ],
+                ),
+            )
             .await?;
+        serde_json::from_str(&output)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse getinfo: {e}")))
+    }
+
+    /// Forget cached info of every node in a network once the returned
+    /// guard drops, i.e. when an operation that changes node state returns.
+    ///
+    /// Info cached while the operation ran is dropped with the rest, and an
+    /// operation failing halfway still invalidates what it changed.
+    const fn invalidate_info_on_exit<'a>(&'a self, network_name: &'a str) -> InfoInvalidation<'a> {
+        InfoInvalidation {
+            manager: self,
+            network_name,
+        }
+    }
+
+    /// Forget cached info of every node in a network.
+    ///
+    /// Called whenever a node stops or starts, since that also changes what
+    /// its peers report.
+    fn invalidate_info(&self, network_name: &str) {
+        let Some(network) = self.get_network(network_name) else {
+            return;
+        };
+        let container_ids: Vec<&String> = network
+            .nodes
+            .iter()
+            .filter_map(|n| n.container_id.as_ref())
+            .collect();
+        self.info_cache()
+            .retain(|(id, _), _| !container_ids.contains(&id));
+    }
+
+    /// Map the identity pubkey of every running LND node in a network to
+    /// the node's name.
+    ///
+    /// Pubkeys are cached per container; nodes that cannot be queried are
+    /// left out, so their peers fall back to showing the pubkey.
+    async fn node_names_by_pubkey(&self, network: &Network) -> HashMap<String, String> {
+        let mut names = HashMap::new();
+        for node in network.nodes.iter().filter(|n| n.kind == NodeKind::Lnd) {
+            let Some(container_id) = &node.container_id else {
+                continue;
+            };
+            let cached = self.pubkeys().get(container_id).cloned();
+            if let Some(pubkey) = cached {
+                names.insert(pubkey, node.name.clone());
+                continue;
+            }
+
+            let fetched = self
+                .lnd_getinfo(container_id)
+                .await
+                .ok()
+                .and_then(|info| info["identity_pubkey"].as_str().map(ToString::to_string));
+            if let Some(pubkey) = fetched {
+                self.pubkeys().insert(container_id.clone(), pubkey.clone());
+                names.insert(pubkey, node.name.clone());
+            }
+        }
+        names
+    }
+
+    fn pubkeys(&self) -> MutexGuard<'_, HashMap<String, String>> {
+        self.pubkeys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn info_cache(&self) -> MutexGuard<'_, InfoCache> {
+        self.info_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn rest_clients(&self) -> MutexGuard<'_, HashMap<String, LndRestClient>> {
+        self.rest_clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get information about an LND node.
+    pub async fn get_lnd_node_info(&self, container_id: &str) -> Result<LndNodeInfo> {
+        self.get_lnd_node_info_with_ports(container_id, None).await
+    }
+
+    /// Get information about an LND node, reporting endpoints from the
+    /// persisted port configuration when available.
+    ///
+    /// # Arguments
+    /// * `container_id` - Docker container ID of the node
+    /// * `port_config` - Persisted port configuration (falls back to container inspection)
+    #[tracing::instrument(level = "debug", skip(self, port_config))]
+    pub async fn get_lnd_node_info_with_ports(
+        &self,
+        container_id: &str,
+        port_config: Option<&PortConfig>,
+    ) -> Result<LndNodeInfo> {
+        // LND commands with proper network flag and TLS cert path
+        let lncli_args = vec![
+            "lncli",
+            "--network=regtest",
+            "--tlscertpath=/home/lnd/.lnd/tls.cert",
+            "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
+        ];
+
+        // Prefer the REST API when enabled, falling back to lncli on any error
+        let rest_port = match port_config.map(|c| &c.ports) {
+            Some(NodePorts::Lnd { rest, .. }) if self.config.lnd_rest_client => Some(*rest),
+            _ => None,
+        };
+        let rest_json = match rest_port {
+            Some(port) => self
+                .lnd_rest_info(container_id, port)
+                .await
+                .inspect_err(|e| tracing::debug!("LND REST unavailable, using lncli: {e}"))
+                .ok(),
+            None => None,
+        };
+        let [info_json, wallet_json, channels_json] = match rest_json {
+            Some(json) => json,
+            None => self.lnd_exec_info(container_id, &lncli_args).await?,
+        };
 
         // Execute lncli channelbalance
         let mut channel_cmd = lncli_args.clone();
         channel_cmd.push("channelbalance");
         let channel_balance = self
-            .container_manager
-            .exec_command(container_id, channel_cmd)
+            .cached_info(
+                container_id,
+                "channelbalance",
+                self.container_manager
+                    .exec_command(container_id, channel_cmd),
+            )
             .await?;
 
-        // Execute lncli listchannels
-        let mut list_channels_cmd = lncli_args.clone();
-        list_channels_cmd.push("listchannels");
-        let list_channels = self
-            .container_manager
-            .exec_command(container_id, list_channels_cmd)
+        // Execute lncli pendingchannels
+        let mut pending_channels_cmd = lncli_args.clone();
+        pending_channels_cmd.push("pendingchannels");
+        let pending_channels = self
+            .cached_info(
+                container_id,
+                "pendingchannels",
+                self.container_manager
+                    .exec_command(container_id, pending_channels_cmd),
+            )
             .await?;
 
-        // Parse JSON responses
-        let info_json: serde_json::Value = serde_json::from_str(&getinfo)
-            .map_err(|e| Error::Config(format!("Failed to parse getinfo: {}", e)))?;
-
-        let wallet_json: serde_json::Value = serde_json::from_str(&wallet_balance)
-            .map_err(|e| Error::Config(format!("Failed to parse wallet balance: {}", e)))?;
+        // Execute lncli listpeers
+        let mut list_peers_cmd = lncli_args.clone();
+        list_peers_cmd.push("listpeers");
+        let list_peers = self
+            .cached_info(
+                container_id,
+                "listpeers",
+                self.container_manager
+                    .exec_command(container_id, list_peers_cmd),
+            )
+            .await?;
 
+        // Parse JSON responses
         let channel_json: serde_json::Value = serde_json::from_str(&channel_balance)
-            .map_err(|e| Error::Config(format!("Failed to parse channel balance: {}", e)))?;
+            .map_err(|e| Error::RpcParse(format!("Failed to parse channel balance: {}", e)))?;
+
+        let pending_json: serde_json::Value = serde_json::from_str(&pending_channels)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse pending channels: {}", e)))?;
+
+        let peers_json: serde_json::Value = serde_json::from_str(&list_peers)
+            .map_err(|e| Error::RpcParse(format!("Failed to parse peers: {e}")))?;
+
+        // Execute lncli wtclient towers, which fails unless the client is active
+        let towers_json = if self.config.lnd_watchtowers {
+            let mut towers_cmd = lncli_args.clone();
+            towers_cmd.extend(["wtclient", "towers"]);
+            let towers = self
+                .cached_info(
+                    container_id,
+                    "wtclient towers",
+                    self.container_manager
+                        .exec_command_unchecked(container_id, towers_cmd),
+                )
+                .await?;
+            serde_json::from_str(&towers).unwrap_or(serde_json::Value::Null)
+        } else {
+            serde_json::Value::Null
+        };
 
-        let channels_json: serde_json::Value = serde_json::from_str(&list_channels)
-            .map_err(|e| Error::Config(format!("Failed to parse channels list: {}", e)))?;
+        // Channels whose peer is connected can be closed cooperatively
+        let peers = parse_peers(&peers_json);
 
         // Parse channel list
         let channels = channels_json["channels"]
@@ -609,6 +2198,7 @@ impl NetworkManager {
             .map(|arr| {
                 arr.iter()
                     .map(|ch| polar_core::ChannelInfo {
+                        chan_id: ch["chan_id"].as_str().unwrap_or_default().to_string(),
                         channel_point: ch["channel_point"]
                             .as_str()
                             .unwrap_or("unknown")
@@ -617,64 +2207,32 @@ impl NetworkManager {
                             .as_str()
                             .unwrap_or("unknown")
                             .to_string(),
-                        capacity: ch["capacity"]
-                            .as_str()
-                            .and_then(|s| s.parse::<i64>().ok())
-                            .unwrap_or(0),
-                        local_balance: ch["local_balance"]
+                        remote_name: None,
+                        capacity: lncli_number::<i64>(&ch["capacity"]),
+                        local_balance: lncli_number::<i64>(&ch["local_balance"]),
+                        remote_balance: lncli_number::<i64>(&ch["remote_balance"]),
+                        active: ch["active"].as_bool().unwrap_or(false),
+                        peer_online: ch["remote_pubkey"]
                             .as_str()
-                            .and_then(|s| s.parse::<i64>().ok())
-                            .unwrap_or(0),
-                        remote_balance: ch["remote_balance"]
+                            .is_some_and(|pubkey| peers.iter().any(|p| p.pubkey == pubkey)),
+                        commitment_type: ch["commitment_type"]
                             .as_str()
-                            .and_then(|s| s.parse::<i64>().ok())
-                            .unwrap_or(0),
-                        active: ch["active"].as_bool().unwrap_or(false),
+                            .unwrap_or_default()
+                            .to_string(),
+                        private: ch["private"].as_bool().unwrap_or(false),
                     })
                     .collect()
             })
             .unwrap_or_default();
 
-        // Get container info for ports
-        let container_info = self
-            .container_manager
-            .inspect_container(container_id)
-            .await?;
-
-        let ports = container_info
-            .network_settings
-            .as_ref()
-            .and_then(|ns| ns.ports.as_ref())
-            .cloned()
-            .unwrap_or_default();
-
-        // Extract REST port (8080)
-        let rest_host = ports
-            .get("8080/tcp")
-            .and_then(|bindings| bindings.as_ref())
-            .and_then(|b| b.first())
-            .map(|binding| {
-                format!(
-                    "{}:{}",
-                    binding.host_ip.as_deref().unwrap_or("0.0.0.0"),
-                    binding.host_port.as_deref().unwrap_or("8080")
-                )
-            })
-            .unwrap_or_else(|| "8080".to_string());
-
-        // Extract gRPC port (10009)
-        let grpc_host = ports
-            .get("10009/tcp")
-            .and_then(|bindings| bindings.as_ref())
-            .and_then(|b| b.first())
-            .map(|binding| {
-                format!(
-                    "{}:{}",
-                    binding.host_ip.as_deref().unwrap_or("0.0.0.0"),
-                    binding.host_port.as_deref().unwrap_or("10009")
-                )
-            })
-            .unwrap_or_else(|| "10009".to_string());
+        // Prefer the persisted port configuration, fall back to container inspection
+        let [rest_host, grpc_host] =
+            if let Some(NodePorts::Lnd { rest, grpc, .. }) = port_config.map(|c| &c.ports) {
+                [format!("0.0.0.0:{rest}"), format!("0.0.0.0:{grpc}")]
+            } else {
+                self.inspect_host_endpoints(container_id, [8080, LND_GRPC_PORT])
+                    .await?
+            };
 
         Ok(LndNodeInfo {
             alias: info_json["alias"].as_str().unwrap_or("unknown").to_string(),
@@ -692,26 +2250,90 @@ impl NetworkManager {
             synced_to_chain: info_json["synced_to_chain"].as_bool().unwrap_or(false),
             synced_to_graph: info_json["synced_to_graph"].as_bool().unwrap_or(false),
             block_height: info_json["block_height"].as_u64().unwrap_or(0) as u32,
+            chain_height_drift: None,
             block_hash: info_json["block_hash"]
                 .as_str()
                 .unwrap_or("unknown")
                 .to_string(),
-            wallet_balance: wallet_json["confirmed_balance"]
-                .as_str()
-                .and_then(|s| s.parse::<i64>().ok())
-                .unwrap_or(0),
-            channel_balance: channel_json["balance"]
-                .as_str()
-                .and_then(|s| s.parse::<i64>().ok())
-                .unwrap_or(0),
+            wallet_confirmed: parse_sats(&wallet_json["confirmed_balance"]),
+            wallet_unconfirmed: parse_sats(&wallet_json["unconfirmed_balance"]),
+            wallet_locked: parse_sats(&wallet_json["locked_balance"]),
+            channel_local: parse_sats(&channel_json["local_balance"]),
+            channel_remote: parse_sats(&channel_json["remote_balance"]),
+            channel_pending: parse_sats(&channel_json["pending_open_local_balance"]),
             rest_host,
             grpc_host,
             channels,
+            pending_channels: parse_pending_channels(&pending_json),
+            peers,
+            towers: parse_towers(&towers_json),
         })
     }
 
+    /// Resolve host endpoints for container ports by inspecting the container.
+    ///
+    /// Ports without a host binding are reported as the bare container port.
+    async fn inspect_host_endpoints<const N: usize>(
+        &self,
+        container_id: &str,
+        container_ports: [u16; N],
+    ) -> Result<[String; N]> {
+        let container_info = self
+            .container_manager
+            .inspect_container(container_id)
+            .await?;
+
+        let ports = container_info
+            .network_settings
+            .as_ref()
+            .and_then(|ns| ns.ports.as_ref())
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(container_ports.map(|port| {
+            ports
+                .get(&format!("{port}/tcp"))
+                .and_then(|bindings| bindings.as_ref())
+                .and_then(|b| b.first())
+                .map_or_else(
+                    || port.to_string(),
+                    |binding| {
+                        format!(
+                            "{}:{}",
+                            binding.host_ip.as_deref().unwrap_or("0.0.0.0"),
+                            binding
+                                .host_port
+                                .clone()
+                                .unwrap_or_else(|| port.to_string())
+                        )
+                    },
+                )
+        }))
+    }
+
     /// Get node information for any node type.
+    ///
+    /// Command output fetched within `info_cache_ttl_ms` is reused; see
+    /// [`Self::get_node_info_with_options`] to bypass it.
     pub async fn get_node_info(&self, network_name: &str, node_name: &str) -> Result<NodeInfo> {
+        self.get_node_info_with_options(network_name, node_name, false)
+            .await
+    }
+
+    /// Get node information for any node type with custom options.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the node
+    /// * `force_refresh` - Whether to drop cached output and query the node
+    ///   again, e.g. when the user asks for a refresh
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_node_info_with_options(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        force_refresh: bool,
+    ) -> Result<NodeInfo> {
         let network = self
             .get_network(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
@@ -720,25 +2342,202 @@ impl NetworkManager {
             .nodes
             .iter()
             .find(|n| n.name == node_name)
-            .ok_or_else(|| Error::Config(format!("Node '{}' not found", node_name)))?;
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
 
         let container_id = node
             .container_id
             .as_ref()
-            .ok_or_else(|| Error::Config("Node is not running".to_string()))?;
+            .ok_or_else(|| Error::NodeNotRunning(node_name.to_string()))?;
+
+        let port_config = network.port_mappings.get(&node.id);
+
+        if force_refresh {
+            self.info_cache().retain(|(id, _), _| id != container_id);
+        }
 
         match node.kind {
             NodeKind::BitcoinCore => {
-                let info = self.get_bitcoin_node_info(container_id).await?;
+                let info = self
+                    .get_bitcoin_node_info_with_ports(container_id, port_config)
+                    .await?;
                 Ok(NodeInfo::Bitcoin(info))
             }
             NodeKind::Lnd => {
-                let info = self.get_lnd_node_info(container_id).await?;
+                let mut info = self
+                    .get_lnd_node_info_with_ports(container_id, port_config)
+                    .await?;
+                info.chain_height_drift = self
+                    .chain_height(network)
+                    .await
+                    .map(|height| chain_height_drift(height, info.block_height));
+                name_remote_nodes(&mut info, &self.node_names_by_pubkey(network).await);
                 Ok(NodeInfo::Lnd(info))
             }
         }
     }
 
+    /// Get the block height of the network's primary Bitcoin node, or `None`
+    /// if it is not running or cannot be queried.
+    async fn chain_height(&self, network: &Network) -> Option<u64> {
+        let container_id = network.primary_bitcoin_node()?.container_id.as_ref()?;
+        let output = self
+            .bitcoin_cli(container_id, &["getblockcount"])
+            .await
+            .ok()?;
+        output.trim().parse().ok()
+    }
+
+    /// Get the container status of a node.
+    ///
+    /// Nodes without a container are reported as not started, and containers
+    /// that can no longer be inspected as unknown.
+    pub async fn node_status(&self, network_name: &str, node_name: &str) -> Result<NodeStatus> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let node = network
+            .nodes
+            .iter()
+            .find(|n| n.name == node_name)
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
+
+        Ok(
+            Self::container_node_status(&self.container_manager, node.container_id.as_deref())
+                .await,
+        )
+    }
+
+    /// Name and container ID of every node in a network, so their statuses
+    /// can be checked with [`Self::container_node_status`] without holding
+    /// the manager.
+    pub fn node_containers(&self, network_name: &str) -> Result<Vec<(String, Option<String>)>> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        Ok(network
+            .nodes
+            .iter()
+            .map(|n| (n.name.clone(), n.container_id.clone()))
+            .collect())
+    }
+
+    /// Docker connection of the manager, for use outside the manager lock.
+    pub fn container_manager(&self) -> ContainerManager {
+        self.container_manager.clone()
+    }
+
+    /// Status of a node's container, see [`Self::node_status`].
+    pub async fn container_node_status(
+        container_manager: &ContainerManager,
+        container_id: Option<&str>,
+    ) -> NodeStatus {
+        let Some(container_id) = container_id else {
+            return NodeStatus::NotStarted;
+        };
+        container_manager
+            .container_status(container_id)
+            .await
+            .unwrap_or(NodeStatus::Unknown)
+    }
+
+    /// Get information about every running node in a network concurrently.
+    ///
+    /// Each node is fetched in its own task, so a crashed or unresponsive node
+    /// only produces an error for that node instead of failing the batch.
+    /// Stopped nodes are skipped.
+    ///
+    /// # Returns
+    /// A map from node name to that node's info or error
+    pub async fn get_all_node_info(
+        &self,
+        network_name: &str,
+    ) -> Result<HashMap<String, Result<NodeInfo>>> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        // LND drift is measured against one chain height for the whole batch
+        let chain_height = self.chain_height(network).await;
+        let fetcher = Arc::new(self.detached());
+        let mut tasks = JoinSet::new();
+
+        for node in &network.nodes {
+            let Some(container_id) = node.container_id.clone() else {
+                continue;
+            };
+            let name = node.name.clone();
+            let kind = node.kind;
+            let port_config = network.port_mappings.get(&node.id).cloned();
+            let fetcher = Arc::clone(&fetcher);
+
+            tasks.spawn(async move {
+                let info = match kind {
+                    NodeKind::BitcoinCore => fetcher
+                        .get_bitcoin_node_info_with_ports(&container_id, port_config.as_ref())
+                        .await
+                        .map(NodeInfo::Bitcoin),
+                    NodeKind::Lnd => fetcher
+                        .get_lnd_node_info_with_ports(&container_id, port_config.as_ref())
+                        .await
+                        .map(|mut info| {
+                            info.chain_height_drift = chain_height
+                                .map(|height| chain_height_drift(height, info.block_height));
+                            NodeInfo::Lnd(info)
+                        }),
+                };
+                (name, info)
+            });
+        }
+
+        let mut results = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((name, info)) => {
+                    results.insert(name, info);
+                }
+                Err(e) => self.log(format!("Warning: node info task failed: {e}")),
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Aggregate channel liquidity and on-chain funds across a network.
+    ///
+    /// Node info is fetched concurrently with [`Self::get_all_node_info`].
+    ///
+    /// # Returns
+    /// The totals, or None when the network is not running
+    pub async fn network_summary(&self, network_name: &str) -> Result<Option<LiquiditySummary>> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        if network.status != NetworkStatus::Running {
+            return Ok(None);
+        }
+
+        let infos = self.get_all_node_info(network_name).await?;
+        Ok(Some(summarize_liquidity(infos.values())))
+    }
+
+    /// Create a manager sharing this one's Docker connection and configuration
+    /// but no network state, for use in spawned tasks.
+    fn detached(&self) -> Self {
+        Self {
+            container_manager: self.container_manager.clone(),
+            networks: HashMap::new(),
+            config: self.config.clone(),
+            log_tx: self.log_tx.clone(),
+            block_generators: HashMap::new(),
+            orphaned_containers: Vec::new(),
+            rest_clients: Mutex::new(HashMap::new()),
+            info_cache: Arc::clone(&self.info_cache),
+            pubkeys: Arc::clone(&self.pubkeys),
+            network_locks: Arc::clone(&self.network_locks),
+        }
+    }
+
     /// Add a new Lightning node to an existing network.
     ///
     /// # Arguments
@@ -752,6 +2551,7 @@ impl NetworkManager {
         network_name: &str,
         implementation: LightningImpl,
     ) -> Result<String> {
+        let _guard = self.lock_network(network_name).await;
         let network = self
             .networks
             .get_mut(network_name)
@@ -767,14 +2567,20 @@ impl NetworkManager {
         let impl_count = network.nodes.iter().filter(|n| n.kind == node_kind).count();
         let next_number = impl_count + 1;
 
-        // Create new Lightning node with implementation-specific naming
-        let node_name = format!("{}-{}", implementation.short_name(), next_number);
+        // Create new Lightning node with implementation-specific naming,
+        // skipping names taken by renamed nodes
+        let mut node_name = format!("{}-{}", implementation.short_name(), next_number);
+        let mut suffix = next_number;
+        while network.nodes.iter().any(|n| n.name == node_name) {
+            suffix += 1;
+            node_name = format!("{}-{}", implementation.short_name(), suffix);
+        }
         let lightning_node = Node::new(node_name.clone(), node_kind);
         network.add_node(lightning_node);
 
         // Check if network is running and get needed data
         let is_running = network.status == NetworkStatus::Running;
-        let network_id = network.id;
+        let docker_network_name = network.docker_network_name();
         let alias_prefix = network
             .alias_prefix
             .clone()
@@ -786,13 +2592,10 @@ impl NetworkManager {
 
         // If network is running, start the new node automatically
         if is_running {
-            // Find the Bitcoin node ID
-            let btc_node_id = network
-                .nodes
-                .iter()
-                .find(|n| n.kind == NodeKind::BitcoinCore)
-                .map(|n| n.id.to_string())
-                .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+            // Find the primary Bitcoin node
+            let backend = lnd_backend(network)?;
+            let btc_node_id = backend.node.id.to_string();
+            let bitcoin_host = lnd_backend_host(&self.container_manager, network).await?;
 
             // Find the newly added node
             let new_node = network
@@ -808,11 +2611,20 @@ impl NetworkManager {
                     let mut lnd_node =
                         LndNode::with_alias(node_name.clone(), btc_node_id, node_alias);
                     lnd_node.node.id = new_node.id;
+                    lnd_node.bitcoin_host = bitcoin_host;
                     lnd_node.image = lnd_version;
+                    lnd_node.rpc = self.config.rpc_credentials();
+                    lnd_node.extra_args = lnd_extra_args(&self.config, &network.extra_args);
+                    lnd_node.check_zmq_backend(&backend)?;
 
-                    let docker_network_name = format!("polar-{}", network_id);
                     lnd_node
-                        .start_with_network(&self.container_manager, Some(&docker_network_name))
+                        .start_with_ports(
+                            &self.container_manager,
+                            Some(&docker_network_name),
+                            None,
+                            self.config.lnd_watchtowers,
+                            self.config.resource_limits.lnd,
+                        )
                         .await?;
 
                     new_node.container_id = lnd_node.node.container_id;
@@ -840,6 +2652,8 @@ impl NetworkManager {
         network_name: &str,
         node_name: &str,
     ) -> Result<()> {
+        let _guard = self.lock_network(network_name).await;
+        self.invalidate_info(network_name);
         let network = self
             .networks
             .get_mut(network_name)
@@ -850,7 +2664,7 @@ impl NetworkManager {
             .nodes
             .iter()
             .find(|n| n.name == node_name)
-            .ok_or_else(|| Error::Config(format!("Node '{}' not found", node_name)))?;
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
 
         // Don't allow deleting Bitcoin node
         if node.kind == NodeKind::BitcoinCore {
@@ -859,8 +2673,9 @@ impl NetworkManager {
             ));
         }
 
-        // If node is running, stop it first
-        if node.container_id.is_some() {
+        // If node is running, stop it first, leaving adopted containers alone
+        let mut forced_stop = false;
+        if node.container_id.is_some() && !node.external {
             let node_clone = node.clone();
             let node_kind = node.kind;
 
@@ -873,9 +2688,15 @@ impl NetworkManager {
                             .clone()
                             .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
                         bitcoin_node: String::new(),
+                        bitcoin_host: None,
                         alias: String::new(),
+                        rpc: self.config.rpc_credentials(),
+                        extra_args: Vec::new(),
                     };
-                    lnd_node.stop(&self.container_manager).await?;
+                    let stop_timeout = Duration::from_secs(self.config.lnd_stop_timeout_secs);
+                    forced_stop = !lnd_node
+                        .stop_with_timeout(&self.container_manager, stop_timeout)
+                        .await?;
                 }
                 NodeKind::BitcoinCore => {
                     // Already checked above, but included for completeness
@@ -885,56 +2706,550 @@ impl NetworkManager {
         }
 
         // Remove the node from the network
+        let removed = node.clone();
         network.nodes.retain(|n| n.name != node_name);
 
         // Save the updated network state
         let network_clone = network.clone();
         self.save_network(&network_clone)?;
+        self.remove_node_volume(&removed).await;
 
-        Ok(())
-    }
+        if forced_stop {
+            self.log(format!(
+                "Warning: {node_name} did not stop within {}s, container was force stopped",
+                self.config.lnd_stop_timeout_secs
+            ));
+        }
 
-    /// Check if Docker is available.
-    pub async fn check_docker(&self) -> Result<()> {
-        self.container_manager.ping().await
+        Ok(())
     }
 
-    /// Mine blocks on the Bitcoin node in a network.
+    /// Stop a single node, leaving the rest of the network running.
     ///
-    /// # Arguments
-    /// * `network_name` - Name of the network
-    /// * `num_blocks` - Number of blocks to mine (default: 100)
-    pub async fn mine_blocks(&self, network_name: &str, num_blocks: u32) -> Result<Vec<String>> {
+    /// The node keeps its allocated ports so [`Self::start_node`] brings it
+    /// back on the same endpoints. The primary Bitcoin node cannot be stopped
+    /// while LND nodes that use it as their backend are still running.
+    pub async fn stop_node(&mut self, network_name: &str, node_name: &str) -> Result<()> {
+        let _guard = self.lock_network(network_name).await;
+        self.invalidate_info(network_name);
         let network = self
-            .get_network(network_name)
+            .networks
+            .get_mut(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
 
-        // Find the Bitcoin node
-        let btc_node = network
+        let node = network
             .nodes
             .iter()
-            .find(|n| n.kind == NodeKind::BitcoinCore)
-            .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+            .find(|n| n.name == node_name)
+            .cloned()
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
 
-        if btc_node.container_id.is_none() {
-            return Err(Error::Config(
-                "Bitcoin node is not running. Please start the network first.".to_string(),
-            ));
+        if node.container_id.is_none() {
+            return Ok(());
+        }
+        if node.external {
+            return Err(Error::Config(format!(
+                "{node_name} runs in a container managed outside polar"
+            )));
         }
 
-        let btc_node_obj = BitcoinNode {
-            node: btc_node.clone(),
-            image: network
-                .btc_version
-                .clone()
-                .unwrap_or_else(|| BitcoinNode::DEFAULT_IMAGE.to_string()),
-        };
+        if network
+            .primary_bitcoin_node()
+            .is_some_and(|n| n.id == node.id)
+        {
+            let dependents: Vec<_> = network
+                .nodes
+                .iter()
+                .filter(|n| n.kind == NodeKind::Lnd && n.container_id.is_some())
+                .map(|n| n.name.as_str())
+                .collect();
+            if !dependents.is_empty() {
+                return Err(Error::Config(format!(
+                    "Cannot stop {node_name} while LND nodes depend on it: {}. Stop them first.",
+                    dependents.join(", ")
+                )));
+            }
+        }
 
+        let stop_timeout = Duration::from_secs(self.config.lnd_stop_timeout_secs);
+        let mut forced_stop = false;
+        match node.kind {
+            NodeKind::Lnd => {
+                let mut lnd_node = LndNode::new(node.name.clone(), String::new());
+                lnd_node.node = node.clone();
+                forced_stop = !lnd_node
+                    .stop_with_timeout(&self.container_manager, stop_timeout)
+                    .await?;
+            }
+            NodeKind::BitcoinCore => {
+                let mut btc_node = BitcoinNode::new(node.name.clone());
+                btc_node.node = node.clone();
+                btc_node.stop(&self.container_manager).await?;
+            }
+        }
+
+        if let Some(stored) = network.nodes.iter_mut().find(|n| n.id == node.id) {
+            stored.container_id = None;
+        }
+
+        let network_clone = network.clone();
+        self.save_network(&network_clone)?;
+
+        if forced_stop {
+            self.log(format!(
+                "Warning: {node_name} did not stop within {}s, container was force stopped",
+                stop_timeout.as_secs()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Start a single stopped node in a running network.
+    ///
+    /// The node reuses its allocated ports and joins the network's existing
+    /// Docker network. An LND node needs the primary Bitcoin node running.
+    pub async fn start_node(&mut self, network_name: &str, node_name: &str) -> Result<()> {
+        let _guard = self.lock_network(network_name).await;
+        self.invalidate_info(network_name);
+        let network = self
+            .networks
+            .get_mut(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        if network.status != NetworkStatus::Running {
+            return Err(Error::Config(format!(
+                "Network '{network_name}' is not running. Start the network instead."
+            )));
+        }
+
+        let node = network
+            .nodes
+            .iter()
+            .find(|n| n.name == node_name)
+            .cloned()
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
+
+        if node.container_id.is_some() {
+            return Ok(());
+        }
+
+        let port_config = match network.port_mappings.get(&node.id) {
+            Some(config) => config.clone(),
+            None => network.allocate_ports(node.id, node.kind),
+        };
+        let docker_network_name = network.docker_network_name();
+
+        let container_id = match node.kind {
+            NodeKind::BitcoinCore => {
+                let mut btc_node = BitcoinNode::new(node.name.clone());
+                btc_node.node.id = node.id;
+                btc_node.image = network
+                    .btc_version
+                    .clone()
+                    .unwrap_or_else(|| BitcoinNode::DEFAULT_IMAGE.to_string());
+                btc_node.rpc = self.config.rpc_credentials();
+                btc_node.extra_args.clone_from(&network.extra_args.bitcoin);
+                btc_node.node.env.clone_from(&node.env);
+
+                btc_node
+                    .start_with_ports(
+                        &self.container_manager,
+                        Some(&docker_network_name),
+                        bitcoin_ports(&port_config.ports),
+                        self.config.resource_limits.bitcoin,
+                    )
+                    .await?;
+                btc_node.node.container_id
+            }
+            NodeKind::Lnd => {
+                let btc_node = network
+                    .primary_bitcoin_node()
+                    .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+                if btc_node.container_id.is_none() {
+                    return Err(Error::NodeNotRunning(btc_node.name.clone()));
+                }
+                let btc_node_id = btc_node.id.to_string();
+
+                // Keep the alias start_network gave this node
+                let alias_prefix = network
+                    .alias_prefix
+                    .clone()
+                    .unwrap_or_else(|| "polar-node".to_string());
+                let lnd_number = network
+                    .nodes
+                    .iter()
+                    .filter(|n| n.kind == NodeKind::Lnd)
+                    .position(|n| n.id == node.id)
+                    .unwrap_or(0)
+                    + 1;
+
+                let mut lnd_node = LndNode::with_alias(
+                    node.name.clone(),
+                    btc_node_id,
+                    format!("{alias_prefix}-{lnd_number}"),
+                );
+                lnd_node.node.id = node.id;
+                lnd_node.bitcoin_host = lnd_backend_host(&self.container_manager, network).await?;
+                lnd_node.image = network
+                    .lnd_version
+                    .clone()
+                    .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string());
+                lnd_node.rpc = self.config.rpc_credentials();
+                lnd_node.extra_args = lnd_extra_args(&self.config, &network.extra_args);
+                lnd_node.node.env.clone_from(&node.env);
+                lnd_node.check_zmq_backend(&lnd_backend(network)?)?;
+
+                lnd_node
+                    .start_with_ports(
+                        &self.container_manager,
+                        Some(&docker_network_name),
+                        lnd_ports(&port_config.ports),
+                        self.config.lnd_watchtowers,
+                        self.config.resource_limits.lnd,
+                    )
+                    .await?;
+                lnd_node.node.container_id
+            }
+        };
+
+        if let Some(stored) = network.nodes.iter_mut().find(|n| n.id == node.id) {
+            stored.container_id = container_id;
+        }
+
+        let network_clone = network.clone();
+        self.save_network(&network_clone)?;
+
+        Ok(())
+    }
+
+    /// Run a bitcoin-cli command using the configured RPC credentials.
+    async fn bitcoin_cli(&self, container_id: &str, args: &[&str]) -> Result<String> {
+        let cmd = self.config.rpc_credentials().bitcoin_cli_command(args);
+        self.container_manager
+            .exec_command(container_id, cmd.iter().map(String::as_str).collect())
+            .await
+    }
+
+    /// Run a read-only bitcoin-cli command through the info cache.
+    async fn cached_bitcoin_cli(&self, container_id: &str, args: &[&str]) -> Result<String> {
+        self.cached_info(
+            container_id,
+            &args.join(" "),
+            self.bitcoin_cli(container_id, args),
+        )
+        .await
+    }
+
+    /// Run `op` against a shared manager, retrying while a node is still
+    /// starting up.
+    ///
+    /// `op` gets the locked manager for one attempt, and the lock is released
+    /// during the pause between attempts, so other tasks can use the manager while a node
+    /// boots. Attempts and delays follow
+    /// [`ContainerManager::exec_command_with_retry`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `op` fails with anything but a startup error, or
+    /// still fails once the retries run out.
+    pub async fn retry_while_starting<T, F, Fut>(
+        manager: &Arc<tokio::sync::Mutex<Self>>,
+        mut op: F,
+    ) -> Result<T>
+    where
+        F: FnMut(tokio::sync::OwnedMutexGuard<Self>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut delay = ContainerManager::EXEC_RETRY_DELAY;
+        let mut attempt = 0;
+        loop {
+            let result = op(Arc::clone(manager).lock_owned().await).await;
+            match result {
+                Err(e)
+                    if attempt < ContainerManager::EXEC_RETRIES
+                        && is_retryable_exec_error(&e.to_string()) =>
+                {
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(ContainerManager::MAX_EXEC_RETRY_DELAY);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Check if Docker is available.
+    pub async fn check_docker(&self) -> Result<()> {
+        self.container_manager.ping().await
+    }
+
+    /// Mine blocks on the Bitcoin node in a network.
+    ///
+    /// Blocks mined to the Bitcoin wallet reuse one coinbase address unless
+    /// `reuse_mining_address` is disabled in the config.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `num_blocks` - Number of blocks to mine (default: 100)
+    /// * `to_node` - Optional node to receive the coinbase rewards
+    pub async fn mine_blocks(
+        &self,
+        network_name: &str,
+        num_blocks: u32,
+        to_node: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        // Find the primary Bitcoin node
+        let btc_node = network
+            .primary_bitcoin_node()
+            .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+
+        if btc_node.container_id.is_none() {
+            return Err(Error::NodeNotRunning(btc_node.name.clone()));
+        }
+
+        let btc_node_obj = self.bitcoin_node_for(network, btc_node);
+
+        // Resolve the coinbase address when mining to an LND node; mining
+        // to the Bitcoin node itself uses its own wallet
+        let address = match to_node {
+            Some(node_name) => {
+                let target = network
+                    .nodes
+                    .iter()
+                    .find(|n| n.name == node_name)
+                    .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
+
+                match target.kind {
+                    NodeKind::BitcoinCore => None,
+                    NodeKind::Lnd => {
+                        let lnd_node_obj = LndNode {
+                            node: target.clone(),
+                            image: network
+                                .lnd_version
+                                .clone()
+                                .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
+                            bitcoin_node: btc_node.id.to_string(),
+                            bitcoin_host: None,
+                            alias: target.name.clone(),
+                            rpc: self.config.rpc_credentials(),
+                            extra_args: Vec::new(),
+                        };
+                        Some(
+                            lnd_node_obj
+                                .get_new_address(&self.container_manager, None)
+                                .await?,
+                        )
+                    }
+                }
+            }
+            None => None,
+        };
+
+        if address.is_none() && self.config.reuse_mining_address {
+            return btc_node_obj
+                .mine_blocks_fast(&self.container_manager, num_blocks)
+                .await;
+        }
         btc_node_obj
-            .mine_blocks(&self.container_manager, num_blocks, None)
+            .mine_blocks(&self.container_manager, num_blocks, address.as_deref())
             .await
     }
 
+    /// Start mining blocks on a timer to simulate an ongoing chain.
+    ///
+    /// A background task mines `blocks_per_tick` blocks to the Bitcoin wallet
+    /// every `interval_secs` seconds through [`Self::mine_blocks`] until
+    /// [`Self::stop_block_generator`] is called or the network is stopped.
+    /// Starting a generator on a network that already has one replaces it.
+    pub fn start_block_generator(
+        &mut self,
+        network_name: &str,
+        interval_secs: u64,
+        blocks_per_tick: u32,
+    ) -> Result<()> {
+        self.start_block_generator_with_mode(
+            network_name,
+            interval_secs,
+            blocks_per_tick,
+            GeneratorMode::FixedInterval,
+        )
+    }
+
+    /// Start a block generator that decides on each tick whether to mine.
+    ///
+    /// Like [`Self::start_block_generator`], but ticks only mine when `mode`
+    /// says so, checking the mempool of the primary Bitcoin node when the
+    /// mode depends on it. The mode can be changed while the generator runs
+    /// with [`Self::set_block_generator_mode`].
+    pub fn start_block_generator_with_mode(
+        &mut self,
+        network_name: &str,
+        interval_secs: u64,
+        blocks_per_tick: u32,
+        mode: GeneratorMode,
+    ) -> Result<()> {
+        if interval_secs == 0 || blocks_per_tick == 0 {
+            return Err(Error::Config(
+                "Block generator interval and block count must be greater than zero".to_string(),
+            ));
+        }
+        check_generator_mode(mode)?;
+
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        if network.status != NetworkStatus::Running {
+            return Err(Error::Config(
+                "Network is not running. Please start the network first.".to_string(),
+            ));
+        }
+
+        // The task works on a snapshot of the network, which stays valid
+        // until the network is stopped and the generator with it
+        let mut generator = self.detached();
+        generator
+            .networks
+            .insert(network_name.to_string(), network.clone());
+        let name = network_name.to_string();
+        let shared_mode = Arc::new(Mutex::new(mode));
+        let task_mode = Arc::clone(&shared_mode);
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+            let mut idle_ticks = 0;
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                idle_ticks += 1;
+                let mode = *task_mode.lock().unwrap_or_else(PoisonError::into_inner);
+                let mempool_txs = if mode.needs_mempool() {
+                    match generator.mempool_size(&name).await {
+                        Ok(size) => size,
+                        Err(e) => {
+                            generator.log(format!(
+                                "Block generator could not read the mempool of '{name}': {e}"
+                            ));
+                            continue;
+                        }
+                    }
+                } else {
+                    0
+                };
+                if !mode.should_mine(mempool_txs, idle_ticks) {
+                    continue;
+                }
+
+                match generator.mine_blocks(&name, blocks_per_tick, None).await {
+                    Ok(hashes) => {
+                        idle_ticks = 0;
+                        generator.log(format!(
+                            "Block generator mined {} block(s) on '{name}' with {mempool_txs} mempool tx(s)",
+                            hashes.len()
+                        ));
+                    }
+                    Err(e) => generator.log(format!("Block generator on '{name}' failed: {e}")),
+                }
+            }
+        });
+
+        self.stop_block_generator(network_name);
+        self.block_generators.insert(
+            network_name.to_string(),
+            BlockGenerator {
+                handle,
+                mode: shared_mode,
+            },
+        );
+        self.log(format!(
+            "Started block generator on '{network_name}': {blocks_per_tick} block(s) every {interval_secs}s, {mode}"
+        ));
+
+        Ok(())
+    }
+
+    /// Change when a running block generator mines.
+    ///
+    /// Takes effect on the generator's next tick.
+    pub fn set_block_generator_mode(
+        &mut self,
+        network_name: &str,
+        mode: GeneratorMode,
+    ) -> Result<()> {
+        check_generator_mode(mode)?;
+        let generator = self
+            .block_generators
+            .get(network_name)
+            .filter(|generator| !generator.handle.is_finished())
+            .ok_or_else(|| {
+                Error::Config(format!("No block generator is running on '{network_name}'"))
+            })?;
+        *generator
+            .mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = mode;
+        self.log(format!(
+            "Block generator on '{network_name}' now mines {mode}"
+        ));
+        Ok(())
+    }
+
+    /// Get the mode of a running block generator.
+    #[must_use]
+    pub fn block_generator_mode(&self, network_name: &str) -> Option<GeneratorMode> {
+        self.block_generators
+            .get(network_name)
+            .filter(|generator| !generator.handle.is_finished())
+            .map(|generator| {
+                *generator
+                    .mode
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+            })
+    }
+
+    /// Count the transactions in the mempool of a network's primary Bitcoin
+    /// node.
+    pub async fn mempool_size(&self, network_name: &str) -> Result<u64> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let mut backend = lnd_backend(network)?;
+        backend.node.container_id = network
+            .primary_bitcoin_node()
+            .and_then(|node| node.container_id.clone());
+        backend.rpc = self.config.rpc_credentials();
+        let info = backend.get_mempool_info(&self.container_manager).await?;
+        Ok(info.size)
+    }
+
+    /// Stop the block generator for a network.
+    ///
+    /// Returns `true` if a generator was running.
+    pub fn stop_block_generator(&mut self, network_name: &str) -> bool {
+        let Some(generator) = self.block_generators.remove(network_name) else {
+            return false;
+        };
+        generator.handle.abort();
+        self.log(format!("Stopped block generator on '{network_name}'"));
+        true
+    }
+
+    /// Check whether a block generator is running for a network.
+    #[must_use]
+    pub fn is_block_generator_running(&self, network_name: &str) -> bool {
+        self.block_generators
+            .get(network_name)
+            .is_some_and(|generator| !generator.handle.is_finished())
+    }
+
     /// Fund an LND node's wallet from the Bitcoin node.
     ///
     /// # Arguments
@@ -951,8 +3266,145 @@ impl NetworkManager {
         lnd_node_name: &str,
         amount: f64,
     ) -> Result<String> {
-        self.fund_lnd_wallet_with_options(network_name, lnd_node_name, amount, true)
-            .await
+        self.fund_lnd_wallet_with_options(
+            network_name,
+            lnd_node_name,
+            amount,
+            FundOptions::default(),
+        )
+        .await
+    }
+
+    /// Send on-chain funds from an LND node to another node in the network.
+    ///
+    /// A fresh address is taken from the destination, which may be an LND or
+    /// Bitcoin Core node. Passing `None` as the amount sweeps the whole
+    /// wallet, which is useful for draining a node before deleting it.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `from_node` - Name of the sending LND node
+    /// * `to_node` - Name of the receiving node
+    /// * `amount_sat` - Amount in satoshis, or `None` to sweep
+    ///
+    /// # Returns
+    /// The transaction ID
+    pub async fn send_onchain(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        to_node: &str,
+        amount_sat: Option<u64>,
+    ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        if from_node == to_node {
+            return Err(Error::Config(
+                "Source and destination must be different nodes".to_string(),
+            ));
+        }
+
+        let from_lnd = self.find_lnd_node(network_name, from_node)?;
+
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let target = network
+            .nodes
+            .iter()
+            .find(|n| n.name == to_node)
+            .ok_or_else(|| Error::NodeNotFound(to_node.to_string()))?;
+
+        let address = match target.kind {
+            NodeKind::Lnd => {
+                self.find_lnd_node(network_name, to_node)?
+                    .get_new_address(&self.container_manager, None)
+                    .await?
+            }
+            NodeKind::BitcoinCore => {
+                let btc_node = self.bitcoin_node_for(network, target);
+                btc_node
+                    .get_new_address(&self.container_manager, None)
+                    .await?
+            }
+        };
+
+        let txid = from_lnd
+            .send_coins(
+                &self.container_manager,
+                &address,
+                amount_sat.unwrap_or_default(),
+                amount_sat.is_none(),
+            )
+            .await?;
+
+        let amount = amount_sat.map_or_else(|| "all funds".to_string(), |a| format!("{a} sats"));
+        self.log(format!(
+            "Sent {amount} on-chain from {from_node} to {to_node} (txid {txid})"
+        ));
+
+        Ok(txid)
+    }
+
+    /// Raise the fee of a stuck unconfirmed transaction.
+    ///
+    /// Bitcoin Core nodes replace the transaction by fee (RBF), so `target`
+    /// is a txid. LND nodes spend one of their outputs in a higher-fee
+    /// child (CPFP), so `target` is a `txid:index` outpoint; this also
+    /// rescues channel opens, which do not signal RBF.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the node whose wallet pays the extra fee
+    /// * `target` - Txid for Bitcoin Core, outpoint for LND
+    /// * `fee_rate` - New fee rate in sat/vB
+    ///
+    /// # Returns
+    /// The replacement txid for Bitcoin Core, or None for LND, whose sweeper
+    /// broadcasts the child transaction itself
+    pub async fn bump_transaction(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        target: &str,
+        fee_rate: u64,
+    ) -> Result<Option<String>> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let node = network
+            .nodes
+            .iter()
+            .find(|n| n.name == node_name)
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
+
+        match node.kind {
+            NodeKind::Lnd => {
+                if !target.contains(':') {
+                    return Err(Error::Config(format!(
+                        "LND bumps an output, expected txid:index but got '{target}'"
+                    )));
+                }
+                self.find_lnd_node(network_name, node_name)?
+                    .bump_fee(&self.container_manager, target, fee_rate)
+                    .await?;
+                self.log(format!(
+                    "Bumped fee of {target} to {fee_rate} sat/vB from {node_name} (CPFP)"
+                ));
+                Ok(None)
+            }
+            NodeKind::BitcoinCore => {
+                let btc_node = self.bitcoin_node_for(network, node);
+                let txid = btc_node
+                    .bump_fee(&self.container_manager, target, Some(fee_rate))
+                    .await?;
+                self.log(format!(
+                    "Replaced {target} with {txid} at {fee_rate} sat/vB on {node_name}"
+                ));
+                Ok(Some(txid))
+            }
+        }
     }
 
     /// Fund an LND node's wallet from the Bitcoin node with custom options.
@@ -961,26 +3413,27 @@ impl NetworkManager {
     /// * `network_name` - Name of the network
     /// * `lnd_node_name` - Name of the LND node to fund
     /// * `amount` - Amount in BTC
-    /// * `auto_mine` - Whether to automatically mine blocks to confirm the transaction
+    /// * `options` - Mining and fee options
     ///
     /// # Returns
     /// The transaction ID of the funding transaction
+    #[tracing::instrument(skip(self))]
     pub async fn fund_lnd_wallet_with_options(
         &self,
         network_name: &str,
         lnd_node_name: &str,
         amount: f64,
-        auto_mine: bool,
+        options: FundOptions,
     ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
         let network = self
             .get_network(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
 
-        // Find the Bitcoin node
+        // Find the primary Bitcoin node
         let btc_node = network
-            .nodes
-            .iter()
-            .find(|n| n.kind == NodeKind::BitcoinCore)
+            .primary_bitcoin_node()
             .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
 
         // Find the LND node
@@ -988,15 +3441,9 @@ impl NetworkManager {
             .nodes
             .iter()
             .find(|n| n.name == lnd_node_name && n.kind == NodeKind::Lnd)
-            .ok_or_else(|| Error::Config(format!("LND node '{}' not found", lnd_node_name)))?;
+            .ok_or_else(|| Error::NodeNotFound(lnd_node_name.to_string()))?;
 
-        let btc_node_obj = BitcoinNode {
-            node: btc_node.clone(),
-            image: network
-                .btc_version
-                .clone()
-                .unwrap_or_else(|| BitcoinNode::DEFAULT_IMAGE.to_string()),
-        };
+        let btc_node_obj = self.bitcoin_node_for(network, btc_node);
 
         let lnd_node_obj = LndNode {
             node: lnd_node.clone(),
@@ -1005,51 +3452,205 @@ impl NetworkManager {
                 .clone()
                 .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
             bitcoin_node: btc_node.id.to_string(),
+            bitcoin_host: None,
             alias: lnd_node.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
         };
 
+        if options.auto_mine_maturity {
+            self.mine_to_maturity(
+                &btc_node_obj,
+                btc_to_sats(amount) + FUNDING_FEE_RESERVE_SATS,
+            )
+            .await?;
+        }
+
         // Check Bitcoin node balance before attempting to send
         let btc_balance = btc_node_obj.get_balance(&self.container_manager).await?;
         if btc_balance < amount {
-            return Err(Error::Config(format!(
-                "Insufficient balance in Bitcoin node. Have: {} BTC, Need: {} BTC. Try mining blocks first.",
-                btc_balance, amount
-            )));
+            return Err(Error::InsufficientFunds {
+                node: btc_node_obj.node.name.clone(),
+                have: btc_to_sats(btc_balance),
+                need: btc_to_sats(amount),
+            });
         }
 
         // Get a new address from the LND node
         let address = lnd_node_obj
-            .get_new_address(&self.container_manager)
+            .get_new_address(&self.container_manager, None)
             .await?;
 
         // Send funds from Bitcoin node to LND address
         let txid = btc_node_obj
-            .send_to_address(&self.container_manager, &address, amount)
+            .send_to_address_with_fee(
+                &self.container_manager,
+                &address,
+                amount,
+                options.fee_rate_sat_vb,
+            )
             .await?;
 
         // Mine blocks to confirm the transaction if auto_mine is enabled
-        if auto_mine {
-            self.log("Auto-mining 6 blocks to confirm funding transaction");
+        if options.auto_mine {
+            let confs = self.config.funding_confirmations;
+            self.log(format!(
+                "Auto-mining {confs} blocks to confirm funding transaction"
+            ));
             btc_node_obj
-                .mine_blocks(&self.container_manager, 6, None)
+                .mine_blocks(&self.container_manager, confs, None)
                 .await?;
 
-            // Give LND a moment to detect the confirmed transaction
-            self.log("Waiting for LND to sync with confirmed blocks");
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+            let seen = btc_node_obj
+                .wait_for_confirmations(&self.container_manager, &txid, confs)
+                .await?;
+            self.log(format!(
+                "Funding transaction confirmed ({seen} confirmations)"
+            ));
+
+            // LND only counts the deposit once it has seen the blocks too
+            let height = btc_node_obj
+                .get_block_count(&self.container_manager)
+                .await?;
+            self.wait_for_lnd_chain_sync(&lnd_node_obj, height).await?;
         }
 
         Ok(txid)
     }
 
-    /// Open a Lightning channel between two LND nodes.
+    /// Fund every LND node of a network with `amount_each` BTC.
     ///
-    /// # Arguments
-    /// * `network_name` - Name of the network
-    /// * `from_node` - Name of the node opening the channel
-    /// * `to_node` - Name of the node to open channel to
-    /// * `capacity` - Channel capacity in satoshis
-    /// * `push_amount` - Amount to push to peer (optional)
+    /// Mines blocks until enough coinbase rewards have matured when the
+    /// Bitcoin wallet is short, then pays all nodes in a single `sendmany`
+    /// transaction and mines `funding_confirmations` blocks once, waiting
+    /// for every node to sync to the new tip.
+    ///
+    /// Returns the funding txid keyed by node name; all nodes share it.
+    pub async fn fund_all_nodes(
+        &self,
+        network_name: &str,
+        amount_each: f64,
+    ) -> Result<HashMap<String, String>> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let btc_node = network
+            .primary_bitcoin_node()
+            .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+        let btc_node_obj = self.bitcoin_node_for(network, btc_node);
+
+        let lnd_nodes: Vec<LndNode> = network
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Lnd)
+            .map(|n| LndNode {
+                node: n.clone(),
+                image: network
+                    .lnd_version
+                    .clone()
+                    .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
+                bitcoin_node: btc_node.id.to_string(),
+                bitcoin_host: None,
+                alias: n.name.clone(),
+                rpc: self.config.rpc_credentials(),
+                extra_args: Vec::new(),
+            })
+            .collect();
+        if lnd_nodes.is_empty() {
+            return Err(Error::Config(format!(
+                "Network '{network_name}' has no LND nodes to fund"
+            )));
+        }
+
+        // Leave room for the sendmany fee
+        let count = lnd_nodes.len() as u64;
+        let need = btc_to_sats(amount_each) * count + FUNDING_FEE_RESERVE_SATS;
+        self.mine_to_maturity(&btc_node_obj, need).await?;
+
+        let mut outputs = Vec::with_capacity(lnd_nodes.len());
+        for lnd_node in &lnd_nodes {
+            let address = lnd_node
+                .get_new_address(&self.container_manager, None)
+                .await?;
+            outputs.push((address, amount_each));
+        }
+        let txid = btc_node_obj
+            .send_many(&self.container_manager, &outputs)
+            .await?;
+        self.log(format!(
+            "Funded {count} LND nodes with {amount_each} BTC each in {txid}"
+        ));
+
+        let confs = self.config.funding_confirmations;
+        btc_node_obj
+            .mine_blocks(&self.container_manager, confs, None)
+            .await?;
+        btc_node_obj
+            .wait_for_confirmations(&self.container_manager, &txid, confs)
+            .await?;
+
+        let height = btc_node_obj
+            .get_block_count(&self.container_manager)
+            .await?;
+        for lnd_node in &lnd_nodes {
+            self.wait_for_lnd_chain_sync(lnd_node, height).await?;
+        }
+
+        Ok(lnd_nodes
+            .into_iter()
+            .map(|lnd_node| (lnd_node.node.name, txid.clone()))
+            .collect())
+    }
+
+    /// Mine just enough blocks for the Bitcoin wallet to hold `need` sats of
+    /// spendable coins, if it holds less.
+    ///
+    /// Fails without mining when that takes more than
+    /// [`BitcoinNode::MAX_FUNDING_BLOCKS`] blocks.
+    async fn mine_to_maturity(&self, btc_node: &BitcoinNode, need: u64) -> Result<()> {
+        let have = btc_to_sats(btc_node.get_balance(&self.container_manager).await?);
+        if have >= need {
+            return Ok(());
+        }
+
+        let height = btc_node.get_block_count(&self.container_manager).await?;
+        let blocks = BitcoinNode::blocks_to_fund(height, need - have).ok_or_else(|| {
+            Error::Config(format!(
+                "Need {need} sats but the wallet has {have}, more than {} blocks of \
+                 coinbase rewards can provide at height {height}",
+                BitcoinNode::MAX_FUNDING_BLOCKS
+            ))
+        })?;
+        self.log(format!(
+            "Wallet has {have} of {need} sats, mining {blocks} blocks so coinbase rewards mature"
+        ));
+        btc_node
+            .mine_blocks(&self.container_manager, blocks, None)
+            .await?;
+
+        let have = btc_to_sats(btc_node.get_balance(&self.container_manager).await?);
+        self.log(format!("Wallet balance is now {have} sats"));
+        if have < need {
+            return Err(Error::InsufficientFunds {
+                node: btc_node.node.name.clone(),
+                have,
+                need,
+            });
+        }
+        Ok(())
+    }
+
+    /// Open a Lightning channel between two LND nodes.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `from_node` - Name of the node opening the channel
+    /// * `to_node` - Name of the node to open channel to
+    /// * `capacity` - Channel capacity in satoshis
+    /// * `push_amount` - Amount to push to peer (optional)
     pub async fn open_channel(
         &self,
         network_name: &str,
@@ -1058,22 +3659,54 @@ impl NetworkManager {
         capacity: u64,
         push_amount: Option<u64>,
     ) -> Result<String> {
+        let options = OpenChannelOptions {
+            push_amount,
+            ..OpenChannelOptions::default()
+        };
+        self.open_channel_with_options(network_name, from_node, to_node, capacity, &options)
+            .await
+    }
+
+    /// Open a Lightning channel with a commitment type, visibility, funding
+    /// outpoints or acceptance settings, such as `min_confs` 0 to fund it
+    /// from unconfirmed outputs.
+    ///
+    /// Funding outpoints must be unspent outputs of `from_node`, as listed
+    /// by [`Self::list_utxos`], and together cover `capacity`. Takes the same
+    /// arguments as [`Self::open_channel`], with the push amount moved into
+    /// the [`OpenChannelOptions`] passed on to `lncli openchannel`.
+    #[tracing::instrument(skip(self, options))]
+    pub async fn open_channel_with_options(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        to_node: &str,
+        capacity: u64,
+        options: &OpenChannelOptions,
+    ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
         let network = self
             .get_network(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
 
+        check_channel_capacity(
+            capacity,
+            max_channel_size(self.config.wumbo, &network.extra_args),
+        )?;
+
         // Find both nodes
         let from = network
             .nodes
             .iter()
             .find(|n| n.name == from_node && n.kind == NodeKind::Lnd)
-            .ok_or_else(|| Error::Config(format!("LND node '{}' not found", from_node)))?;
+            .ok_or_else(|| Error::NodeNotFound(from_node.to_string()))?;
 
         let to = network
             .nodes
             .iter()
             .find(|n| n.name == to_node && n.kind == NodeKind::Lnd)
-            .ok_or_else(|| Error::Config(format!("LND node '{}' not found", to_node)))?;
+            .ok_or_else(|| Error::NodeNotFound(to_node.to_string()))?;
 
         let from_lnd = LndNode {
             node: from.clone(),
@@ -1082,7 +3715,10 @@ impl NetworkManager {
                 .clone()
                 .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
             bitcoin_node: String::new(), // Not needed for this operation
+            bitcoin_host: None,
             alias: from.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
         };
 
         let to_lnd = LndNode {
@@ -1092,7 +3728,10 @@ impl NetworkManager {
                 .clone()
                 .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
             bitcoin_node: String::new(),
+            bitcoin_host: None,
             alias: to.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
         };
 
         // Get the target node's pubkey
@@ -1106,14 +3745,319 @@ impl NetworkManager {
             .connect_peer(&self.container_manager, &to_pubkey, &peer_host)
             .await?;
 
+        // Both sides must advertise the requested commitment type
+        if let Some(channel_type) = options.channel_type {
+            for lnd in [&from_lnd, &to_lnd] {
+                let features = lnd.get_features(&self.container_manager).await?;
+                if !channel_type.is_supported_by(&features) {
+                    let hint = if channel_type == ChannelType::SimpleTaproot {
+                        format!(" (start LND with {})", LndNode::TAPROOT_FLAG)
+                    } else {
+                        String::new()
+                    };
+                    return Err(Error::Config(format!(
+                        "{} does not support {channel_type} channels{hint}",
+                        lnd.node.name
+                    )));
+                }
+            }
+        }
+
         // Open the channel
         let funding_txid = from_lnd
-            .open_channel(&self.container_manager, &to_pubkey, capacity, push_amount)
+            .open_channel_with_options(&self.container_manager, &to_pubkey, capacity, options)
             .await?;
 
         Ok(funding_txid)
     }
 
+    /// List the unspent outputs of an LND node's wallet.
+    pub async fn list_utxos(&self, network_name: &str, node_name: &str) -> Result<Vec<UtxoInfo>> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        lnd.list_unspent(&self.container_manager).await
+    }
+
+    /// Set the fees and time lock delta a node charges for forwarding.
+    ///
+    /// Passing `None` as the channel point updates every channel of the
+    /// node. Peers learn the new policy through gossip, after which it shows
+    /// up in [`Self::describe_graph`].
+    pub async fn set_channel_fees(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        chan_point: Option<&str>,
+        base_fee_msat: u64,
+        fee_rate_ppm: u64,
+        time_lock_delta: u32,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        self.find_lnd_node(network_name, node_name)?
+            .update_channel_policy(
+                &self.container_manager,
+                chan_point,
+                base_fee_msat,
+                fee_rate_ppm,
+                time_lock_delta,
+            )
+            .await?;
+        self.log(format!(
+            "Set {node_name} fees on {} to {base_fee_msat} msat + {fee_rate_ppm} ppm, time lock delta {time_lock_delta}",
+            chan_point.unwrap_or("all channels")
+        ));
+        Ok(())
+    }
+
+    /// Abandon a channel stuck in a pending state.
+    ///
+    /// This only edits the node's local channel state; no transaction is
+    /// broadcast and the peer is not notified.
+    pub async fn abandon_channel(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        channel_point: &str,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let channel_point: ChannelPoint = channel_point.parse()?;
+        self.find_lnd_node(network_name, node_name)?
+            .abandon_channel(&self.container_manager, &channel_point)
+            .await?;
+        self.log(format!(
+            "Abandoned channel {channel_point} on {node_name} (local state only, nothing broadcast)"
+        ));
+        Ok(())
+    }
+
+    /// Open a channel between every pair of funded LND nodes.
+    ///
+    /// Each pair gets a single channel opened by the node listed first in the
+    /// network. Pairs that already share a channel are skipped, and every
+    /// opener's wallet is checked up front so a mesh is never half-built for
+    /// lack of funds.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `capacity` - Capacity of each channel in satoshis
+    pub async fn open_mesh(&self, network_name: &str, capacity: u64) -> Result<MeshSummary> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let lnd_nodes: Vec<LndNode> = network
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Lnd)
+            .map(|n| LndNode {
+                node: n.clone(),
+                image: network
+                    .lnd_version
+                    .clone()
+                    .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
+                bitcoin_node: String::new(),
+                bitcoin_host: None,
+                alias: n.name.clone(),
+                rpc: self.config.rpc_credentials(),
+                extra_args: Vec::new(),
+            })
+            .collect();
+
+        // Gather balances, pubkeys and existing channel peers for funded nodes
+        let mut funded = Vec::new();
+        for lnd in &lnd_nodes {
+            let balance = lnd.wallet_balance(&self.container_manager).await?;
+            if balance == 0 {
+                continue;
+            }
+            let pubkey = lnd.get_pubkey(&self.container_manager).await?;
+            let channels = lnd.list_channels(&self.container_manager).await?;
+            let peers: Vec<String> = channels["channels"]
+                .as_array()
+                .map(|chans| {
+                    chans
+                        .iter()
+                        .filter_map(|ch| ch["remote_pubkey"].as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            funded.push((lnd, balance, pubkey, peers));
+        }
+
+        // Plan which pairs need a channel
+        let mut summary = MeshSummary::default();
+        let mut planned = Vec::new();
+        for (i, (from, _, _, from_peers)) in funded.iter().enumerate() {
+            for (to, _, to_pubkey, _) in funded.iter().skip(i + 1) {
+                if from_peers.contains(to_pubkey) {
+                    summary
+                        .skipped
+                        .push((from.node.name.clone(), to.node.name.clone()));
+                } else {
+                    planned.push((i, to));
+                }
+            }
+        }
+
+        // Make sure each opener can afford all of its channels before opening any
+        for (i, (from, balance, _, _)) in funded.iter().enumerate() {
+            let count = planned.iter().filter(|(idx, _)| *idx == i).count() as u64;
+            let required = count * (capacity + CHANNEL_FEE_RESERVE_SAT);
+            if required > *balance {
+                self.log(format!(
+                    "{} cannot afford {count} channels of {capacity} sats. Fund it first.",
+                    from.node.name
+                ));
+                return Err(Error::InsufficientFunds {
+                    node: from.node.name.clone(),
+                    have: *balance,
+                    need: required,
+                });
+            }
+        }
+
+        for (i, to) in planned {
+            let (from, _, _, _) = &funded[i];
+            let from_name = from.node.name.clone();
+            let to_name = to.node.name.clone();
+            match self
+                .open_channel(network_name, &from_name, &to_name, capacity, None)
+                .await
+            {
+                Ok(txid) => {
+                    self.log(format!("Opened channel {from_name} -> {to_name}: {txid}"));
+                    summary.opened.push((from_name, to_name, txid));
+                }
+                Err(e) => {
+                    self.log(format!(
+                        "Failed to open channel {from_name} -> {to_name}: {e}"
+                    ));
+                    summary.failed.push((from_name, to_name, e.to_string()));
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Fund LND nodes and open the channels of a topology between them.
+    ///
+    /// Nodes are wired in network order, so the first LND node is the hub
+    /// of a star. Each opener whose wallet cannot cover its channels is
+    /// funded first, mining coinbase rewards to maturity when the Bitcoin
+    /// wallet is short. Pairs that already share a channel are skipped.
+    /// Opened channels are confirmed and the graph synced before returning,
+    /// waiting up to [`GRAPH_SYNC_TIMEOUT`] for every node to see them.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `topology` - Shape of the channel graph to build
+    /// * `capacity` - Capacity of each channel in satoshis
+    pub async fn apply_topology(
+        &self,
+        network_name: &str,
+        topology: Topology,
+        capacity: u64,
+    ) -> Result<MeshSummary> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let lnd_nodes = network
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Lnd)
+            .map(|n| self.find_lnd_node(network_name, &n.name))
+            .collect::<Result<Vec<_>>>()?;
+        if lnd_nodes.len() < 2 {
+            return Err(Error::Config(format!(
+                "A {topology} topology needs at least two LND nodes"
+            )));
+        }
+        let pairs = topology.channels(lnd_nodes.len());
+        self.log(format!(
+            "Wiring {topology} topology of {} channels in '{network_name}'",
+            pairs.len()
+        ));
+
+        // Fund every opener that cannot afford its channels
+        for (i, lnd) in lnd_nodes.iter().enumerate() {
+            let count = pairs.iter().filter(|(from, _)| *from == i).count() as u64;
+            let need = count * (capacity + CHANNEL_FEE_RESERVE_SAT);
+            let have = lnd.wallet_balance(&self.container_manager).await?;
+            if have < need {
+                self.log(format!(
+                    "Funding {} with {} sats",
+                    lnd.node.name,
+                    need - have
+                ));
+                self.fund_lnd_wallet_with_options(
+                    network_name,
+                    &lnd.node.name,
+                    sats_to_btc(need - have),
+                    FundOptions::default().with_auto_mine_maturity(true),
+                )
+                .await?;
+            }
+        }
+
+        // Gather pubkeys and existing channel peers to skip wired pairs
+        let mut pubkeys = Vec::with_capacity(lnd_nodes.len());
+        let mut peers = Vec::with_capacity(lnd_nodes.len());
+        for lnd in &lnd_nodes {
+            pubkeys.push(lnd.get_pubkey(&self.container_manager).await?);
+            let channels = lnd.list_channels(&self.container_manager).await?;
+            let remotes: Vec<String> = channels["channels"]
+                .as_array()
+                .map(|chans| {
+                    chans
+                        .iter()
+                        .filter_map(|ch| ch["remote_pubkey"].as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            peers.push(remotes);
+        }
+
+        let mut summary = MeshSummary::default();
+        for (from, to) in pairs {
+            let from_name = lnd_nodes[from].node.name.clone();
+            let to_name = lnd_nodes[to].node.name.clone();
+            if peers[from].contains(&pubkeys[to]) {
+                summary.skipped.push((from_name, to_name));
+                continue;
+            }
+            match self
+                .open_channel(network_name, &from_name, &to_name, capacity, None)
+                .await
+            {
+                Ok(txid) => {
+                    self.log(format!("Opened channel {from_name} -> {to_name}: {txid}"));
+                    summary.opened.push((from_name, to_name, txid));
+                }
+                Err(e) => {
+                    self.log(format!(
+                        "Failed to open channel {from_name} -> {to_name}: {e}"
+                    ));
+                    summary.failed.push((from_name, to_name, e.to_string()));
+                }
+            }
+        }
+
+        if !summary.opened.is_empty() {
+            self.mine_blocks(network_name, CHANNEL_CONFIRMATION_BLOCKS, None)
+                .await?;
+            self.sync_graph(network_name).await?;
+            if let Err(e) = self
+                .wait_for_graph_sync(network_name, GRAPH_SYNC_TIMEOUT)
+                .await
+            {
+                self.log(format!("Payments may fail until gossip catches up: {e}"));
+            }
+        }
+
+        Ok(summary)
+    }
+
     /// Close a Lightning channel.
     ///
     /// # Arguments
@@ -1121,6 +4065,9 @@ impl NetworkManager {
     /// * `node_name` - Name of the node that owns the channel
     /// * `channel_point` - Channel point in format "funding_txid:output_index"
     /// * `force` - Whether to force close the channel
+    ///
+    /// A malformed channel point fails with [`Error::InvalidChannelPoint`]
+    /// before anything runs in the container.
     pub async fn close_channel(
         &self,
         network_name: &str,
@@ -1128,6 +4075,9 @@ impl NetworkManager {
         channel_point: &str,
         force: bool,
     ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        let channel_point: ChannelPoint = channel_point.parse()?;
         let network = self
             .get_network(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
@@ -1136,7 +4086,7 @@ impl NetworkManager {
             .nodes
             .iter()
             .find(|n| n.name == node_name && n.kind == NodeKind::Lnd)
-            .ok_or_else(|| Error::Config(format!("LND node '{}' not found", node_name)))?;
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
 
         let lnd = LndNode {
             node: node.clone(),
@@ -1145,11 +4095,14 @@ impl NetworkManager {
                 .clone()
                 .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
             bitcoin_node: String::new(),
+            bitcoin_host: None,
             alias: node.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
         };
 
         let closing_txid = lnd
-            .close_channel(&self.container_manager, channel_point, force)
+            .close_channel(&self.container_manager, &channel_point, force)
             .await?;
 
         Ok(closing_txid)
@@ -1171,6 +4124,42 @@ impl NetworkManager {
         amount: u64,
         memo: Option<&str>,
     ) -> Result<String> {
+        self.send_payment_with_options(
+            network_name,
+            from_node,
+            to_node,
+            amount,
+            memo,
+            PaymentOptions::default(),
+        )
+        .await
+    }
+
+    /// Send a Lightning payment from one node to another with custom options.
+    ///
+    /// The payment is routed over the full graph, so the two nodes do not need
+    /// a direct channel as long as `sync_graph` has connected the network.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `from_node` - Name of the paying node
+    /// * `to_node` - Name of the receiving node
+    /// * `amount` - Amount in satoshis
+    /// * `memo` - Optional payment memo
+    /// * `options` - Fee limit and multi-part payment settings; large payments
+    ///   over small channels need MPP enabled to be split across them
+    pub async fn send_payment_with_options(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        to_node: &str,
+        amount: u64,
+        memo: Option<&str>,
+        options: PaymentOptions,
+    ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        self.wait_for_payer_graph(network_name, from_node).await?;
         let network = self
             .get_network(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
@@ -1180,13 +4169,13 @@ impl NetworkManager {
             .nodes
             .iter()
             .find(|n| n.name == from_node && n.kind == NodeKind::Lnd)
-            .ok_or_else(|| Error::Config(format!("LND node '{}' not found", from_node)))?;
+            .ok_or_else(|| Error::NodeNotFound(from_node.to_string()))?;
 
         let to = network
             .nodes
             .iter()
             .find(|n| n.name == to_node && n.kind == NodeKind::Lnd)
-            .ok_or_else(|| Error::Config(format!("LND node '{}' not found", to_node)))?;
+            .ok_or_else(|| Error::NodeNotFound(to_node.to_string()))?;
 
         let from_lnd = LndNode {
             node: from.clone(),
@@ -1195,7 +4184,10 @@ impl NetworkManager {
                 .clone()
                 .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
             bitcoin_node: String::new(),
+            bitcoin_host: None,
             alias: from.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
         };
 
         let to_lnd = LndNode {
@@ -1205,96 +4197,484 @@ impl NetworkManager {
                 .clone()
                 .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
             bitcoin_node: String::new(),
+            bitcoin_host: None,
             alias: to.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
         };
 
-        // Create invoice on receiving node
-        let invoice = to_lnd
-            .create_invoice(&self.container_manager, amount, memo)
-            .await?;
+        // Create invoice on receiving node, an AMP one when paying with AMP
+        let invoice = if options.amp {
+            to_lnd
+                .create_amp_invoice(&self.container_manager, amount, memo)
+                .await?
+        } else {
+            to_lnd
+                .create_invoice(&self.container_manager, amount, memo)
+                .await?
+        };
 
         // Pay invoice from sending node
         let payment_hash = from_lnd
-            .pay_invoice(&self.container_manager, &invoice)
+            .pay_invoice_with_options(&self.container_manager, &invoice, options)
             .await?;
 
         Ok(payment_hash)
     }
 
-    /// Synchronize the Lightning Network graph across all LND nodes.
-    /// This connects all LND nodes to each other as peers so they can discover
-    /// channels and route payments.
+    /// Decode a bolt11 invoice using one of the network's LND nodes.
+    ///
+    /// Expired invoices are rejected so they are never offered for payment.
     ///
     /// # Arguments
     /// * `network_name` - Name of the network
-    ///
-    /// # Returns
-    /// Number of LND nodes synchronized
-    pub async fn sync_graph(&self, network_name: &str) -> Result<usize> {
-        let network = self
-            .get_network(network_name)
-            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
-
-        // Get all LND nodes
-        let lnd_nodes: Vec<_> = network
-            .nodes
-            .iter()
-            .filter(|n| n.kind == NodeKind::Lnd)
-            .collect();
+    /// * `node_name` - Name of the LND node used to decode
+    /// * `payment_request` - The bolt11 invoice string
+    pub async fn decode_invoice(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        payment_request: &str,
+    ) -> Result<DecodedInvoice> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        let invoice = lnd
+            .decode_invoice(&self.container_manager, payment_request.trim())
+            .await?;
 
-        if lnd_nodes.len() < 2 {
-            return Ok(0); // Nothing to sync with less than 2 nodes
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        if invoice.is_expired(now) {
+            return Err(Error::Config(format!(
+                "Invoice expired {} seconds ago",
+                now - invoice.expires_at()
+            )));
         }
 
-        // Connect each LND node to all other LND nodes
-        for (i, from_node) in lnd_nodes.iter().enumerate() {
-            for to_node in lnd_nodes.iter().skip(i + 1) {
-                let from_lnd = LndNode {
-                    node: (*from_node).clone(),
-                    image: network
-                        .lnd_version
-                        .clone()
-                        .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
-                    bitcoin_node: String::new(),
-                    alias: from_node.name.clone(),
-                };
-
-                let to_lnd = LndNode {
-                    node: (*to_node).clone(),
-                    image: network
-                        .lnd_version
-                        .clone()
-                        .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
-                    bitcoin_node: String::new(),
-                    alias: to_node.name.clone(),
-                };
-
-                // Get the target node's pubkey
-                let to_pubkey = to_lnd.get_pubkey(&self.container_manager).await?;
-
-                // Connect as peers using the container name (within Docker network)
-                let peer_host = format!("polar-lnd-{}:9735", to_node.id);
-
-                // Try to connect, but don't fail if already connected
-                let _ = from_lnd
-                    .connect_peer(&self.container_manager, &to_pubkey, &peer_host)
-                    .await;
-            }
-        }
+        Ok(invoice)
+    }
 
-        Ok(lnd_nodes.len())
+    /// Estimate the fee and hop count of a payment without sending it.
+    ///
+    /// Returns the cheapest route `lncli queryroutes` finds, or
+    /// [`Error::NoRoute`] if the sender has no path to the destination.
+    pub async fn probe_route(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        to_node: &str,
+        amount: u64,
+    ) -> Result<RouteEstimate> {
+        let from = self.find_lnd_node(network_name, from_node)?;
+        let to = self.find_lnd_node(network_name, to_node)?;
+        let dest_pubkey = to.get_pubkey(&self.container_manager).await?;
+
+        from.query_routes(&self.container_manager, &dest_pubkey, amount)
+            .await?
+            .into_iter()
+            .min_by_key(|route| route.total_fees_msat)
+            .ok_or_else(|| Error::NoRoute {
+                from: from_node.to_string(),
+                to: to_node.to_string(),
+            })
     }
 
-    /// Synchronize LND nodes with the Bitcoin blockchain.
-    /// This waits for all LND nodes to be synced to the chain.
+    /// Pay `to_node` along a fixed path through the `via` nodes.
     ///
-    /// # Arguments
-    /// * `network_name` - Name of the network
+    /// Every hop must have an active channel to the next one. `to_node`
+    /// creates the invoice, the route is built with `lncli buildroute` and
+    /// paid with `lncli sendtoroute`, so LND never picks a path itself.
     ///
     /// # Returns
-    /// Number of LND nodes synchronized
-    pub async fn sync_chain(&self, network_name: &str) -> Result<usize> {
-        let network = self
+    /// The hex encoded payment preimage
+    pub async fn send_along_route(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        to_node: &str,
+        amount: u64,
+        via: Vec<&str>,
+    ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        self.wait_for_payer_graph(network_name, from_node).await?;
+        let from = self.find_lnd_node(network_name, from_node)?;
+        let to = self.find_lnd_node(network_name, to_node)?;
+
+        // Check each hop has a channel to the next before building anything
+        let mut path = vec![from_node];
+        path.extend(via.iter().copied());
+        path.push(to_node);
+        let mut hop_pubkeys = Vec::with_capacity(path.len() - 1);
+        for pair in path.windows(2) {
+            let (hop, next) = (pair[0], pair[1]);
+            let channels = self
+                .find_lnd_node(network_name, hop)?
+                .list_channels(&self.container_manager)
+                .await?;
+            let next_pubkey = self
+                .find_lnd_node(network_name, next)?
+                .get_pubkey(&self.container_manager)
+                .await?;
+            if !has_active_channel(&channels, &next_pubkey) {
+                return Err(Error::NoRoute {
+                    from: hop.to_string(),
+                    to: next.to_string(),
+                });
+            }
+            hop_pubkeys.push(next_pubkey);
+        }
+
+        let payment_request = to
+            .create_invoice(&self.container_manager, amount, Some("fixed route payment"))
+            .await?;
+        let invoice = from
+            .decode_invoice(&self.container_manager, &payment_request)
+            .await?;
+
+        let mut route = from
+            .build_route(&self.container_manager, amount, &hop_pubkeys)
+            .await?;
+        set_route_payment_addr(&mut route, &invoice.payment_addr);
+        let preimage = from
+            .send_to_route(&self.container_manager, &invoice.payment_hash, &route)
+            .await?;
+
+        self.log(format!(
+            "Paid {amount} sats from {from_node} to {to_node} via {}",
+            path.join(" -> ")
+        ));
+        Ok(preimage)
+    }
+
+    /// Sign a message with an LND node's identity key.
+    ///
+    /// Returns the zbase32 encoded signature.
+    pub async fn sign_message(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        message: &str,
+    ) -> Result<String> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        let signature = lnd.sign_message(&self.container_manager, message).await?;
+        self.log(format!("Signed message with {node_name}"));
+        Ok(signature)
+    }
+
+    /// Verify a message signature on an LND node.
+    ///
+    /// The signature is only reported valid when the signer is in the
+    /// node's channel graph.
+    pub async fn verify_message(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        message: &str,
+        signature: &str,
+    ) -> Result<MessageVerification> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        lnd.verify_message(&self.container_manager, message, signature.trim())
+            .await
+    }
+
+    /// Pay a bolt11 invoice from an LND node.
+    ///
+    /// Each payment update, with the HTLC attempts made so far, is logged
+    /// while the payment is routed.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `from_node` - Name of the paying node
+    /// * `payment_request` - The bolt11 invoice string
+    /// * `fee_limit_sat` - Maximum routing fee in satoshis (optional)
+    /// * `timeout_secs` - Seconds before giving up (60 if `None`)
+    pub async fn pay_invoice(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        payment_request: &str,
+        fee_limit_sat: Option<u64>,
+        timeout_secs: Option<u64>,
+    ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let _guard = self.lock_network(network_name).await;
+        self.wait_for_payer_graph(network_name, from_node).await?;
+        let lnd = self.find_lnd_node(network_name, from_node)?;
+        let options = PaymentOptions {
+            fee_limit_sat,
+            timeout_secs,
+            ..PaymentOptions::default()
+        };
+        lnd.pay_invoice_streaming(
+            &self.container_manager,
+            payment_request.trim(),
+            options,
+            |update| self.log(format!("Payment from {from_node}: {update}")),
+        )
+        .await
+    }
+
+    /// Disconnect one LND node from another without stopping either container.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `from_node` - Name of the node dropping the connection
+    /// * `to_node` - Name of the peer to disconnect from
+    pub async fn disconnect_peer(
+        &self,
+        network_name: &str,
+        from_node: &str,
+        to_node: &str,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let from_lnd = self.find_lnd_node(network_name, from_node)?;
+        let to_lnd = self.find_lnd_node(network_name, to_node)?;
+
+        let to_pubkey = to_lnd.get_pubkey(&self.container_manager).await?;
+        from_lnd
+            .disconnect_peer(&self.container_manager, &to_pubkey)
+            .await?;
+
+        self.log(format!("Disconnected {from_node} from peer {to_node}"));
+
+        Ok(())
+    }
+
+    /// Rebalance a node's channels with a circular payment to itself.
+    ///
+    /// The payment leaves through `out_channel` and returns through
+    /// `in_channel`, shifting `amount` sats of local balance from the first
+    /// channel to the second. Channel IDs are the `chan_id` values reported
+    /// by `listchannels`.
+    ///
+    /// # Returns
+    /// The routing fee paid in satoshis
+    pub async fn rebalance(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        out_channel: &str,
+        in_channel: &str,
+        amount: u64,
+    ) -> Result<u64> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        if out_channel == in_channel {
+            return Err(Error::Config(
+                "Outgoing and incoming channels must differ to form a loop".to_string(),
+            ));
+        }
+
+        self.wait_for_payer_graph(network_name, node_name).await?;
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        let channels = lnd.list_channels(&self.container_manager).await?;
+        let find_channel = |chan_id: &str| {
+            channels["channels"]
+                .as_array()
+                .and_then(|list| list.iter().find(|c| c["chan_id"].as_str() == Some(chan_id)))
+                .cloned()
+                .ok_or_else(|| Error::Config(format!("Channel {chan_id} not found on {node_name}")))
+        };
+        let out = find_channel(out_channel)?;
+        let inbound = find_channel(in_channel)?;
+
+        let local_balance = lncli_number::<u64>(&out["local_balance"]);
+        if local_balance < amount {
+            return Err(Error::InsufficientFunds {
+                node: node_name.to_string(),
+                have: local_balance,
+                need: amount,
+            });
+        }
+
+        let last_hop = inbound["remote_pubkey"].as_str().unwrap_or_default();
+        let invoice = lnd
+            .create_invoice(&self.container_manager, amount, Some("rebalance"))
+            .await?;
+        let fee = lnd
+            .pay_circular(&self.container_manager, &invoice, out_channel, last_hop)
+            .await
+            .map_err(|e| {
+                Error::Config(format!(
+                    "No loop from channel {out_channel} back through {in_channel}: {e}"
+                ))
+            })?;
+
+        self.log(format!(
+            "Rebalanced {amount} sats on {node_name} from {out_channel} to {in_channel} (fee {fee} sats)"
+        ));
+
+        Ok(fee)
+    }
+
+    /// Create a hold invoice on an LND node.
+    ///
+    /// The caller generates the preimage/hash pair (e.g. with
+    /// [`polar_core::Preimage::generate`]) and keeps the preimage so the
+    /// invoice can later be settled with [`Self::settle_invoice`] or
+    /// cancelled with [`Self::cancel_invoice`]. Payments to it stay in flight
+    /// until then.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the receiving node
+    /// * `payment_hash` - Hex-encoded SHA-256 of the preimage
+    /// * `amount` - Invoice amount in satoshis
+    pub async fn add_hold_invoice(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        payment_hash: &str,
+        amount: u64,
+    ) -> Result<String> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        let payment_request = lnd
+            .add_hold_invoice(&self.container_manager, payment_hash, amount)
+            .await?;
+
+        self.log(format!(
+            "Created hold invoice on {node_name} for {amount} sats (hash {payment_hash})"
+        ));
+
+        Ok(payment_request)
+    }
+
+    /// Settle a held invoice on an LND node by revealing its preimage.
+    pub async fn settle_invoice(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        preimage: &str,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        lnd.settle_invoice(&self.container_manager, preimage)
+            .await?;
+
+        self.log(format!("Settled hold invoice on {node_name}"));
+        Ok(())
+    }
+
+    /// Cancel an invoice on an LND node, failing any held HTLCs back to the payer.
+    pub async fn cancel_invoice(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        payment_hash: &str,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        lnd.cancel_invoice(&self.container_manager, payment_hash)
+            .await?;
+
+        self.log(format!("Cancelled invoice {payment_hash} on {node_name}"));
+        Ok(())
+    }
+
+    /// Get the state of an invoice on an LND node.
+    pub async fn invoice_state(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        payment_hash: &str,
+    ) -> Result<String> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        lnd.invoice_state(&self.container_manager, payment_hash)
+            .await
+    }
+
+    /// Build a `BitcoinNode` handle for `node` of `network`.
+    fn bitcoin_node_for(&self, network: &Network, node: &Node) -> BitcoinNode {
+        BitcoinNode {
+            node: node.clone(),
+            image: network
+                .btc_version
+                .clone()
+                .unwrap_or_else(|| BitcoinNode::DEFAULT_IMAGE.to_string()),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
+        }
+    }
+
+    /// Build an `LndNode` handle for a named LND node in a network.
+    fn find_lnd_node(&self, network_name: &str, node_name: &str) -> Result<LndNode> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let node = network
+            .nodes
+            .iter()
+            .find(|n| n.name == node_name && n.kind == NodeKind::Lnd)
+            .ok_or_else(|| Error::NodeNotFound(node_name.to_string()))?;
+
+        Ok(LndNode {
+            node: node.clone(),
+            image: network
+                .lnd_version
+                .clone()
+                .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
+            bitcoin_node: String::new(),
+            bitcoin_host: None,
+            alias: node.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
+        })
+    }
+
+    /// Register one LND node's watchtower with another node's watchtower client.
+    ///
+    /// Both nodes must run with `lnd_watchtowers` enabled in the config. The
+    /// tower is reached through its container name on the Docker network.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `client_node` - Name of the LND node to protect
+    /// * `tower_node` - Name of the LND node acting as the watchtower
+    pub async fn add_watchtower(
+        &self,
+        network_name: &str,
+        client_node: &str,
+        tower_node: &str,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        if client_node == tower_node {
+            return Err(Error::Config(
+                "A node cannot be its own watchtower".to_string(),
+            ));
+        }
+
+        let client = self.find_lnd_node(network_name, client_node)?;
+        let tower = self.find_lnd_node(network_name, tower_node)?;
+
+        let tower_pubkey = tower.tower_pubkey(&self.container_manager).await?;
+        let tower_address = format!("polar-lnd-{}:{}", tower.node.id, LndNode::WATCHTOWER_PORT);
+
+        client
+            .add_watchtower(&self.container_manager, &tower_pubkey, &tower_address)
+            .await?;
+
+        self.log(format!(
+            "Registered {tower_node} as watchtower for {client_node}"
+        ));
+
+        Ok(())
+    }
+
+    /// Synchronize the Lightning Network graph across all LND nodes.
+    /// This connects all LND nodes to each other as peers so they can discover
+    /// channels and route payments.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    ///
+    /// # Returns
+    /// Number of LND nodes synchronized
+    pub async fn sync_graph(&self, network_name: &str) -> Result<usize> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let network = self
             .get_network(network_name)
             .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
 
@@ -1305,39 +4685,1639 @@ impl NetworkManager {
             .filter(|n| n.kind == NodeKind::Lnd)
             .collect();
 
-        if lnd_nodes.is_empty() {
-            return Ok(0);
+        if lnd_nodes.len() < 2 {
+            return Ok(0); // Nothing to sync with less than 2 nodes
         }
 
-        // Wait for each LND node to sync with the chain
-        // We'll check if synced_to_chain is true for each node
-        let mut synced_count = 0;
-        for node in &lnd_nodes {
-            if let Some(container_id) = &node.container_id {
-                // Use getinfo to check sync status
-                let output = self.container_manager
-                    .exec_command(
-                        container_id,
-                        vec![
-                            "lncli",
-                            "--network=regtest",
-                            "--tlscertpath=/home/lnd/.lnd/tls.cert",
-                            "--macaroonpath=/home/lnd/.lnd/data/chain/bitcoin/regtest/admin.macaroon",
-                            "getinfo",
-                        ],
-                    )
+        // Connect each LND node to all other LND nodes
+        for (i, from_node) in lnd_nodes.iter().enumerate() {
+            for to_node in lnd_nodes.iter().skip(i + 1) {
+                let from_lnd = LndNode {
+                    node: (*from_node).clone(),
+                    image: network
+                        .lnd_version
+                        .clone()
+                        .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
+                    bitcoin_node: String::new(),
+                    bitcoin_host: None,
+                    alias: from_node.name.clone(),
+                    rpc: self.config.rpc_credentials(),
+                    extra_args: Vec::new(),
+                };
+
+                // Get the target node's pubkey
+                let to_pubkey = match &to_node.container_id {
+                    Some(container_id) => self.lnd_getinfo(container_id).await?["identity_pubkey"]
+                        .as_str()
+                        .map(String::from)
+                        .ok_or_else(|| Error::RpcParse("getinfo has no pubkey".to_string()))?,
+                    None => return Err(Error::NodeNotRunning(to_node.name.clone())),
+                };
+
+                // Connect as peers using the container name (within Docker network)
+                let peer_host = format!("polar-lnd-{}:9735", to_node.id);
+
+                // Try to connect, but don't fail if already connected
+                let _ = from_lnd
+                    .connect_peer(&self.container_manager, &to_pubkey, &peer_host)
                     .await;
+            }
+        }
 
-                if let Ok(info) = output {
-                    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&info) {
-                        if json["synced_to_chain"].as_bool().unwrap_or(false) {
-                            synced_count += 1;
-                        }
-                    }
+        Ok(lnd_nodes.len())
+    }
+
+    /// Wait until every running LND node has the whole channel graph.
+    ///
+    /// [`Self::sync_graph`] only connects the peers; gossip then takes a
+    /// while to spread. A node counts as synced once it reports
+    /// `synced_to_graph` and its `describegraph` holds every public channel
+    /// any node of the network has open. Fails with [`Error::Timeout`]
+    /// naming the lagging nodes when `timeout` elapses first.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `timeout` - How long to keep polling
+    ///
+    /// # Returns
+    /// How long the graph took to sync
+    pub async fn wait_for_graph_sync(
+        &self,
+        network_name: &str,
+        timeout: Duration,
+    ) -> Result<Duration> {
+        let started = Instant::now();
+        loop {
+            let lagging = self.graph_lagging_nodes(network_name).await?;
+            if let Some(elapsed) = self.graph_sync_done(network_name, started, timeout, &lagging)? {
+                return Ok(elapsed);
+            }
+            tokio::time::sleep(GRAPH_SYNC_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Like [`Self::wait_for_graph_sync`], but on a shared manager that is
+    /// only locked while the nodes are polled, so other tasks can use it
+    /// while gossip spreads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a node cannot be queried, or the graph is not
+    /// synced within `timeout`.
+    pub async fn poll_graph_sync(
+        manager: &Arc<tokio::sync::Mutex<Self>>,
+        network_name: &str,
+        timeout: Duration,
+    ) -> Result<Duration> {
+        let started = Instant::now();
+        loop {
+            {
+                let manager = manager.lock().await;
+                let lagging = manager.graph_lagging_nodes(network_name).await?;
+                if let Some(elapsed) =
+                    manager.graph_sync_done(network_name, started, timeout, &lagging)?
+                {
+                    return Ok(elapsed);
                 }
             }
+            tokio::time::sleep(GRAPH_SYNC_POLL_INTERVAL).await;
         }
+    }
 
-        Ok(synced_count)
+    /// Wait for the paying node to see the channel graph before routing a
+    /// payment from it, so a route opened moments ago is found.
+    async fn wait_for_payer_graph(&self, network_name: &str, payer: &str) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            let lagging = self.graph_lagging_nodes(network_name).await?;
+            if !lagging.iter().any(|node| node == payer) {
+                return Ok(());
+            }
+            if started.elapsed() >= GRAPH_SYNC_TIMEOUT {
+                return Err(Error::Timeout(format!(
+                    "channel graph not synced after {}s on {payer}",
+                    GRAPH_SYNC_TIMEOUT.as_secs()
+                )));
+            }
+            tokio::time::sleep(GRAPH_SYNC_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Running LND nodes of a network whose view of the channel graph is
+    /// still incomplete, polled fresh rather than from the info cache.
+    async fn graph_lagging_nodes(&self, network_name: &str) -> Result<Vec<String>> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        self.invalidate_info(network_name);
+
+        let mut views = Vec::new();
+        for node in &network.nodes {
+            let Some(container_id) = node.container_id.as_deref() else {
+                continue;
+            };
+            if node.kind != NodeKind::Lnd {
+                continue;
+            }
+            let lnd = self.find_lnd_node(network_name, &node.name)?;
+            let synced = self.lnd_getinfo(container_id).await?["synced_to_graph"]
+                .as_bool()
+                .unwrap_or(false);
+            let channels = lnd.list_channels(&self.container_manager).await?;
+            let graph = lnd.describe_graph(&self.container_manager).await?;
+            views.push((node.name.clone(), synced, channels, graph));
+        }
+        Ok(graph_lagging_nodes(&views))
+    }
+
+    /// Outcome of one [`Self::wait_for_graph_sync`] poll: the time taken
+    /// once nothing lags, `None` to keep polling, or a timeout error.
+    fn graph_sync_done(
+        &self,
+        network_name: &str,
+        started: Instant,
+        timeout: Duration,
+        lagging: &[String],
+    ) -> Result<Option<Duration>> {
+        if lagging.is_empty() {
+            let elapsed = started.elapsed();
+            self.log(format!(
+                "Channel graph of '{network_name}' synced in {:.1}s",
+                elapsed.as_secs_f64()
+            ));
+            return Ok(Some(elapsed));
+        }
+        if started.elapsed() >= timeout {
+            return Err(Error::Timeout(format!(
+                "channel graph not synced after {}s on {}",
+                timeout.as_secs(),
+                lagging.join(", ")
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Connect every Bitcoin Core node in a network to each other over P2P.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    ///
+    /// # Returns
+    /// Number of Bitcoin Core nodes connected
+    pub async fn connect_bitcoin_peers(&self, network_name: &str) -> Result<usize> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let btc_nodes: Vec<_> = network
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::BitcoinCore)
+            .collect();
+
+        if btc_nodes.len() < 2 {
+            return Ok(0); // Nothing to connect with less than 2 nodes
+        }
+
+        for (i, from_node) in btc_nodes.iter().enumerate() {
+            let from_btc = self.bitcoin_node_for(network, from_node);
+
+            for to_node in btc_nodes.iter().skip(i + 1) {
+                // Connect using the container name (within Docker network)
+                let peer_host = format!("polar-btc-{}:18444", to_node.id);
+                from_btc
+                    .add_node(&self.container_manager, &peer_host, "add")
+                    .await?;
+                self.log(format!(
+                    "Connected {} to Bitcoin peer {}",
+                    from_node.name, to_node.name
+                ));
+            }
+        }
+
+        Ok(btc_nodes.len())
+    }
+
+    /// Disconnect two Bitcoin Core nodes so they can build competing chains.
+    ///
+    /// Both sides drop the peer from their `addnode` list and close the
+    /// connection, so they stay partitioned until `connect_bitcoin_peers`
+    /// is called again.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of one Bitcoin Core node
+    /// * `peer_name` - Name of the Bitcoin Core node to disconnect from
+    pub async fn disconnect_bitcoin_peer(
+        &self,
+        network_name: &str,
+        node_name: &str,
+        peer_name: &str,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_info_on_exit(network_name);
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let find_btc = |name: &str| {
+            network
+                .nodes
+                .iter()
+                .find(|n| n.name == name && n.kind == NodeKind::BitcoinCore)
+                .map(|n| self.bitcoin_node_for(network, n))
+                .ok_or_else(|| Error::NodeNotFound(name.to_string()))
+        };
+
+        let node = find_btc(node_name)?;
+        let peer = find_btc(peer_name)?;
+
+        for (from, to) in [(&node, &peer), (&peer, &node)] {
+            let peer_host = format!("polar-btc-{}:18444", to.node.id);
+            // The peer may not be in the addnode list or connected, which is fine
+            let _ = from
+                .add_node(&self.container_manager, &peer_host, "remove")
+                .await;
+            let _ = from
+                .disconnect_node(&self.container_manager, &peer_host)
+                .await;
+        }
+
+        self.log(format!(
+            "Disconnected Bitcoin node {node_name} from {peer_name}"
+        ));
+
+        Ok(())
+    }
+
+    /// Simulate a chain reorganisation of `depth` blocks.
+    ///
+    /// The primary Bitcoin node and the next Bitcoin node are connected and
+    /// synced, then partitioned to mine competing chains: `depth` blocks on
+    /// the primary and `depth + 1` on its peer. Reconnecting them makes the
+    /// primary, and with it every LND node it backs, switch to the longer
+    /// chain. The block generator must be stopped first so it cannot extend
+    /// the primary's branch.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `depth` - Number of blocks to replace on the primary node
+    pub async fn simulate_reorg(&self, network_name: &str, depth: u32) -> Result<ReorgSummary> {
+        if depth == 0 {
+            return Err(Error::Config(
+                "Reorg depth must be at least one block".to_string(),
+            ));
+        }
+        if self.is_block_generator_running(network_name) {
+            return Err(Error::Config(
+                "Stop the block generator before simulating a reorg".to_string(),
+            ));
+        }
+
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+        let btc_nodes: Vec<BitcoinNode> = network
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::BitcoinCore)
+            .take(2)
+            .map(|n| self.bitcoin_node_for(network, n))
+            .collect();
+        let [node, peer] = btc_nodes.as_slice() else {
+            return Err(Error::Config(format!(
+                "Network '{network_name}' needs two Bitcoin nodes to simulate a reorg"
+            )));
+        };
+
+        // Start both branches from the same tip
+        self.connect_bitcoin_peers(network_name).await?;
+        self.wait_for_same_tip(node, peer).await?;
+
+        self.disconnect_bitcoin_peer(network_name, &node.node.name, &peer.node.name)
+            .await?;
+        node.mine_blocks(&self.container_manager, depth, None)
+            .await?;
+        peer.mine_blocks(&self.container_manager, depth + 1, None)
+            .await?;
+        let before = node.get_best_block_hash(&self.container_manager).await?;
+
+        self.connect_bitcoin_peers(network_name).await?;
+        let after = self.wait_for_same_tip(node, peer).await?;
+        self.invalidate_info(network_name);
+        if after == before {
+            return Err(Error::Config(format!(
+                "{} kept its own chain instead of reorging to {}",
+                node.node.name, peer.node.name
+            )));
+        }
+
+        self.log(format!(
+            "Reorged {depth} blocks on {} onto the chain of {}",
+            node.node.name, peer.node.name
+        ));
+        Ok(ReorgSummary {
+            node: node.node.name.clone(),
+            peer: peer.node.name.clone(),
+            depth,
+            before,
+            after,
+        })
+    }
+
+    /// Wait until two Bitcoin nodes report the same best block hash.
+    async fn wait_for_same_tip(&self, node: &BitcoinNode, peer: &BitcoinNode) -> Result<String> {
+        let started = Instant::now();
+        loop {
+            let tip = node.get_best_block_hash(&self.container_manager).await?;
+            if tip == peer.get_best_block_hash(&self.container_manager).await? {
+                return Ok(tip);
+            }
+            if started.elapsed() >= CHAIN_SYNC_TIMEOUT {
+                return Err(Error::Timeout(format!(
+                    "{} and {} did not agree on a chain tip after {}s",
+                    node.node.name,
+                    peer.node.name,
+                    CHAIN_SYNC_TIMEOUT.as_secs()
+                )));
+            }
+            tokio::time::sleep(CHAIN_SYNC_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Synchronize LND nodes with the Bitcoin blockchain.
+    /// This waits for all LND nodes to be synced to the chain.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    ///
+    /// # Returns
+    /// Number of LND nodes synchronized
+    pub async fn sync_chain(&self, network_name: &str) -> Result<usize> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        // Get all LND nodes
+        let lnd_nodes: Vec<_> = network
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Lnd)
+            .collect();
+
+        if lnd_nodes.is_empty() {
+            return Ok(0);
+        }
+
+        // Wait for each LND node to sync with the chain
+        // We'll check if synced_to_chain is true for each node
+        let mut synced_count = 0;
+        for node in &lnd_nodes {
+            if let Some(container_id) = &node.container_id {
+                // Use getinfo to check sync status
+                if let Ok(json) = self.lnd_getinfo(container_id).await {
+                    if json["synced_to_chain"].as_bool().unwrap_or(false) {
+                        synced_count += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(synced_count)
+    }
+
+    /// Get the open channels of an LND node, with counterparties in the
+    /// network named.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the LND node
+    pub async fn list_channels(
+        &self,
+        network_name: &str,
+        node_name: &str,
+    ) -> Result<Vec<ChannelInfo>> {
+        match self.get_node_info(network_name, node_name).await? {
+            NodeInfo::Lnd(info) => Ok(info.channels),
+            NodeInfo::Bitcoin(_) => Err(Error::Config(format!(
+                "{node_name} is not a Lightning node"
+            ))),
+        }
+    }
+
+    /// Copy the TLS cert and admin macaroon out of an LND container.
+    ///
+    /// Both land next to each other in `credentials/<network>/<node>/` under
+    /// the data directory, so external wallets and tools like lndconnect can
+    /// be pointed at one place.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the LND node, which must be running
+    pub async fn export_node_credentials(
+        &self,
+        network_name: &str,
+        node_name: &str,
+    ) -> Result<ExportedCredentials> {
+        let lnd_node = self.find_lnd_node(network_name, node_name)?;
+        let dir = self.credentials_dir(network_name).join(node_name);
+        let exported = lnd_node
+            .export_credentials(&self.container_manager, &dir)
+            .await?;
+
+        self.log(format!(
+            "Exported credentials of {} to {}",
+            node_name,
+            dir.display()
+        ));
+        Ok(exported)
+    }
+
+    /// Build an `lndconnect://` URL for pairing a wallet with an LND node.
+    ///
+    /// Points at the gRPC port published on the Docker host, which is the
+    /// remote host for `tcp://` Docker hosts, rather than the container's own
+    /// 10009, and embeds the node's TLS cert and admin
+    /// macaroon.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the LND node, which must be running
+    pub async fn lndconnect_url(&self, network_name: &str, node_name: &str) -> Result<String> {
+        let lnd_node = self.find_lnd_node(network_name, node_name)?;
+        let tls_cert = lnd_node
+            .read_credential(&self.container_manager, LndCredential::TlsCert)
+            .await?;
+        let macaroon = lnd_node
+            .read_credential(&self.container_manager, LndCredential::AdminMacaroon)
+            .await?;
+
+        let port_config = self
+            .get_network(network_name)
+            .and_then(|network| network.port_mappings.get(&lnd_node.node.id));
+        let grpc_port = if let Some(NodePorts::Lnd { grpc, .. }) = port_config.map(|c| &c.ports) {
+            grpc.to_string()
+        } else {
+            let container_id = lnd_node
+                .node
+                .container_id
+                .as_deref()
+                .ok_or_else(|| Error::NodeNotRunning(node_name.to_string()))?;
+            let [grpc_host] = self
+                .inspect_host_endpoints(container_id, [LND_GRPC_PORT])
+                .await?;
+            // Unpublished ports come back as the bare container port
+            grpc_host
+                .rsplit_once(':')
+                .map(|(_, port)| port.to_string())
+                .ok_or_else(|| {
+                    Error::Config(format!("gRPC port of {node_name} is not published"))
+                })?
+        };
+
+        let host = self.published_host();
+        lndconnect_url(&format!("{host}:{grpc_port}"), &tls_cert, &macaroon)
+    }
+
+    /// Check that an LND node follows the chain of its Bitcoin backend.
+    ///
+    /// Mines one block and waits for the node to reach the new height and
+    /// report `synced_to_chain`. LND learns about blocks over ZMQ, so a node
+    /// that never catches up points at broken ZMQ wiring rather than a slow
+    /// sync, and fails with [`Error::NoBlockNotifications`].
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the LND node
+    pub async fn verify_lnd_backend(&self, network_name: &str, node_name: &str) -> Result<()> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        self.mine_blocks(network_name, 1, None).await?;
+        let target = self
+            .chain_height(network)
+            .await
+            .ok_or_else(|| Error::Config("Could not read the chain height".to_string()))?;
+
+        let height = self.wait_for_lnd_chain_sync(&lnd, target).await?;
+        self.log(format!("{node_name} follows the chain at height {height}"));
+        Ok(())
+    }
+
+    /// Wait until an LND node reaches `target` height and reports
+    /// `synced_to_chain`, returning the height it reached.
+    ///
+    /// Fails with [`Error::NoBlockNotifications`] when the node doesn't catch
+    /// up within [`BLOCK_NOTIFICATION_TIMEOUT`].
+    async fn wait_for_lnd_chain_sync(&self, lnd: &LndNode, target: u64) -> Result<u32> {
+        let deadline = Instant::now() + BLOCK_NOTIFICATION_TIMEOUT;
+        loop {
+            let (height, synced) = lnd.chain_sync(&self.container_manager).await?;
+            if synced && u64::from(height) >= target {
+                return Ok(height);
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::NoBlockNotifications(lnd.node.name.clone()));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    /// Get the recent invoices and outgoing payments of an LND node.
+    ///
+    /// This is fetched on demand rather than with the node info, since
+    /// long-running nodes accumulate many entries.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    /// * `node_name` - Name of the LND node
+    pub async fn payment_history(
+        &self,
+        network_name: &str,
+        node_name: &str,
+    ) -> Result<PaymentHistory> {
+        let lnd = self.find_lnd_node(network_name, node_name)?;
+
+        let invoices = lnd.list_invoices(&self.container_manager).await?;
+        let payments = lnd.list_payments(&self.container_manager).await?;
+
+        Ok(PaymentHistory {
+            node: node_name.to_string(),
+            invoices: parse_invoices(&invoices),
+            payments: parse_payments(&payments),
+        })
+    }
+
+    /// Describe the Lightning Network channel graph.
+    ///
+    /// The graph is queried from the first running LND node in the network.
+    ///
+    /// # Arguments
+    /// * `network_name` - Name of the network
+    pub async fn describe_graph(&self, network_name: &str) -> Result<GraphInfo> {
+        let network = self
+            .get_network(network_name)
+            .ok_or_else(|| Error::NetworkNotFound(network_name.to_string()))?;
+
+        let node = network
+            .nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Lnd && n.container_id.is_some())
+            .ok_or_else(|| Error::Config("No running LND node found in network".to_string()))?;
+
+        let lnd = LndNode {
+            node: node.clone(),
+            image: network
+                .lnd_version
+                .clone()
+                .unwrap_or_else(|| LndNode::DEFAULT_IMAGE.to_string()),
+            bitcoin_node: String::new(),
+            bitcoin_host: None,
+            alias: node.name.clone(),
+            rpc: self.config.rpc_credentials(),
+            extra_args: Vec::new(),
+        };
+
+        let graph_json = lnd.describe_graph(&self.container_manager).await?;
+
+        Ok(parse_graph(&graph_json))
+    }
+}
+
+/// Parse the output of `lncli pendingchannels` into a flat list.
+fn parse_pending_channels(json: &serde_json::Value) -> Vec<PendingChannelInfo> {
+    let sections = [
+        ("pending_open_channels", PendingChannelState::Opening),
+        ("pending_closing_channels", PendingChannelState::Closing),
+        (
+            "pending_force_closing_channels",
+            PendingChannelState::ForceClosing,
+        ),
+        ("waiting_close_channels", PendingChannelState::WaitingClose),
+    ];
+
+    let mut pending = Vec::new();
+    for (key, state) in sections {
+        let Some(entries) = json[key].as_array() else {
+            continue;
+        };
+
+        for entry in entries {
+            let channel = &entry["channel"];
+            pending.push(PendingChannelInfo {
+                channel_point: channel["channel_point"]
+                    .as_str()
+                    .unwrap_or("unknown")
+                    .to_string(),
+                remote_pubkey: channel["remote_node_pub"]
+                    .as_str()
+                    .unwrap_or("unknown")
+                    .to_string(),
+                remote_name: None,
+                capacity: lncli_number::<i64>(&channel["capacity"]),
+                local_balance: lncli_number::<i64>(&channel["local_balance"]),
+                state,
+                blocks_til_maturity: entry["blocks_til_maturity"]
+                    .as_i64()
+                    .and_then(|b| i32::try_from(b).ok()),
+            });
+        }
+    }
+
+    pending
+}
+
+/// Fill in the node names of channel counterparties and peers that belong
+/// to the same network, keyed by pubkey.
+fn name_remote_nodes(info: &mut LndNodeInfo, names: &HashMap<String, String>) {
+    for channel in &mut info.channels {
+        channel.remote_name = names.get(&channel.remote_pubkey).cloned();
+    }
+    for channel in &mut info.pending_channels {
+        channel.remote_name = names.get(&channel.remote_pubkey).cloned();
+    }
+    for peer in &mut info.peers {
+        peer.name = names.get(&peer.pubkey).cloned();
+    }
+}
+
+/// Parse the output of `lncli listpeers`.
+fn parse_peers(json: &serde_json::Value) -> Vec<PeerInfo> {
+    json["peers"]
+        .as_array()
+        .map(|peers| {
+            peers
+                .iter()
+                .map(|peer| PeerInfo {
+                    pubkey: peer["pub_key"].as_str().unwrap_or("unknown").to_string(),
+                    name: None,
+                    address: peer["address"].as_str().unwrap_or("unknown").to_string(),
+                    ping_time: lncli_number(&peer["ping_time"]),
+                    sat_sent: lncli_number(&peer["sat_sent"]),
+                    sat_recv: lncli_number(&peer["sat_recv"]),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Forgets a network's cached node info when dropped, see
+/// [`NetworkManager::invalidate_info_on_exit`].
+#[must_use]
+struct InfoInvalidation<'a> {
+    manager: &'a NetworkManager,
+    network_name: &'a str,
+}
+
+impl Drop for InfoInvalidation<'_> {
+    fn drop(&mut self) {
+        self.manager.invalidate_info(self.network_name);
+    }
+}
+
+/// Sats kept back for the fee of a batched funding transaction.
+const FUNDING_FEE_RESERVE_SATS: u64 = 100_000;
+
+/// Extra lnd flags for a network, adding wumbo channels when enabled in the
+/// config and not already requested by the network.
+fn lnd_extra_args(config: &Config, extra_args: &ExtraArgs) -> Vec<String> {
+    let mut args = extra_args.lnd.clone();
+    if config.wumbo && !args.iter().any(|arg| arg == LndNode::WUMBO_FLAG) {
+        args.insert(0, LndNode::WUMBO_FLAG.to_string());
+    }
+    args
+}
+
+/// Whether a container image looks like it runs a node of `kind`.
+fn image_matches_kind(image: &str, kind: NodeKind) -> bool {
+    let last = image.rsplit('/').next().unwrap_or(image);
+    let name = last.split([':', '@']).next().unwrap_or(last);
+    match kind {
+        NodeKind::BitcoinCore => name.contains("bitcoin"),
+        NodeKind::Lnd => name.contains("lnd"),
+    }
+}
+
+/// The primary Bitcoin node of a network as LND nodes connect to it, for
+/// [`LndNode::check_zmq_backend`].
+fn lnd_backend(network: &Network) -> Result<BitcoinNode> {
+    let node = network
+        .primary_bitcoin_node()
+        .ok_or_else(|| Error::Config("No Bitcoin node found in network".to_string()))?;
+    let mut backend = BitcoinNode::new(node.name.clone());
+    backend.node.id = node.id;
+    backend.extra_args.clone_from(&network.extra_args.bitcoin);
+    Ok(backend)
+}
+
+/// Host LND nodes reach the network's Bitcoin backend at, when that node is
+/// an adopted container rather than one polar started.
+async fn lnd_backend_host(
+    container_manager: &ContainerManager,
+    network: &Network,
+) -> Result<Option<String>> {
+    let Some(node) = network.primary_bitcoin_node().filter(|n| n.external) else {
+        return Ok(None);
+    };
+    let Some(container_id) = &node.container_id else {
+        return Err(Error::NodeNotRunning(node.name.clone()));
+    };
+    let info = container_manager.inspect_container(container_id).await?;
+    Ok(info
+        .name
+        .map(|name| name.trim_start_matches('/').to_string()))
+}
+
+/// Connect the adopted containers of a network to its Docker network, so
+/// the nodes polar starts can reach them by name.
+async fn connect_external_nodes(
+    container_manager: &ContainerManager,
+    network: &Network,
+) -> Result<()> {
+    let docker_network_name = network.docker_network_name();
+    for node in network.nodes.iter().filter(|n| n.external) {
+        if let Some(container_id) = &node.container_id {
+            container_manager
+                .connect_network(&docker_network_name, container_id)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Largest channel the LND nodes of a network accept, in satoshis.
+fn max_channel_size(wumbo: bool, extra_args: &ExtraArgs) -> u64 {
+    if wumbo || extra_args.lnd.iter().any(|arg| arg == LndNode::WUMBO_FLAG) {
+        LndNode::MAX_WUMBO_CHANNEL_SIZE
+    } else {
+        LndNode::MAX_CHANNEL_SIZE
+    }
+}
+
+/// Reject a channel larger than the nodes accept before asking LND to open it.
+fn check_channel_capacity(capacity: u64, max: u64) -> Result<()> {
+    if capacity <= max {
+        return Ok(());
+    }
+    let hint = if max < LndNode::MAX_WUMBO_CHANNEL_SIZE {
+        "; set `wumbo` to true in config.json to allow larger channels"
+    } else {
+        ""
+    };
+    Err(Error::Config(format!(
+        "Channel capacity {capacity} sats exceeds the {max} sat limit{hint}"
+    )))
+}
+
+/// Parse a saved network file, migrating it to the current schema first.
+///
+/// Returns the network and the schema version it was migrated from, if any.
+fn parse_network(content: &str) -> Result<(Network, Option<u32>)> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    let migrated_from = Network::migrate(&mut value)?;
+    Ok((serde_json::from_value(value)?, migrated_from))
+}
+
+/// Whether `lncli listchannels` output has an active channel to `remote_pubkey`.
+fn has_active_channel(channels: &serde_json::Value, remote_pubkey: &str) -> bool {
+    channels["channels"].as_array().is_some_and(|channels| {
+        channels
+            .iter()
+            .any(|channel| channel["remote_pubkey"] == remote_pubkey && channel["active"] == true)
+    })
+}
+
+/// Nodes whose view of the channel graph is incomplete.
+///
+/// Takes each node's name, `synced_to_graph` flag, `listchannels` and
+/// `describegraph` output. Every public channel of any node must appear as
+/// an edge in every node's graph.
+fn graph_lagging_nodes(
+    views: &[(String, bool, serde_json::Value, serde_json::Value)],
+) -> Vec<String> {
+    let expected: HashSet<&str> = views
+        .iter()
+        .flat_map(|(_, _, channels, _)| channels["channels"].as_array().into_iter().flatten())
+        .filter(|c| !c["private"].as_bool().unwrap_or(false))
+        .filter_map(|c| c["channel_point"].as_str())
+        .collect();
+
+    views
+        .iter()
+        .filter(|(_, synced, _, graph)| {
+            let known: HashSet<&str> = graph["edges"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e["chan_point"].as_str())
+                .collect();
+            !synced || !expected.is_subset(&known)
+        })
+        .map(|(name, ..)| name.clone())
+        .collect()
+}
+
+/// Size of a file, or of everything below a directory; 0 when missing.
+fn path_size(path: &Path) -> std::io::Result<u64> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += path_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// Entries of `dir` with their sizes, as (name, bytes).
+///
+/// With an `extension`, only files carrying it are listed, under their name
+/// without it. A missing directory has no entries.
+fn dir_entries(dir: &Path, extension: Option<&str>) -> std::io::Result<Vec<(String, u64)>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = match extension {
+            Some(extension) if path.extension().and_then(|e| e.to_str()) != Some(extension) => {
+                continue;
+            }
+            Some(_) => path.file_stem(),
+            None => path.file_name(),
+        };
+        if let Some(name) = name.and_then(|n| n.to_str()) {
+            entries.push((name.to_string(), path_size(&path)?));
+        }
+    }
+    Ok(entries)
+}
+
+/// Split a node container name like `polar-lnd-<uuid>` into its node kind and
+/// node ID.
+fn parse_container_name(name: &str) -> Option<(NodeKind, &str)> {
+    name.strip_prefix("polar-btc-")
+        .map(|id| (NodeKind::BitcoinCore, id))
+        .or_else(|| {
+            name.strip_prefix("polar-lnd-")
+                .map(|id| (NodeKind::Lnd, id))
+        })
+}
+
+/// Name of the container a managed `node` runs in, the inverse of
+/// [`parse_container_name`].
+fn node_container_name(node: &Node) -> String {
+    match node.kind {
+        NodeKind::BitcoinCore => format!("polar-btc-{}", node.id),
+        NodeKind::Lnd => format!("polar-lnd-{}", node.id),
+    }
+}
+
+/// Name of the Docker volume holding a managed `node`'s chain data.
+fn node_volume_name(node: &Node) -> String {
+    data_volume_name(&node_container_name(node))
+}
+
+/// Reject an image that is not one of the supported versions.
+fn check_supported_image(kind: &str, image: &str, supported: &[&str]) -> Result<()> {
+    if supported.contains(&image) {
+        return Ok(());
+    }
+    Err(Error::Config(format!(
+        "Unsupported {kind} image '{image}'. Supported versions: {}. Set allow_custom_images in config.json to use other images.",
+        supported.join(", ")
+    )))
+}
+
+/// Reject a hybrid generator mode that would mine an empty block every tick.
+fn check_generator_mode(mode: GeneratorMode) -> Result<()> {
+    if mode
+        == (GeneratorMode::Hybrid {
+            empty_block_ticks: 0,
+        })
+    {
+        return Err(Error::Config(
+            "Hybrid block generator needs at least one idle tick between empty blocks".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject environment variables Docker cannot set as `NAME=value`.
+fn check_env(env: &[(String, String)]) -> Result<()> {
+    for (key, _) in env {
+        if key.is_empty() || key.contains('=') || key.contains('\0') {
+            return Err(Error::Config(format!(
+                "Invalid environment variable name '{key}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Host to container port bindings published for a node.
+fn port_bindings(ports: &NodePorts) -> Vec<PortBinding> {
+    let binding = |service, host, container| PortBinding {
+        service,
+        host,
+        container,
+    };
+    match *ports {
+        NodePorts::BitcoinCore {
+            rpc,
+            p2p,
+            zmq_block,
+            zmq_tx,
+        } => vec![
+            binding("rpc", rpc, 18443),
+            binding("p2p", p2p, 18444),
+            binding("zmq_block", zmq_block, 28334),
+            binding("zmq_tx", zmq_tx, 28335),
+        ],
+        NodePorts::Lnd { rest, grpc, p2p } => vec![
+            binding("rest", rest, 8080),
+            binding("grpc", grpc, 10009),
+            binding("p2p", p2p, 9735),
+        ],
+    }
+}
+
+/// Host ports of a Bitcoin Core node, as taken by `BitcoinNode::start_with_ports`.
+const fn bitcoin_ports(ports: &NodePorts) -> Option<(u16, u16, u16, u16)> {
+    match *ports {
+        NodePorts::BitcoinCore {
+            rpc,
+            p2p,
+            zmq_block,
+            zmq_tx,
+        } => Some((rpc, p2p, zmq_block, zmq_tx)),
+        NodePorts::Lnd { .. } => None,
+    }
+}
+
+/// Host ports of an LND node, as taken by `LndNode::start_with_ports`.
+const fn lnd_ports(ports: &NodePorts) -> Option<(u16, u16, u16)> {
+    match *ports {
+        NodePorts::Lnd { rest, grpc, p2p } => Some((rest, grpc, p2p)),
+        NodePorts::BitcoinCore { .. } => None,
+    }
+}
+
+/// Number of blocks an LND node at `block_height` trails the chain by.
+///
+/// A node briefly ahead of a chain height sampled earlier counts as in sync.
+fn chain_height_drift(chain_height: u64, block_height: u32) -> u64 {
+    chain_height.saturating_sub(u64::from(block_height))
+}
+
+/// Total up the LND entries of a [`NetworkManager::get_all_node_info`] batch.
+fn summarize_liquidity<'a>(infos: impl Iterator<Item = &'a Result<NodeInfo>>) -> LiquiditySummary {
+    let mut summary = LiquiditySummary::default();
+    let mut channels = HashSet::new();
+
+    for info in infos {
+        match info {
+            Ok(NodeInfo::Lnd(info)) => {
+                summary.nodes += 1;
+                summary.onchain += info.wallet_confirmed;
+                for channel in &info.channels {
+                    summary.local += channel.local_balance;
+                    summary.remote += channel.remote_balance;
+                    // Both ends list a channel between two nodes of the network
+                    if channels.insert(channel.channel_point.as_str()) {
+                        summary.capacity += channel.capacity;
+                        if channel.active {
+                            summary.active_channels += 1;
+                        }
+                    }
+                }
+            }
+            Ok(NodeInfo::Bitcoin(_)) => {}
+            Err(_) => summary.unreachable += 1,
+        }
+    }
+
+    summary
+}
+
+/// Parse a satoshi amount from `lncli walletbalance` or `channelbalance`.
+///
+/// LND encodes amounts as strings, and `channelbalance` nests them as
+/// `{"sat": "...", "msat": "..."}`. Missing amounts read as zero.
+fn parse_sats(json: &serde_json::Value) -> i64 {
+    lncli_number(json.get("sat").unwrap_or(json))
+}
+
+/// Parse the output of `lncli wtclient towers`.
+fn parse_towers(json: &serde_json::Value) -> Vec<TowerInfo> {
+    json["towers"]
+        .as_array()
+        .map(|towers| {
+            towers
+                .iter()
+                .map(|tower| TowerInfo {
+                    pubkey: tower["pubkey"].as_str().unwrap_or("unknown").to_string(),
+                    addresses: tower["addresses"]
+                        .as_array()
+                        .map(|addrs| {
+                            addrs
+                                .iter()
+                                .filter_map(|a| a.as_str().map(ToString::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    num_sessions: tower["num_sessions"]
+                        .as_u64()
+                        .and_then(|n| u32::try_from(n).ok())
+                        .unwrap_or(0),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the output of `lncli listinvoices`, newest first.
+fn parse_invoices(json: &serde_json::Value) -> Vec<InvoiceInfo> {
+    json["invoices"]
+        .as_array()
+        .map(|invoices| {
+            invoices
+                .iter()
+                .rev()
+                .map(|invoice| InvoiceInfo {
+                    payment_hash: invoice["r_hash"].as_str().unwrap_or_default().to_string(),
+                    memo: invoice["memo"].as_str().unwrap_or_default().to_string(),
+                    amount_sat: lncli_number(&invoice["value"]),
+                    amount_paid_sat: lncli_number(&invoice["amt_paid_sat"]),
+                    state: invoice["state"].as_str().unwrap_or("UNKNOWN").to_string(),
+                    creation_date: lncli_number(&invoice["creation_date"]),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the output of `lncli listpayments`, newest first.
+fn parse_payments(json: &serde_json::Value) -> Vec<PaymentInfo> {
+    json["payments"]
+        .as_array()
+        .map(|payments| {
+            payments
+                .iter()
+                .rev()
+                .map(|payment| PaymentInfo {
+                    payment_hash: payment["payment_hash"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    amount_sat: lncli_number(&payment["value_sat"]),
+                    fee_sat: lncli_number(&payment["fee_sat"]),
+                    status: payment["status"].as_str().unwrap_or("UNKNOWN").to_string(),
+                    parts: payment["htlcs"].as_array().map_or(0, |htlcs| {
+                        htlcs
+                            .iter()
+                            .filter(|htlc| htlc["status"] == "SUCCEEDED")
+                            .count()
+                    }),
+                    creation_date: lncli_number(&payment["creation_date"]),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the output of `lncli describegraph`.
+fn parse_graph(json: &serde_json::Value) -> GraphInfo {
+    let nodes = json["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|n| GraphNode {
+                    pubkey: n["pub_key"].as_str().unwrap_or("unknown").to_string(),
+                    alias: n["alias"].as_str().unwrap_or_default().to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let edges = json["edges"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|e| GraphEdge {
+                    channel_id: e["channel_id"].as_str().unwrap_or("unknown").to_string(),
+                    channel_point: e["chan_point"].as_str().unwrap_or_default().to_string(),
+                    node1_pubkey: e["node1_pub"].as_str().unwrap_or("unknown").to_string(),
+                    node2_pubkey: e["node2_pub"].as_str().unwrap_or("unknown").to_string(),
+                    capacity: lncli_number::<i64>(&e["capacity"]),
+                    node1_policy: parse_routing_policy(&e["node1_policy"]),
+                    node2_policy: parse_routing_policy(&e["node2_policy"]),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    GraphInfo { nodes, edges }
+}
+
+/// Parse a `describegraph` edge policy, which is null until announced.
+fn parse_routing_policy(json: &serde_json::Value) -> Option<RoutingPolicy> {
+    json.is_object().then(|| RoutingPolicy {
+        base_fee_msat: lncli_number(&json["fee_base_msat"]),
+        fee_rate_ppm: lncli_number(&json["fee_rate_milli_msat"]),
+        time_lock_delta: lncli_number(&json["time_lock_delta"]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// LND info holding `wallet` on-chain sats and the given channels.
+    fn lnd_info(wallet: i64, channels: &serde_json::Value) -> NodeInfo {
+        NodeInfo::Lnd(
+            serde_json::from_value(serde_json::json!({
+                "alias": "", "version": "", "identity_pubkey": "",
+                "num_active_channels": 0, "num_pending_channels": 0, "num_peers": 0,
+                "synced_to_chain": true, "synced_to_graph": true,
+                "block_height": 0, "block_hash": "",
+                "wallet_confirmed": wallet, "wallet_unconfirmed": 0, "wallet_locked": 0,
+                "channel_local": 0, "channel_remote": 0, "channel_pending": 0,
+                "rest_host": "", "grpc_host": "",
+                "channels": channels, "pending_channels": [], "peers": []
+            }))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_has_active_channel() {
+        let channels = serde_json::json!({"channels": [
+            {"remote_pubkey": "02aa", "active": true},
+            {"remote_pubkey": "03bb", "active": false}
+        ]});
+        assert!(has_active_channel(&channels, "02aa"));
+        assert!(!has_active_channel(&channels, "03bb"));
+        assert!(!has_active_channel(&channels, "04cc"));
+    }
+
+    #[test]
+    fn test_graph_lagging_nodes() {
+        let channels = |points: &[(&str, bool)]| {
+            serde_json::json!({ "channels": points
+                .iter()
+                .map(|(point, private)| serde_json::json!({ "channel_point": point, "private": private }))
+                .collect::<Vec<_>>() })
+        };
+        let graph = |points: &[&str]| {
+            serde_json::json!({ "edges": points
+                .iter()
+                .map(|point| serde_json::json!({ "chan_point": point }))
+                .collect::<Vec<_>>() })
+        };
+
+        // lnd-3 has not heard of a:0 yet, and the private c:0 is never gossiped
+        let views = vec![
+            (
+                "lnd-1".to_string(),
+                true,
+                channels(&[("a:0", false)]),
+                graph(&["a:0", "b:0"]),
+            ),
+            (
+                "lnd-2".to_string(),
+                true,
+                channels(&[("a:0", false), ("b:0", false)]),
+                graph(&["a:0", "b:0"]),
+            ),
+            (
+                "lnd-3".to_string(),
+                true,
+                channels(&[("b:0", false), ("c:0", true)]),
+                graph(&["b:0"]),
+            ),
+        ];
+        assert_eq!(graph_lagging_nodes(&views), ["lnd-3"]);
+
+        let mut synced = views;
+        synced[2].3 = graph(&["a:0", "b:0"]);
+        assert!(graph_lagging_nodes(&synced).is_empty());
+
+        synced[0].1 = false;
+        assert_eq!(graph_lagging_nodes(&synced), ["lnd-1"]);
+    }
+
+    #[test]
+    fn test_dir_entries() {
+        let dir = std::env::temp_dir().join(format!("polar-dir-entries-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("alpha").join("lnd-1")).unwrap();
+        std::fs::write(dir.join("alpha").join("lnd-1").join("tls.cert"), [0; 10]).unwrap();
+        std::fs::write(dir.join("alpha").join("notes"), [0; 5]).unwrap();
+        std::fs::write(dir.join("beta.json"), [0; 3]).unwrap();
+
+        let mut all = dir_entries(&dir, None).unwrap();
+        all.sort();
+        let json = dir_entries(&dir, Some("json")).unwrap();
+        let missing = dir_entries(&dir.join("missing"), None).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            all,
+            [("alpha".to_string(), 15), ("beta.json".to_string(), 3)]
+        );
+        assert_eq!(json, [("beta".to_string(), 3)]);
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn test_image_matches_kind() {
+        assert!(image_matches_kind(
+            "polarlightning/bitcoind:28.0",
+            NodeKind::BitcoinCore
+        ));
+        assert!(image_matches_kind(
+            "lightninglabs/lnd:v0.18.5-beta",
+            NodeKind::Lnd
+        ));
+        assert!(image_matches_kind("lnd", NodeKind::Lnd));
+        assert!(!image_matches_kind(
+            "polarlightning/lnd:0.18.5-beta",
+            NodeKind::BitcoinCore
+        ));
+        // Registry hosts and ports are not part of the image name
+        assert!(!image_matches_kind(
+            "lnd-registry:5000/postgres:16",
+            NodeKind::Lnd
+        ));
+    }
+
+    #[test]
+    fn test_summarize_liquidity() {
+        let channel = |point: &str, local: i64, remote: i64, active: bool| {
+            serde_json::json!({
+                "channel_point": point, "remote_pubkey": "",
+                "capacity": 1_000_000, "local_balance": local, "remote_balance": remote,
+                "active": active
+            })
+        };
+        let infos = [
+            Ok(lnd_info(
+                50_000,
+                &serde_json::json!([
+                    channel("aa:0", 900_000, 100_000, true),
+                    channel("bb:0", 1_000_000, 0, false)
+                ]),
+            )),
+            Ok(lnd_info(
+                20_000,
+                &serde_json::json!([channel("aa:0", 100_000, 900_000, true)]),
+            )),
+            Err(Error::NodeNotRunning("lnd-3".to_string())),
+        ];
+
+        let summary = summarize_liquidity(infos.iter());
+        assert_eq!(
+            summary,
+            LiquiditySummary {
+                nodes: 2,
+                unreachable: 1,
+                onchain: 70_000,
+                capacity: 2_000_000,
+                local: 2_000_000,
+                remote: 1_000_000,
+                active_channels: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_pending_channels() {
+        let json = serde_json::json!({
+            "total_limbo_balance": "0",
+            "pending_open_channels": [{
+                "channel": {
+                    "remote_node_pub": "02aa",
+                    "channel_point": "abcd:0",
+                    "capacity": "1000000",
+                    "local_balance": "990000"
+                }
+            }],
+            "pending_force_closing_channels": [{
+                "channel": {
+                    "remote_node_pub": "03bb",
+                    "channel_point": "ef01:1",
+                    "capacity": "500000",
+                    "local_balance": "250000"
+                },
+                "blocks_til_maturity": 143
+            }],
+            "waiting_close_channels": []
+        });
+
+        let pending = parse_pending_channels(&json);
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].state, PendingChannelState::Opening);
+        assert_eq!(pending[0].capacity, 1_000_000);
+        assert_eq!(pending[0].blocks_til_maturity, None);
+        assert_eq!(pending[1].state, PendingChannelState::ForceClosing);
+        assert_eq!(pending[1].remote_pubkey, "03bb");
+        assert_eq!(pending[1].blocks_til_maturity, Some(143));
+    }
+
+    #[test]
+    fn test_parse_peers() {
+        let json = serde_json::json!({
+            "peers": [{
+                "pub_key": "02aa",
+                "address": "172.18.0.4:9735",
+                "bytes_sent": "1200",
+                "sat_sent": "5000",
+                "sat_recv": "0",
+                "ping_time": "412"
+            }]
+        });
+
+        let peers = parse_peers(&json);
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].pubkey, "02aa");
+        assert_eq!(peers[0].address, "172.18.0.4:9735");
+        assert_eq!(peers[0].ping_time, 412);
+        assert_eq!(peers[0].sat_sent, 5000);
+        assert!(parse_peers(&serde_json::json!({ "peers": [] })).is_empty());
+    }
+
+    #[test]
+    fn test_check_channel_capacity() {
+        let plain = max_channel_size(false, &ExtraArgs::default());
+        assert_eq!(plain, LndNode::MAX_CHANNEL_SIZE);
+        let err = check_channel_capacity(50_000_000, plain).unwrap_err();
+        assert!(err.to_string().contains("wumbo"));
+        assert!(check_channel_capacity(LndNode::MAX_CHANNEL_SIZE, plain).is_ok());
+
+        let wumbo = max_channel_size(true, &ExtraArgs::default());
+        assert!(check_channel_capacity(50_000_000, wumbo).is_ok());
+
+        let args = ExtraArgs {
+            lnd: vec![LndNode::WUMBO_FLAG.to_string()],
+            bitcoin: Vec::new(),
+        };
+        assert_eq!(
+            max_channel_size(false, &args),
+            LndNode::MAX_WUMBO_CHANNEL_SIZE
+        );
+        let config = Config {
+            wumbo: true,
+            ..Config::default()
+        };
+        assert_eq!(lnd_extra_args(&config, &args), args.lnd);
+    }
+
+    #[test]
+    fn test_parse_container_name() {
+        let id = "0b6f6d7e-3f0a-4b8e-9a57-2c1d0e6f4a11";
+        assert_eq!(
+            parse_container_name(&format!("polar-lnd-{id}")),
+            Some((NodeKind::Lnd, id))
+        );
+        assert_eq!(
+            parse_container_name(&format!("polar-btc-{id}")),
+            Some((NodeKind::BitcoinCore, id))
+        );
+        assert_eq!(parse_container_name("polar-web"), None);
+    }
+
+    #[test]
+    fn test_node_volume_name() {
+        let btc = BitcoinNode::new("bitcoin-1");
+        assert_eq!(node_container_name(&btc.node), btc.container_name());
+        assert_eq!(node_volume_name(&btc.node), btc.volume_name());
+        let id = btc.node.id.to_string();
+        assert_eq!(
+            parse_container_name(&node_container_name(&btc.node)),
+            Some((NodeKind::BitcoinCore, id.as_str()))
+        );
+
+        let lnd = LndNode::new("lnd-1", "bitcoin-1");
+        assert_eq!(node_container_name(&lnd.node), lnd.container_name());
+        assert_eq!(node_volume_name(&lnd.node), lnd.volume_name());
+    }
+
+    #[test]
+    fn test_check_supported_image() {
+        assert!(check_supported_image("LND", LndNode::DEFAULT_IMAGE, LND_VERSIONS).is_ok());
+
+        let err = check_supported_image("LND", "polarlightning/lnd:0.1.0-beta", LND_VERSIONS)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("polarlightning/lnd:0.1.0-beta"));
+        assert!(err.contains(LndNode::DEFAULT_IMAGE));
+    }
+
+    #[test]
+    fn test_generator_mode_should_mine() {
+        let hybrid = GeneratorMode::Hybrid {
+            empty_block_ticks: 3,
+        };
+        assert!(GeneratorMode::FixedInterval.should_mine(0, 1));
+        assert!(!GeneratorMode::OnMempool.should_mine(0, 100));
+        assert!(GeneratorMode::OnMempool.should_mine(2, 1));
+        assert!(hybrid.should_mine(1, 1));
+        assert!(!hybrid.should_mine(0, 2));
+        assert!(hybrid.should_mine(0, 3));
+
+        assert!(!GeneratorMode::FixedInterval.needs_mempool());
+        assert_eq!(
+            GeneratorMode::Hybrid {
+                empty_block_ticks: 1
+            }
+            .next(),
+            GeneratorMode::FixedInterval
+        );
+        assert!(
+            check_generator_mode(GeneratorMode::Hybrid {
+                empty_block_ticks: 0
+            })
+            .is_err()
+        );
+        assert!(check_generator_mode(hybrid).is_ok());
+    }
+
+    #[test]
+    fn test_check_env() {
+        let var = |key: &str| vec![(key.to_string(), "debug".to_string())];
+        assert!(check_env(&var("GRPC_VERBOSITY")).is_ok());
+        assert!(check_env(&[]).is_ok());
+        assert!(check_env(&var("")).is_err());
+        assert!(check_env(&var("A=B")).is_err());
+    }
+
+    #[test]
+    fn test_start_plan() {
+        let ports = NodePorts::Lnd {
+            rest: 20010,
+            grpc: 20011,
+            p2p: 20012,
+        };
+        let mut plan = StartPlan {
+            network: "demo".to_string(),
+            already_running: false,
+            docker_network: "polar-1234".to_string(),
+            images: vec![(LndNode::DEFAULT_IMAGE.to_string(), ImageStatus::Local)],
+            containers: vec![ContainerPlan {
+                node: "lnd-1".to_string(),
+                kind: NodeKind::Lnd,
+                container_name: "polar-lnd-1234".to_string(),
+                image: LndNode::DEFAULT_IMAGE.to_string(),
+                ports: port_bindings(&ports),
+            }],
+            port_conflicts: Vec::new(),
+        };
+        assert_eq!(plan.containers[0].ports[1].host, 20011);
+        assert_eq!(plan.containers[0].ports[1].container, 10009);
+        assert!(!plan.has_problems());
+
+        plan.port_conflicts.push(20011);
+        assert!(plan.has_problems());
+        let text = plan.to_string();
+        assert!(text.contains("polar-lnd-1234"));
+        assert!(text.contains("20011 -> 10009 (IN USE)"));
+        assert!(text.contains("20010 -> 8080\n"));
+    }
+
+    #[test]
+    fn test_chain_height_drift() {
+        assert_eq!(chain_height_drift(150, 150), 0);
+        assert_eq!(chain_height_drift(155, 150), 5);
+        assert_eq!(chain_height_drift(149, 150), 0);
+    }
+
+    #[test]
+    fn test_parse_sats() {
+        let wallet = serde_json::json!({
+            "total_balance": "150000",
+            "confirmed_balance": "100000",
+            "unconfirmed_balance": "50000",
+            "locked_balance": "0"
+        });
+        assert_eq!(parse_sats(&wallet["confirmed_balance"]), 100_000);
+        assert_eq!(parse_sats(&wallet["unconfirmed_balance"]), 50_000);
+        assert_eq!(parse_sats(&wallet["locked_balance"]), 0);
+        assert_eq!(parse_sats(&wallet["reserved_balance_anchor_chan"]), 0);
+
+        let channels = serde_json::json!({
+            "balance": "400000",
+            "local_balance": {"sat": "400000", "msat": "400000000"},
+            "remote_balance": {"sat": "600000", "msat": "600000000"},
+            "pending_open_local_balance": {"sat": "0", "msat": "0"}
+        });
+        assert_eq!(parse_sats(&channels["local_balance"]), 400_000);
+        assert_eq!(parse_sats(&channels["remote_balance"]), 600_000);
+        assert_eq!(parse_sats(&channels["pending_open_local_balance"]), 0);
+    }
+
+    #[test]
+    fn test_parse_towers() {
+        let json = serde_json::json!({
+            "towers": [{
+                "pubkey": "03cc",
+                "addresses": ["172.18.0.5:9911"],
+                "active_session_candidate": true,
+                "num_sessions": 2,
+                "sessions": []
+            }]
+        });
+
+        let towers = parse_towers(&json);
+        assert_eq!(towers.len(), 1);
+        assert_eq!(towers[0].pubkey, "03cc");
+        assert_eq!(towers[0].addresses, vec!["172.18.0.5:9911".to_string()]);
+        assert_eq!(towers[0].num_sessions, 2);
+        assert!(parse_towers(&serde_json::Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_parse_payment_history() {
+        let invoices = serde_json::json!({
+            "invoices": [
+                {"r_hash": "aa", "memo": "coffee", "value": "1000", "amt_paid_sat": "1000", "state": "SETTLED", "creation_date": "1700000000"},
+                {"r_hash": "bb", "memo": "", "value": "500", "amt_paid_sat": "0", "state": "OPEN", "creation_date": "1700000100"}
+            ]
+        });
+        let invoices = parse_invoices(&invoices);
+        assert_eq!(invoices.len(), 2);
+        assert_eq!(invoices[0].payment_hash, "bb");
+        assert_eq!(invoices[1].memo, "coffee");
+        assert_eq!(invoices[1].amount_paid_sat, 1000);
+
+        let payments = serde_json::json!({
+            "payments": [{
+                "payment_hash": "cc",
+                "value_sat": "2500",
+                "fee_sat": "3",
+                "status": "SUCCEEDED",
+                "creation_date": "1700000200",
+                "htlcs": [
+                    {"status": "SUCCEEDED"},
+                    {"status": "FAILED"},
+                    {"status": "SUCCEEDED"}
+                ]
+            }]
+        });
+        let payments = parse_payments(&payments);
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].amount_sat, 2500);
+        assert_eq!(payments[0].fee_sat, 3);
+        assert_eq!(payments[0].parts, 2);
+        assert!(parse_payments(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_parse_graph() {
+        let json = serde_json::json!({
+            "nodes": [
+                {"pub_key": "02aa", "alias": "alice"},
+                {"pub_key": "03bb", "alias": "bob"}
+            ],
+            "edges": [{
+                "channel_id": "118747255865345",
+                "node1_pub": "02aa",
+                "node2_pub": "03bb",
+                "capacity": "1000000",
+                "chan_point": "ab12:0",
+                "node1_policy": {
+                    "time_lock_delta": 80,
+                    "fee_base_msat": "5000",
+                    "fee_rate_milli_msat": "250"
+                },
+                "node2_policy": null
+            }]
+        });
+
+        let graph = parse_graph(&json);
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].capacity, 1_000_000);
+        assert_eq!(graph.edges[0].channel_point, "ab12:0");
+        assert_eq!(
+            graph.edges[0].node1_policy,
+            Some(RoutingPolicy {
+                base_fee_msat: 5000,
+                fee_rate_ppm: 250,
+                time_lock_delta: 80,
+            })
+        );
+        assert_eq!(graph.edges[0].node2_policy, None);
+        assert_eq!(graph.alias_for("03bb"), Some("bob"));
+        assert_eq!(graph.alias_for("04cc"), None);
     }
 }
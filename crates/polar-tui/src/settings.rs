@@ -0,0 +1,212 @@
+//! Settings screen form backed by [`Config`].
+
+use std::path::PathBuf;
+
+use polar_core::Config;
+
+use crate::format::DisplayUnit;
+
+/// Field labels in display order; text fields come first.
+const LABELS: [&str; 10] = [
+    "Data dir:",
+    "RPC user:",
+    "RPC password:",
+    "Channel capacity:",
+    "Push amount:",
+    "Payment amount:",
+    "Fund amount (BTC):",
+    "Mine blocks:",
+    "Stop on exit:",
+    "Display unit:",
+];
+
+/// Number of free text fields at the start of [`LABELS`].
+const TEXT_FIELDS: usize = 8;
+
+/// Editable copy of the config shown in the settings screen.
+#[derive(Debug, Clone)]
+pub struct SettingsForm {
+    /// Config the form was loaded from; settings not on the form are kept.
+    base: Config,
+    /// Text field values, indexed like [`LABELS`].
+    values: [String; TEXT_FIELDS],
+    stop_on_exit: bool,
+    display_unit: DisplayUnit,
+    /// Active field, indexes [`LABELS`].
+    pub field: usize,
+    /// Why the last save was rejected.
+    pub error: Option<String>,
+}
+
+impl Default for SettingsForm {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+impl SettingsForm {
+    /// Number of fields on the form.
+    pub const FIELDS: usize = LABELS.len();
+
+    /// Fill the form from `config`.
+    pub fn new(config: Config) -> Self {
+        let values = [
+            config.data_dir.display().to_string(),
+            config.rpc_user.clone(),
+            config.rpc_pass.clone(),
+            config.default_channel_capacity.to_string(),
+            config.default_push_amount.to_string(),
+            config.default_payment_amount.to_string(),
+            // Debug keeps the decimal point on whole amounts, e.g. "1.0"
+            format!("{:?}", config.default_fund_amount),
+            config.default_mine_blocks.to_string(),
+        ];
+        Self {
+            stop_on_exit: config.stop_on_exit,
+            display_unit: DisplayUnit::from_btc(config.display_btc),
+            base: config,
+            values,
+            field: 0,
+            error: None,
+        }
+    }
+
+    /// Label of field `idx`.
+    pub fn label(idx: usize) -> &'static str {
+        LABELS.get(idx).copied().unwrap_or_default()
+    }
+
+    /// Whether field `idx` is typed into rather than toggled.
+    pub const fn is_text(idx: usize) -> bool {
+        idx < TEXT_FIELDS
+    }
+
+    /// Displayed value of field `idx`.
+    pub fn value(&self, idx: usize) -> &str {
+        match idx {
+            8 if self.stop_on_exit => "Stop running networks",
+            8 => "Leave networks running",
+            9 => self.display_unit.label(),
+            _ => self.values.get(idx).map_or("", String::as_str),
+        }
+    }
+
+    /// Move to the next field.
+    pub const fn next_field(&mut self) {
+        self.field = (self.field + 1) % Self::FIELDS;
+    }
+
+    /// Move to the previous field.
+    pub const fn prev_field(&mut self) {
+        self.field = (self.field + Self::FIELDS - 1) % Self::FIELDS;
+    }
+
+    /// Type `c` into the active field, ignoring characters it cannot hold.
+    pub fn push(&mut self, c: char) {
+        let accepted = match self.field {
+            0..=2 => !c.is_control(),
+            6 => c.is_ascii_digit() || c == '.',
+            3..=7 => c.is_ascii_digit(),
+            _ => false,
+        };
+        if accepted {
+            self.values[self.field].push(c);
+        }
+    }
+
+    /// Delete the last character of the active field.
+    pub fn pop(&mut self) {
+        if let Some(value) = self.values.get_mut(self.field) {
+            value.pop();
+        }
+    }
+
+    /// Flip the active option field.
+    pub const fn toggle(&mut self) {
+        match self.field {
+            8 => self.stop_on_exit = !self.stop_on_exit,
+            9 => self.display_unit = self.display_unit.toggle(),
+            _ => {}
+        }
+    }
+
+    /// Build the config the form describes and validate it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first field that does not parse or
+    /// fails [`Config::validate`].
+    pub fn to_config(&self) -> Result<Config, String> {
+        let number = |idx: usize| {
+            self.values[idx].parse::<u64>().map_err(|_| {
+                format!(
+                    "{} must be a whole number",
+                    LABELS[idx].trim_end_matches(':')
+                )
+            })
+        };
+
+        let config = Config {
+            data_dir: PathBuf::from(self.values[0].trim()),
+            rpc_user: self.values[1].clone(),
+            rpc_pass: self.values[2].clone(),
+            default_channel_capacity: number(3)?,
+            default_push_amount: number(4)?,
+            default_payment_amount: number(5)?,
+            default_fund_amount: self.values[6]
+                .parse()
+                .map_err(|_| "Fund amount must be a BTC amount such as 0.5".to_string())?,
+            default_mine_blocks: u32::try_from(number(7)?)
+                .map_err(|_| "Mine blocks is too large".to_string())?,
+            stop_on_exit: self.stop_on_exit,
+            display_btc: self.display_unit == DisplayUnit::Btc,
+            ..self.base.clone()
+        };
+        config.validate().map_err(|e| e.to_string())?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_form_round_trip() {
+        let dir = std::env::temp_dir().join(format!("polar-settings-test-{}", std::process::id()));
+        let mut form = SettingsForm::new(Config {
+            data_dir: dir.clone(),
+            ..Config::default()
+        });
+        assert_eq!(form.value(6), "1.0");
+        assert_eq!(form.value(9), "sats");
+
+        // Capacity only takes digits
+        form.field = 3;
+        form.push('x');
+        form.push('0');
+        assert_eq!(form.value(3), "10000000");
+
+        form.prev_field();
+        form.prev_field();
+        form.prev_field();
+        form.prev_field();
+        form.toggle();
+        assert_eq!(form.field, 9);
+        assert_eq!(form.value(9), "BTC");
+
+        let config = form.to_config().unwrap();
+        assert_eq!(config.default_channel_capacity, 10_000_000);
+        assert!(config.display_btc);
+        assert_eq!(config.data_dir, dir);
+
+        // Empty numbers and invalid configs are reported, not saved
+        form.field = 5;
+        for _ in 0..5 {
+            form.pop();
+        }
+        assert!(form.to_config().unwrap_err().starts_with("Payment amount"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+//! Read-only HTTP server exposing network status as JSON.
+//!
+//! Lets external dashboards watch the networks while the TUI runs:
+//!
+//! - `GET /networks` lists every network with its nodes and status
+//! - `GET /networks/<network>/nodes/<node>/info` returns the node's info
+//!
+//! The server only binds to localhost and never changes any state.
+
+use std::convert::Infallible;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{CONTENT_TYPE, HeaderValue};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use polar_core::{Error, Network};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+use crate::network_manager::NetworkManager;
+
+/// Longest a request waits for the network manager before giving up, since
+/// background operations like starting a network hold it for a while.
+const MANAGER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// HTTP server bound to a local port, ready to serve status requests.
+pub struct StatusServer {
+    listener: TcpListener,
+}
+
+impl StatusServer {
+    /// Bind the server to `port` on localhost; port 0 picks a free one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port is in use or cannot be bound
+    pub async fn bind(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+        Ok(Self { listener })
+    }
+
+    /// Address the server is listening on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket address cannot be read
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serve requests until the task is dropped.
+    pub async fn serve(self, manager: Arc<Mutex<NetworkManager>>) {
+        loop {
+            let stream = match self.listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::debug!("Status server accept failed: {e}");
+                    continue;
+                }
+            };
+
+            let manager = Arc::clone(&manager);
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let manager = Arc::clone(&manager);
+                    async move { Ok::<_, Infallible>(handle(&manager, &request).await) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    tracing::debug!("Status server connection closed: {e}");
+                }
+            });
+        }
+    }
+}
+
+/// Endpoints the server answers.
+#[derive(Debug, PartialEq, Eq)]
+enum Route<'a> {
+    /// `GET /networks`
+    Networks,
+    /// `GET /networks/<network>/nodes/<node>/info`
+    NodeInfo { network: &'a str, node: &'a str },
+}
+
+/// Match a request path to a route.
+fn route(path: &str) -> Option<Route<'_>> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["networks"] => Some(Route::Networks),
+        ["networks", network, "nodes", node, "info"] => Some(Route::NodeInfo { network, node }),
+        _ => None,
+    }
+}
+
+async fn handle(
+    manager: &Mutex<NetworkManager>,
+    request: &Request<Incoming>,
+) -> Response<Full<Bytes>> {
+    if request.method() != Method::GET {
+        return error_response(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported");
+    }
+    let Some(route) = route(request.uri().path()) else {
+        return error_response(StatusCode::NOT_FOUND, "no such endpoint");
+    };
+    let Ok(manager) = tokio::time::timeout(MANAGER_TIMEOUT, manager.lock()).await else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "busy with another operation, try again",
+        );
+    };
+
+    let body = match route {
+        Route::Networks => {
+            let mut networks: Vec<&Network> = manager.networks().values().collect();
+            networks.sort_by(|a, b| a.name.cmp(&b.name));
+            serde_json::to_vec(&networks)
+        }
+        Route::NodeInfo { network, node } => match manager.get_node_info(network, node).await {
+            Ok(info) => serde_json::to_vec(&info),
+            Err(e) => return error_response(error_status(&e), &e.to_string()),
+        },
+    };
+
+    match body {
+        Ok(body) => json_response(StatusCode::OK, body),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+/// HTTP status for a failed info request.
+const fn error_status(error: &Error) -> StatusCode {
+    match error {
+        Error::NetworkNotFound(_) | Error::NodeNotFound(_) => StatusCode::NOT_FOUND,
+        Error::NodeNotRunning(_) => StatusCode::CONFLICT,
+        Error::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::BAD_GATEWAY,
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    let body = serde_json::json!({ "error": message }).to_string();
+    json_response(status, body.into_bytes())
+}
+
+fn json_response(status: StatusCode, body: Vec<u8>) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("/networks"), Some(Route::Networks));
+        assert_eq!(route("/networks/"), Some(Route::Networks));
+        assert_eq!(
+            route("/networks/dev/nodes/lnd-1/info"),
+            Some(Route::NodeInfo {
+                network: "dev",
+                node: "lnd-1"
+            })
+        );
+        assert_eq!(route("/"), None);
+        assert_eq!(route("/networks/dev"), None);
+        assert_eq!(route("/networks/dev/nodes/lnd-1"), None);
+    }
+
+    #[test]
+    fn test_error_status() {
+        assert_eq!(
+            error_status(&Error::NodeNotFound("lnd-9".to_string())),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            error_status(&Error::NodeNotRunning("lnd-1".to_string())),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            error_status(&Error::InvalidArgument("bad invoice".to_string())),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            error_status(&Error::ContainerExec("boom".to_string())),
+            StatusCode::BAD_GATEWAY
+        );
+    }
+}
@@ -1,36 +1,68 @@
 #! Main layout rendering for the TUI.
 
-use polar_core::{BitcoinNodeInfo, LndNodeInfo, NodeInfo};
+use polar_core::{
+    BitcoinNodeInfo, ChannelInfo, ChannelPoint, GraphInfo, LndNodeInfo, NodeInfo, NodeStatus,
+    PaymentHistory, PendingChannelState,
+};
+use polar_nodes::ExportedCredentials;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect, Size},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 
-use crate::app::{ActivePanel, App, UiMode};
+use super::{remote_node_label, spinner_frame, truncate_str, wrapped_rows};
+use crate::app::{ActivePanel, App, CloseMode, UiMode};
+use crate::format::{DisplayUnit, format_btc, format_sats};
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::settings::SettingsForm;
 
 /// Render the entire UI.
 pub fn render(frame: &mut Frame, app: &App) {
     match app.ui_mode {
         UiMode::CreateNetwork => render_create_network(frame, app),
-        UiMode::Main => render_main(frame, app),
+        UiMode::Main | UiMode::Filter => render_main(frame, app),
+        UiMode::Help => render_help(frame, app),
+        UiMode::Settings => render_settings(frame, app),
+        UiMode::DockerUnavailable => render_docker_unavailable(frame, app),
         UiMode::NodeDetails => render_node_details(frame, app),
         UiMode::MineBlocks => render_mine_blocks(frame, app),
         UiMode::FundWallet => render_fund_wallet(frame, app),
+        UiMode::FundAllNodes => render_fund_all_nodes(frame, app),
+        UiMode::RenameNode => render_rename_node(frame, app),
+        UiMode::SignMessage => render_sign_message(frame, app),
         UiMode::OpenChannel => render_open_channel(frame, app),
         UiMode::CloseChannel => render_close_channel(frame, app),
         UiMode::SendPayment => render_send_payment(frame, app),
+        UiMode::GraphView => render_graph_view(frame, app),
+        UiMode::NetworkSummary => render_network_summary(frame, app),
+        UiMode::PaymentHistory => render_payment_history(frame, app),
+        UiMode::ConfirmDelete => render_confirm(frame, app),
+        UiMode::ConfirmInvoice => render_confirm_invoice(frame, app),
+        UiMode::ConfirmPayment => render_confirm_payment(frame, app),
+        UiMode::ConfirmQuit => render_confirm_quit(frame, app),
     }
 }
 
 /// Render the main application view.
 fn render_main(frame: &mut Frame, app: &App) {
+    let [networks, nodes, logs, status_bar] = main_layout(frame.area());
+
+    render_networks_panel(frame, app, networks);
+    render_nodes_panel(frame, app, nodes);
+    render_logs_panel(frame, app, logs);
+    render_status_bar(frame, app, status_bar);
+}
+
+/// Split the main view into the networks, nodes and logs panels and the
+/// status bar.
+fn main_layout(area: Rect) -> [Rect; 4] {
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(3), Constraint::Length(3)])
-        .split(frame.area());
+        .split(area);
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -41,10 +73,27 @@ fn render_main(frame: &mut Frame, app: &App) {
         ])
         .split(main_chunks[0]);
 
-    render_networks_panel(frame, app, chunks[0]);
-    render_nodes_panel(frame, app, chunks[1]);
-    render_logs_panel(frame, app, chunks[2]);
-    render_status_bar(frame, app, main_chunks[1]);
+    [chunks[0], chunks[1], chunks[2], main_chunks[1]]
+}
+
+/// Size of the text inside the Logs panel of the main view, for a terminal
+/// of the given size.
+pub fn logs_viewport(area: Rect) -> Size {
+    let [_, _, logs, _] = main_layout(area);
+    Size::new(logs.width.saturating_sub(2), logs.height.saturating_sub(2))
+}
+
+/// Largest scroll position of the Logs panel, which shows the last
+/// screenful of lines there.
+pub fn log_max_scroll(app: &App, viewport: Size) -> usize {
+    let rows = wrapped_rows(
+        app.logs
+            .iter()
+            .filter(|entry| entry.level >= app.log_min_level)
+            .map(|entry| log_line(entry).width()),
+        usize::from(viewport.width),
+    );
+    rows.saturating_sub(usize::from(viewport.height))
 }
 
 /// Render the create network dialog.
@@ -71,6 +120,7 @@ fn render_create_network(frame: &mut Frame, app: &App) {
             Constraint::Length(3), // LND count
             Constraint::Length(3), // LND version
             Constraint::Length(3), // Bitcoin version
+            Constraint::Length(3), // Topology
             Constraint::Min(1),    // Help text
         ])
         .split(area);
@@ -186,6 +236,20 @@ fn render_create_network(frame: &mut Frame, app: &App) {
     ]);
     frame.render_widget(Paragraph::new(btc_version_text), chunks[5]);
 
+    // Topology field (field 5)
+    let (topology, topology_hint) = app.create_topology().map_or_else(
+        || ("None".to_string(), "  (no channels)"),
+        |t| (t.to_string(), "  (funded and opened on first start)"),
+    );
+    let topology_text = Line::from(vec![
+        Span::styled("Topology: ", field_style(5)),
+        Span::styled("< ", Style::default().fg(Color::DarkGray)),
+        Span::styled(topology, field_style(5)),
+        Span::styled(" >", Style::default().fg(Color::DarkGray)),
+        Span::styled(topology_hint, Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(topology_text), chunks[6]);
+
     // Help text - all shortcuts on the same line
     let help = vec![
         Line::from(""),
@@ -209,7 +273,7 @@ fn render_create_network(frame: &mut Frame, app: &App) {
                 .add_modifier(Modifier::ITALIC),
         )),
     ];
-    frame.render_widget(Paragraph::new(help).wrap(Wrap { trim: false }), chunks[6]);
+    frame.render_widget(Paragraph::new(help).wrap(Wrap { trim: false }), chunks[7]);
 }
 
 /// Render the networks panel (left).
@@ -235,7 +299,12 @@ fn render_networks_panel(frame: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items).block(
         Block::default()
-            .title(" Networks ")
+            .title(panel_title(
+                "Networks",
+                ActivePanel::Networks,
+                &app.network_filter,
+                app,
+            ))
             .borders(Borders::ALL)
             .border_style(style),
     );
@@ -252,13 +321,21 @@ fn render_nodes_panel(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .enumerate()
         .map(|(i, node)| {
+            let name = node.split(" (").next().unwrap_or(node);
+            let status = app
+                .node_statuses
+                .get(name)
+                .copied()
+                .unwrap_or(NodeStatus::Unknown);
+            let marker = Span::styled("● ", Style::default().fg(node_status_color(status)));
             let content = if Some(i) == app.selected_node {
                 Line::from(vec![
                     Span::raw("> "),
+                    marker,
                     Span::styled(node, Style::default().add_modifier(Modifier::BOLD)),
                 ])
             } else {
-                Line::from(format!("  {node}"))
+                Line::from(vec![Span::raw("  "), marker, Span::raw(node)])
             };
             ListItem::new(content)
         })
@@ -266,7 +343,12 @@ fn render_nodes_panel(frame: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items).block(
         Block::default()
-            .title(" Nodes ")
+            .title(panel_title(
+                "Nodes",
+                ActivePanel::Nodes,
+                &app.node_filter,
+                app,
+            ))
             .borders(Borders::ALL)
             .border_style(style),
     );
@@ -274,24 +356,94 @@ fn render_nodes_panel(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(list, area);
 }
 
+/// Build a list panel title, showing its filter query when one is set or being edited.
+fn panel_title(name: &str, panel: ActivePanel, filter: &str, app: &App) -> String {
+    let editing = app.ui_mode == UiMode::Filter && app.active_panel == panel;
+    if editing {
+        format!(" {name} /{filter}█ ")
+    } else if filter.is_empty() {
+        format!(" {name} ")
+    } else {
+        format!(" {name} /{filter} ")
+    }
+}
+
+/// Color used for a node's container status marker.
+const fn node_status_color(status: NodeStatus) -> Color {
+    match status {
+        NodeStatus::Running => Color::Green,
+        NodeStatus::Exited(_) => Color::Red,
+        NodeStatus::Restarting => Color::Yellow,
+        NodeStatus::NotStarted | NodeStatus::Unknown => Color::DarkGray,
+    }
+}
+
 /// Render the logs panel (right).
 fn render_logs_panel(frame: &mut Frame, app: &App, area: Rect) {
     let style = panel_style(app.active_panel == ActivePanel::Logs);
 
-    let text: Vec<Line> = app.logs.iter().map(|l| Line::from(l.as_str())).collect();
+    let text: Vec<Line> = app
+        .logs
+        .iter()
+        .filter(|entry| entry.level >= app.log_min_level)
+        .map(log_line)
+        .collect();
+
+    let mut title = String::from(" Logs ");
+    if app.log_min_level > LogLevel::Info {
+        title.push_str("(warnings and errors) ");
+    }
+    if !app.log_follow {
+        title.push_str("[paused] ");
+    }
+
+    // Clamp scrolling to the last screenful, following new lines unless
+    // the user has scrolled up
+    let max_scroll = log_max_scroll(
+        app,
+        Size::new(area.width.saturating_sub(2), area.height.saturating_sub(2)),
+    );
+    let scroll = if app.log_follow {
+        max_scroll
+    } else {
+        app.log_scroll.min(max_scroll)
+    };
 
     let paragraph = Paragraph::new(text)
         .block(
             Block::default()
-                .title(" Logs ")
+                .title(title)
                 .borders(Borders::ALL)
                 .border_style(style),
         )
-        .wrap(ratatui::widgets::Wrap { trim: false });
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
 
     frame.render_widget(paragraph, area);
 }
 
+/// Style a parsed log line by its level.
+fn log_line(entry: &LogEntry) -> Line<'_> {
+    let style = match entry.level {
+        LogLevel::Error => Style::default().fg(Color::Red),
+        LogLevel::Warn => Style::default().fg(Color::Yellow),
+        LogLevel::Info => Style::default(),
+        LogLevel::Debug | LogLevel::Trace => Style::default().fg(Color::DarkGray),
+    };
+
+    let Some(timestamp) = &entry.timestamp else {
+        return Line::from(Span::styled(entry.message.as_str(), style));
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{timestamp} "),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(format!("{} ", entry.level.label()), style),
+        Span::styled(entry.message.as_str(), style),
+    ])
+}
+
 /// Render the status bar (bottom).
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let help_text = vec![Line::from(vec![
@@ -304,32 +456,60 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::raw(": Start | "),
         Span::styled("x", Style::default().fg(Color::Red)),
         Span::raw(": Stop | "),
-        Span::styled("d", Style::default().fg(Color::Red)),
-        Span::raw(": Del Net | "),
-        Span::styled("r", Style::default().fg(Color::Red)),
-        Span::raw(": Del Node | "),
         Span::styled("i", Style::default().fg(Color::Magenta)),
         Span::raw(": Info | "),
-        Span::styled("m", Style::default().fg(Color::Yellow)),
-        Span::raw(": Mine | "),
-        Span::styled("f", Style::default().fg(Color::Yellow)),
-        Span::raw(": Fund | "),
-        Span::styled("c", Style::default().fg(Color::Yellow)),
-        Span::raw(": Open | "),
-        Span::styled("l", Style::default().fg(Color::Red)),
-        Span::raw(": Close | "),
-        Span::styled("p", Style::default().fg(Color::Yellow)),
-        Span::raw(": Payment | "),
-        Span::styled("g", Style::default().fg(Color::Cyan)),
-        Span::raw(": Graph | "),
-        Span::styled("y", Style::default().fg(Color::Cyan)),
-        Span::raw(": Chain | "),
+        Span::styled("/", Style::default().fg(Color::Cyan)),
+        Span::raw(": Filter | "),
+        Span::styled("?", Style::default().fg(Color::Yellow)),
+        Span::raw(": Help | "),
         Span::raw("q: Quit"),
     ])];
 
     let mut status_lines = help_text;
 
-    if let Some(ref msg) = app.status_message {
+    // Only known while the selected network runs
+    if let Some((network_name, height)) = &app.block_height {
+        status_lines[0].spans.splice(
+            0..0,
+            [
+                Span::styled(
+                    format!("⛓ {network_name}: block {height}"),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" | "),
+            ],
+        );
+    }
+
+    if let Some(progress) = &app.progress {
+        let mut spans = vec![
+            Span::styled(
+                format!("{} ", spinner_frame(progress.started.elapsed())),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                progress.label.as_str(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ];
+        if let Some(detail) = &progress.detail {
+            spans.push(Span::styled(
+                format!(" {detail}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if app.quit_requested {
+            spans.push(Span::styled(
+                " (quitting when done)",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        status_lines.push(Line::from(spans));
+    } else if let Some(ref msg) = app.status_message {
         // Determine if this is an error message
         let is_error = msg.contains("Failed") || msg.contains("Error") || msg.contains("error");
 
@@ -405,10 +585,21 @@ fn render_node_details(frame: &mut Frame, app: &App) {
 
         match node_info {
             NodeInfo::Bitcoin(info) => {
-                lines.extend(render_bitcoin_info(info));
+                lines.extend(render_bitcoin_info(
+                    info,
+                    app.selected_endpoint_idx,
+                    app.display_unit,
+                ));
             }
             NodeInfo::Lnd(info) => {
-                lines.extend(render_lnd_info(info, app.selected_channel_idx));
+                lines.extend(render_lnd_info(
+                    info,
+                    app.selected_channel_idx,
+                    app.selected_endpoint_idx,
+                    app.display_unit,
+                ));
+                lines.extend(render_credentials(app.node_credentials.as_ref()));
+                lines.extend(render_lndconnect(app.lndconnect_url.as_deref()));
             }
         }
 
@@ -417,8 +608,23 @@ fn render_node_details(frame: &mut Frame, app: &App) {
         let mut help_spans = vec![
             Span::styled("↑↓/j/k", Style::default().fg(Color::Cyan)),
             Span::raw(": Scroll  |  "),
+            Span::styled("u", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(": Show {}  |  ", app.display_unit.toggle().label())),
+            Span::styled("r", Style::default().fg(Color::Cyan)),
+            Span::raw(": Refresh  |  "),
+            Span::styled("Tab/y", Style::default().fg(Color::Cyan)),
+            Span::raw(": Select/Copy Endpoint  |  "),
         ];
 
+        if let NodeInfo::Lnd(_) = node_info {
+            help_spans.extend(vec![
+                Span::styled("E", Style::default().fg(Color::Cyan)),
+                Span::raw(": Export Credentials  |  "),
+                Span::styled("Q", Style::default().fg(Color::Cyan)),
+                Span::raw(": lndconnect  |  "),
+            ]);
+        }
+
         // Add channel navigation help if there are channels
         if let NodeInfo::Lnd(info) = node_info {
             if !info.channels.is_empty() {
@@ -460,9 +666,363 @@ fn render_node_details(frame: &mut Frame, app: &App) {
     }
 }
 
+/// Render the channel graph view.
+fn render_graph_view(frame: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, frame.area());
+
+    // Clear the background
+    frame.render_widget(
+        Block::default().style(Style::default().bg(Color::Black)),
+        frame.area(),
+    );
+
+    let mut lines = app.graph_info.as_ref().map_or_else(
+        || vec![Line::from("No graph information available")],
+        render_graph_lines,
+    );
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("↑↓/j/k", Style::default().fg(Color::Cyan)),
+        Span::raw(": Scroll  |  "),
+        Span::styled("Esc/q", Style::default().fg(Color::Red)),
+        Span::raw(": Back"),
+    ]));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Channel Graph ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((u16::try_from(app.graph_scroll).unwrap_or(u16::MAX), 0));
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the liquidity totals of the selected network.
+fn render_network_summary(frame: &mut Frame, app: &App) {
+    render_main(frame, app);
+
+    let area = centered_rect(60, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let network_name = app
+        .selected_network
+        .and_then(|idx| app.networks.get(idx))
+        .map_or("", String::as_str);
+
+    let mut lines = vec![Line::from("")];
+    match &app.network_summary {
+        Some(summary) => {
+            let mut nodes = vec![
+                Span::styled(
+                    format!("{:<16}", "LND nodes"),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(summary.nodes.to_string()),
+            ];
+            if summary.unreachable > 0 {
+                nodes.push(Span::styled(
+                    format!(" ({} unreachable, not counted)", summary.unreachable),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            lines.push(Line::from(nodes));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<16}", "Active channels"),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(summary.active_channels.to_string()),
+            ]));
+            lines.push(Line::from(""));
+            lines.push(balance_line(
+                "On-chain",
+                summary.onchain,
+                "confirmed",
+                app.display_unit,
+            ));
+            lines.push(balance_line(
+                "Capacity",
+                summary.capacity,
+                "in channels",
+                app.display_unit,
+            ));
+            lines.push(balance_line(
+                "Local",
+                summary.local,
+                "outbound",
+                app.display_unit,
+            ));
+            lines.push(balance_line(
+                "Remote",
+                summary.remote,
+                "inbound",
+                app.display_unit,
+            ));
+        }
+        None => lines.push(Line::from(Span::styled(
+            "Network is not running",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Esc/q", Style::default().fg(Color::Red)),
+        Span::raw(": Back"),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" Network Summary: {network_name} "))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the channel graph as an adjacency list.
+/// Render the invoice and payment history of an LND node.
+fn render_payment_history(frame: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, frame.area());
+
+    // Clear the background
+    frame.render_widget(
+        Block::default().style(Style::default().bg(Color::Black)),
+        frame.area(),
+    );
+
+    let mut lines = app.payment_history.as_ref().map_or_else(
+        || vec![Line::from("No payment history available")],
+        render_payment_history_lines,
+    );
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("↑↓/j/k", Style::default().fg(Color::Cyan)),
+        Span::raw(": Scroll  |  "),
+        Span::styled("Esc/q", Style::default().fg(Color::Red)),
+        Span::raw(": Back"),
+    ]));
+
+    let title = app.payment_history.as_ref().map_or_else(
+        || " Payment History ".to_string(),
+        |history| format!(" Payment History: {} ", history.node),
+    );
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((
+            u16::try_from(app.payment_history_scroll).unwrap_or(u16::MAX),
+            0,
+        ));
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_payment_history_lines(history: &PaymentHistory) -> Vec<Line<'static>> {
+    use polar_nodes::LndNode;
+
+    let section = |title: String| {
+        Line::from(vec![Span::styled(
+            title,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )])
+    };
+    let limit = usize::try_from(LndNode::HISTORY_LIMIT).unwrap_or(usize::MAX);
+    let capped = |count: usize| {
+        if count >= limit {
+            format!(" (latest {limit})")
+        } else {
+            String::new()
+        }
+    };
+
+    let mut lines = vec![
+        section(format!(
+            "Invoices: {}{}",
+            history.invoices.len(),
+            capped(history.invoices.len())
+        )),
+        Line::from(""),
+    ];
+
+    if history.invoices.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No invoices yet",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for invoice in &history.invoices {
+        let state_color = match invoice.state.as_str() {
+            "SETTLED" => Color::Green,
+            "OPEN" | "ACCEPTED" => Color::Yellow,
+            _ => Color::Red,
+        };
+        let memo = if invoice.memo.is_empty() {
+            "(no memo)".to_string()
+        } else {
+            invoice.memo.clone()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<10}", invoice.state),
+                Style::default().fg(state_color),
+            ),
+            Span::raw(format!("{:>12} sats  ", invoice.amount_sat)),
+            Span::styled(memo, Style::default().fg(Color::Cyan)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(section(format!(
+        "Payments: {}{}",
+        history.payments.len(),
+        capped(history.payments.len())
+    )));
+    lines.push(Line::from(""));
+
+    if history.payments.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No payments yet",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for payment in &history.payments {
+        let status_color = match payment.status.as_str() {
+            "SUCCEEDED" => Color::Green,
+            "IN_FLIGHT" | "INITIATED" => Color::Yellow,
+            _ => Color::Red,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<10}", payment.status),
+                Style::default().fg(status_color),
+            ),
+            Span::raw(format!(
+                "{:>12} sats  fee {} sats  ",
+                payment.amount_sat, payment.fee_sat
+            )),
+            Span::styled(
+                format!("{}...", truncate_str(&payment.payment_hash, 16)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    lines
+}
+
+fn render_graph_lines(graph: &GraphInfo) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!(
+                "{} nodes, {} channels",
+                graph.nodes.len(),
+                graph.edges.len()
+            ),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    if graph.edges.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No channels in the graph yet. Press 'g' in the main view to sync the graph,",
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(Span::styled(
+            "and make sure channels have at least 6 confirmations to be announced.",
+            Style::default().fg(Color::DarkGray),
+        )));
+        return lines;
+    }
+
+    let display_name = |pubkey: &str| {
+        graph.alias_for(pubkey).map_or_else(
+            || pubkey.chars().take(16).collect::<String>(),
+            ToString::to_string,
+        )
+    };
+
+    for node in &graph.nodes {
+        let peers: Vec<_> = graph
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                // Each side's own policy is what it charges to forward
+                if edge.node1_pubkey == node.pubkey {
+                    Some((&edge.node2_pubkey, edge.capacity, edge.node1_policy))
+                } else if edge.node2_pubkey == node.pubkey {
+                    Some((&edge.node1_pubkey, edge.capacity, edge.node2_policy))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        lines.push(Line::from(vec![Span::styled(
+            display_name(&node.pubkey),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]));
+
+        if peers.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  (no channels)",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        for (peer, capacity, policy) in peers {
+            let mut spans = vec![
+                Span::raw("  ── "),
+                Span::raw(display_name(peer)),
+                Span::styled(
+                    format!("  ({capacity} sats)"),
+                    Style::default().fg(Color::Green),
+                ),
+            ];
+            if let Some(policy) = policy {
+                spans.push(Span::styled(
+                    format!(
+                        "  fee {} msat + {} ppm, delta {}",
+                        policy.base_fee_msat, policy.fee_rate_ppm, policy.time_lock_delta
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines
+}
+
 /// Render Bitcoin Core node information.
-fn render_bitcoin_info(info: &BitcoinNodeInfo) -> Vec<Line<'static>> {
-    vec![
+fn render_bitcoin_info(
+    info: &BitcoinNodeInfo,
+    selected_endpoint_idx: usize,
+    unit: DisplayUnit,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![
         Line::from(vec![Span::styled(
             "Bitcoin Core Node",
             Style::default()
@@ -504,7 +1064,7 @@ fn render_bitcoin_info(info: &BitcoinNodeInfo) -> Vec<Line<'static>> {
         Line::from(vec![
             Span::styled("Wallet Balance: ", Style::default().fg(Color::Cyan)),
             Span::styled(
-                format!("{:.8} BTC", info.balance),
+                format_btc(info.balance, unit),
                 Style::default().fg(Color::Green),
             ),
         ]),
@@ -516,55 +1076,273 @@ fn render_bitcoin_info(info: &BitcoinNodeInfo) -> Vec<Line<'static>> {
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("RPC:            ", Style::default().fg(Color::Cyan)),
-            Span::raw(info.rpc_host.clone()),
-        ]),
-        Line::from(vec![
-            Span::styled("P2P:            ", Style::default().fg(Color::Cyan)),
-            Span::raw(info.p2p_host.clone()),
-        ]),
-    ]
+        endpoint_line("RPC:", &info.rpc_host, selected_endpoint_idx == 0),
+        endpoint_line("P2P:", &info.p2p_host, selected_endpoint_idx == 1),
+    ];
+
+    // A single tip is the normal case; more than one means a fork was seen
+    if info.chain_tips.len() > 1 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Chain Tips",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        for tip in &info.chain_tips {
+            let status_color = match tip.status.as_str() {
+                "active" => Color::Green,
+                "valid-fork" | "valid-headers" => Color::Yellow,
+                _ => Color::Red,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<14}", tip.status),
+                    Style::default().fg(status_color),
+                ),
+                Span::raw(format!(
+                    "height {} (branch {}) {}...",
+                    tip.height,
+                    tip.branch_len,
+                    truncate_str(&tip.hash, 16)
+                )),
+            ]));
+        }
+    }
+
+    lines.extend(mempool_lines(info, unit));
+    lines.extend(peer_lines(info));
+
+    lines
 }
 
-/// Render LND node information.
-fn render_lnd_info(info: &LndNodeInfo, selected_channel_idx: Option<usize>) -> Vec<Line<'static>> {
+/// Mempool section of the Bitcoin node details.
+fn mempool_lines(info: &BitcoinNodeInfo, unit: DisplayUnit) -> Vec<Line<'static>> {
     let mut lines = vec![
+        Line::from(""),
         Line::from(vec![Span::styled(
-            "LND Node",
+            format!("Mempool ({} txs)", info.mempool_size),
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Alias:          ", Style::default().fg(Color::Cyan)),
-            Span::raw(info.alias.clone()),
-        ]),
-        Line::from(vec![
-            Span::styled("Version:        ", Style::default().fg(Color::Cyan)),
-            Span::raw(info.version.clone()),
-        ]),
-        Line::from(vec![
-            Span::styled("Identity:       ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{}...", &info.identity_pubkey[..20])),
-        ]),
+    ];
+    if info.mempool.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No unconfirmed transactions",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for entry in info.mempool.iter().take(MEMPOOL_ROWS) {
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {}... ", truncate_str(&entry.txid, 16))),
+            Span::styled(
+                format_sats(i64::try_from(entry.fee_sat).unwrap_or(i64::MAX), unit),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(format!(
+                " ({:.1} sat/vB, {} vB)",
+                entry.fee_rate(),
+                entry.vsize
+            )),
+        ]));
+    }
+    if info.mempool.len() > MEMPOOL_ROWS {
+        lines.push(Line::from(Span::styled(
+            format!("  ... and {} more", info.mempool.len() - MEMPOOL_ROWS),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines
+}
+
+/// Peers section of the Bitcoin node details; empty for a lone node.
+fn peer_lines(info: &BitcoinNodeInfo) -> Vec<Line<'static>> {
+    if info.peers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
-            "Sync Status",
+            "Peers",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Chain Synced:   ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                if info.synced_to_chain { "Yes" } else { "No" },
-                if info.synced_to_chain {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::Yellow)
+    ];
+    for peer in &info.peers {
+        let (direction, color) = if peer.inbound {
+            ("in ", Color::Magenta)
+        } else {
+            ("out", Color::Cyan)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {direction} "), Style::default().fg(color)),
+            Span::raw(format!(
+                "{:<22} {} headers {} blocks {}",
+                peer.addr,
+                peer.subver,
+                synced_height(peer.synced_headers),
+                synced_height(peer.synced_blocks)
+            )),
+        ]));
+    }
+    lines
+}
+
+/// Most mempool transactions listed in the Bitcoin node details.
+const MEMPOOL_ROWS: usize = 10;
+
+/// Show a synced peer height, which bitcoind reports as -1 until known.
+fn synced_height(height: i64) -> String {
+    if height < 0 {
+        "?".to_string()
+    } else {
+        height.to_string()
+    }
+}
+
+/// Render LND node information.
+/// Blocks an LND node may trail the chain by before it is flagged.
+const CHAIN_DRIFT_WARNING_BLOCKS: u64 = 1;
+
+/// Show how far an LND node trails the Bitcoin node, if known.
+fn chain_drift_span(drift: Option<u64>) -> Span<'static> {
+    match drift {
+        None => Span::raw(""),
+        Some(0) => Span::styled(" (in sync)", Style::default().fg(Color::Green)),
+        Some(blocks) => Span::styled(
+            format!(" ({blocks} behind)"),
+            if blocks > CHAIN_DRIFT_WARNING_BLOCKS {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::Yellow)
+            },
+        ),
+    }
+}
+
+/// One amount in the Balances section, labelled with what kind it is.
+///
+/// An empty `label` continues the group above it.
+fn balance_line(label: &str, sats: i64, kind: &str, unit: DisplayUnit) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{label:<16}"), Style::default().fg(Color::Cyan)),
+        Span::styled(format_sats(sats, unit), Style::default().fg(Color::Green)),
+        Span::styled(format!(" {kind}"), Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+/// Credentials section of an LND node, listing exported file paths.
+fn render_credentials(exported: Option<&ExportedCredentials>) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Credentials",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    match exported {
+        Some(exported) => {
+            for (label, path) in [
+                ("TLS Cert:", &exported.tls_cert),
+                ("Macaroon:", &exported.admin_macaroon),
+            ] {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{label:<16}"), Style::default().fg(Color::Cyan)),
+                    Span::raw(path.display().to_string()),
+                ]));
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            "Press E to export the TLS cert and admin macaroon to the data dir",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+    lines
+}
+
+/// lndconnect section of an LND node, with the URL as a QR code when it fits.
+fn render_lndconnect(url: Option<&str>) -> Vec<Line<'static>> {
+    let Some(url) = url else {
+        return Vec::new();
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "lndconnect",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(url.to_string()),
+    ];
+    if let Some(qr) = super::qr_lines(url) {
+        lines.push(Line::from(""));
+        lines.extend(qr.into_iter().map(Line::from));
+    }
+    lines
+}
+
+/// One host endpoint, highlighted when it is the one `y` copies.
+fn endpoint_line(label: &str, host: &str, selected: bool) -> Line<'static> {
+    let host_style = if selected {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    Line::from(vec![
+        Span::styled(format!("{label:<16}"), Style::default().fg(Color::Cyan)),
+        Span::styled(host.to_string(), host_style),
+    ])
+}
+
+fn render_lnd_info(
+    info: &LndNodeInfo,
+    selected_channel_idx: Option<usize>,
+    selected_endpoint_idx: usize,
+    unit: DisplayUnit,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        section_title("LND Node"),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Alias:          ", Style::default().fg(Color::Cyan)),
+            Span::raw(info.alias.clone()),
+        ]),
+        Line::from(vec![
+            Span::styled("Version:        ", Style::default().fg(Color::Cyan)),
+            Span::raw(info.version.clone()),
+        ]),
+        Line::from(vec![
+            Span::styled("Identity:       ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{}...", truncate_str(&info.identity_pubkey, 20))),
+        ]),
+        Line::from(""),
+        section_title("Sync Status"),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Chain Synced:   ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                if info.synced_to_chain { "Yes" } else { "No" },
+                if info.synced_to_chain {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Yellow)
                 },
             ),
         ]),
@@ -582,18 +1360,14 @@ fn render_lnd_info(info: &LndNodeInfo, selected_channel_idx: Option<usize>) -> V
         Line::from(vec![
             Span::styled("Block Height:   ", Style::default().fg(Color::Cyan)),
             Span::raw(info.block_height.to_string()),
+            chain_drift_span(info.chain_height_drift),
         ]),
         Line::from(vec![
             Span::styled("Block Hash:     ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{}...", &info.block_hash[..20])),
+            Span::raw(format!("{}...", truncate_str(&info.block_hash, 20))),
         ]),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Network",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
+        section_title("Network"),
         Line::from(""),
         Line::from(vec![
             Span::styled("Peers:          ", Style::default().fg(Color::Cyan)),
@@ -608,205 +1382,786 @@ fn render_lnd_info(info: &LndNodeInfo, selected_channel_idx: Option<usize>) -> V
             Span::raw(info.num_pending_channels.to_string()),
         ]),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Balances",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
+        section_title("Balances"),
+        Line::from(""),
+        balance_line("Wallet:", info.wallet_confirmed, "confirmed", unit),
+        balance_line("", info.wallet_unconfirmed, "unconfirmed", unit),
+        balance_line("", info.wallet_locked, "locked", unit),
+        balance_line("Channels:", info.channel_local, "local", unit),
+        balance_line("", info.channel_remote, "remote", unit),
+        balance_line("", info.channel_pending, "pending open", unit),
+        Line::from(""),
+        section_title("Endpoints"),
+        Line::from(""),
+        endpoint_line("REST API:", &info.rest_host, selected_endpoint_idx == 0),
+        endpoint_line("gRPC:", &info.grpc_host, selected_endpoint_idx == 1),
+    ];
+
+    lines.extend(lnd_channel_lines(info, selected_channel_idx, unit));
+    lines.extend(pending_channel_lines(info, unit));
+    lines.extend(lnd_peer_lines(info, unit));
+    lines.extend(tower_lines(info));
+
+    lines
+}
+
+/// Open channels section of the LND node details, highlighting the
+/// selected channel.
+fn lnd_channel_lines(
+    info: &LndNodeInfo,
+    selected_channel_idx: Option<usize>,
+    unit: DisplayUnit,
+) -> Vec<Line<'static>> {
+    if info.channels.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![Line::from(""), section_title("Channels"), Line::from("")];
+    for (idx, channel) in info.channels.iter().enumerate() {
+        lines.extend(channel_lines(
+            idx,
+            channel,
+            selected_channel_idx == Some(idx),
+            unit,
+        ));
+    }
+    lines
+}
+
+/// One open channel in the LND node details.
+fn channel_lines(
+    idx: usize,
+    channel: &ChannelInfo,
+    is_selected: bool,
+    unit: DisplayUnit,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![channel_title_line(idx, channel, is_selected)];
+
+    // Show full channel point for selected channel, abbreviated for others
+    let chan_point = &channel.channel_point;
+    let chan_point_display = if is_selected {
+        // Show full channel point when selected for easy manual copying
+        chan_point.clone()
+    } else if chan_point.len() > 40 {
+        // Abbreviate non-selected channels
+        format!(
+            "{}...:{}",
+            truncate_str(chan_point, 37),
+            chan_point.rsplit(':').next().unwrap_or("")
+        )
+    } else {
+        chan_point.clone()
+    };
+
+    let field_style = if is_selected {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+
+    let value_style = if is_selected {
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled("  Peer:         ", field_style),
+        Span::styled(
+            remote_node_label(channel.remote_name.as_deref(), &channel.remote_pubkey),
+            value_style,
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Point:        ", field_style),
+        Span::styled(chan_point_display, value_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Chan ID:      ", field_style),
+        Span::styled(channel.chan_id.clone(), value_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Capacity:     ", field_style),
+        Span::styled(format_sats(channel.capacity, unit), value_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Local:        ", field_style),
+        Span::styled(
+            format_sats(channel.local_balance, unit),
+            if is_selected {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Green)
+            },
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Remote:       ", field_style),
+        Span::styled(format_sats(channel.remote_balance, unit), value_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Close:        ", field_style),
+        if channel.peer_online {
+            Span::styled(
+                "cooperative close available",
+                Style::default().fg(Color::Green),
+            )
+        } else {
+            Span::styled(
+                "peer offline - force close only",
+                Style::default().fg(Color::Red),
+            )
+        },
+    ]));
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Title of one open channel, with its status and visibility.
+fn channel_title_line(idx: usize, channel: &ChannelInfo, is_selected: bool) -> Line<'static> {
+    let status_color = if channel.active {
+        Color::Green
+    } else {
+        Color::Red
+    };
+    let status = if channel.active { "Active" } else { "Inactive" };
+
+    // Highlight selected channel
+    let title_prefix = if is_selected { "► " } else { "  " };
+    let title_style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    } else {
+        Style::default()
+            .fg(status_color)
+            .add_modifier(Modifier::BOLD)
+    };
+
+    let (visibility, visibility_color) = if channel.private {
+        ("Private", Color::Magenta)
+    } else {
+        ("Public", Color::DarkGray)
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{}Channel {} ({})", title_prefix, idx + 1, status),
+            title_style,
+        ),
+        Span::styled(
+            format!(" [{visibility}]"),
+            Style::default().fg(visibility_color),
+        ),
+    ])
+}
+
+/// Bold yellow section title in the node details.
+fn section_title(title: &str) -> Line<'static> {
+    Line::from(vec![Span::styled(
+        title.to_string(),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )])
+}
+
+/// Pending channels section of the LND node details, so stuck opens and
+/// closes remain visible.
+fn pending_channel_lines(info: &LndNodeInfo, unit: DisplayUnit) -> Vec<Line<'static>> {
+    if info.pending_channels.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![
+        Line::from(""),
+        section_title("Pending Channels"),
+        Line::from(""),
+    ];
+    for (idx, channel) in info.pending_channels.iter().enumerate() {
+        let state_color = match channel.state {
+            PendingChannelState::Opening => Color::Cyan,
+            PendingChannelState::Closing | PendingChannelState::WaitingClose => Color::Yellow,
+            PendingChannelState::ForceClosing => Color::Red,
+        };
+
+        lines.push(Line::from(vec![Span::styled(
+            format!("  Pending {} ({})", idx + 1, channel.state),
+            Style::default()
+                .fg(state_color)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(vec![
+            Span::styled("  Peer:         ", Style::default().fg(Color::Cyan)),
+            Span::raw(remote_node_label(
+                channel.remote_name.as_deref(),
+                &channel.remote_pubkey,
+            )),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Point:        ", Style::default().fg(Color::Cyan)),
+            Span::raw(channel.channel_point.clone()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Capacity:     ", Style::default().fg(Color::Cyan)),
+            Span::raw(format_sats(channel.capacity, unit)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Local:        ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format_sats(channel.local_balance, unit),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+        if let Some(blocks) = channel.blocks_til_maturity {
+            lines.push(Line::from(vec![
+                Span::styled("  Maturity:     ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{blocks} blocks")),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// Peers section of the LND node details, so dropped connections are easy
+/// to spot.
+fn lnd_peer_lines(info: &LndNodeInfo, unit: DisplayUnit) -> Vec<Line<'static>> {
+    if info.peers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![Line::from(""), section_title("Peers"), Line::from("")];
+    for peer in &info.peers {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  {}",
+                remote_node_label(peer.name.as_deref(), &peer.pubkey)
+            ),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(vec![
+            Span::styled("  Address:      ", Style::default().fg(Color::Cyan)),
+            Span::raw(peer.address.clone()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Ping:         ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{} ms", peer.ping_time / 1000)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Sent/Recv:    ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "{} / {}",
+                format_sats(peer.sat_sent, unit),
+                format_sats(peer.sat_recv, unit)
+            )),
+        ]));
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// Watchtowers registered with the watchtower client of the LND node.
+fn tower_lines(info: &LndNodeInfo) -> Vec<Line<'static>> {
+    if info.towers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![Line::from(""), section_title("Watchtowers"), Line::from("")];
+    for tower in &info.towers {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  {}...", truncate_str(&tower.pubkey, 16)),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(vec![
+            Span::styled("  Address:      ", Style::default().fg(Color::Cyan)),
+            Span::raw(tower.addresses.join(", ")),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Sessions:     ", Style::default().fg(Color::Cyan)),
+            Span::raw(tower.num_sessions.to_string()),
+        ]));
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// Helper function to create a form field line.
+fn create_form_field<'a>(
+    label: &'a str,
+    value: &'a str,
+    is_active: bool,
+    show_cursor: bool,
+) -> Line<'a> {
+    let label_style = if is_active {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+
+    let value_style = if is_active {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    let mut spans = vec![
+        Span::styled(format!("{:<20}", label), label_style),
+        Span::styled(value, value_style),
+    ];
+
+    if is_active && show_cursor {
+        spans.push(Span::styled("_", Style::default().fg(Color::Yellow)));
+    }
+
+    Line::from(spans)
+}
+
+/// Key bindings shown in the help overlay, grouped by context.
+const HELP_SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Global",
+        &[
+            ("Tab / Shift+Tab", "Switch between panels"),
+            ("↑↓ / k j", "Navigate lists or scroll"),
+            ("/", "Filter the networks or nodes panel"),
+            ("w", "Show only warnings and errors in the logs panel"),
+            ("Esc", "Clear the active filter"),
+            ("?", "Toggle this help"),
+            (",", "Edit settings saved in config.json"),
+            ("q / Esc", "Quit, asking whether to stop running networks"),
+        ],
+    ),
+    (
+        "Networks",
+        &[
+            ("n", "Create a new network"),
+            ("s / Enter", "Start the selected network"),
+            ("x", "Stop the selected network"),
+            ("d / Delete", "Delete the selected network"),
+            ("R", "Reset the network, wiping chain data"),
+            ("a", "Add an LND node"),
+            ("m", "Mine blocks"),
+            ("b", "Toggle timed block generation"),
+            ("B", "Cycle block generation: fixed, on mempool, hybrid"),
+            ("g", "Sync graph (connect LND peers)"),
+            ("y", "Sync LND nodes to the chain"),
+            ("v", "View the channel graph"),
+            ("L", "Show liquidity totals of the network"),
+            ("e", "Export the network to a portable file"),
+            ("O", "Remove orphaned polar containers"),
+        ],
+    ),
+    (
+        "Nodes",
+        &[
+            ("i / Enter", "View node info"),
+            ("r", "Delete the selected LND node"),
+            ("f", "Fund an LND wallet"),
+            ("F", "Fund every LND node in one transaction"),
+            ("c", "Open a channel"),
+            ("l", "Close or abandon a channel"),
+            ("p", "Send a payment or pay an invoice"),
+            ("h", "View invoice and payment history"),
+            ("N", "Rename the selected node"),
+            ("M", "Sign a message with the selected LND node"),
+            ("S / X", "Start / stop only the selected node"),
+        ],
+    ),
+    (
+        "Node details",
+        &[
+            ("n / p", "Select next / previous channel"),
+            ("c", "Copy the selected channel point"),
+            ("Tab / y", "Select / copy a host endpoint"),
+            (
+                "E",
+                "Export the LND TLS cert and admin macaroon and copy their directory",
+            ),
+            ("Q", "Show the lndconnect URL and QR code of an LND node"),
+            ("u", "Toggle amounts between sats and BTC"),
+            ("r", "Refresh, bypassing the node info cache"),
+            ("q / Esc", "Back to main view"),
+        ],
+    ),
+    (
+        "Dialogs",
+        &[
+            ("Tab / ↑↓", "Move between fields"),
+            ("← →", "Change the selected option"),
+            ("Enter", "Submit"),
+            ("y / n", "Confirm or cancel a prompt"),
+            ("Esc", "Cancel"),
+        ],
+    ),
+];
+
+/// Render the help overlay listing every key binding.
+fn render_help(frame: &mut Frame, app: &App) {
+    render_main(frame, app);
+
+    let area = centered_rect(70, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = Vec::new();
+    for (section, bindings) in HELP_SECTIONS {
+        lines.push(Line::from(Span::styled(
+            *section,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (keys, action) in *bindings {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {keys:<18}"), Style::default().fg(Color::Cyan)),
+                Span::raw(*action),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("↑↓/j/k", Style::default().fg(Color::Cyan)),
+        Span::raw(": Scroll  |  "),
+        Span::styled("Esc/?", Style::default().fg(Color::Red)),
+        Span::raw(": Back"),
+    ]));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Key Bindings ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .scroll((u16::try_from(app.help_scroll).unwrap_or(u16::MAX), 0));
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the confirmation dialog on top of the main view.
+fn render_docker_unavailable(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 50, frame.area());
+
+    let block = Block::default()
+        .title(" Docker Unavailable ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Docker is not reachable.",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from("Every node runs in a Docker container, so nothing works until it responds."),
+        Line::from(""),
+        Line::from(Span::styled(
+            app.docker_error.clone().unwrap_or_default(),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Start the Docker daemon, or set docker_host in config.json (or DOCKER_HOST).",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("r", Style::default().fg(Color::Green)),
+            Span::raw(": Retry  |  "),
+            Span::styled("q/Esc", Style::default().fg(Color::Red)),
+            Span::raw(": Quit"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_confirm(frame: &mut Frame, app: &App) {
+    render_main(frame, app);
+
+    let area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Confirm ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            app.confirm_prompt.as_str(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "This action cannot be undone.",
+            Style::default().fg(Color::Red),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y/Enter", Style::default().fg(Color::Green)),
+            Span::raw(": Confirm  |  "),
+            Span::styled("n/Esc", Style::default().fg(Color::Red)),
+            Span::raw(": Cancel"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the decoded invoice for confirmation before paying.
+fn render_confirm_invoice(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 50, frame.area());
+
+    let block = Block::default()
+        .title(" Confirm Invoice Payment ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let from_node = app
+        .nodes
+        .get(app.payment_from_idx)
+        .map_or("None", String::as_str);
+
+    let Some(invoice) = &app.decoded_invoice else {
+        return;
+    };
+
+    let amount = if invoice.num_satoshis == 0 {
+        "any amount".to_string()
+    } else {
+        format!("{} sats", invoice.num_satoshis)
+    };
+    let memo = if invoice.description.is_empty() {
+        "(none)"
+    } else {
+        invoice.description.as_str()
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let expires = format!("in {} seconds", invoice.expires_at().saturating_sub(now));
+
+    let text = vec![
+        Line::from(""),
+        create_form_field("Pay From:", from_node, false, false),
+        create_form_field("Amount:", &amount, true, false),
+        create_form_field("Memo:", memo, false, false),
+        create_form_field("Destination:", &invoice.destination, false, false),
+        create_form_field("Expires:", &expires, false, false),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Wallet Balance: ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                format!("{} sats", info.wallet_balance),
-                Style::default().fg(Color::Green),
-            ),
-        ]),
-        Line::from(vec![
-            Span::styled("Channel Balance:", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                format!("{} sats", info.channel_balance),
-                Style::default().fg(Color::Green),
-            ),
+            Span::styled("y/Enter", Style::default().fg(Color::Green)),
+            Span::raw(": Pay  |  "),
+            Span::styled("n/Esc", Style::default().fg(Color::Red)),
+            Span::raw(": Cancel"),
         ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the quit dialog shown while networks are still running.
+fn render_confirm_quit(frame: &mut Frame, app: &App) {
+    render_main(frame, app);
+
+    let area = centered_rect(50, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Quit ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let (stop_hint, leave_hint) = if app.quit_stop_default {
+        ("s/Enter", "l")
+    } else {
+        ("s", "l/Enter")
+    };
+
+    let text = vec![
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Endpoints",
+        Line::from(Span::styled(
+            format!("Still running: {}", app.quit_running_networks.join(", ")),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(Color::White)
                 .add_modifier(Modifier::BOLD),
-        )]),
+        )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("REST API:       ", Style::default().fg(Color::Cyan)),
-            Span::raw(info.rest_host.clone()),
+            Span::styled(stop_hint, Style::default().fg(Color::Red)),
+            Span::raw(": Stop and quit  |  "),
+            Span::styled(leave_hint, Style::default().fg(Color::Green)),
+            Span::raw(": Leave running and quit"),
         ]),
         Line::from(vec![
-            Span::styled("gRPC:           ", Style::default().fg(Color::Cyan)),
-            Span::raw(info.grpc_host.clone()),
+            Span::styled("n/Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": Cancel"),
         ]),
     ];
 
-    // Add channels section if there are any channels
-    if !info.channels.is_empty() {
-        lines.push(Line::from(""));
-        lines.push(Line::from(vec![Span::styled(
-            "Channels",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]));
-        lines.push(Line::from(""));
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
-        for (idx, channel) in info.channels.iter().enumerate() {
-            let is_selected = selected_channel_idx == Some(idx);
+    frame.render_widget(paragraph, area);
+}
 
-            let status_color = if channel.active {
-                Color::Green
-            } else {
-                Color::Red
-            };
-            let status = if channel.active { "Active" } else { "Inactive" };
+/// Render the route estimate shown before sending a payment.
+fn render_confirm_payment(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 50, frame.area());
 
-            // Highlight selected channel
-            let title_prefix = if is_selected { "► " } else { "  " };
-            let title_style = if is_selected {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
-            } else {
-                Style::default()
-                    .fg(status_color)
-                    .add_modifier(Modifier::BOLD)
-            };
+    let block = Block::default()
+        .title(" Confirm Payment ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
 
-            lines.push(Line::from(vec![Span::styled(
-                format!("{}Channel {} ({})", title_prefix, idx + 1, status),
-                title_style,
-            )]));
-
-            // Show full channel point for selected channel, abbreviated for others
-            let chan_point = &channel.channel_point;
-            let chan_point_display = if is_selected {
-                // Show full channel point when selected for easy manual copying
-                chan_point.clone()
-            } else if chan_point.len() > 40 {
-                // Abbreviate non-selected channels
-                format!(
-                    "{}...:{}",
-                    &chan_point[..37],
-                    chan_point.split(':').last().unwrap_or("")
-                )
-            } else {
-                chan_point.clone()
-            };
+    let Some(route) = &app.route_estimate else {
+        return;
+    };
 
-            let field_style = if is_selected {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default().fg(Color::Cyan)
-            };
+    let node = |idx: usize| app.nodes.get(idx).map_or("None", String::as_str);
+    let amount = format!("{} sats", app.payment_amount);
+    let fee = format!(
+        "{} sats ({} msat)",
+        route.total_fees_sat(),
+        route.total_fees_msat
+    );
+    let hops = if route.hops == 1 {
+        "1 (direct channel)".to_string()
+    } else {
+        route.hops.to_string()
+    };
 
-            let value_style = if is_selected {
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
+    let text = vec![
+        Line::from(""),
+        create_form_field("Pay From:", node(app.payment_from_idx), false, false),
+        create_form_field("Pay To:", node(app.payment_to_idx), false, false),
+        create_form_field("Amount:", &amount, true, false),
+        create_form_field("Est. Fee:", &fee, false, false),
+        create_form_field("Hops:", &hops, false, false),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y/Enter", Style::default().fg(Color::Green)),
+            Span::raw(": Send  |  "),
+            Span::styled("n/Esc", Style::default().fg(Color::Red)),
+            Span::raw(": Cancel"),
+        ]),
+    ];
 
-            lines.push(Line::from(vec![
-                Span::styled("  Point:        ", field_style),
-                Span::styled(chan_point_display, value_style),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  Capacity:     ", field_style),
-                Span::styled(format!("{} sats", channel.capacity), value_style),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  Local:        ", field_style),
-                Span::styled(
-                    format!("{} sats", channel.local_balance),
-                    if is_selected {
-                        Style::default()
-                            .fg(Color::Green)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(Color::Green)
-                    },
-                ),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  Remote:       ", field_style),
-                Span::styled(format!("{} sats", channel.remote_balance), value_style),
-            ]));
-            lines.push(Line::from(""));
-        }
-    }
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
 
-    lines
+    frame.render_widget(paragraph, area);
 }
 
-/// Helper function to create a form field line.
-fn create_form_field<'a>(
-    label: &'a str,
-    value: &'a str,
-    is_active: bool,
-    show_cursor: bool,
-) -> Line<'a> {
-    let label_style = if is_active {
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
+/// Render the settings screen.
+fn render_settings(frame: &mut Frame, app: &App) {
+    render_main(frame, app);
 
-    let value_style = if is_active {
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::White)
-    };
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
 
-    let mut spans = vec![
-        Span::styled(format!("{:<20}", label), label_style),
-        Span::styled(value, value_style),
-    ];
+    let block = Block::default()
+        .title(" Settings ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
 
-    if is_active && show_cursor {
-        spans.push(Span::styled("_", Style::default().fg(Color::Yellow)));
+    let form = &app.settings;
+    let mut text = vec![Line::from("")];
+    for idx in 0..SettingsForm::FIELDS {
+        text.push(create_form_field(
+            SettingsForm::label(idx),
+            form.value(idx),
+            form.field == idx,
+            SettingsForm::is_text(idx),
+        ));
     }
+    text.push(Line::from(""));
+    if let Some(error) = &form.error {
+        text.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    text.push(Line::from(Span::styled(
+        "Amounts are in sats unless noted; data dir and RPC credentials apply after a restart",
+        Style::default().fg(Color::DarkGray),
+    )));
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "Tab/↑↓: Navigate | ← →: Toggle option | Enter: Save | Esc: Cancel",
+        Style::default().fg(Color::DarkGray),
+    )));
 
-    Line::from(spans)
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
 }
 
 /// Render the mine blocks dialog.
 fn render_mine_blocks(frame: &mut Frame, app: &App) {
-    let area = centered_rect(50, 30, frame.area());
+    let area = centered_rect(60, 40, frame.area());
 
     let block = Block::default()
         .title(" Mine Blocks ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow));
 
+    let target = app
+        .mine_target_idx
+        .checked_sub(1)
+        .and_then(|idx| app.nodes.get(idx))
+        .map_or("Bitcoin node wallet", String::as_str);
+
     let text = vec![
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Number of blocks: ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                &app.mine_blocks_count,
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("_"),
-        ]),
+        create_form_field(
+            "Number of blocks:",
+            &app.mine_blocks_count,
+            app.mine_form_field == 0,
+            true,
+        ),
         Line::from(""),
+        create_form_field("Mine to:", target, app.mine_form_field == 1, false),
+        Line::from(Span::styled(
+            "  (Use ← → to change)",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(Span::styled(
+            "  Note: coinbase rewards need 100 confirmations before they can be spent",
+            Style::default().fg(Color::Yellow),
+        )),
         Line::from(""),
         Line::from(Span::styled(
-            "Enter a number and press Enter to mine blocks | Esc: Cancel",
+            "Tab/↑↓: Navigate | ← →: Select target | Enter: Mine | Esc: Cancel",
             Style::default().fg(Color::DarkGray),
         )),
     ];
@@ -858,6 +2213,95 @@ fn render_fund_wallet(frame: &mut Frame, app: &App) {
     frame.render_widget(paragraph, area);
 }
 
+/// Render the fund every LND node dialog.
+fn render_fund_all_nodes(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 30, frame.area());
+
+    let block = Block::default()
+        .title(" Fund All Nodes ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let text = vec![
+        Line::from(""),
+        create_form_field("Amount each (BTC):", &app.fund_all_amount, true, true),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Mines blocks first if the Bitcoin wallet is short",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter: Fund | Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the sign message dialog.
+fn render_sign_message(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 40, frame.area());
+
+    let block = Block::default()
+        .title(" Sign Message ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let mut text = vec![
+        Line::from(""),
+        create_form_field("Node:", &app.sign_node, false, false),
+        Line::from(""),
+        create_form_field("Message:", &app.sign_message, true, true),
+        Line::from(""),
+    ];
+    if let Some(signature) = &app.sign_signature {
+        text.push(Line::from(Span::styled(
+            "Signature:",
+            Style::default().fg(Color::Cyan),
+        )));
+        text.push(Line::from(signature.as_str()));
+        text.push(Line::from(""));
+    }
+    text.push(Line::from(Span::styled(
+        "Enter: Sign | Esc: Close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the rename node dialog.
+fn render_rename_node(frame: &mut Frame, app: &App) {
+    let area = centered_rect(50, 25, frame.area());
+
+    let block = Block::default()
+        .title(" Rename Node ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let text = vec![
+        Line::from(""),
+        create_form_field("Node:", &app.rename_from, false, false),
+        Line::from(""),
+        create_form_field("New name:", &app.rename_input, true, true),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter: Rename | Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
 /// Render the open channel dialog.
 fn render_open_channel(frame: &mut Frame, app: &App) {
     let area = centered_rect(70, 50, frame.area());
@@ -906,6 +2350,20 @@ fn render_open_channel(frame: &mut Frame, app: &App) {
             true,
         ),
         Line::from(""),
+        create_form_field(
+            "Visibility:",
+            if app.channel_private {
+                "Private (not announced)"
+            } else {
+                "Public"
+            },
+            app.channel_form_field == 4,
+            false,
+        ),
+        Line::from(Span::styled(
+            "  (Use ← → or Space to toggle)",
+            Style::default().fg(Color::DarkGray),
+        )),
         Line::from(""),
         Line::from(Span::styled(
             "Tab/↑↓: Navigate | ← →: Select nodes | Enter: Open | Esc: Cancel",
@@ -933,10 +2391,16 @@ fn render_close_channel(frame: &mut Frame, app: &App) {
         .map(|s| s.as_str())
         .unwrap_or("None");
 
-    let force_text = if app.close_channel_force {
-        "Force Close (on-chain)"
-    } else {
-        "Cooperative Close"
+    let (close_text, close_style) = match app.close_channel_mode {
+        CloseMode::Cooperative => ("Cooperative Close", Style::default().fg(Color::Green)),
+        CloseMode::Force => (
+            "Force Close (on-chain)",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        CloseMode::Abandon => (
+            "Abandon (local state only)",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
     };
 
     let text = vec![
@@ -947,16 +2411,23 @@ fn render_close_channel(frame: &mut Frame, app: &App) {
             Style::default().fg(Color::DarkGray),
         )),
         Line::from(""),
+        close_channel_picker(app),
         create_form_field(
             "Channel Point:",
             &app.close_channel_point,
             app.close_channel_form_field == 1,
             true,
         ),
-        Line::from(Span::styled(
-            "  (Format: txid:index)",
-            Style::default().fg(Color::DarkGray),
-        )),
+        match app.close_channel_point.parse::<ChannelPoint>() {
+            Err(e) if !app.close_channel_point.is_empty() => Line::from(Span::styled(
+                format!("  {e}"),
+                Style::default().fg(Color::Red),
+            )),
+            _ => Line::from(Span::styled(
+                "  (Use ← → to pick a channel, or type txid:index)",
+                Style::default().fg(Color::DarkGray),
+            )),
+        },
         Line::from(""),
         Line::from(vec![
             Span::styled(
@@ -970,18 +2441,11 @@ fn render_close_channel(frame: &mut Frame, app: &App) {
                 },
             ),
             Span::styled("< ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                force_text,
-                if app.close_channel_force {
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(Color::Green)
-                },
-            ),
+            Span::styled(close_text, close_style),
             Span::styled(" >", Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(Span::styled(
-            "  (Use ← → to toggle)",
+            "  (Use ← → to change)",
             Style::default().fg(Color::DarkGray),
         )),
         Line::from(""),
@@ -997,9 +2461,46 @@ fn render_close_channel(frame: &mut Frame, app: &App) {
     frame.render_widget(paragraph, area);
 }
 
+/// Line of the close dialog showing the picked channel of the selected node.
+fn close_channel_picker(app: &App) -> Line<'static> {
+    let label_style = if app.close_channel_form_field == 1 {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+    let muted = Style::default().fg(Color::DarkGray);
+
+    let mut spans = vec![Span::styled("Channel:        ", label_style)];
+    match (&app.close_channels, app.picked_close_channel()) {
+        (None, _) => spans.push(Span::styled("Loading channels...", muted)),
+        (Some(channels), _) if channels.is_empty() => {
+            spans.push(Span::styled("No open channels", muted));
+        }
+        (Some(channels), Some(channel)) => {
+            spans.push(Span::styled("< ", muted));
+            spans.push(Span::styled(
+                remote_node_label(channel.remote_name.as_deref(), &channel.remote_pubkey),
+                Style::default().fg(Color::Green),
+            ));
+            spans.push(Span::raw(format!(
+                " · {}",
+                format_sats(channel.capacity, app.display_unit)
+            )));
+            spans.push(Span::styled(
+                format!(" ({}/{}) >", app.close_channel_idx + 1, channels.len()),
+                muted,
+            ));
+        }
+        (Some(_), None) => spans.push(Span::styled("Manual entry", muted)),
+    }
+    Line::from(spans)
+}
+
 /// Render the send payment dialog.
 fn render_send_payment(frame: &mut Frame, app: &App) {
-    let area = centered_rect(70, 50, frame.area());
+    let area = centered_rect(70, 60, frame.area());
 
     let block = Block::default()
         .title(" Send Lightning Payment ")
@@ -1045,9 +2546,19 @@ fn render_send_payment(frame: &mut Frame, app: &App) {
             true,
         ),
         Line::from(""),
+        create_form_field(
+            "Invoice:",
+            &app.payment_invoice,
+            app.payment_form_field == 4,
+            true,
+        ),
+        Line::from(Span::styled(
+            "  (Paste a bolt11 invoice to pay it instead of the To Node)",
+            Style::default().fg(Color::DarkGray),
+        )),
         Line::from(""),
         Line::from(Span::styled(
-            "Tab/↑↓: Navigate | ← →: Select nodes | Enter: Send | Esc: Cancel",
+            "Tab/↑↓: Navigate | ← →: Select nodes | Enter: Estimate route | Esc: Cancel",
             Style::default().fg(Color::DarkGray),
         )),
     ];
@@ -2,4 +2,126 @@
 
 mod layout;
 
-pub use layout::render;
+use std::time::Duration;
+
+pub use layout::{log_max_scroll, logs_viewport, render};
+
+/// Get at most the first `max_chars` characters of a string.
+///
+/// Returns the whole string when it is shorter, so previews of txids,
+/// hashes and pubkeys never panic on unexpected output.
+pub fn truncate_str(s: &str, max_chars: usize) -> &str {
+    s.char_indices()
+        .nth(max_chars)
+        .and_then(|(idx, _)| s.get(..idx))
+        .unwrap_or(s)
+}
+
+/// Label for a remote node: its name when it is one of ours, otherwise the
+/// start of its pubkey.
+pub fn remote_node_label(name: Option<&str>, pubkey: &str) -> String {
+    name.map_or_else(
+        || format!("{}...", truncate_str(pubkey, 16)),
+        |name| format!("{name} ({}...)", truncate_str(pubkey, 8)),
+    )
+}
+
+/// Spinner frames, advanced every [`SPINNER_FRAME`].
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How long each spinner frame shows.
+const SPINNER_FRAME: Duration = Duration::from_millis(100);
+
+/// Spinner frame for an operation that has been running for `elapsed`.
+pub fn spinner_frame(elapsed: Duration) -> &'static str {
+    let frame = elapsed.as_millis() / SPINNER_FRAME.as_millis();
+    SPINNER[usize::try_from(frame % SPINNER.len() as u128).unwrap_or(0)]
+}
+
+/// Render `data` as a QR code of half-block characters, light on dark to
+/// suit the black background of the views.
+///
+/// Returns `None` when the data is too long for a QR code, or always
+/// without the `qr` feature.
+#[cfg(feature = "qr")]
+pub fn qr_lines(data: &str) -> Option<Vec<String>> {
+    use qrcode::render::unicode::Dense1x2;
+
+    let code = qrcode::QrCode::new(data.as_bytes()).ok()?;
+    let rendered = code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build();
+    Some(rendered.lines().map(ToString::to_string).collect())
+}
+
+/// Render `data` as a QR code; this build has no QR support.
+#[cfg(not(feature = "qr"))]
+pub const fn qr_lines(_data: &str) -> Option<Vec<String>> {
+    None
+}
+
+/// Number of rows lines of the given widths take when wrapped to `width`.
+///
+/// Counts character wrapping, which word wrapping rarely exceeds by more
+/// than a row, and gives empty lines one row.
+pub fn wrapped_rows(line_widths: impl IntoIterator<Item = usize>, width: usize) -> usize {
+    line_widths
+        .into_iter()
+        .map(|w| w.div_ceil(width.max(1)).max(1))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_str() {
+        assert_eq!(truncate_str("abc", 8), "abc");
+        assert_eq!(truncate_str("abc", 3), "abc");
+        assert_eq!(truncate_str("abcdef", 3), "abc");
+        assert_eq!(truncate_str("", 16), "");
+        assert_eq!(truncate_str("ünïcode", 3), "ünï");
+    }
+
+    #[test]
+    fn test_remote_node_label() {
+        let pubkey = "02aabbccddeeff00112233445566778899";
+        assert_eq!(
+            remote_node_label(Some("lnd-2"), pubkey),
+            "lnd-2 (02aabbcc...)"
+        );
+        assert_eq!(remote_node_label(None, pubkey), "02aabbccddeeff00...");
+    }
+
+    #[test]
+    fn test_spinner_frame() {
+        assert_eq!(spinner_frame(Duration::ZERO), "⠋");
+        assert_eq!(spinner_frame(Duration::from_millis(150)), "⠙");
+        assert_eq!(spinner_frame(Duration::from_secs(1)), "⠋");
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_qr_lines() {
+        let lines = qr_lines("lndconnect://127.0.0.1:10009?cert=AAEC&macaroon=AgE").unwrap();
+        // Two modules per row, with a 4 module quiet zone on each side
+        let width = lines[0].chars().count();
+        assert!(width > 21 + 8);
+        assert_eq!(lines.len(), width.div_ceil(2));
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+
+        assert!(qr_lines(&"x".repeat(8000)).is_none());
+    }
+
+    #[test]
+    fn test_wrapped_rows() {
+        assert_eq!(wrapped_rows([10, 0, 20], 10), 4);
+        assert_eq!(wrapped_rows([21], 10), 3);
+        assert_eq!(wrapped_rows(Vec::new(), 10), 0);
+        assert_eq!(wrapped_rows([5], 0), 5);
+    }
+}